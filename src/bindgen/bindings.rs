@@ -3,20 +3,41 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::env;
 use std::fs;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::Write;
 use std::path;
+use std::process::{Command, Stdio};
 use std::rc::Rc;
+use std::thread;
 
-use crate::bindgen::config::{Config, Language};
+use heck::SnakeCase;
+
+use crate::bindgen::cargo::cargo_layout::TypeLayout;
+use crate::bindgen::cargo::cargo_metadata::PackageRef;
+use crate::bindgen::cdecl;
+use crate::bindgen::config::{
+    Config, ItemKind, ItemType, Language, ALIGNED_MACRO, PRIVATE_HEADER, VERSION,
+};
+use crate::bindgen::cpp_wrappers::{points_to, CppWrappersWriter};
+use crate::bindgen::csharp::CSharpWriter;
+use crate::bindgen::ctypes::CtypesWriter;
+use crate::bindgen::diff;
+use crate::bindgen::gir::GirWriter;
+use crate::bindgen::ir::annotation::AnnotationSet;
 use crate::bindgen::ir::{
-    Constant, Function, GObject, ItemContainer, ItemMap, Path as BindgenPath, Static, Struct,
+    dispatch_argument, ArrayLength, Cfg, ConditionWrite, Constant, Enum, Function, GObject, Item,
+    ItemContainer, ItemMap, Path as BindgenPath, ReprAlign, Static, Struct, ToCondition, Type,
 };
+use crate::bindgen::napi::NapiWriter;
+use crate::bindgen::rename::{IdentifierType, RenameRule};
+use crate::bindgen::vapi::VapiWriter;
 use crate::bindgen::writer::{Source, SourceWriter};
 
 /// A bindings header that can be written.
+#[derive(Clone)]
 pub struct Bindings {
     pub config: Config,
     /// The map from path to struct, used to lookup whether a given type is a
@@ -28,14 +49,124 @@ pub struct Bindings {
     items: Vec<ItemContainer>,
     functions: Vec<Function>,
     gobjects: Vec<GObject>,
+    /// The Rust source files the bindings were generated from.
+    pub(crate) source_files: Vec<path::PathBuf>,
+    /// The layouts computed by rustc, used for `layout_asserts`, by the Rust
+    /// path of their type.
+    pub(crate) layouts: HashMap<String, TypeLayout>,
+    /// The other headers each header includes, when splitting the bindings.
+    pub(crate) split_includes: BTreeMap<String, BTreeSet<String>>,
+    /// The structs and unions used before they're written, which are
+    /// forward declared.
+    forward_declared: HashSet<BindgenPath>,
+    /// The types of the anonymous members written in their struct or union,
+    /// with `structure.anonymous_members = "inline"`.
+    pub(crate) inline_members: HashSet<BindgenPath>,
+    /// The types of `export.extern_types` the bindings use.
+    pub(crate) extern_types: BTreeSet<String>,
+    /// The contents of the template file to write the bindings in.
+    pub(crate) template: Option<String>,
+    /// The crate the bindings are generated from, if any.
+    pub(crate) package: Option<PackageRef>,
+}
+
+/// The items written to one of the headers of split bindings.
+#[derive(Default)]
+struct SplitGroup {
+    constants: Vec<Constant>,
+    globals: Vec<Static>,
+    items: Vec<ItemContainer>,
+    functions: Vec<Function>,
+    gobjects: Vec<GObject>,
+}
+
+/// The items of the bindings, as written by `Bindings::to_ir_json`.
+/// `IrFile` reads them back.
+#[derive(Serialize)]
+struct Ir<'a> {
+    version: &'a str,
+    constants: &'a [Constant],
+    globals: &'a [Static],
+    items: &'a [ItemContainer],
+    functions: &'a [Function],
+    gobjects: &'a [GObject],
+}
+
+/// An IR file, read by `Builder::with_ir_file`.
+#[derive(Deserialize)]
+pub(crate) struct IrFile {
+    pub version: String,
+    pub constants: Vec<Constant>,
+    pub globals: Vec<Static>,
+    pub items: Vec<ItemContainer>,
+    pub functions: Vec<Function>,
+    pub gobjects: Vec<GObject>,
 }
 
+/// A struct or union with its C type, name, layout and field offsets.
+type ItemLayout<'a> = (String, &'a str, &'a TypeLayout, Vec<(&'a str, usize)>);
+
 #[derive(PartialEq)]
 enum NamespaceOperation {
     Open,
     Close,
 }
 
+/// The namespaces of the modules of the items being written, which are
+/// opened and closed in the root namespace as the items go. The references
+/// to the items in them are qualified with their namespaces.
+#[derive(Default)]
+struct ModuleNamespaces {
+    /// The namespaces currently open.
+    open: Vec<String>,
+}
+
+impl ModuleNamespaces {
+    /// Opens the namespaces of an item, closing the ones of the previous
+    /// item if they're different.
+    fn enter<F: Write>(
+        &mut self,
+        config: &Config,
+        annotations: &AnnotationSet,
+        out: &mut SourceWriter<F>,
+    ) {
+        let namespaces = config.item_namespaces(annotations);
+        if namespaces != self.open {
+            self.close(out);
+            for namespace in &namespaces {
+                out.new_line_if_not_start();
+                write!(out, "namespace {} {{", namespace);
+            }
+            if !namespaces.is_empty() {
+                out.new_line();
+            }
+            self.open = namespaces;
+        }
+    }
+
+    /// Closes the open namespaces.
+    fn close<F: Write>(&mut self, out: &mut SourceWriter<F>) {
+        if self.open.is_empty() {
+            return;
+        }
+        for namespace in self.open.iter().rev() {
+            out.new_line_if_not_start();
+            write!(out, "}} // namespace {}", namespace);
+        }
+        out.new_line();
+        self.open.clear();
+    }
+}
+
+/// A constant written outside of the struct it's associated to, if any.
+struct TopLevelConstant<'a>(&'a Constant);
+
+impl<'a> Source for TopLevelConstant<'a> {
+    fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
+        self.0.write(config, out, None);
+    }
+}
+
 impl Bindings {
     pub(crate) fn new(
         config: Config,
@@ -46,6 +177,7 @@ impl Bindings {
         functions: Vec<Function>,
         gobjects: Vec<GObject>,
     ) -> Bindings {
+        let forward_declared = Self::forward_declared_items(&config, &items);
         Bindings {
             config,
             struct_map,
@@ -55,7 +187,83 @@ impl Bindings {
             items,
             functions,
             gobjects,
+            source_files: Vec::new(),
+            layouts: HashMap::new(),
+            split_includes: BTreeMap::new(),
+            forward_declared,
+            inline_members: HashSet::new(),
+            extern_types: BTreeSet::new(),
+            template: None,
+            package: None,
+        }
+    }
+
+    /// The structs and unions which are used by themselves or by the items
+    /// written before them, like the ones referencing each other, and need
+    /// a forward declaration. C only needs them without struct tags.
+    fn forward_declared_items(config: &Config, items: &[ItemContainer]) -> HashSet<BindgenPath> {
+        let mut forward_declared = HashSet::new();
+        let needed = match config.language {
+            Language::Cxx => true,
+            Language::C => !config.style.generate_tag(),
+            _ => false,
+        };
+        if !needed {
+            return forward_declared;
+        }
+
+        let declarable: HashSet<&BindgenPath> = items
+            .iter()
+            .filter(|item| {
+                !item
+                    .deref()
+                    .annotations()
+                    .bool("no-export")
+                    .unwrap_or(false)
+            })
+            .filter_map(|item| match *item {
+                ItemContainer::Struct(ref x)
+                    if !x.is_transparent && x.generic_params.is_empty() =>
+                {
+                    Some(&x.path)
+                }
+                ItemContainer::Union(ref x) if x.generic_params.is_empty() => Some(&x.path),
+                _ => None,
+            })
+            .collect();
+        let mut written = HashSet::new();
+        for item in items {
+            let mut types = Vec::new();
+            match *item {
+                ItemContainer::Struct(ref x) => types.extend(x.fields.iter().map(|x| &x.ty)),
+                ItemContainer::Union(ref x) => types.extend(x.fields.iter().map(|x| &x.ty)),
+                ItemContainer::Typedef(ref x) => types.push(&x.aliased),
+                _ => {}
+            }
+            for ty in types {
+                ty.visit_paths(&mut |path| {
+                    if declarable.contains(path) && !written.contains(path) {
+                        forward_declared.insert(path.clone());
+                    }
+                });
+            }
+            written.insert(item.deref().path().clone());
+        }
+        forward_declared
+    }
+
+    /// The struct or union at `path`, if it's an anonymous member written in
+    /// its struct or union.
+    pub(crate) fn inline_member(&self, path: &BindgenPath) -> Option<&ItemContainer> {
+        if !self.inline_members.contains(path) {
+            return None;
         }
+        self.items.iter().find(|x| x.deref().path() == path)
+    }
+
+    /// Whether the struct or union at `path` is forward declared.
+    pub(crate) fn is_forward_declared(&self, path: &BindgenPath) -> bool {
+        self.forward_declared.contains(path)
     }
 
     // FIXME(emilio): What to do when the configuration doesn't match?
@@ -96,31 +304,475 @@ impl Bindings {
     }
 
     pub fn write_to_file<P: AsRef<path::Path>>(&self, path: P) -> bool {
-        // Don't compare files if we've never written this file before
-        if !path.as_ref().is_file() {
-            if let Some(parent) = path::Path::new(path.as_ref()).parent() {
-                fs::create_dir_all(parent).unwrap();
+        self.write_files(path.as_ref(), &mut |path, contents| {
+            write_if_changed(path, contents)
+        })
+    }
+
+    /// Returns a unified diff between the files `write_to_file` would write
+    /// at `path` and their current contents, or `None` if they're up to date.
+    #[allow(unused)]
+    pub fn diff_against_file<P: AsRef<path::Path>>(&self, path: P) -> Option<String> {
+        let mut diffs = String::new();
+        self.write_files(path.as_ref(), &mut |path, contents| {
+            let old_contents = fs::read(path).unwrap_or_default();
+            if old_contents == contents {
+                return false;
+            }
+            let name = path.to_string_lossy();
+            diffs.push_str(&diff::unified_diff(
+                &name,
+                &String::from_utf8_lossy(&old_contents),
+                &String::from_utf8_lossy(contents),
+            ));
+            true
+        });
+        if diffs.is_empty() {
+            None
+        } else {
+            Some(diffs)
+        }
+    }
+
+    /// Passes the contents of each file of the bindings, the header at `path`
+    /// and the ones next to it, to `write`, which returns whether it changed.
+    fn write_files(&self, path: &path::Path, write: &mut WriteFile<'_>) -> bool {
+        if let Some(ref guard) = self.config.include_guard {
+            if guard.contains("{file}") {
+                let mut bindings = self.clone();
+                let name = path.file_name().unwrap().to_string_lossy();
+                bindings.config.resolve_include_guard("{file}", &name);
+                return bindings.write_files(path, write);
+            }
+        }
+        let mut write_formatted =
+            |path: &path::Path, contents: &[u8]| match self.format(path, contents) {
+                Some(formatted) => write(path, &formatted),
+                None => write(path, contents),
+            };
+        let write: &mut WriteFile<'_> = &mut write_formatted;
+        let changed = self.write_bindings_to_file(path, write);
+        if !self.config.cpp.wrappers.enabled {
+            return changed;
+        }
+        match self.config.language {
+            Language::C | Language::Cxx => self.write_wrappers_to_file(path, write) || changed,
+            _ => {
+                warn!("Only C and C++ bindings can have C++ wrappers.");
+                changed
+            }
+        }
+    }
+
+    /// Pipes the `contents` of the file at `path` through `format.command`,
+    /// or returns `None` if there's no formatter or it failed.
+    fn format(&self, path: &path::Path, contents: &[u8]) -> Option<Vec<u8>> {
+        let command = self.config.format.command.as_ref()?;
+        let path = env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| path.to_owned());
+        let file = path.to_string_lossy();
+        let mut formatter = Command::new(command);
+        formatter
+            .args(
+                self.config
+                    .format
+                    .args
+                    .iter()
+                    .map(|arg| arg.replace("{}", &file)),
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = path.parent() {
+            formatter.current_dir(dir);
+        }
+        let mut child = match formatter.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                error!("Couldn't run the formatter `{}`: {}", command, e);
+                return None;
+            }
+        };
+
+        // Write from another thread, so that the formatter doesn't block on
+        // a full stdout while we're still writing its input.
+        let mut stdin = child.stdin.take().unwrap();
+        let input = contents.to_owned();
+        let writer = thread::spawn(move || stdin.write_all(&input));
+        let output = child.wait_with_output();
+        let written = writer.join().unwrap();
+        // A formatter exiting early breaks the pipe, so its own error comes
+        // first.
+        let output = match output.and_then(|output| match written {
+            Err(_) if !output.status.success() => Ok(output),
+            written => written.map(|()| output),
+        }) {
+            Ok(output) => output,
+            Err(e) => {
+                error!("Couldn't run the formatter `{}`: {}", command, e);
+                return None;
+            }
+        };
+        if !output.status.success() {
+            error!(
+                "The formatter `{}` failed on {}: {}",
+                command,
+                file,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return None;
+        }
+        Some(output.stdout)
+    }
+
+    fn write_bindings_to_file(&self, path: &path::Path, write: &mut WriteFile<'_>) -> bool {
+        let split = self.config.output.split.enabled;
+        if split || self.split_includes.contains_key(PRIVATE_HEADER) {
+            match self.config.language {
+                Language::C | Language::Cxx if split => {
+                    return self.write_split_to_files(path, write)
+                }
+                Language::C | Language::Cxx => return self.write_private_to_files(path, write),
+                _ => warn!("Only C and C++ bindings can be written to several headers."),
             }
-            self.write(File::create(path).unwrap());
-            return true;
         }
 
         let mut new_file_contents = Vec::new();
         self.write(&mut new_file_contents);
+        write(path, &new_file_contents)
+    }
+
+    /// Writes the C++ classes owning the opaque types to a `.hpp` header next
+    /// to the bindings written at `path`, which it includes.
+    fn write_wrappers_to_file(&self, path: &path::Path, write: &mut WriteFile<'_>) -> bool {
+        let mut wrappers_path = path.with_extension("hpp");
+        if wrappers_path == path {
+            let mut name = path.file_stem().unwrap().to_owned();
+            name.push("-wrappers.hpp");
+            wrappers_path = path.with_file_name(name);
+        }
 
-        let mut old_file_contents = Vec::new();
+        let header = path.file_name().unwrap().to_string_lossy();
+        let mut contents = Vec::new();
         {
-            let mut old_file = File::open(&path).unwrap();
-            old_file.read_to_end(&mut old_file_contents).unwrap();
+            let mut out = SourceWriter::new(&mut contents, self);
+            CppWrappersWriter::new(&self.config, &self.items, &self.functions).write(
+                &mut out,
+                &header,
+                &self.all_namespaces(),
+            );
         }
+        write(&wrappers_path, &contents)
+    }
 
-        if old_file_contents != new_file_contents {
-            let mut new_file = File::create(&path).unwrap();
-            new_file.write_all(&new_file_contents).unwrap();
-            true
-        } else {
-            false
+    /// Writes the items of each group to its own header, in a directory next
+    /// to the umbrella header written at `path`, which includes them all.
+    fn write_split_to_files(&self, path: &path::Path, write: &mut WriteFile<'_>) -> bool {
+        let directory = match self.config.output.split.directory {
+            Some(ref directory) => directory.clone(),
+            None => path.file_stem().unwrap().to_string_lossy().into_owned(),
+        };
+        let extension = path
+            .extension()
+            .map_or("h".to_owned(), |x| x.to_string_lossy().into_owned());
+        let header_name = |group: &str| format!("{}.{}", group, extension);
+
+        let groups = self.split_groups();
+        for (group, includes) in &self.split_includes {
+            for include in includes {
+                let mutual = self
+                    .split_includes
+                    .get(include)
+                    .map_or(false, |x| x.contains(group));
+                if mutual && group < include {
+                    warn!(
+                        "The {} and {} headers use each other's items, they may not compile.",
+                        group, include
+                    );
+                }
+            }
+        }
+
+        let names: Vec<String> = groups.keys().cloned().collect();
+        let mut changed = false;
+        let dir = path.parent().unwrap().join(&directory);
+        for (group, items) in groups {
+            let mut config = self.group_config(&group);
+            if let Some(includes) = self.split_includes.get(&group) {
+                let includes = includes.iter().filter(|x| names.contains(x));
+                config.includes.extend(includes.map(|x| header_name(x)));
+            }
+            let bindings = self.group_bindings(config, items);
+            changed |= bindings.write_files(&dir.join(header_name(&group)), write);
+        }
+
+        let mut config = self.config.clone();
+        config.cpp.wrappers.enabled = false;
+        config.no_includes = true;
+        config.sys_includes.clear();
+        config.includes = names
+            .iter()
+            .map(|group| format!("{}/{}", directory, header_name(group)))
+            .collect();
+        config.after_includes = None;
+        config.namespace = None;
+        config.namespaces = None;
+        config.cpp.inline_namespace = None;
+        let mut umbrella = self.group_bindings(config, SplitGroup::default());
+        umbrella.extern_types.clear();
+        let mut contents = Vec::new();
+        umbrella.write(&mut contents);
+        write(path, &contents) || changed
+    }
+
+    /// Writes the public items to `path`, and the private ones to a header
+    /// next to it, suffixed with `-private`, which includes it.
+    fn write_private_to_files(&self, path: &path::Path, write: &mut WriteFile<'_>) -> bool {
+        if self
+            .split_includes
+            .get("")
+            .map_or(false, |x| x.contains(PRIVATE_HEADER))
+        {
+            warn!("Public items use private ones, the public header may not compile.");
+        }
+
+        let mut groups = self.split_groups();
+        let public = groups.remove("").unwrap_or_default();
+        let private = groups.remove(PRIVATE_HEADER).unwrap_or_default();
+
+        let mut private_name = path.file_stem().unwrap().to_owned();
+        private_name.push("-private");
+        if let Some(extension) = path.extension() {
+            private_name.push(".");
+            private_name.push(extension);
+        }
+        let mut config = self.group_config(PRIVATE_HEADER);
+        config
+            .includes
+            .push(path.file_name().unwrap().to_string_lossy().into_owned());
+
+        let changed = self
+            .group_bindings(self.group_config(""), public)
+            .write_files(path, write);
+        self.group_bindings(config, private)
+            .write_files(&path.with_file_name(private_name), write)
+            || changed
+    }
+
+    /// The settings of the header of a group, whose include guard is suffixed
+    /// with its name.
+    fn group_config(&self, group: &str) -> Config {
+        let mut config = self.config.clone();
+        config.output.split.enabled = false;
+        config.cpp.wrappers.enabled = false;
+        if !group.is_empty() {
+            config.include_guard = self.config.include_guard.as_ref().map(|guard| {
+                let suffix = RenameRule::ScreamingSnakeCase.apply(group, IdentifierType::Type);
+                format!(
+                    "{}_{}",
+                    guard,
+                    suffix.replace(|c: char| !c.is_alphanumeric(), "_")
+                )
+            });
+        }
+        config
+    }
+
+    fn group_bindings(&self, config: Config, items: SplitGroup) -> Bindings {
+        let mut bindings = Bindings::new(
+            config,
+            self.struct_map.clone(),
+            items.constants,
+            items.globals,
+            items.items,
+            items.functions,
+            items.gobjects,
+        );
+        bindings.layouts = self.layouts.clone();
+        bindings.inline_members = self.inline_members.clone();
+        bindings.extern_types = self.extern_types.clone();
+        bindings
+    }
+
+    /// Sorts the items by the header they go in, keeping their order.
+    fn split_groups(&self) -> BTreeMap<String, SplitGroup> {
+        let config = &self.config;
+        let mut groups: BTreeMap<String, SplitGroup> = self
+            .split_includes
+            .keys()
+            .map(|group| (group.clone(), SplitGroup::default()))
+            .collect();
+
+        for constant in &self.constants {
+            let group = groups.entry(config.header_group(&constant.annotations));
+            group.or_default().constants.push(constant.clone());
+        }
+        for global in &self.globals {
+            let group = groups.entry(config.header_group(&global.annotations));
+            group.or_default().globals.push(global.clone());
+        }
+        for item in &self.items {
+            let group = groups.entry(config.header_group(item.deref().annotations()));
+            group.or_default().items.push(item.clone());
+        }
+        for function in &self.functions {
+            let group = groups.entry(config.header_group(&function.annotations));
+            group.or_default().functions.push(function.clone());
+        }
+        for gobject in &self.gobjects {
+            let group = groups.entry(config.header_group(gobject.annotations()));
+            group.or_default().gobjects.push(gobject.clone());
+        }
+        groups
+    }
+
+    /// Returns the items to write, after renaming and monomorphization, in the
+    /// order they're written in, as JSON.
+    pub fn to_ir_json(&self) -> String {
+        let ir = Ir {
+            version: VERSION,
+            constants: &self.constants,
+            globals: &self.globals,
+            items: &self.items,
+            functions: &self.functions,
+            gobjects: &self.gobjects,
+        };
+        serde_json::to_string_pretty(&ir).unwrap()
+    }
+
+    pub fn write_ir_to_file<P: AsRef<path::Path>>(&self, path: P) {
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, self.to_ir_json()).unwrap();
+    }
+
+    /// The symbols the library exports: its functions and globals, without
+    /// the functions of `extern` blocks, which it imports. Each one tells
+    /// whether it's data, and the cfg it's only exported with.
+    fn exported_symbols(&self) -> BTreeMap<&str, (bool, Option<&Cfg>)> {
+        let mut symbols = BTreeMap::new();
+        for function in &self.functions {
+            if !function.extern_decl {
+                symbols.insert(function.path.name(), (false, function.cfg.as_ref()));
+            }
+        }
+        for global in &self.globals {
+            symbols.insert(global.export_name(), (true, global.cfg.as_ref()));
         }
+        symbols
+    }
+
+    /// The version node of the symbols, named after the crate and the part
+    /// of its version which changes with incompatible releases, like
+    /// `MY_LIB_1` for 1.4.2, or `MY_LIB_0.3` for 0.3.1.
+    fn version_node(&self) -> Option<String> {
+        let package = self.package.as_ref()?;
+        let name = package
+            .name
+            .to_uppercase()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        let version = match package.version {
+            Some(ref version) => version,
+            None => return Some(name),
+        };
+        let mut parts = version.split(|c| c == '.' || c == '-' || c == '+');
+        match (parts.next(), parts.next()) {
+            (Some("0"), Some(minor)) => Some(format!("{}_0.{}", name, minor)),
+            (Some(major), _) => Some(format!("{}_{}", name, major)),
+            _ => Some(name),
+        }
+    }
+
+    /// Returns the symbol map of the library: a module-definition file
+    /// listing its exports when `def` is set, for Windows linkers, or else a
+    /// linker version script declaring them in the version node of the
+    /// crate, and hiding the other symbols. The symbols only exported with
+    /// some cfgs are followed by a comment with their cfg.
+    pub fn symbols(&self, def: bool) -> String {
+        let symbols = self.exported_symbols();
+        let mut out = String::new();
+        if def {
+            out.push_str("EXPORTS\n");
+            for (name, (data, cfg)) in symbols {
+                out.push_str(&format!("    {}", name));
+                if data {
+                    out.push_str(" DATA");
+                }
+                if let Some(cfg) = cfg {
+                    out.push_str(&format!(" ; cfg({})", cfg));
+                }
+                out.push('\n');
+            }
+            return out;
+        }
+
+        match self.version_node() {
+            Some(node) => out.push_str(&format!("{} {{\n", node)),
+            None => out.push_str("{\n"),
+        }
+        if !symbols.is_empty() {
+            out.push_str("  global:\n");
+            for (name, (_, cfg)) in symbols {
+                out.push_str(&format!("    {};", name));
+                if let Some(cfg) = cfg {
+                    out.push_str(&format!(" /* cfg({}) */", cfg));
+                }
+                out.push('\n');
+            }
+        }
+        out.push_str("  local:\n    *;\n};\n");
+        out
+    }
+
+    /// Writes the symbol map of the library to `path`, as a module-definition
+    /// file if its extension is `.def`, and returns whether it changed. The
+    /// symbols only exported with some cfgs are warned about, since the
+    /// builds without them don't export them.
+    pub fn write_symbols_to_file<P: AsRef<path::Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        let def = path
+            .extension()
+            .and_then(|x| x.to_str())
+            .map_or(false, |x| x.eq_ignore_ascii_case("def"));
+        for (name, (_, cfg)) in self.exported_symbols() {
+            if let Some(cfg) = cfg {
+                warn!(
+                    "{} is only exported with cfg({}), but {} lists it for every build.",
+                    name,
+                    cfg,
+                    path.display()
+                );
+            }
+        }
+        write_if_changed(path, self.symbols(def).as_bytes())
+    }
+
+    /// Writes a Makefile-style dependency file, making `header_path` depend
+    /// on every Rust source file that was parsed.
+    pub fn write_depfile<P: AsRef<path::Path>>(&self, header_path: P, depfile_path: P) {
+        // Make escapes spaces and '#', and '$' doubles.
+        fn escape(path: &path::Path) -> String {
+            path.to_string_lossy()
+                .replace(' ', "\\ ")
+                .replace('#', "\\#")
+                .replace('$', "$$")
+        }
+
+        let mut depfile = String::new();
+        depfile.push_str(&escape(header_path.as_ref()));
+        depfile.push(':');
+        for source_file in &self.source_files {
+            depfile.push_str(" \\\n  ");
+            depfile.push_str(&escape(source_file));
+        }
+        depfile.push('\n');
+
+        if let Some(parent) = depfile_path.as_ref().parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(depfile_path, depfile).unwrap();
     }
 
     pub fn write_headers<F: Write>(&self, out: &mut SourceWriter<F>) {
@@ -156,11 +808,17 @@ impl Bindings {
             out.new_line();
         }
 
+        self.write_includes(out);
+    }
+
+    /// Writes the includes, followed by `after_includes`.
+    fn write_includes<F: Write>(&self, out: &mut SourceWriter<F>) {
         if self.config.no_includes
             && self.config.sys_includes().is_empty()
             && self.config.includes().is_empty()
             && (self.config.cython.cimports.is_empty() || self.config.language != Language::Cython)
             && self.config.after_includes.is_none()
+            && self.extern_types.is_empty()
         {
             return;
         }
@@ -174,11 +832,15 @@ impl Bindings {
                     out.new_line();
                     out.write("#include <stdbool.h>");
                     out.new_line();
-                    if self.config.usize_is_size_t {
+                    if self.config.usize_is_size_t || self.config.layout_asserts {
                         out.write("#include <stddef.h>");
                         out.new_line();
                     }
-                    out.write("#include <stdint.h>");
+                    if self.config.gobject.glib_types(Language::C) {
+                        out.write("#include <glib.h>");
+                    } else {
+                        out.write("#include <stdint.h>");
+                    }
                     out.new_line();
                     out.write("#include <stdlib.h>");
                     out.new_line();
@@ -186,11 +848,15 @@ impl Bindings {
                 Language::Cxx => {
                     out.write("#include <cstdarg>");
                     out.new_line();
-                    if self.config.usize_is_size_t {
+                    if self.config.usize_is_size_t || self.config.layout_asserts {
                         out.write("#include <cstddef>");
                         out.new_line();
                     }
-                    out.write("#include <cstdint>");
+                    if self.config.gobject.glib_types(Language::Cxx) {
+                        out.write("#include <glib.h>");
+                    } else {
+                        out.write("#include <cstdint>");
+                    }
                     out.new_line();
                     out.write("#include <cstdlib>");
                     out.new_line();
@@ -223,15 +889,23 @@ impl Bindings {
                     out.new_line();
                     out.close_brace(false);
                 }
+                Language::Gir
+                | Language::CSharp
+                | Language::Vapi
+                | Language::Napi
+                | Language::Ctypes => {
+                    unreachable!()
+                }
             }
         }
 
-        for include in self.config.sys_includes() {
+        let (sys_includes, includes) = self.extern_type_includes();
+        for include in self.config.sys_includes().iter().chain(&sys_includes) {
             write!(out, "#include <{}>", include);
             out.new_line();
         }
 
-        for include in self.config.includes() {
+        for include in self.config.includes().iter().chain(&includes) {
             write!(out, "#include \"{}\"", include);
             out.new_line();
         }
@@ -243,120 +917,797 @@ impl Bindings {
             }
         }
 
+        self.write_extern_type_declarations(out);
+
         if let Some(ref line) = self.config.after_includes {
             write!(out, "{}", line);
             out.new_line();
         }
     }
 
-    pub fn write<F: Write>(&self, file: F) {
-        let mut out = SourceWriter::new(file, self);
+    /// The system headers and the headers declaring the used extern types,
+    /// which aren't already included.
+    fn extern_type_includes(&self) -> (Vec<String>, Vec<String>) {
+        let mut sys_includes = Vec::new();
+        let mut includes = Vec::new();
+        if self.config.language == Language::Cython {
+            return (sys_includes, includes);
+        }
 
-        self.write_headers(&mut out);
+        for name in &self.extern_types {
+            let extern_type = &self.config.export.extern_types[name];
+            if let Some(ref include) = extern_type.sys_include {
+                if !self.config.sys_includes.contains(include) && !sys_includes.contains(include) {
+                    sys_includes.push(include.clone());
+                }
+            }
+            if let Some(ref include) = extern_type.include {
+                if !self.config.includes.contains(include) && !includes.contains(include) {
+                    includes.push(include.clone());
+                }
+            }
+        }
+        (sys_includes, includes)
+    }
 
-        self.open_namespaces(&mut out);
+    /// Forward declares the used extern types with `forward_declaration` as
+    /// opaque structs.
+    fn write_extern_type_declarations<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let names: Vec<&String> = self
+            .extern_types
+            .iter()
+            .filter(|x| self.config.export.extern_types[*x].forward_declaration)
+            .collect();
+        if names.is_empty() {
+            return;
+        }
 
+        out.new_line_if_not_start();
+        if self.config.language == Language::Cython {
+            out.write("cdef extern from *");
+            out.open_brace();
+        }
+        for name in names {
+            match self.config.language {
+                Language::C => write!(out, "typedef struct {} {};", name, name),
+                Language::Cxx => write!(out, "struct {};", name),
+                Language::Cython => write!(out, "ctypedef struct {}", name),
+                Language::Gir
+                | Language::CSharp
+                | Language::Vapi
+                | Language::Napi
+                | Language::Ctypes => unreachable!(),
+            }
+            out.new_line();
+        }
+        if self.config.language == Language::Cython {
+            out.close_brace(false);
+        }
+    }
+
+    /// Defines the export macro, unless it's already defined, to export the
+    /// symbols when `MACRO_EXPORTS` is defined while building the library and
+    /// import them otherwise.
+    fn write_export_macro<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let name = match self.config.export_macro() {
+            Some(name) => name,
+            None => return,
+        };
+        if self.functions.is_empty() && self.globals.is_empty() {
+            return;
+        }
+
+        out.new_line_if_not_start();
+        write!(out, "#ifndef {}", name);
+        out.new_line();
+        out.write("#if defined(_WIN32) || defined(__CYGWIN__)");
+        out.new_line();
+        write!(out, "#ifdef {}_EXPORTS", name);
+        out.new_line();
+        write!(out, "#define {} __declspec(dllexport)", name);
+        out.new_line();
+        out.write("#else");
+        out.new_line();
+        write!(out, "#define {} __declspec(dllimport)", name);
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+        out.write("#elif defined(__GNUC__)");
+        out.new_line();
+        write!(
+            out,
+            "#define {} __attribute__((visibility(\"default\")))",
+            name
+        );
+        out.new_line();
+        out.write("#else");
+        out.new_line();
+        write!(out, "#define {}", name);
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+    }
+
+    /// Defines the macro aligning the types with `layout.aligned_n =
+    /// "portable"`, unless it's already defined.
+    fn write_aligned_macro<F: Write>(&self, out: &mut SourceWriter<F>) {
+        if !self
+            .config
+            .layout
+            .defines_aligned_macro(self.config.language)
+        {
+            return;
+        }
+        let is_aligned = |alignment: Option<ReprAlign>| match alignment {
+            Some(ReprAlign::Align(_)) => true,
+            _ => false,
+        };
+        let aligned = self.items.iter().any(|item| match *item {
+            ItemContainer::Struct(ref x) => is_aligned(x.alignment),
+            ItemContainer::Union(ref x) => is_aligned(x.alignment),
+            _ => false,
+        });
+        if !aligned {
+            return;
+        }
+
+        out.new_line_if_not_start();
+        write!(out, "#ifndef {}", ALIGNED_MACRO);
+        out.new_line();
+        out.write("#if defined(__cplusplus)");
+        out.new_line();
+        write!(out, "#define {}(n) alignas(n)", ALIGNED_MACRO);
+        out.new_line();
+        out.write("#elif defined(_MSC_VER)");
+        out.new_line();
+        write!(out, "#define {}(n) __declspec(align(n))", ALIGNED_MACRO);
+        out.new_line();
+        out.write("#else");
+        out.new_line();
+        write!(
+            out,
+            "#define {}(n) __attribute__((aligned(n)))",
+            ALIGNED_MACRO
+        );
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+    }
+
+    /// Defines `EMSCRIPTEN_KEEPALIVE`, marking the functions the crate exports
+    /// to the WebAssembly host, when `<emscripten.h>` doesn't.
+    fn write_wasm_keepalive<F: Write>(&self, out: &mut SourceWriter<F>) {
+        if !self.config.wasm_attributes() || self.functions.iter().all(|f| f.extern_decl) {
+            return;
+        }
+
+        out.new_line_if_not_start();
+        out.write("#ifdef __EMSCRIPTEN__");
+        out.new_line();
+        out.write("#include <emscripten.h>");
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+        out.write("#ifndef EMSCRIPTEN_KEEPALIVE");
+        out.new_line();
+        out.write("#define EMSCRIPTEN_KEEPALIVE __attribute__((used))");
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+    }
+
+    /// Whether some items are declared deprecated.
+    fn has_deprecated(&self) -> bool {
+        self.config.deprecated_attribute("").is_some()
+            && (self
+                .items
+                .iter()
+                .any(|item| item.deref().annotations().deprecated.is_some())
+                || self
+                    .functions
+                    .iter()
+                    .any(|function| function.annotations.deprecated.is_some())
+                || self
+                    .globals
+                    .iter()
+                    .any(|global| global.annotations.deprecated.is_some()))
+    }
+
+    /// Starts or ends ignoring the deprecation warnings, so the deprecated
+    /// items used by the bindings only warn where the including code uses
+    /// them.
+    fn write_ignore_deprecations<F: Write>(&self, out: &mut SourceWriter<F>, start: bool) {
+        out.new_line_if_not_start();
+        out.write("#if defined(__GNUC__)");
+        out.new_line();
+        if start {
+            out.write("#pragma GCC diagnostic push");
+            out.new_line();
+            out.write("#pragma GCC diagnostic ignored \"-Wdeprecated-declarations\"");
+        } else {
+            out.write("#pragma GCC diagnostic pop");
+        }
+        out.new_line();
+        out.write("#elif defined(_MSC_VER)");
+        out.new_line();
+        if start {
+            out.write("#pragma warning(push)");
+            out.new_line();
+            out.write("#pragma warning(disable: 4996)");
+        } else {
+            out.write("#pragma warning(pop)");
+        }
+        out.new_line();
+        out.write("#endif");
+        out.new_line();
+    }
+
+    /// Writes the constants, or only the ones using primitive types or the
+    /// other ones.
+    fn write_constants<F: Write>(&self, out: &mut SourceWriter<F>, primitive: Option<bool>) {
+        let mut namespaces = ModuleNamespaces::default();
         for constant in &self.constants {
-            if constant.uses_only_primitive_types() {
+            if primitive.map_or(true, |x| x == constant.uses_only_primitive_types()) {
+                let name = constant.export_name();
+                namespaces.enter(&self.config, &constant.annotations, out);
                 out.new_line_if_not_start();
-                constant.write(&self.config, &mut out, None);
+                self.write_hooked(out, &TopLevelConstant(constant), ItemType::Constants, name);
                 out.new_line();
             }
         }
+        namespaces.close(out);
+    }
 
+    /// Writes the forward declarations of the structs and unions used before
+    /// they're written.
+    fn write_forward_declarations<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let mut namespaces = ModuleNamespaces::default();
+        for item in &self.items {
+            let (keyword, name) = match *item {
+                ItemContainer::Struct(ref x) if self.is_forward_declared(&x.path) => {
+                    ("struct", x.export_name())
+                }
+                ItemContainer::Union(ref x) if self.is_forward_declared(&x.path) => {
+                    ("union", x.export_name())
+                }
+                _ => continue,
+            };
+            namespaces.enter(&self.config, item.deref().annotations(), out);
+            out.new_line_if_not_start();
+            if self.config.language == Language::C {
+                write!(out, "typedef {} {} {};", keyword, name, name);
+            } else {
+                write!(out, "{} {};", keyword, name);
+            }
+        }
+        if !self.forward_declared.is_empty() {
+            out.new_line();
+        }
+        namespaces.close(out);
+    }
+
+    /// Writes `item`, after letting the hook set with
+    /// `Builder::with_item_written_hook` rewrite its text.
+    fn write_hooked<S: Source, F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        item: &S,
+        item_type: ItemType,
+        name: &str,
+    ) {
+        let hook = match self.config.item_written_hook {
+            Some(ref hook) => hook,
+            None => return item.write(&self.config, out),
+        };
+        let mut buffer = out.buffer();
+        item.write(&self.config, &mut buffer);
+        let mut text = buffer.into_text();
+        let kind = ItemKind {
+            item_type,
+            name: name.to_owned(),
+        };
+        (hook.0)(&kind, &mut text);
+        out.write_raw_block(&text);
+    }
+
+    fn write_items<F: Write>(&self, out: &mut SourceWriter<F>) {
+        self.write_forward_declarations(out);
+        let mut namespaces = ModuleNamespaces::default();
         for item in &self.items {
             if item
                 .deref()
                 .annotations()
                 .bool("no-export")
                 .unwrap_or(false)
+                || self.inline_members.contains(item.deref().path())
             {
                 continue;
             }
 
+            namespaces.enter(&self.config, item.deref().annotations(), out);
             out.new_line_if_not_start();
             match *item {
                 ItemContainer::Constant(..) => unreachable!(),
                 ItemContainer::Static(..) => unreachable!(),
-                ItemContainer::Enum(ref x) => x.write(&self.config, &mut out),
-                ItemContainer::Struct(ref x) => x.write(&self.config, &mut out),
-                ItemContainer::Union(ref x) => x.write(&self.config, &mut out),
-                ItemContainer::OpaqueItem(ref x) => x.write(&self.config, &mut out),
-                ItemContainer::Typedef(ref x) => x.write(&self.config, &mut out),
+                ItemContainer::Enum(ref x) => {
+                    self.write_hooked(out, x, ItemType::Enums, x.export_name())
+                }
+                ItemContainer::Struct(ref x) => {
+                    self.write_hooked(out, x, ItemType::Structs, x.export_name())
+                }
+                ItemContainer::Union(ref x) => {
+                    self.write_hooked(out, x, ItemType::Unions, x.export_name())
+                }
+                ItemContainer::OpaqueItem(ref x) => {
+                    self.write_hooked(out, x, ItemType::OpaqueItems, x.export_name())
+                }
+                ItemContainer::Typedef(ref x) => {
+                    self.write_hooked(out, x, ItemType::Typedefs, x.export_name())
+                }
                 ItemContainer::GObject(..) => unreachable!(),
             }
             out.new_line();
         }
+        namespaces.close(out);
+    }
 
-        for constant in &self.constants {
-            if !constant.uses_only_primitive_types() {
-                out.new_line_if_not_start();
-                constant.write(&self.config, &mut out, None);
+    /// Writes what follows the items: the offsets of their fields and the
+    /// checks of their layouts, and the declarations of the gobject types.
+    fn write_after_items<F: Write>(&self, out: &mut SourceWriter<F>) {
+        if self.config.field_offset_defines {
+            self.write_field_offset_defines(out);
+        }
+        if self.config.layout_asserts {
+            self.write_layout_asserts(out);
+        }
+
+        for gobject in &self.gobjects {
+            // The GObject macros aren't available in Cython, which only
+            // needs the structs they declare.
+            if self.config.language == Language::Cython && !gobject.declares_struct() {
+                continue;
+            }
+            out.new_line_if_not_start();
+            gobject.write(&self.config, out, None);
+            out.new_line();
+        }
+    }
+
+    /// The fieldless enums, which get a `get_type` function in gobject mode.
+    fn gobject_enums(&self) -> Vec<&Enum> {
+        if !self.config.gobject.enabled {
+            return Vec::new();
+        }
+        self.items
+            .iter()
+            .filter_map(|item| match *item {
+                ItemContainer::Enum(ref x) if x.has_gtype() => Some(x),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn has_gobject_functions(&self) -> bool {
+        !self.gobject_enums().is_empty()
+            || self.gobjects.iter().any(|gobject| gobject.has_functions())
+    }
+
+    /// Writes the globals and the functions, in an `extern "C"` block for
+    /// C++.
+    fn write_functions<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let has_gobject_functions = self.has_gobject_functions();
+        if self.functions.is_empty() && self.globals.is_empty() && !has_gobject_functions {
+            return;
+        }
+
+        let extern_c_block = self.config.extern_c_block();
+        if extern_c_block && self.config.cpp_compatible_c() {
+            out.new_line_if_not_start();
+            out.write("#ifdef __cplusplus");
+        }
+
+        if self.config.language == Language::Cxx {
+            if let Some(ref using_namespaces) = self.config.using_namespaces {
+                for namespace in using_namespaces {
+                    out.new_line();
+                    write!(out, "using namespace {};", namespace);
+                }
                 out.new_line();
             }
         }
 
-        for gobject in &self.gobjects {
+        if extern_c_block {
+            out.new_line();
+            out.write("extern \"C\" {");
+            out.new_line();
+        }
+
+        if extern_c_block && self.config.cpp_compatible_c() {
+            out.write("#endif // __cplusplus");
+            out.new_line();
+        }
+
+        let mut namespaces = ModuleNamespaces::default();
+        for global in &self.globals {
+            let name = global.export_name();
+            namespaces.enter(&self.config, &global.annotations, out);
             out.new_line_if_not_start();
-            gobject.write(&self.config, &mut out, None);
+            self.write_hooked(out, global, ItemType::Globals, name);
             out.new_line();
         }
 
-        if !self.functions.is_empty() || !self.globals.is_empty() {
-            if self.config.cpp_compatible_c() {
-                out.new_line_if_not_start();
-                out.write("#ifdef __cplusplus");
+        for function in &self.functions {
+            let name = function.path.name();
+            namespaces.enter(&self.config, &function.annotations, out);
+            out.new_line_if_not_start();
+            self.write_hooked(out, function, ItemType::Functions, name);
+            out.new_line();
+        }
+        namespaces.close(out);
+
+        if self.config.language != Language::Cython {
+            self.write_vtables(out);
+        }
+        if self.config.language == Language::C {
+            self.write_generic_macros(out);
+        }
+
+        // GLib types like `GType` aren't declared in Cython.
+        if has_gobject_functions && self.config.language != Language::Cython {
+            let exported: Vec<&str> = self.functions.iter().map(|f| f.path.name()).collect();
+            for gobject in &self.gobjects {
+                gobject.write_functions(out, &exported);
             }
+            for enumeration in &self.gobject_enums() {
+                let get_type = enumeration.get_type_function();
+                if !exported.contains(&get_type.as_str()) {
+                    out.new_line_if_not_start();
+                    write!(out, "GType {}(void);", get_type);
+                    out.new_line();
+                }
+            }
+        }
 
-            if self.config.language == Language::Cxx {
-                if let Some(ref using_namespaces) = self.config.using_namespaces {
-                    for namespace in using_namespaces {
-                        out.new_line();
-                        write!(out, "using namespace {};", namespace);
-                    }
+        if self.config.gobject.enabled && self.config.language != Language::Cython {
+            self.write_autoptr_cleanups(out);
+        }
+
+        if extern_c_block && self.config.cpp_compatible_c() {
+            out.new_line();
+            out.write("#ifdef __cplusplus");
+        }
+
+        if extern_c_block {
+            out.new_line();
+            out.write("} // extern \"C\"");
+            out.new_line();
+        }
+
+        if extern_c_block && self.config.cpp_compatible_c() {
+            out.write("#endif // __cplusplus");
+            out.new_line();
+        }
+    }
+
+    /// Defines a `static const` table of each struct declared for the
+    /// `vtable` annotations, pointing to the functions annotated with it.
+    fn write_vtables<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let mut vtables: Vec<(String, Vec<&Function>)> = Vec::new();
+        for function in &self.functions {
+            let name = match function.annotations.atom("vtable") {
+                Some(Some(name)) => name,
+                _ => continue,
+            };
+            match vtables.iter_mut().find(|x| x.0 == name) {
+                Some(vtable) => vtable.1.push(function),
+                None => vtables.push((name, vec![function])),
+            }
+        }
+
+        let keyword =
+            if self.config.language == Language::C && !self.config.style.generate_typedef() {
+                "struct "
+            } else {
+                ""
+            };
+        for (name, functions) in vtables {
+            let structure = match self.struct_map.get_items(&BindgenPath::new(name)) {
+                Some(items) => items,
+                None => continue,
+            };
+            let export_name = match structure.first() {
+                Some(ItemContainer::Struct(ref x)) => x.export_name().to_owned(),
+                _ => continue,
+            };
+            out.new_line_if_not_start();
+            write!(
+                out,
+                "static const {}{} {} =",
+                keyword,
+                export_name,
+                export_name.to_snake_case()
+            );
+            out.open_brace();
+            for (i, function) in functions.iter().enumerate() {
+                let condition = function.cfg.to_condition(&self.config);
+                condition.write_before(&self.config, out);
+                write!(out, "{},", function.path.name());
+                condition.write_after(&self.config, out);
+                if i != functions.len() - 1 {
                     out.new_line();
                 }
             }
+            out.close_brace(true);
+            out.new_line();
+        }
+    }
 
-            if self.config.language == Language::Cxx || self.config.cpp_compatible_c() {
-                out.new_line();
-                out.write("extern \"C\" {");
-                out.new_line();
+    /// Defines a C11 `_Generic` macro for each `generic-group`, calling the
+    /// function of the group matching the type of its dispatch argument.
+    fn write_generic_macros<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let mut groups: Vec<(String, Vec<&Function>)> = Vec::new();
+        for function in &self.functions {
+            let group = match function.annotations.atom("generic-group") {
+                Some(Some(group)) => group,
+                _ => continue,
+            };
+            match groups.iter_mut().find(|x| x.0 == group) {
+                Some(group) => group.1.push(function),
+                None => groups.push((group, vec![function])),
             }
+        }
+
+        for (group, functions) in groups {
+            let index = match dispatch_argument(&functions) {
+                Ok(index) => index,
+                Err(_) => continue,
+            };
+            let args: Vec<String> = functions[0]
+                .args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| arg.name.clone().unwrap_or_else(|| format!("arg{}", i)))
+                .collect();
+            let args = args.join(", ");
 
-            if self.config.cpp_compatible_c() {
-                out.write("#endif // __cplusplus");
+            out.new_line_if_not_start();
+            let condition = functions[0].cfg.to_condition(&self.config);
+            condition.write_before(&self.config, out);
+            out.write("#if defined(__STDC_VERSION__) && __STDC_VERSION__ >= 201112L");
+            out.new_line();
+            write!(
+                out,
+                "#define {}({}) _Generic(({}), \\",
+                group,
+                args,
+                functions[0].args[index]
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("arg{}", index))
+            );
+            for (i, function) in functions.iter().enumerate() {
+                let mut ty = Vec::new();
+                cdecl::write_type(
+                    &mut SourceWriter::new(&mut ty, self),
+                    &function.args[index].ty,
+                    &self.config,
+                );
                 out.new_line();
+                write!(
+                    out,
+                    "    {}: {}{}",
+                    String::from_utf8(ty).unwrap(),
+                    function.path.name(),
+                    if i == functions.len() - 1 {
+                        ")"
+                    } else {
+                        ", \\"
+                    }
+                );
             }
+            write!(out, "({})", args);
+            out.new_line();
+            out.write("#endif");
+            condition.write_after(&self.config, out);
+            out.new_line();
+        }
+    }
 
-            for global in &self.globals {
-                out.new_line_if_not_start();
-                global.write(&self.config, &mut out);
-                out.new_line();
+    /// Defines the `g_autoptr` cleanup of the opaque types freed by an
+    /// exported `name_unref` or `name_free` function, or by the `name_free`
+    /// function of a boxed type.
+    fn write_autoptr_cleanups<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let mut cleanups = Vec::new();
+        for item in &self.items {
+            let name = match *item {
+                ItemContainer::OpaqueItem(ref o) => o.export_name(),
+                _ => continue,
+            };
+            let snake = name.to_snake_case();
+            let exported = ["unref", "free"].iter().find_map(|suffix| {
+                let free = format!("{}_{}", snake, suffix);
+                self.functions.iter().find(|f| {
+                    f.path.name() == free && f.args.len() == 1 && points_to(&f.args[0].ty, name)
+                })
+            });
+            match exported {
+                Some(function) => {
+                    cleanups.push((name, function.path.name().to_owned(), function.cfg.clone()))
+                }
+                None if self.gobjects.iter().any(|x| x.is_boxed() && x.name == name) => {
+                    cleanups.push((name, format!("{}_free", snake), None))
+                }
+                None => {}
             }
+        }
+        if cleanups.is_empty() {
+            return;
+        }
 
-            for function in &self.functions {
-                out.new_line_if_not_start();
-                function.write(&self.config, &mut out);
-                out.new_line();
+        // g_autoptr is available since GLib 2.44.
+        out.new_line_if_not_start();
+        out.write("#if GLIB_CHECK_VERSION(2, 44, 0)");
+        out.new_line();
+        for (name, free, cfg) in cleanups {
+            let condition = cfg.to_condition(&self.config);
+            condition.write_before(&self.config, out);
+            write!(out, "G_DEFINE_AUTOPTR_CLEANUP_FUNC({}, {})", name, free);
+            out.new_line();
+            condition.write_after(&self.config, out);
+        }
+        out.write("#endif");
+        out.new_line();
+    }
+
+    /// Writes `template`, with its placeholders replaced by the sections of
+    /// the bindings. Only the template is searched for placeholders, never
+    /// the sections written in it.
+    fn write_template<F: Write>(&self, out: &mut SourceWriter<F>, template: &str) {
+        const SECTIONS: [&str; 4] = ["includes", "constants", "typedefs", "functions"];
+
+        let mut contents = String::new();
+        let mut written = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            contents.push_str(&rest[..start]);
+            rest = &rest[start + 2..];
+            let name = rest.find("}}").map(|end| &rest[..end]).unwrap_or("");
+            if SECTIONS.contains(&name) {
+                let mut section = Vec::new();
+                self.write_section(&mut SourceWriter::new(&mut section, self), name);
+                let section = String::from_utf8(section).unwrap();
+                contents.push_str(section.trim_matches('\n'));
+                written.push(name);
+                rest = &rest[name.len() + 2..];
+                continue;
             }
+            if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                warn!("Unknown placeholder {{{{{}}}}} in the template.", name);
+            }
+            contents.push_str("{{");
+        }
+        contents.push_str(rest);
 
-            if self.config.cpp_compatible_c() {
-                out.new_line();
-                out.write("#ifdef __cplusplus");
+        for name in &SECTIONS[1..] {
+            if !written.contains(name) && !self.is_section_empty(name) {
+                warn!(
+                    "The template has no {{{{{}}}}} placeholder, so the {} aren't written.",
+                    name,
+                    if *name == "typedefs" { "types" } else { name }
+                );
             }
+        }
+        write!(out, "{}", contents);
+    }
 
-            if self.config.language == Language::Cxx || self.config.cpp_compatible_c() {
-                out.new_line();
-                out.write("} // extern \"C\"");
-                out.new_line();
+    /// Whether nothing is written in the section of the bindings replacing
+    /// the `{{name}}` placeholder of a template, other than the includes.
+    fn is_section_empty(&self, name: &str) -> bool {
+        match name {
+            "constants" => self.constants.is_empty(),
+            "typedefs" => self.items.is_empty() && self.gobjects.is_empty(),
+            _ => {
+                self.functions.is_empty()
+                    && self.globals.is_empty()
+                    && !self.has_gobject_functions()
             }
+        }
+    }
 
-            if self.config.cpp_compatible_c() {
-                out.write("#endif // __cplusplus");
-                out.new_line();
+    /// Writes the section of the bindings replacing the `{{name}}`
+    /// placeholder of a template.
+    fn write_section<F: Write>(&self, out: &mut SourceWriter<F>, name: &str) {
+        if name == "includes" {
+            self.write_includes(out);
+            self.write_export_macro(out);
+            self.write_wasm_keepalive(out);
+            self.write_aligned_macro(out);
+            return;
+        }
+        if self.is_section_empty(name) {
+            return;
+        }
+
+        let ignore_deprecations = self.has_deprecated();
+        if ignore_deprecations {
+            self.write_ignore_deprecations(out, true);
+        }
+        self.open_namespaces(out);
+        match name {
+            "constants" => self.write_constants(out, None),
+            "typedefs" => {
+                self.write_items(out);
+                self.write_after_items(out);
             }
+            _ => self.write_functions(out),
         }
+        self.close_namespaces(out);
+        if ignore_deprecations {
+            self.write_ignore_deprecations(out, false);
+        }
+    }
+
+    pub fn write<F: Write>(&self, file: F) {
+        if self.config.language == Language::Gir {
+            self.write_gir(file);
+            return;
+        }
+        if self.config.language == Language::CSharp {
+            let mut out = SourceWriter::new(file, self);
+            CSharpWriter::new(&self.config, &self.items).write(
+                &mut out,
+                &self.items,
+                &self.constants,
+                &self.functions,
+            );
+            return;
+        }
+        if self.config.language == Language::Vapi {
+            self.write_vapi(file, None);
+            return;
+        }
+        if self.config.language == Language::Ctypes {
+            let mut out = SourceWriter::new(file, self);
+            CtypesWriter::new(&self.config, &self.items).write(
+                &mut out,
+                &self.items,
+                &self.constants,
+                &self.functions,
+            );
+            return;
+        }
+        if self.config.language == Language::Napi {
+            let mut out = SourceWriter::new(file, self);
+            NapiWriter::new(&self.config, &self.items).write(&mut out, &self.functions);
+            return;
+        }
+
+        let mut out = SourceWriter::new(file, self);
+
+        if let Some(ref template) = self.template {
+            self.write_template(&mut out, template);
+            return;
+        }
+
+        self.write_headers(&mut out);
+        self.write_export_macro(&mut out);
+        self.write_wasm_keepalive(&mut out);
+        self.write_aligned_macro(&mut out);
+        let ignore_deprecations = self.has_deprecated();
+        if ignore_deprecations {
+            self.write_ignore_deprecations(&mut out, true);
+        }
+
+        self.open_namespaces(&mut out);
+        self.write_constants(&mut out, Some(true));
+        self.write_items(&mut out);
+        self.write_constants(&mut out, Some(false));
+        self.write_after_items(&mut out);
+        self.write_functions(&mut out);
 
         if self.config.language == Language::Cython
             && self.globals.is_empty()
@@ -369,6 +1720,10 @@ impl Bindings {
 
         self.close_namespaces(&mut out);
 
+        if ignore_deprecations {
+            self.write_ignore_deprecations(&mut out, false);
+        }
+
         if let Some(f) = self.config.include_guard() {
             out.new_line_if_not_start();
             if self.config.language == Language::C {
@@ -387,6 +1742,201 @@ impl Bindings {
         }
     }
 
+    /// Returns the structs and unions of the bindings that rustc computed a
+    /// layout for, with the C type to name them and the offsets of the
+    /// fields that can be checked.
+    fn item_layouts(&self) -> Vec<ItemLayout<'_>> {
+        let mut layouts = Vec::new();
+        for item in &self.items {
+            let (keyword, item, fields) = match *item {
+                ItemContainer::Struct(ref x) => ("struct", x as &dyn Item, Some(&x.fields)),
+                ItemContainer::Union(ref x) => ("union", x as &dyn Item, None),
+                _ => continue,
+            };
+            if self.inline_members.contains(item.path()) {
+                continue;
+            }
+            // Only the types of the crate are matched, by their Rust path.
+            let layout = match item
+                .annotations()
+                .rust_path
+                .as_ref()
+                .and_then(|x| self.layouts.get(x))
+            {
+                Some(layout) => layout,
+                None => continue,
+            };
+            let name = item.export_name();
+            let ty = if self.config.language == Language::C && !self.config.style.generate_typedef()
+            {
+                format!("{} {}", keyword, name)
+            } else {
+                name.to_owned()
+            };
+            // The fields were renamed, so they are matched with the ones of
+            // rustc by position; bitfields have no offset.
+            let offsets = match fields {
+                Some(fields) if fields.len() == layout.fields.len() => fields
+                    .iter()
+                    .zip(&layout.fields)
+                    .filter(|(field, _)| field.bitfield().is_none())
+                    .map(|(field, &(_, offset))| (field.name.as_str(), offset))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            layouts.push((ty, name, layout, offsets));
+        }
+        layouts
+    }
+
+    /// Writes `#define FOO_OFFSETOF_BAR n` constants for the fields of the
+    /// structs.
+    fn write_field_offset_defines<F: Write>(&self, out: &mut SourceWriter<F>) {
+        match self.config.language {
+            Language::C | Language::Cxx => {}
+            _ => return,
+        }
+
+        for (_, name, _, offsets) in self.item_layouts() {
+            if offsets.is_empty() {
+                continue;
+            }
+            out.new_line_if_not_start();
+            let prefix = RenameRule::ScreamingSnakeCase.apply(name, IdentifierType::Type);
+            for (field, offset) in offsets {
+                let field = RenameRule::ScreamingSnakeCase.apply(field, IdentifierType::Type);
+                write!(out, "#define {}_OFFSETOF_{} {}", prefix, field, offset);
+                out.new_line();
+            }
+        }
+    }
+
+    /// Checks that the size and alignment of the structs and unions, and the
+    /// offsets of the fields of the structs, match the ones rustc computed,
+    /// so that ABI drift is caught at compile time.
+    fn write_layout_asserts<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let (static_assert, alignof) = match self.config.language {
+            Language::C => ("_Static_assert", "_Alignof"),
+            Language::Cxx => ("static_assert", "alignof"),
+            _ => return,
+        };
+
+        let layouts = self.item_layouts();
+        if layouts.is_empty() {
+            return;
+        }
+
+        out.new_line_if_not_start();
+        if self.config.cpp_compatible_c() {
+            out.write("#ifndef __cplusplus");
+            out.new_line();
+        }
+        for (ty, name, layout, offsets) in layouts {
+            // A flexible array member replacing a `[T; 1]` array doesn't
+            // count in the size.
+            let flexible_one = self.items.iter().any(|item| match *item {
+                ItemContainer::Struct(ref x) if x.export_name() == name => {
+                    x.fields.last().map_or(false, |field| match field.ty {
+                        Type::Array(_, ArrayLength::Value(ref length)) => {
+                            field.is_flexible_array() && length == "1"
+                        }
+                        _ => false,
+                    })
+                }
+                _ => false,
+            });
+            if !flexible_one {
+                write!(
+                    out,
+                    "{}(sizeof({}) == {}, \"unexpected size of {}\");",
+                    static_assert, ty, layout.size, name
+                );
+                out.new_line();
+            }
+            write!(
+                out,
+                "{}({}({}) == {}, \"unexpected alignment of {}\");",
+                static_assert, alignof, ty, layout.align, name
+            );
+            out.new_line();
+            for (field, offset) in offsets {
+                write!(
+                    out,
+                    "{}(offsetof({}, {}) == {}, \"unexpected offset of {}::{}\");",
+                    static_assert, ty, field, offset, name, field
+                );
+                out.new_line();
+            }
+        }
+        if self.config.cpp_compatible_c() {
+            out.write("#endif // __cplusplus");
+            out.new_line();
+        }
+    }
+
+    /// Writes the C source defining the `get_type` functions declared in
+    /// gobject mode which the crate doesn't export, those of the boxed types
+    /// and of the enums, `header` being the header declaring them.
+    #[allow(unused)]
+    pub fn write_c_source<F: Write>(&self, file: F, header: &str) {
+        if self.config.language != Language::C {
+            warn!("Only C bindings can have a C source.");
+            return;
+        }
+        let mut out = SourceWriter::new(file, self);
+        if let Some(ref f) = self.config.autogen_warning {
+            write!(out, "{}", f);
+            out.new_line();
+        }
+        out.new_line_if_not_start();
+        write!(out, "#include \"{}\"", header);
+        out.new_line();
+
+        let exported: Vec<&str> = self.functions.iter().map(|f| f.path.name()).collect();
+        for gobject in self.gobjects.iter().filter(|x| x.is_boxed()) {
+            if !exported.contains(&gobject.get_type_function().as_str()) {
+                out.new_line();
+                gobject.write_boxed_type_definition(&mut out);
+                out.new_line();
+            }
+        }
+        for enumeration in self.gobject_enums() {
+            if !exported.contains(&enumeration.get_type_function().as_str()) {
+                out.new_line();
+                enumeration.write_get_type(&self.config, &mut out);
+                out.new_line();
+            }
+        }
+    }
+
+    /// Writes a GObject introspection repository describing the bindings,
+    /// whatever the configured language.
+    pub fn write_gir<F: Write>(&self, file: F) {
+        let mut out = SourceWriter::new(file, self);
+
+        GirWriter::new(&self.config).write(
+            &mut out,
+            &self.items,
+            &self.constants,
+            &self.functions,
+            &self.gobjects,
+        );
+    }
+
+    /// Writes a Vala API file describing the bindings, declared in `header`
+    /// unless `vapi.headers` or `gir.c_includes` are set.
+    pub fn write_vapi<F: Write>(&self, file: F, header: Option<&str>) {
+        let mut out = SourceWriter::new(file, self);
+
+        VapiWriter::new(&self.config, &self.items, &self.gobjects, header).write(
+            &mut out,
+            &self.items,
+            &self.constants,
+            &self.functions,
+            &self.gobjects,
+        );
+    }
+
     fn all_namespaces(&self) -> Vec<&str> {
         if self.config.language != Language::Cxx && !self.config.cpp_compatible_c() {
             return vec![];
@@ -400,6 +1950,9 @@ impl Bindings {
                 ret.push(&**namespace);
             }
         }
+        if let Some(ref namespace) = self.config.cpp.inline_namespace {
+            ret.push(namespace);
+        }
         ret
     }
 
@@ -430,8 +1983,16 @@ impl Bindings {
             out.write("#ifdef __cplusplus");
         }
 
-        for namespace in namespaces {
+        let inline = match op {
+            NamespaceOperation::Open => self.config.cpp.inline_namespace.as_ref(),
+            NamespaceOperation::Close => None,
+        };
+        let count = namespaces.len();
+        for (i, namespace) in namespaces.into_iter().enumerate() {
             out.new_line();
+            if inline.is_some() && i + 1 == count {
+                out.write("inline ");
+            }
             match op {
                 NamespaceOperation::Open => write!(out, "namespace {} {{", namespace),
                 NamespaceOperation::Close => write!(out, "}} // namespace {}", namespace),
@@ -453,3 +2014,22 @@ impl Bindings {
         self.open_close_namespaces(NamespaceOperation::Close, out);
     }
 }
+
+/// Takes the path and the contents of a file of the bindings, and returns
+/// whether the file changed.
+type WriteFile<'a> = dyn FnMut(&path::Path, &[u8]) -> bool + 'a;
+
+/// Writes `contents` to `path`, unless it already has them, and returns
+/// whether it did.
+pub(crate) fn write_if_changed(path: &path::Path, contents: &[u8]) -> bool {
+    if let Ok(old_contents) = fs::read(path) {
+        if old_contents == contents {
+            return false;
+        }
+    } else if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    let mut file = File::create(path).unwrap();
+    file.write_all(contents).unwrap();
+    true
+}
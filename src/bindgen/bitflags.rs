@@ -5,6 +5,8 @@
 use proc_macro2::TokenStream;
 use syn::parse::{Parse, ParseStream, Parser, Result as ParseResult};
 
+use crate::bindgen::ir::Literal;
+
 // $(#[$outer:meta])*
 // ($($vis:tt)*) $BitFlags:ident: $T:ty {
 //     $(
@@ -51,6 +53,30 @@ impl Bitflags {
 
         (struct_, impl_)
     }
+
+    /// Expands the flags to a fieldless enum with the integer type of the
+    /// flags as representation, so that they are OR-able in C.
+    pub fn expand_enum(&self) -> syn::ItemEnum {
+        let Bitflags {
+            ref attrs,
+            ref vis,
+            ref name,
+            ref repr,
+            ref flags,
+            ..
+        } = *self;
+
+        let attrs = attrs.iter().filter(|attr| !attr.path.is_ident("repr"));
+        let variants = flags.expand_variants(name);
+        parse_quote! {
+            /// cbindgen:bitflags
+            #(#attrs)*
+            #[repr(#repr)]
+            #vis enum #name {
+                #variants
+            }
+        }
+    }
 }
 
 impl Parse for Bitflags {
@@ -92,6 +118,19 @@ impl Flag {
             pub const #name : #struct_name = #struct_name { bits: (#value) as #repr };
         }
     }
+
+    fn expand_variant(&self) -> TokenStream {
+        let Flag {
+            ref attrs,
+            ref name,
+            ref value,
+            ..
+        } = *self;
+        quote! {
+            #(#attrs)*
+            #name = #value,
+        }
+    }
 }
 
 impl Parse for Flag {
@@ -130,6 +169,25 @@ impl Flags {
         }
         ts
     }
+
+    /// Expands the flags to enum variants, skipping the ones whose value
+    /// can't be evaluated, like `Self::A.bits | Self::B.bits`.
+    fn expand_variants(&self, enum_name: &syn::Ident) -> TokenStream {
+        let mut ts = quote! {};
+        for flag in &self.0 {
+            if Literal::load(&flag.value).is_err() {
+                warn_at!(
+                    flag.value,
+                    "Skip {}::{} - (its value can't be evaluated).",
+                    enum_name,
+                    flag.name
+                );
+                continue;
+            }
+            ts.extend(flag.expand_variant());
+        }
+        ts
+    }
 }
 
 pub fn parse(tokens: TokenStream) -> ParseResult<Bitflags> {
@@ -2,20 +2,44 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
+use std::fs;
 use std::path;
 
 use crate::bindgen::bindings::Bindings;
+use crate::bindgen::cargo::cargo_layout::TypeLayout;
+use crate::bindgen::cargo::cargo_metadata::PackageRef;
 use crate::bindgen::cargo::Cargo;
-use crate::bindgen::config::{Braces, Config, Language, Profile, Style};
+use crate::bindgen::config::{
+    Braces, Config, ItemKind, ItemType, ItemWrittenFn, Language, MangleFn, Profile, RenameFn, Style,
+};
 use crate::bindgen::error::Error;
 use crate::bindgen::library::Library;
 use crate::bindgen::parser::{self, Parse};
+use crate::bindgen::targets::{TargetBindings, TargetCfgs};
+
+/// An item to add to the bindings, as if it was declared by the crate.
+#[allow(unused)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemDecl {
+    /// Rust items, e.g. `#[repr(C)] pub struct Foo { x: i32 }`.
+    Rust(String),
+    /// A type alias, e.g. `name: "Handle", ty: "*mut Foo"`.
+    Typedef { name: String, ty: String },
+    /// A type only declared in the bindings, e.g. `typedef struct Foo Foo;`.
+    Opaque(String),
+    /// The signature of a function exported by some other code, e.g.
+    /// `fn foo_new() -> *mut Foo`.
+    Function(String),
+}
 
 /// A builder for generating a bindings header.
 #[derive(Debug, Clone)]
 pub struct Builder {
     config: Config,
     srcs: Vec<path::PathBuf>,
+    ir_files: Vec<path::PathBuf>,
+    extra_declarations: Vec<ItemDecl>,
     lib: Option<(path::PathBuf, Option<String>)>,
     lib_cargo: Option<Cargo>,
     std_types: bool,
@@ -28,6 +52,8 @@ impl Builder {
         Builder {
             config: Config::default(),
             srcs: Vec::new(),
+            ir_files: Vec::new(),
+            extra_declarations: Vec::new(),
             lib: None,
             lib_cargo: None,
             std_types: true,
@@ -140,7 +166,7 @@ impl Builder {
 
     #[allow(unused)]
     pub fn with_gobject(mut self, gobject: bool) -> Builder {
-        self.config.gobject = gobject;
+        self.config.gobject.enabled = gobject;
         self
     }
 
@@ -237,6 +263,18 @@ impl Builder {
         self
     }
 
+    #[allow(unused)]
+    pub fn with_parse_cache(mut self, cache: bool) -> Builder {
+        self.config.parse.cache = cache;
+        self
+    }
+
+    #[allow(unused)]
+    pub fn with_parse_jobs(mut self, jobs: usize) -> Builder {
+        self.config.parse.jobs = jobs;
+        self
+    }
+
     #[allow(unused)]
     pub fn with_parse_extra_bindings<S: AsRef<str>>(mut self, extra_bindings: &[S]) -> Builder {
         self.config.parse.extra_bindings = extra_bindings
@@ -288,6 +326,53 @@ impl Builder {
         self
     }
 
+    /// Computes the names to export the types and constants as, for the ones
+    /// `[export.rename]` doesn't list.
+    #[allow(unused)]
+    pub fn with_rename_fn<F>(mut self, rename: F) -> Builder
+    where
+        F: Fn(ItemType, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.config.export.rename_fn = Some(RenameFn::new(rename));
+        self
+    }
+
+    /// Computes the names of the instantiations of generic types, given the
+    /// name of the type and the mangled names of its generic arguments.
+    #[allow(unused)]
+    pub fn with_mangle_fn<F>(mut self, mangle: F) -> Builder
+    where
+        F: Fn(&str, &[String]) -> Option<String> + Send + Sync + 'static,
+    {
+        self.config.export.mangle.mangle_fn = Some(MangleFn::new(mangle));
+        self
+    }
+
+    /// Rewrites the text written for each constant, global, type and function
+    /// of C, C++ and Cython bindings, e.g. to wrap it in macros.
+    #[allow(unused)]
+    pub fn with_item_written_hook<F>(mut self, hook: F) -> Builder
+    where
+        F: Fn(&ItemKind, &mut String) + Send + Sync + 'static,
+    {
+        self.config.item_written_hook = Some(ItemWrittenFn::new(hook));
+        self
+    }
+
+    #[allow(unused)]
+    pub fn with_extra_declaration(mut self, decl: ItemDecl) -> Builder {
+        self.extra_declarations.push(decl);
+        self
+    }
+
+    /// Adds the items of an IR file written by `--emit-ir` or
+    /// `Bindings::write_ir_to_file`.
+    #[allow(unused)]
+    pub fn with_ir_file<P: AsRef<path::Path>>(mut self, path: P) -> Builder {
+        self.ir_files.push(path.as_ref().to_owned());
+        self
+    }
+
     #[allow(unused)]
     pub fn with_crate<P: AsRef<path::Path>>(mut self, lib_dir: P) -> Builder {
         debug_assert!(self.lib.is_none());
@@ -327,8 +412,90 @@ impl Builder {
         self
     }
 
+    /// Writes the bindings in a template file, see `Config::template`.
+    #[allow(unused)]
+    pub fn with_template<P: AsRef<path::Path>>(mut self, template: P) -> Builder {
+        self.config.template = Some(template.as_ref().to_string_lossy().into_owned());
+        self
+    }
+
     pub fn generate(self) -> Result<Bindings, Error> {
+        let template = self.load_template()?;
+        let (library, source_files, layouts, package) = self.parse()?;
+        let mut bindings = library.generate()?;
+        bindings.source_files = source_files;
+        bindings.layouts = layouts;
+        bindings.package = package;
+        bindings.template = template;
+        Ok(bindings)
+    }
+
+    /// Generates the bindings of each of `targets`, parsing the crate once
+    /// and resolving its cfgs with the ones of each target.
+    #[allow(unused)]
+    pub fn generate_for_targets<S: AsRef<str>>(
+        self,
+        targets: &[S],
+    ) -> Result<TargetBindings, Error> {
+        let template = self.load_template()?;
+        let (library, source_files, layouts, package) = self.parse()?;
+        let mut result = Vec::new();
+        for target in targets {
+            let target = target.as_ref();
+            let cfgs = TargetCfgs::load(target)?;
+            let mut library = library.clone();
+            library.resolve_target_cfgs(&cfgs);
+            let mut bindings = library.generate()?;
+            bindings.source_files = source_files.clone();
+            bindings.layouts = layouts.clone();
+            bindings.package = package.clone();
+            bindings.template = template.clone();
+            result.push((target.to_owned(), cfgs, bindings));
+        }
+        Ok(TargetBindings::new(result))
+    }
+
+    /// Parses the crate and lists the problems of the config: obsolete
+    /// options, and `[export]` entries which don't match any parsed item.
+    /// Unknown keys are already rejected when loading the config.
+    #[allow(unused)]
+    pub fn validate_config(self) -> Result<Vec<String>, Error> {
+        let mut issues = Vec::new();
+        if self.config.parse.expand.shorthand {
+            issues.push(
+                "parse.expand = [\"crate\"] is obsolete, use parse.expand.crates instead."
+                    .to_owned(),
+            );
+        }
+        let (library, ..) = self.parse()?;
+        issues.extend(library.config_issues());
+        Ok(issues)
+    }
+
+    fn load_template(&self) -> Result<Option<String>, Error> {
+        match self.config.template {
+            Some(ref path) => fs::read_to_string(path)
+                .map(Some)
+                .map_err(|x| Error::Template(path.clone(), x)),
+            None => Ok(None),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn parse(
+        mut self,
+    ) -> Result<
+        (
+            Library,
+            Vec<path::PathBuf>,
+            HashMap<String, TypeLayout>,
+            Option<PackageRef>,
+        ),
+        Error,
+    > {
         let mut result = Parse::new();
+        let mut layouts = None;
+        let mut package = None;
 
         if self.std_types {
             result.add_std_types(&self.config);
@@ -350,12 +517,62 @@ impl Builder {
                 /* existing_metadata = */ None,
             )?;
 
+            if self.config.needs_layouts() {
+                layouts = Some(type_layouts(&cargo)?);
+            }
+            package = Some(cargo.binding_crate_ref());
             result.extend_with(&parser::parse_lib(cargo, &self.config)?);
         } else if let Some(cargo) = self.lib_cargo.clone() {
+            package = Some(cargo.binding_crate_ref());
+            if self.config.needs_layouts() {
+                layouts = Some(type_layouts(&cargo)?);
+            }
             result.extend_with(&parser::parse_lib(cargo, &self.config)?);
         }
 
-        Library::new(
+        for x in &self.ir_files {
+            result.extend_with(&parser::parse_ir_file(x)?);
+        }
+
+        for decl in &self.extra_declarations {
+            result.extend_with(&parser::parse_declaration(decl, &self.config)?);
+        }
+
+        if self.config.needs_layouts() && layouts.is_none() {
+            warn!("Can't check the type layouts without a crate to compile.");
+        }
+
+        match package {
+            Some(ref package) => self.config.resolve_include_guard("{crate}", &package.name),
+            None if self
+                .config
+                .include_guard
+                .as_ref()
+                .map_or(false, |x| x.contains("{crate}")) =>
+            {
+                warn!("The include guard uses {{crate}}, but there's no crate.")
+            }
+            None => {}
+        }
+
+        // C# imports the functions from the library cargo builds, which is
+        // named after the crate.
+        if self.config.language == Language::CSharp && self.config.csharp.library.is_none() {
+            match package {
+                Some(ref package) => {
+                    self.config.csharp.library = Some(package.name.replace('-', "_"));
+                }
+                None => {
+                    return Err(Error::Config(
+                        "C# bindings need `csharp.library`, the library to import the \
+                         functions from."
+                            .to_owned(),
+                    ));
+                }
+            }
+        }
+
+        let library = Library::new(
             self.config,
             result.constants,
             result.globals,
@@ -366,14 +583,48 @@ impl Builder {
             result.typedefs,
             result.functions,
             result.gobjects,
-        )
-        .generate()
+            result.shadowed,
+            result.skipped,
+        );
+        Ok((
+            library,
+            result.source_files,
+            layouts.unwrap_or_default(),
+            package,
+        ))
     }
 }
 
+fn type_layouts(cargo: &Cargo) -> Result<HashMap<String, TypeLayout>, Error> {
+    let binding_crate = cargo.binding_crate_ref();
+    cargo
+        .type_layouts(&binding_crate)
+        .map_err(|x| Error::CargoLayout(binding_crate.name.clone(), x))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bindgen::cargo::cargo_layout::{crate_layouts, parse_type_sizes};
+    use crate::bindgen::config::{
+        CtypesConfig, GirConfig, NapiConfig, RenameRule, Unresolved, VapiConfig,
+    };
+    use std::fs;
+
+    /// Generates the bindings of `builder` with `source` declared.
+    fn generate(builder: Builder, source: &str) -> Bindings {
+        builder
+            .with_extra_declaration(ItemDecl::Rust(source.to_owned()))
+            .generate()
+            .unwrap()
+    }
+
+    /// Collects what `write` writes to a string.
+    fn written<F: FnOnce(&mut Vec<u8>)>(write: F) -> String {
+        let mut out = Vec::new();
+        write(&mut out);
+        String::from_utf8(out).unwrap()
+    }
 
     #[test]
     fn with_style() {
@@ -382,4 +633,1055 @@ mod tests {
             Builder::new().with_style(Style::Tag).config.style
         );
     }
+
+    #[test]
+    fn with_extra_declaration() {
+        let bindings = Builder::new()
+            .with_language(Language::C)
+            .with_no_includes()
+            .with_extra_declaration(ItemDecl::Opaque("Foo".to_owned()))
+            .with_extra_declaration(ItemDecl::Typedef {
+                name: "FooHandle".to_owned(),
+                ty: "*mut Foo".to_owned(),
+            })
+            .with_extra_declaration(ItemDecl::Function("fn foo_new() -> FooHandle".to_owned()))
+            .generate()
+            .unwrap();
+        let out = written(|out| bindings.write(out));
+
+        assert!(out.contains("typedef struct Foo Foo;"));
+        assert!(out.contains("typedef struct Foo *FooHandle;"));
+        assert!(out.contains("extern FooHandle foo_new(void);"));
+    }
+
+    #[test]
+    fn with_rename_fn() {
+        let mut builder = Builder::new();
+        builder
+            .config
+            .export
+            .rename
+            .insert("Bar".to_owned(), "RenamedBar".to_owned());
+        let bindings = generate(
+            builder
+                .with_language(Language::C)
+                .with_no_includes()
+                .with_rename_fn(|item_type, name| match item_type {
+                    ItemType::Structs => Some(format!("my_{}", name)),
+                    _ => None,
+                }),
+            "#[repr(C)] pub struct Foo { x: i32 }
+             #[repr(C)] pub struct Bar { foo: Foo }
+             pub type Baz = Bar;
+             #[no_mangle] pub extern \"C\" fn baz(bar: Baz) {}",
+        );
+        let out = written(|out| bindings.write(out));
+
+        assert!(out.contains("typedef struct my_Foo {"));
+        assert!(out.contains("struct my_Foo foo;"));
+        assert!(out.contains("typedef struct RenamedBar {"));
+        assert!(out.contains("typedef struct RenamedBar Baz;"));
+    }
+
+    #[test]
+    fn with_mangle_fn() {
+        let bindings = generate(
+            Builder::new()
+                .with_language(Language::C)
+                .with_no_includes()
+                .with_mangle_fn(|name, args| match name {
+                    "Vec" => Some(format!("vec_{}_t", args.join("_"))),
+                    _ => None,
+                }),
+            "#[repr(C)] pub struct Vec<T> { ptr: *mut T, len: usize }
+             #[repr(C)] pub struct Pair<A, B> { a: A, b: B }
+             #[no_mangle] pub extern \"C\" fn foo(v: Vec<u8>, p: Pair<Vec<i32>, u8>) {}",
+        );
+        let out = written(|out| bindings.write(out));
+
+        assert!(out.contains("} vec_u8_t;"));
+        assert!(out.contains("} Pair_vec_i32_t__u8;"));
+        assert!(out.contains("void foo(struct vec_u8_t v, struct Pair_vec_i32_t__u8 p);"));
+    }
+
+    #[test]
+    fn to_ir_json() {
+        let bindings = generate(
+            Builder::new(),
+            "/// A point.
+             #[repr(C)] pub struct Point { x: i32, y: i32 }
+             #[no_mangle] pub extern \"C\" fn origin() -> Point { unimplemented!() }",
+        );
+        let ir: serde_json::Value = serde_json::from_str(&bindings.to_ir_json()).unwrap();
+
+        let point = &ir["items"][0]["Struct"];
+        assert_eq!(point["export_name"], "Point");
+        assert_eq!(point["fields"].as_array().unwrap().len(), 2);
+        assert_eq!(point["documentation"]["doc_comment"][0], " A point.");
+        assert_eq!(ir["functions"][0]["path"], "origin");
+    }
+
+    #[test]
+    fn with_ir_file() {
+        let header = |builder: Builder| {
+            let bindings = builder.with_language(Language::C).generate().unwrap();
+            written(|out| bindings.write(out))
+        };
+
+        let builder = Builder::new().with_extra_declaration(ItemDecl::Rust(
+            "#[repr(C)] pub struct Point<T> { x: T, y: T }
+             pub const ORIGIN_X: i32 = 0;
+             #[no_mangle] pub extern \"C\" fn origin() -> Point<i32> { unimplemented!() }"
+                .to_owned(),
+        ));
+        let dir = tempfile::tempdir().unwrap();
+        let ir_path = dir.path().join("ir.json");
+        builder
+            .clone()
+            .generate()
+            .unwrap()
+            .write_ir_to_file(&ir_path);
+
+        assert_eq!(
+            header(builder),
+            header(Builder::new().with_ir_file(&ir_path))
+        );
+    }
+
+    #[test]
+    fn split_output() {
+        let mut builder = Builder::new()
+            .with_language(Language::C)
+            .with_include_guard("BINDINGS_H")
+            .with_extra_declaration(ItemDecl::Rust(
+                "pub mod geometry {
+                     #[repr(C)] pub struct Point { x: f32, y: f32 }
+                 }
+                 pub mod shapes {
+                     #[repr(C)] pub struct Circle { center: geometry::Point, radius: f32 }
+                     #[no_mangle] pub extern \"C\" fn circle_new() -> Circle { unimplemented!() }
+                 }"
+                .to_owned(),
+            ));
+        builder.config.output.split.enabled = true;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bindings.h");
+        assert!(builder.generate().unwrap().write_to_file(&path));
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        let umbrella = read("bindings.h");
+        assert!(umbrella.contains("#include \"bindings/geometry.h\""));
+        assert!(umbrella.contains("#include \"bindings/shapes.h\""));
+        let geometry = read("bindings/geometry.h");
+        assert!(geometry.contains("#ifndef BINDINGS_H_GEOMETRY"));
+        assert!(geometry.contains("typedef struct Point {"));
+        let shapes = read("bindings/shapes.h");
+        assert!(shapes.contains("#include \"geometry.h\""));
+        assert!(shapes.contains("typedef struct Circle {"));
+        assert!(shapes.contains("struct Circle circle_new(void);"));
+        assert!(!shapes.contains("typedef struct Point {"));
+    }
+
+    #[test]
+    fn private_header() {
+        let builder = Builder::new()
+            .with_language(Language::C)
+            .with_include_guard("BINDINGS_H")
+            .with_extra_declaration(ItemDecl::Rust(
+                "#[repr(C)] pub struct Widget { x: i32 }
+                 /// cbindgen:header=private
+                 #[repr(C)] pub struct WidgetPrivate { widget: Widget }
+                 #[no_mangle] pub extern \"C\" fn widget_new() -> Widget { unimplemented!() }
+                 /// cbindgen:header=private
+                 #[no_mangle] pub extern \"C\" fn widget_private(w: WidgetPrivate) {}"
+                    .to_owned(),
+            ));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("widget.h");
+        assert!(builder.generate().unwrap().write_to_file(&path));
+
+        let public = fs::read_to_string(&path).unwrap();
+        assert!(public.contains("typedef struct Widget {"));
+        assert!(public.contains("struct Widget widget_new(void);"));
+        assert!(!public.contains("WidgetPrivate"));
+        let private = fs::read_to_string(dir.path().join("widget-private.h")).unwrap();
+        assert!(private.contains("#ifndef BINDINGS_H_PRIVATE"));
+        assert!(private.contains("#include \"widget.h\""));
+        assert!(private.contains("typedef struct WidgetPrivate {"));
+        assert!(private.contains("void widget_private(struct WidgetPrivate w);"));
+        assert!(!private.contains("typedef struct Widget {"));
+    }
+
+    #[test]
+    fn cpp_wrappers() {
+        let mut builder = Builder::new()
+            .with_language(Language::C)
+            .with_include_guard("PARSER_H")
+            .with_extra_declaration(ItemDecl::Rust(
+                "pub struct Parser { depth: u32 }
+                 pub struct Token { len: usize }
+                 #[no_mangle] pub extern \"C\" fn parser_new(depth: u32) -> *mut Parser { unimplemented!() }
+                 #[no_mangle] pub extern \"C\" fn parser_free(parser: *mut Parser) {}
+                 #[no_mangle] pub extern \"C\" fn token_new() -> *mut Token { unimplemented!() }"
+                    .to_owned(),
+            ));
+        builder.config.cpp.wrappers.enabled = true;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("parser.h");
+        assert!(builder.generate().unwrap().write_to_file(&path));
+
+        let wrappers = fs::read_to_string(dir.path().join("parser.hpp")).unwrap();
+        assert!(wrappers.contains("#ifndef PARSER_H_WRAPPERS"));
+        assert!(wrappers.contains("#include \"parser.h\""));
+        assert!(wrappers.contains("namespace wrappers {"));
+        assert!(wrappers.contains("class Parser {"));
+        assert!(wrappers.contains("explicit Parser(uint32_t depth) : ptr_(::parser_new(depth)) {}"));
+        assert!(wrappers.contains("::parser_free(ptr_);"));
+        assert!(wrappers.contains("Parser(Parser &&other) noexcept"));
+        assert!(wrappers.contains("::Parser *release() {"));
+        assert!(!wrappers.contains("class Token"));
+    }
+
+    #[test]
+    fn diff_against_file() {
+        let bindings = generate(
+            Builder::new().with_language(Language::C),
+            "#[repr(C)] pub struct Point { x: i32, y: i32 }
+             #[no_mangle] pub extern \"C\" fn origin() -> Point { unimplemented!() }",
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("point.h");
+        assert!(bindings.write_to_file(&path));
+        assert_eq!(bindings.diff_against_file(&path), None);
+
+        let old = fs::read_to_string(&path).unwrap();
+        fs::write(&path, old.replace("int32_t y;", "int32_t z;")).unwrap();
+        let diff = bindings.diff_against_file(&path).unwrap();
+        let name = path.to_string_lossy();
+        assert!(diff.starts_with(&format!("--- {}\n+++ {}\n@@ -", name, name)));
+        assert!(diff.contains("\n   int32_t x;\n-  int32_t z;\n+  int32_t y;\n } Point;\n"));
+
+        let missing = bindings.diff_against_file(dir.path().join("missing.h"));
+        assert!(missing.unwrap().contains("@@ -0,0 +1,"));
+    }
+
+    #[test]
+    fn generate_for_targets() {
+        let builder = Builder::new()
+            .with_language(Language::C)
+            .with_extra_declaration(ItemDecl::Rust(
+                "#[repr(C)] pub struct Handle { id: u32, #[cfg(unix)] fd: i32 }
+                 #[cfg(windows)] #[no_mangle] pub extern \"C\" fn handle_win32(h: Handle) {}
+                 #[cfg(target_os = \"linux\")] #[no_mangle] pub extern \"C\" fn handle_linux(h: Handle) {}"
+                    .to_owned(),
+            ));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("handle.h");
+        let bindings = builder
+            .generate_for_targets(&["x86_64-unknown-linux-gnu", "x86_64-pc-windows-msvc"])
+            .unwrap();
+        assert!(bindings.write_to_files(&path, true));
+
+        let linux =
+            fs::read_to_string(dir.path().join("handle-x86_64-unknown-linux-gnu.h")).unwrap();
+        assert!(linux.contains("int32_t fd;"));
+        assert!(linux.contains("void handle_linux("));
+        assert!(!linux.contains("handle_win32"));
+        assert!(!linux.contains("#if"));
+
+        let windows =
+            fs::read_to_string(dir.path().join("handle-x86_64-pc-windows-msvc.h")).unwrap();
+        assert!(!windows.contains("int32_t fd;"));
+        assert!(windows.contains("void handle_win32("));
+        assert!(!windows.contains("handle_linux"));
+
+        let merged = fs::read_to_string(&path).unwrap();
+        assert!(merged.contains("#include \"handle-x86_64-unknown-linux-gnu.h\""));
+        assert!(merged.contains("#elif "));
+        assert!(merged.contains("#include \"handle-x86_64-pc-windows-msvc.h\""));
+        assert!(merged.contains("#error"));
+    }
+
+    #[test]
+    fn fetched_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |path: &str, contents: &str| {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write(
+            "home/registry/src/index.crates.io-1949cf8c6b5b557f/shapes-1.2.0/Cargo.toml",
+            "[package]\nname = \"shapes\"\nversion = \"1.2.0\"\n",
+        );
+        write(
+            "home/registry/src/index.crates.io-1949cf8c6b5b557f/shapes-1.2.0/src/lib.rs",
+            "#[repr(C)] pub struct Square { side: f32 }",
+        );
+        write(
+            "home/git/checkouts/colors-5c6a0e2f1b3d4a7e/4d5e6f7/Cargo.toml",
+            "[workspace]\nmembers = [\"crates/colors\"]\n",
+        );
+        write(
+            "home/git/checkouts/colors-5c6a0e2f1b3d4a7e/4d5e6f7/crates/colors/Cargo.toml",
+            "[package]\nname = \"colors\"\nversion = \"0.3.0\"\n[lib]\npath = \"colors.rs\"\n",
+        );
+        write(
+            "home/git/checkouts/colors-5c6a0e2f1b3d4a7e/4d5e6f7/crates/colors/colors.rs",
+            "#[repr(C)] pub struct Rgb { r: u8, g: u8, b: u8 }",
+        );
+        write(
+            "app/Cargo.toml",
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        );
+        write(
+            "app/src/lib.rs",
+            "#[no_mangle] pub extern \"C\" fn draw(square: Square, color: Rgb) {}",
+        );
+        write(
+            "app/Cargo.lock",
+            "[[package]]\nname = \"app\"\nversion = \"0.1.0\"\ndependencies = [\"colors\", \"shapes\"]\n\n\
+             [[package]]\nname = \"colors\"\nversion = \"0.3.0\"\n\
+             source = \"git+https://example.com/colors?branch=main#4d5e6f7a8b9c0d1e\"\n\n\
+             [[package]]\nname = \"shapes\"\nversion = \"1.2.0\"\n\
+             source = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+        );
+        // Metadata which doesn't list the dependencies, like one computed
+        // somewhere else.
+        let app = dir.path().join("app");
+        let metadata = serde_json::json!({
+            "packages": [{
+                "name": "app",
+                "version": "0.1.0",
+                "id": "app 0.1.0",
+                "source": null,
+                "dependencies": [],
+                "targets": [{
+                    "name": "app",
+                    "kind": ["lib"],
+                    "src_path": app.join("src").join("lib.rs"),
+                }],
+                "features": {},
+                "manifest_path": app.join("Cargo.toml"),
+            }],
+            "version": 1,
+            "workspace_root": app,
+        });
+        let metadata_path = dir.path().join("metadata.json");
+        fs::write(&metadata_path, metadata.to_string()).unwrap();
+
+        let cargo = Cargo::load(&app, None, None, true, false, Some(&metadata_path))
+            .unwrap()
+            .with_cargo_home(dir.path().join("home"));
+        let bindings = Builder::new()
+            .with_language(Language::C)
+            .with_parse_deps(true)
+            .with_cargo(cargo)
+            .generate();
+
+        let header = written(|out| bindings.unwrap().write(out));
+        assert!(header.contains("float side;"));
+        assert!(header.contains("uint8_t r;"));
+        assert!(header.contains("void draw(struct Square square, struct Rgb color);"));
+    }
+
+    #[test]
+    fn workspace_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut packages = vec![];
+        for name in &["widgets", "app", "gadgets"] {
+            let crate_dir = dir.path().join(name);
+            fs::create_dir_all(crate_dir.join("src")).unwrap();
+            let source = format!("#[no_mangle] pub extern \"C\" fn {}_init() {{}}", name);
+            fs::write(crate_dir.join("src").join("lib.rs"), source).unwrap();
+            packages.push(serde_json::json!({
+                "name": name,
+                "version": "0.1.0",
+                "id": format!("{} 0.1.0", name),
+                "source": null,
+                "dependencies": [],
+                "targets": [{
+                    "name": name,
+                    "kind": ["lib"],
+                    "src_path": crate_dir.join("src").join("lib.rs"),
+                }],
+                "features": {},
+                "manifest_path": crate_dir.join("Cargo.toml"),
+            }));
+        }
+        let metadata = serde_json::json!({
+            "packages": packages,
+            "version": 1,
+            "workspace_root": dir.path(),
+            "workspace_members": ["widgets 0.1.0", "gadgets 0.1.0"],
+        });
+        let metadata_path = dir.path().join("metadata.json");
+        fs::write(&metadata_path, metadata.to_string()).unwrap();
+
+        let workspace =
+            Cargo::load_workspace(dir.path(), None, false, false, Some(&metadata_path)).unwrap();
+        let members = workspace.workspace_members();
+        let names: Vec<_> = members.iter().map(|x| &*x.name).collect();
+        assert_eq!(names, ["gadgets", "widgets"]);
+
+        for member in &members {
+            let bindings = Builder::new()
+                .with_language(Language::C)
+                .with_cargo(workspace.for_member(member))
+                .generate()
+                .unwrap();
+            let header = written(|out| bindings.write(out));
+            assert!(header.contains(&format!("void {}_init(void);", member.name)));
+        }
+    }
+
+    #[test]
+    fn parse_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src").join("lib.rs");
+        fs::create_dir_all(src.parent().unwrap()).unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"cached\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            &src,
+            "#[no_mangle] pub extern \"C\" fn f() {}\n\
+             #[no_mangle] pub extern \"C\" fn g((a, b): (i32, i32)) {}\n",
+        )
+        .unwrap();
+        let metadata = serde_json::json!({
+            "packages": [{
+                "name": "cached",
+                "version": "0.1.0",
+                "id": "cached 0.1.0",
+                "source": null,
+                "dependencies": [],
+                "targets": [{"name": "cached", "kind": ["lib"], "src_path": src}],
+                "features": {},
+                "manifest_path": dir.path().join("Cargo.toml"),
+            }],
+            "version": 1,
+            "workspace_root": dir.path(),
+        });
+        let metadata_path = dir.path().join("metadata.json");
+        fs::write(&metadata_path, metadata.to_string()).unwrap();
+        let generate = |cache: bool| {
+            let cargo = Cargo::load(dir.path(), None, None, true, false, Some(&metadata_path));
+            let bindings = Builder::new()
+                .with_language(Language::C)
+                .with_cargo(cargo.unwrap())
+                .with_parse_cache(cache)
+                .generate()
+                .unwrap();
+            written(|out| bindings.write(out))
+        };
+
+        let cache_path = dir.path().join("target").join("cbindgen-cache.json");
+        let header = generate(false);
+        assert!(!cache_path.exists());
+
+        // The diagnostics of the cached files are kept to be logged again.
+        assert_eq!(generate(true), header);
+        let cache = fs::read_to_string(&cache_path).unwrap();
+        assert!(cache.contains("Cannot use fn cached::g"));
+        assert_eq!(generate(true), header);
+    }
+
+    #[test]
+    fn expanded_crate_depfile() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src").join("lib.rs");
+        let inner = dir.path().join("src").join("net").join("socket.rs");
+        fs::create_dir_all(inner.parent().unwrap()).unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"expanded\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(&src, "pub mod net;\n").unwrap();
+        fs::write(
+            &inner,
+            "#[no_mangle] pub extern \"C\" fn socket_open() {}\n",
+        )
+        .unwrap();
+        let metadata = serde_json::json!({
+            "packages": [{
+                "name": "expanded",
+                "version": "0.1.0",
+                "id": "expanded 0.1.0",
+                "source": null,
+                "dependencies": [],
+                "targets": [{"name": "expanded", "kind": ["lib"], "src_path": src}],
+                "features": {},
+                "manifest_path": dir.path().join("Cargo.toml"),
+            }],
+            "version": 1,
+            "workspace_root": dir.path(),
+        });
+        let metadata_path = dir.path().join("metadata.json");
+        fs::write(&metadata_path, metadata.to_string()).unwrap();
+
+        let cargo = Cargo::load(dir.path(), None, None, true, false, Some(&metadata_path))
+            .unwrap()
+            .with_expansion("pub mod net { #[no_mangle] pub extern \"C\" fn socket_open() {} }");
+        let mut builder = Builder::new().with_language(Language::C).with_cargo(cargo);
+        builder.config.parse.expand.crates = vec!["expanded".to_owned()];
+        let bindings = builder.generate().unwrap();
+        assert!(written(|out| bindings.write(out)).contains("void socket_open(void);"));
+
+        let header = dir.path().join("expanded.h");
+        let depfile = dir.path().join("expanded.d");
+        bindings.write_depfile(&header, &depfile);
+        assert_eq!(
+            fs::read_to_string(&depfile).unwrap(),
+            format!(
+                "{}: \\\n  {} \\\n  {}\n",
+                header.display(),
+                src.display(),
+                inner.display()
+            )
+        );
+    }
+
+    #[test]
+    fn with_extra_declaration_syntax_error() {
+        let result = Builder::new()
+            .with_extra_declaration(ItemDecl::Rust("pub struct".to_owned()))
+            .generate();
+        match result {
+            Err(Error::ParseSyntaxError { .. }) => {}
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn enum_gtypes() {
+        let bindings = generate(
+            Builder::new().with_language(Language::C).with_gobject(true),
+            "#[repr(C)] pub enum BufferKind { Plain, LineBuffered }
+             #[no_mangle] pub extern \"C\" fn buffer_kind(kind: BufferKind) {}",
+        );
+        let source = written(|out| bindings.write_c_source(out, "buffer.h"));
+        assert_eq!(
+            source,
+            "#include \"buffer.h\"
+
+GType buffer_kind_get_type(void) {
+  static gsize gtype_id = 0;
+  static const GEnumValue values[] = {
+    { Plain, \"Plain\", \"plain\" },
+    { LineBuffered, \"LineBuffered\", \"line-buffered\" },
+    { 0, NULL, NULL }
+  };
+  if (g_once_init_enter(&gtype_id)) {
+    GType new_type = g_enum_register_static(g_intern_static_string(\"BufferKind\"), values);
+    g_once_init_leave(&gtype_id, new_type);
+  }
+  return gtype_id;
+}
+"
+        );
+    }
+
+    #[test]
+    fn c_source_boxed_types() {
+        let bindings = generate(
+            Builder::new().with_language(Language::C).with_gobject(true),
+            "/// cbindgen:gobject=boxed
+             pub struct Range;
+             /// cbindgen:gobject=boxed
+             pub struct Span;
+             #[no_mangle] pub extern \"C\" fn span_get_type() -> usize {}
+             #[no_mangle] pub extern \"C\" fn range_new() -> *mut Range {}
+             #[no_mangle] pub extern \"C\" fn span_new() -> *mut Span {}",
+        );
+        let source = written(|out| bindings.write_c_source(out, "range.h"));
+        assert_eq!(
+            source,
+            "#include \"range.h\"
+
+G_DEFINE_BOXED_TYPE(Range, range, range_copy, range_free)
+"
+        );
+    }
+
+    #[test]
+    fn vapi() {
+        let config = Config {
+            language: Language::Vapi,
+            gir: GirConfig {
+                namespace: Some("Gbd".to_owned()),
+                ..Default::default()
+            },
+            vapi: VapiConfig {
+                headers: vec!["gbd.h".to_owned()],
+            },
+            ..Default::default()
+        };
+        let bindings = generate(
+            Builder::new().with_config(config),
+            "pub struct GbdBuffer;
+             #[repr(C)] pub enum GbdKind { Plain, Lines }
+             #[repr(C)] pub struct GbdRange { start: usize, kind: GbdKind }
+             #[no_mangle] pub extern \"C\" fn gbd_buffer_free(buffer: *mut GbdBuffer) {}
+             #[no_mangle] pub extern \"C\" fn gbd_buffer_name(
+                 buffer: *const GbdBuffer, range: *mut GbdRange) -> *const c_char {}",
+        );
+        let out = written(|out| bindings.write(out));
+
+        assert!(out.starts_with(
+            "[CCode (cprefix = \"Gbd\", lower_case_cprefix = \"gbd_\", \
+             cheader_filename = \"gbd.h\")]
+namespace Gbd {
+"
+        ));
+        assert!(out.contains(
+            "[CCode (cname = \"GbdBuffer\", free_function = \"gbd_buffer_free\", \
+             has_type_id = false)]
+  [Compact]
+  public class Buffer {
+  }
+"
+        ));
+        assert!(out.contains("[CCode (cname = \"GbdKind\", cprefix = \"\", has_type_id = false)]"));
+        assert!(out
+            .contains("public struct Range {\n    public uintptr start;\n    public Kind kind;\n"));
+        assert!(out.contains(
+            "[CCode (cname = \"gbd_buffer_name\")]
+  public static unowned string buffer_name (Buffer buffer, Range* range);"
+        ));
+    }
+
+    #[test]
+    fn napi() {
+        let config = Config {
+            language: Language::Napi,
+            napi: NapiConfig {
+                header: Some("buffer.h".to_owned()),
+            },
+            ..Default::default()
+        };
+        let bindings = generate(
+            Builder::new().with_config(config),
+            "pub struct Buffer;
+             #[repr(C)] pub struct Range { start: u32, end: u32 }
+             #[no_mangle] pub extern \"C\" fn buffer_find(
+                 buffer: *const Buffer, needle: *const c_char) -> i32 {}
+             #[no_mangle] pub extern \"C\" fn buffer_range(buffer: *const Buffer) -> Range {}",
+        );
+        let out = written(|out| bindings.write(out));
+
+        assert!(out.starts_with(
+            "#include <stdlib.h>
+#include <node_api.h>
+#include \"buffer.h\"
+"
+        ));
+        assert!(out.contains(
+            "static napi_value js_buffer_find(napi_env env, napi_callback_info info) {
+  size_t argc = 2;
+  napi_value argv[2];
+  NAPI_CALL(env, napi_get_cb_info(env, info, &argc, argv, NULL, NULL));
+  if (argc < 2) {
+    napi_throw_type_error(env, NULL, \"buffer_find expects 2 arguments\");
+    return NULL;
+  }
+  void *arg0;
+  NAPI_CALL(env, napi_get_value_external(env, argv[0], &arg0));
+  size_t arg1_len;
+  NAPI_CALL(env, napi_get_value_string_utf8(env, argv[1], NULL, 0, &arg1_len));
+  char *arg1 = malloc(arg1_len + 1);
+  NAPI_CALL(env, napi_get_value_string_utf8(env, argv[1], arg1, arg1_len + 1, &arg1_len));
+  int32_t ret = buffer_find(arg0, arg1);
+  free(arg1);
+  napi_value result;
+  NAPI_CALL(env, napi_create_int32(env, ret, &result));
+  return result;
+}
+"
+        ));
+        // Structs passed by value can't be marshalled.
+        assert!(!out.contains("js_buffer_range"));
+        assert!(out.contains(
+            "{ \"buffer_find\", NULL, js_buffer_find, NULL, NULL, NULL, napi_default, NULL },"
+        ));
+    }
+
+    #[test]
+    fn ctypes() {
+        let config = Config {
+            language: Language::Ctypes,
+            ctypes: CtypesConfig {
+                library: Some("libshapes.so".to_owned()),
+            },
+            ..Default::default()
+        };
+        let bindings = generate(
+            Builder::new().with_config(config),
+            "#[repr(u8)] pub enum Kind { Circle, Square = 4 }
+             #[repr(C)] pub struct Shape { kind: Kind, next: *mut Shape, size: [f32; 2] }
+             #[no_mangle] pub extern \"C\" fn shape_area(shape: *const Shape) -> f64 {}
+             #[no_mangle] pub extern \"C\" fn shape_name(kind: Kind) -> *const c_char {}",
+        );
+        let out = written(|out| bindings.write(out));
+
+        assert!(out.starts_with(
+            "import ctypes
+import enum
+
+lib = ctypes.CDLL(\"libshapes.so\")
+"
+        ));
+        assert!(out.contains(
+            "class Kind(enum.IntEnum):
+    Circle = 0
+    Square = 4
+"
+        ));
+        assert!(out.contains(
+            "Shape._fields_ = [
+    (\"kind\", ctypes.c_uint8),
+    (\"next\", ctypes.POINTER(Shape)),
+    (\"size\", ctypes.c_float * 2),
+]
+"
+        ));
+        assert!(out.contains(
+            "lib.shape_area.argtypes = [ctypes.POINTER(Shape)]
+lib.shape_area.restype = ctypes.c_double
+"
+        ));
+        assert!(out.contains(
+            "lib.shape_name.argtypes = [ctypes.c_uint8]
+lib.shape_name.restype = ctypes.c_char_p
+"
+        ));
+    }
+
+    #[test]
+    fn validate_config() {
+        let mut config = Config::default();
+        config.export.exclude.push("handle_nwe".to_owned());
+        config.export.include.push("Pair_u32".to_owned());
+        config
+            .export
+            .rename
+            .insert("Handel".to_owned(), "MyHandle".to_owned());
+        let issues = Builder::new()
+            .with_config(config)
+            .with_extra_declaration(ItemDecl::Rust(
+                "#[repr(C)] pub struct Handle { id: u32 }
+                 #[repr(C)] pub struct Pair<T> { a: T, b: T }
+                 #[no_mangle] pub extern \"C\" fn handle_new() -> Handle { Handle { id: 0 } }"
+                    .to_owned(),
+            ))
+            .validate_config()
+            .unwrap();
+        assert_eq!(
+            issues,
+            [
+                "export.exclude entry `handle_nwe` doesn't match any item. Did you mean `handle_new`?",
+                "export.rename entry `Handel` doesn't match any item. Did you mean `Handle`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_types() {
+        let builder = || {
+            Builder::new().with_extra_declaration(ItemDecl::Rust(
+                "#[no_mangle] pub extern \"C\" fn missing(m: *const Missing) {}".to_owned(),
+            ))
+        };
+        assert!(builder().generate().is_ok());
+
+        let mut config = Config::default();
+        config.parse.unresolved = Unresolved::Error;
+        match builder().with_config(config).generate() {
+            Err(Error::UnresolvedTypes(names)) => assert_eq!(names, ["Missing"]),
+            _ => panic!("Missing should be unresolved"),
+        }
+    }
+
+    #[test]
+    fn field_offset_defines() {
+        let mut config = Config {
+            language: Language::C,
+            field_offset_defines: true,
+            ..Config::default()
+        };
+        config.structure.rename_fields = RenameRule::CamelCase;
+        let mut bindings = generate(
+            Builder::new().with_config(config),
+            "#[repr(C)] pub struct Header { tag: u8, data_len: u32, flags: u16 }
+             #[repr(C)] pub struct Packet { header: Header, crc: u64 }
+             #[repr(C)] pub struct Unknown { a: u8 }
+             #[no_mangle] pub extern \"C\" fn send(p: Packet, u: Unknown) {}",
+        );
+        // Captured from `rustc -Zprint-type-sizes`.
+        let output = "\
+print-type-size type: `Packet`: 24 bytes, alignment: 8 bytes
+print-type-size     field `.header`: 12 bytes
+print-type-size     padding: 4 bytes
+print-type-size     field `.crc`: 8 bytes, alignment: 8 bytes
+print-type-size type: `Header`: 12 bytes, alignment: 4 bytes
+print-type-size     field `.tag`: 1 bytes
+print-type-size     padding: 3 bytes
+print-type-size     field `.data_len`: 4 bytes, alignment: 4 bytes
+print-type-size     field `.flags`: 2 bytes
+print-type-size     end padding: 2 bytes
+print-type-size type: `dep::Unknown`: 4 bytes, alignment: 4 bytes
+print-type-size     field `.a`: 4 bytes
+";
+        bindings.layouts = crate_layouts("extra_declarations", parse_type_sizes(output));
+        let out = written(|out| bindings.write(out));
+
+        // The renamed fields are matched by position.
+        assert!(out.contains(
+            "#define HEADER_OFFSETOF_TAG 0\n\
+             #define HEADER_OFFSETOF_DATA_LEN 4\n\
+             #define HEADER_OFFSETOF_FLAGS 8\n"
+        ));
+        assert!(out.contains(
+            "#define PACKET_OFFSETOF_HEADER 0\n\
+             #define PACKET_OFFSETOF_CRC 16\n"
+        ));
+        // The type of another crate with the same name isn't checked.
+        assert!(!out.contains("UNKNOWN_OFFSETOF"));
+    }
+
+    #[test]
+    fn csharp_library() {
+        let builder = Builder::new()
+            .with_language(Language::CSharp)
+            .with_extra_declaration(ItemDecl::Rust(
+                "#[no_mangle] pub extern \"C\" fn answer() -> u32 { 42 }".to_owned(),
+            ));
+        match builder.generate() {
+            Err(Error::Config(msg)) => assert!(msg.contains("csharp.library")),
+            _ => panic!("C# bindings of a declaration need a library"),
+        }
+    }
+
+    #[test]
+    fn skipped_items() {
+        let builder = || {
+            Builder::new().with_extra_declaration(ItemDecl::Rust(
+                "#[no_mangle] pub extern \"C\" fn pair(p: (u8, u8)) {}".to_owned(),
+            ))
+        };
+        assert!(builder().generate().is_ok());
+
+        let mut config = Config::default();
+        config.parse.unresolved = Unresolved::Error;
+        match builder().with_config(config).generate() {
+            Err(Error::SkippedItems(names)) => assert_eq!(names, ["extra_declarations::pair"]),
+            _ => panic!("pair should be skipped"),
+        }
+    }
+
+    #[test]
+    fn bindings_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cbindgen.toml");
+        fs::write(
+            &path,
+            "include_guard = \"MAIN_H\"\n\
+             [export]\n\
+             exclude = [\"internal\"]\n\
+             [[bindings]]\n\
+             output = \"wrapper.hpp\"\n\
+             language = \"C++\"\n\
+             exclude = []\n",
+        )
+        .unwrap();
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.bindings.len(), 1);
+        let entry = &config.bindings[0];
+        assert_eq!(
+            entry.output,
+            dir.path().join("wrapper.hpp").to_string_lossy()
+        );
+
+        let wrapper = config.for_bindings(entry);
+        assert_eq!(wrapper.language, Language::Cxx);
+        assert_eq!(wrapper.include_guard.as_deref(), Some("MAIN_H"));
+        assert!(wrapper.export.exclude.is_empty());
+        assert!(wrapper.bindings.is_empty());
+    }
+
+    #[test]
+    fn with_item_written_hook() {
+        let bindings = generate(
+            Builder::new()
+                .with_language(Language::C)
+                .with_no_includes()
+                .with_item_written_hook(|kind, text| match kind.item_type {
+                    ItemType::Structs => text.insert_str(0, &format!("/* {} */\n", kind.name)),
+                    ItemType::Functions => *text = format!("TRACED({})", text),
+                    _ => {}
+                }),
+            "#[repr(C)] pub struct Handle { id: u32 }
+             #[no_mangle] pub extern \"C\" fn handle_id(handle: Handle) -> u32 { handle.id }",
+        );
+        let out = written(|out| bindings.write(out));
+
+        assert!(out.contains("/* Handle */\ntypedef struct Handle {\n  uint32_t id;\n} Handle;"));
+        assert!(out.contains("TRACED(uint32_t handle_id(struct Handle handle);)"));
+    }
+
+    #[test]
+    fn format_command() {
+        let mut builder = Builder::new()
+            .with_language(Language::C)
+            .with_no_includes()
+            .with_extra_declaration(ItemDecl::Rust(
+                "#[repr(C)] pub struct Point { x: i32, y: i32 }
+                 #[no_mangle] pub extern \"C\" fn origin() -> Point { unimplemented!() }"
+                    .to_owned(),
+            ));
+        builder.config.format.command = Some("tr".to_owned());
+        builder.config.format.args = vec!["a-z".to_owned(), "A-Z".to_owned()];
+        let bindings = builder.generate().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("point.h");
+        assert!(bindings.write_to_file(&path));
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.contains("TYPEDEF STRUCT POINT {\n  INT32_T X;"));
+
+        // The formatted output is what's compared against the file.
+        assert!(!bindings.write_to_file(&path));
+        assert_eq!(bindings.diff_against_file(&path), None);
+
+        // A formatter that fails leaves the bindings as they are.
+        let mut bindings = bindings;
+        bindings.config.format.command = Some("false".to_owned());
+        assert!(bindings.write_to_file(&path));
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.contains("typedef struct Point {\n  int32_t x;"));
+    }
+
+    #[test]
+    fn include_guard_placeholders() {
+        let bindings = Builder::new()
+            .with_crate("tests/rust/mod_2018")
+            .with_language(Language::C)
+            .with_include_guard("{crate}_{file}")
+            .with_pragma_once(true)
+            .generate()
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mod-2018.h");
+        assert!(bindings.write_to_file(&path));
+        let header = fs::read_to_string(&path).unwrap();
+        assert!(header.contains(
+            "#ifndef MOD_2018_MOD_2018_H\n#define MOD_2018_MOD_2018_H\n\n#pragma once\n"
+        ));
+        assert!(header.ends_with("#endif /* MOD_2018_MOD_2018_H */\n"));
+
+        // There's no file name to use when writing to stdout.
+        let out = written(|out| bindings.write(out));
+        assert!(!out.contains("#ifndef"));
+        assert!(out.contains("#pragma once"));
+    }
+
+    #[test]
+    fn name_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("lib.rs");
+        fs::write(
+            &src,
+            "#[repr(C)] pub struct Point { x: i32 }\n\
+             #[repr(C)] pub struct Vector { x: i32 }\n\
+             #[no_mangle] pub extern \"C\" fn origin(v: Vector) -> Point { unimplemented!() }\n",
+        )
+        .unwrap();
+        let builder = || Builder::new().with_language(Language::C).with_src(&src);
+        assert!(builder().generate().is_ok());
+
+        match builder().rename_item("Vector", "Point").generate() {
+            Err(Error::NameCollisions(names)) => assert_eq!(names, ["Point"]),
+            _ => panic!("Point and Vector should collide"),
+        }
+
+        // Struct tags don't clash with functions in C, unless they get a
+        // typedef.
+        let origin = || builder().rename_item("Point", "origin");
+        assert!(origin().with_style(Style::Tag).generate().is_ok());
+        match origin().with_style(Style::Both).generate() {
+            Err(Error::NameCollisions(names)) => assert_eq!(names, ["origin"]),
+            _ => panic!("Point and origin should collide"),
+        }
+
+        // Items of different modules aren't merged, unless they're declared
+        // for different cfgs.
+        fs::write(
+            &src,
+            "pub mod net { #[repr(C)] pub struct Socket { fd: i32 } }\n\
+             pub mod ipc { #[repr(C)] pub struct Socket { path: *const u8 } }\n\
+             #[no_mangle] pub extern \"C\" fn top(a: net::Socket, b: ipc::Socket) {}\n",
+        )
+        .unwrap();
+        match builder().generate() {
+            Err(Error::NameCollisions(names)) => assert_eq!(names, ["Socket"]),
+            _ => panic!("net::Socket and ipc::Socket should collide"),
+        }
+        fs::write(
+            &src,
+            "#[cfg(unix)] pub mod unix { #[repr(C)] pub struct Socket { fd: i32 } }\n\
+             #[cfg(windows)] pub mod windows { #[repr(C)] pub struct Socket { handle: u64 } }\n\
+             #[no_mangle] pub extern \"C\" fn top(a: Socket) {}\n",
+        )
+        .unwrap();
+        assert!(builder().generate().is_ok());
+    }
+
+    #[test]
+    fn invalid_symbols() {
+        let generate = |source: &str| {
+            Builder::new()
+                .with_language(Language::C)
+                .with_extra_declaration(ItemDecl::Rust(source.to_owned()))
+                .generate()
+        };
+        // The symbols can't be renamed.
+        match generate("#[no_mangle] pub extern \"C\" fn größe() {}") {
+            Err(Error::InvalidSymbols(names)) => assert_eq!(names, ["größe"]),
+            _ => panic!("größe can't be declared"),
+        }
+        match generate("#[no_mangle] pub static r#static: i32 = 0;") {
+            Err(Error::InvalidSymbols(names)) => assert_eq!(names, ["r#static"]),
+            _ => panic!("static can't be declared"),
+        }
+        match generate(
+            "#[repr(C)] pub struct Größe { x: i32 }\n\
+             #[repr(C)] pub struct Grosse { x: i32 }\n\
+             #[no_mangle] pub extern \"C\" fn f(a: Größe, b: Grosse) {}",
+        ) {
+            Err(Error::NameCollisions(names)) => assert_eq!(names, ["Grosse"]),
+            _ => panic!("Größe and Grosse should collide"),
+        }
+    }
+
+    #[test]
+    fn symbols() {
+        let bindings = generate(
+            Builder::new().with_crate("tests/rust/mod_2018"),
+            "#[no_mangle] pub static VERSION: u32 = 1;
+             #[cfg(windows)] #[no_mangle] pub extern \"C\" fn win32_init() {}
+             extern \"C\" { fn imported(); }",
+        );
+        assert_eq!(
+            bindings.symbols(false),
+            "MOD_2018_0.1 {\n  global:\n    VERSION;\n    export_me;\n    export_me_2;\n    \
+             win32_init; /* cfg(windows) */\n  local:\n    *;\n};\n"
+        );
+        assert_eq!(
+            bindings.symbols(true),
+            "EXPORTS\n    VERSION DATA\n    export_me\n    export_me_2\n    \
+             win32_init ; cfg(windows)\n"
+        );
+    }
 }
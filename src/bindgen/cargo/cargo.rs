@@ -2,9 +2,13 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
+use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::bindgen::cargo::cargo_expand;
+use crate::bindgen::cargo::cargo_layout::{self, TypeLayout};
 use crate::bindgen::cargo::cargo_lock::{self, Lock};
 pub(crate) use crate::bindgen::cargo::cargo_metadata::PackageRef;
 use crate::bindgen::cargo::cargo_metadata::{self, Metadata};
@@ -20,6 +24,43 @@ fn parse_dep_string(dep_string: &str) -> (&str, Option<&str>) {
     (split[0], split.get(1).cloned())
 }
 
+/// The directory where cargo keeps the registry and git sources it fetched.
+fn cargo_home() -> Option<PathBuf> {
+    match env::var_os("CARGO_HOME") {
+        Some(cargo_home) => Some(PathBuf::from(cargo_home)),
+        None => env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(|home| Path::new(&home).join(".cargo")),
+    }
+}
+
+fn subdirectories(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|x| x.path()))
+        .filter(|x| x.is_dir())
+}
+
+/// Finds the directory of the package named `name` in a checkout, looking at
+/// most `depth` directories down.
+fn find_package_dir(dir: &Path, name: &str, depth: usize) -> Option<PathBuf> {
+    let manifest = cargo_toml::manifest(&dir.join("Cargo.toml")).ok();
+    if manifest.map_or(false, |x| x.package.name == name) {
+        return Some(dir.to_owned());
+    }
+    if depth == 0 {
+        return None;
+    }
+    subdirectories(dir)
+        .filter(|x| {
+            x.file_name().map_or(false, |x| {
+                x != "target" && !x.to_string_lossy().starts_with('.')
+            })
+        })
+        .find_map(|x| find_package_dir(&x, name, depth - 1))
+}
+
 /// A collection of metadata for a library from cargo.
 #[derive(Clone, Debug)]
 pub(crate) struct Cargo {
@@ -28,6 +69,11 @@ pub(crate) struct Cargo {
     lock: Option<Lock>,
     metadata: Metadata,
     clean: bool,
+    /// Where to look for the sources cargo fetched.
+    cargo_home: Option<PathBuf>,
+    /// The expansion of the crates, instead of running cargo.
+    #[cfg(test)]
+    expansion: Option<String>,
 }
 
 impl Cargo {
@@ -41,6 +87,37 @@ impl Cargo {
         use_cargo_lock: bool,
         clean: bool,
         existing_metadata_file: Option<&Path>,
+    ) -> Result<Cargo, Error> {
+        let mut cargo = Cargo::load_workspace(
+            crate_dir,
+            lock_file,
+            use_cargo_lock,
+            clean,
+            existing_metadata_file,
+        )?;
+
+        // Use the specified binding crate name or infer it from the manifest
+        cargo.binding_crate_name = match binding_crate_name {
+            Some(s) => s.to_owned(),
+            None => {
+                let toml_path = &cargo.manifest_path;
+                let manifest = cargo_toml::manifest(toml_path)
+                    .map_err(|x| Error::CargoToml(toml_path.to_str().unwrap().to_owned(), x))?;
+                manifest.package.name
+            }
+        };
+        Ok(cargo)
+    }
+
+    /// Gathers the metadata of the workspace of the crate or virtual manifest
+    /// in `crate_dir`, without choosing a binding crate. `for_member` gives
+    /// the ones of its members.
+    pub(crate) fn load_workspace(
+        crate_dir: &Path,
+        lock_file: Option<&str>,
+        use_cargo_lock: bool,
+        clean: bool,
+        existing_metadata_file: Option<&Path>,
     ) -> Result<Cargo, Error> {
         let toml_path = crate_dir.join("Cargo.toml");
         let metadata = cargo_metadata::metadata(&toml_path, existing_metadata_file)
@@ -61,25 +138,56 @@ impl Cargo {
             None
         };
 
-        // Use the specified binding crate name or infer it from the manifest
-        let binding_crate_name = match binding_crate_name {
-            Some(s) => s.to_owned(),
-            None => {
-                let manifest = cargo_toml::manifest(&toml_path)
-                    .map_err(|x| Error::CargoToml(toml_path.to_str().unwrap().to_owned(), x))?;
-                manifest.package.name
-            }
-        };
-
         Ok(Cargo {
             manifest_path: toml_path,
-            binding_crate_name,
+            binding_crate_name: String::new(),
             lock,
             metadata,
             clean,
+            cargo_home: cargo_home(),
+            #[cfg(test)]
+            expansion: None,
         })
     }
 
+    /// Looks for the sources cargo fetched in `cargo_home` rather than in
+    /// `$CARGO_HOME`.
+    #[cfg(test)]
+    pub(crate) fn with_cargo_home(mut self, cargo_home: PathBuf) -> Cargo {
+        self.cargo_home = Some(cargo_home);
+        self
+    }
+
+    /// Expands the crates to `expansion` rather than running cargo.
+    #[cfg(test)]
+    pub(crate) fn with_expansion(mut self, expansion: &str) -> Cargo {
+        self.expansion = Some(expansion.to_owned());
+        self
+    }
+
+    /// The packages of the workspace, by name.
+    #[allow(unused)]
+    pub(crate) fn workspace_members(&self) -> Vec<PackageRef> {
+        let members = &self.metadata.workspace_members;
+        let mut packages: Vec<PackageRef> = self
+            .metadata
+            .packages
+            .iter()
+            .filter(|package| members.contains(&package.id))
+            .map(|package| package.name_and_version.clone())
+            .collect();
+        packages.sort_by(|x, y| x.name.cmp(&y.name));
+        packages
+    }
+
+    /// The metadata of the workspace, with `package` as the binding crate.
+    #[allow(unused)]
+    pub(crate) fn for_member(&self, package: &PackageRef) -> Cargo {
+        let mut cargo = self.clone();
+        cargo.binding_crate_name = package.name.clone();
+        cargo
+    }
+
     pub(crate) fn binding_crate_name(&self) -> &str {
         &self.binding_crate_name
     }
@@ -189,6 +297,14 @@ impl Cargo {
         None
     }
 
+    /// The directory of the build artifacts of the workspace.
+    pub(crate) fn target_dir(&self) -> PathBuf {
+        match self.metadata.target_directory {
+            Some(ref target_dir) => PathBuf::from(target_dir),
+            None => Path::new(&self.metadata.workspace_root).join("target"),
+        }
+    }
+
     /// Finds the directory for a specified package reference.
     #[allow(unused)]
     pub(crate) fn find_crate_dir(&self, package: &PackageRef) -> Option<PathBuf> {
@@ -202,8 +318,18 @@ impl Cargo {
             })
     }
 
-    /// Finds `src/lib.rs` for a specified package reference.
+    /// Finds `src/lib.rs` for a specified package reference, in the sources
+    /// cargo fetched if `cargo metadata` doesn't know where it is.
     pub(crate) fn find_crate_src(&self, package: &PackageRef) -> Option<PathBuf> {
+        self.find_metadata_crate_src(package)
+            .filter(|x| x.exists())
+            .or_else(|| {
+                let cargo_home = self.cargo_home.as_ref()?;
+                self.find_fetched_crate_src(package, cargo_home)
+            })
+    }
+
+    fn find_metadata_crate_src(&self, package: &PackageRef) -> Option<PathBuf> {
         let kind_lib = String::from("lib");
         let kind_staticlib = String::from("staticlib");
         let kind_rlib = String::from("rlib");
@@ -228,6 +354,49 @@ impl Cargo {
             })
     }
 
+    /// Finds the lib target of a registry or git dependency in the sources
+    /// fetched into `cargo_home`, from its source in the lock file.
+    fn find_fetched_crate_src(&self, package: &PackageRef, cargo_home: &Path) -> Option<PathBuf> {
+        let lock_package = self.lock.as_ref()?.package.as_ref()?.iter().find(|x| {
+            x.name == package.name && package.version.as_ref().map_or(true, |v| *v == x.version)
+        })?;
+        let source = lock_package.source.as_ref()?;
+
+        let crate_dir = if source.starts_with("registry+") || source.starts_with("sparse+") {
+            let name = format!("{}-{}", lock_package.name, lock_package.version);
+            subdirectories(&cargo_home.join("registry").join("src"))
+                .map(|x| x.join(&name))
+                .find(|x| x.join("Cargo.toml").exists())
+        } else if source.starts_with("git+") {
+            // The checkouts are in directories named after the abbreviated
+            // revision.
+            let rev = &source[source.rfind('#')? + 1..];
+            subdirectories(&cargo_home.join("git").join("checkouts"))
+                .flat_map(|x| subdirectories(&x))
+                .filter(|x| {
+                    let short_rev = x.file_name().unwrap().to_string_lossy();
+                    short_rev.len() >= 7 && rev.starts_with(&*short_rev)
+                })
+                .find_map(|x| find_package_dir(&x, &lock_package.name, 4))
+        } else {
+            None
+        }?;
+
+        let manifest = cargo_toml::manifest(&crate_dir.join("Cargo.toml")).ok()?;
+        let src = match manifest.lib.and_then(|x| x.path) {
+            Some(path) => crate_dir.join(path),
+            None => crate_dir.join("src").join("lib.rs"),
+        };
+        if !src.exists() {
+            return None;
+        }
+        info!(
+            "Found {} {} in {:?}.",
+            package.name, lock_package.version, crate_dir
+        );
+        Some(src)
+    }
+
     pub(crate) fn expand_crate(
         &self,
         package: &PackageRef,
@@ -236,6 +405,12 @@ impl Cargo {
         expand_features: &Option<Vec<String>>,
         profile: Profile,
     ) -> Result<String, cargo_expand::Error> {
+        #[cfg(test)]
+        {
+            if let Some(ref expansion) = self.expansion {
+                return Ok(expansion.clone());
+            }
+        }
         cargo_expand::expand(
             &self.manifest_path,
             &package.name,
@@ -247,4 +422,15 @@ impl Cargo {
             profile,
         )
     }
+
+    pub(crate) fn type_layouts(
+        &self,
+        package: &PackageRef,
+    ) -> Result<HashMap<String, TypeLayout>, cargo_layout::Error> {
+        cargo_layout::type_layouts(
+            &self.manifest_path,
+            &package.name,
+            package.version.as_deref(),
+        )
+    }
 }
@@ -0,0 +1,337 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+use std::env;
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::str::{from_utf8, Utf8Error};
+
+extern crate tempfile;
+use self::tempfile::Builder;
+
+#[derive(Debug)]
+/// Possible errors that can occur during `rustc -Zprint-type-sizes`.
+pub enum Error {
+    /// Error during creation of temporary directory
+    Io(io::Error),
+    /// Output of `cargo rustc` was not valid utf8
+    Utf8(Utf8Error),
+    /// Error during execution of `cargo rustc -Zprint-type-sizes`
+    Compile(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+impl From<Utf8Error> for Error {
+    fn from(err: Utf8Error) -> Self {
+        Error::Utf8(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(ref err) => err.fmt(f),
+            Error::Utf8(ref err) => err.fmt(f),
+            Error::Compile(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(ref err) => Some(err),
+            Error::Utf8(ref err) => Some(err),
+            Error::Compile(..) => None,
+        }
+    }
+}
+
+/// The size and alignment of a type, and the offsets of its fields, in
+/// bytes. Fields are only known for structs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeLayout {
+    pub size: usize,
+    pub align: usize,
+    pub fields: Vec<(String, usize)>,
+}
+
+const PRINT_TYPE_SIZE: &str = "print-type-size";
+const TYPE: &str = " type: `";
+
+/// Returns the numbers in `s`, in order.
+fn numbers(s: &str) -> impl Iterator<Item = usize> + '_ {
+    s.split(|c: char| !c.is_ascii_digit())
+        .filter_map(|number| number.parse().ok())
+}
+
+/// Parses a `print-type-size type: `foo::Bar`: 8 bytes, alignment: 4 bytes`
+/// line, returning the path of the type. The types of the crate have their
+/// path in the crate, and the other ones start with their crate.
+fn parse_type_size(line: &str) -> Option<(&str, TypeLayout)> {
+    if !line.starts_with(TYPE) {
+        return None;
+    }
+    let line = &line[TYPE.len()..];
+    let end = line.rfind('`')?;
+    let (name, rest) = (&line[..end], &line[end + 1..]);
+    // Generic types can't be matched with the generated ones.
+    if name.contains('<') {
+        return None;
+    }
+
+    let mut numbers = numbers(rest);
+    let size = numbers.next()?;
+    let align = numbers.next()?;
+    Some((
+        name,
+        TypeLayout {
+            size,
+            align,
+            fields: Vec::new(),
+        },
+    ))
+}
+
+/// Parses the output of `-Zprint-type-sizes`. Fields are listed in memory
+/// order after their type, with the padding between them, and only have an
+/// explicit offset when they overlap with the previous ones.
+pub(crate) fn parse_type_sizes(output: &str) -> Vec<(String, TypeLayout)> {
+    let mut types: Vec<(String, TypeLayout)> = Vec::new();
+    let mut layout = None;
+    let mut offset = 0;
+    let mut is_struct = true;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if !line.starts_with(PRINT_TYPE_SIZE) {
+            continue;
+        }
+        let line = &line[PRINT_TYPE_SIZE.len()..];
+        if line.starts_with(TYPE) {
+            // The lines of the skipped types don't belong to the previous one.
+            layout = parse_type_size(line).map(|(name, layout)| {
+                types.push((name.to_owned(), layout));
+                types.len() - 1
+            });
+            offset = 0;
+            is_struct = true;
+            continue;
+        }
+
+        let layout = match layout {
+            Some(i) => &mut types[i].1,
+            None => continue,
+        };
+        let content = line.trim_start();
+        let indent = line.len() - content.len();
+        if content.starts_with("discriminant") || content.starts_with("variant") {
+            // Enum variants aren't laid out like the generated structs.
+            is_struct = false;
+            layout.fields.clear();
+        }
+        if !is_struct || indent != 5 {
+            continue;
+        }
+
+        if content.starts_with("padding:") {
+            offset += numbers(content).next().unwrap_or(0);
+        } else if content.starts_with("field `.") {
+            let content = &content["field `.".len()..];
+            let end = match content.find('`') {
+                Some(end) => end,
+                None => continue,
+            };
+            let (name, rest) = (&content[..end], &content[end + 1..]);
+            let size = numbers(rest).next().unwrap_or(0);
+            if let Some(pos) = rest.find("offset:") {
+                offset = numbers(&rest[pos..]).next().unwrap_or(offset);
+            }
+            layout.fields.push((name.to_owned(), offset));
+            offset += size;
+        }
+    }
+
+    types
+}
+
+/// Use rustc to print the layout of the types of the crate. Since rustc
+/// only prints them when it compiles the crate, this always happens in a
+/// temporary target directory.
+pub fn type_layouts(
+    manifest_path: &Path,
+    crate_name: &str,
+    version: Option<&str>,
+) -> Result<HashMap<String, TypeLayout>, Error> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let mut cmd = Command::new(cargo);
+
+    let temp_dir = Builder::new().prefix("cbindgen-layout").tempdir()?;
+    cmd.env("CARGO_TARGET_DIR", temp_dir.path());
+    // Don't call cbindgen recursively from the build script of the crate.
+    cmd.env("_CBINDGEN_IS_RUNNING", "1");
+
+    cmd.arg("rustc");
+    cmd.arg("--lib");
+    cmd.arg("--manifest-path");
+    cmd.arg(manifest_path);
+    cmd.arg("-p");
+    let mut package = crate_name.to_owned();
+    if let Some(version) = version {
+        package.push(':');
+        package.push_str(version);
+    }
+    cmd.arg(&package);
+    cmd.arg("--");
+    cmd.arg("-Z");
+    cmd.arg("print-type-sizes");
+    info!("Command: {:?}", cmd);
+    let output = cmd.output()?;
+
+    if !output.status.success() {
+        return Err(Error::Compile(from_utf8(&output.stderr)?.to_owned()));
+    }
+
+    Ok(crate_layouts(
+        crate_name,
+        parse_type_sizes(from_utf8(&output.stdout)?),
+    ))
+}
+
+/// Keys the layouts printed for the crate `crate_name` by the Rust path of
+/// their type, like `mycrate::net::Socket`. The types of a dependency and of
+/// a module of the crate with the same name can't be told apart, so the
+/// paths printed more than once are left out.
+pub(crate) fn crate_layouts(
+    crate_name: &str,
+    types: Vec<(String, TypeLayout)>,
+) -> HashMap<String, TypeLayout> {
+    let crate_name = crate_name.replace('-', "_");
+    let mut layouts = HashMap::new();
+    let mut ambiguous = Vec::new();
+    for (name, layout) in types {
+        let path = format!("{}::{}", crate_name, name);
+        if layouts.insert(path.clone(), layout).is_some() {
+            ambiguous.push(path);
+        }
+    }
+    for path in ambiguous {
+        layouts.remove(&path);
+    }
+    layouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Captured from `rustc -Zprint-type-sizes`.
+    const OUTPUT: &str = "\
+print-type-size type: `Packet`: 24 bytes, alignment: 8 bytes
+print-type-size     field `.header`: 12 bytes
+print-type-size     padding: 4 bytes
+print-type-size     field `.crc`: 8 bytes, alignment: 8 bytes
+print-type-size type: `Header`: 12 bytes, alignment: 4 bytes
+print-type-size     field `.tag`: 1 bytes
+print-type-size     padding: 3 bytes
+print-type-size     field `.len`: 4 bytes, alignment: 4 bytes
+print-type-size     field `.flags`: 2 bytes
+print-type-size     end padding: 2 bytes
+print-type-size type: `Shape`: 8 bytes, alignment: 4 bytes
+print-type-size     discriminant: 1 bytes
+print-type-size     variant `Circle`: 7 bytes
+print-type-size         padding: 3 bytes
+print-type-size         field `.0`: 4 bytes, alignment: 4 bytes
+print-type-size     variant `Square`: 7 bytes
+print-type-size         padding: 3 bytes
+print-type-size         field `.side`: 4 bytes, alignment: 4 bytes
+print-type-size     variant `Empty`: 0 bytes
+print-type-size type: `Value`: 8 bytes, alignment: 8 bytes
+print-type-size     variant `Value`: 8 bytes
+print-type-size         field `.i`: 4 bytes
+print-type-size         field `.f`: 8 bytes, offset: 0 bytes, alignment: 8 bytes
+print-type-size type: `net::Addr`: 8 bytes, alignment: 4 bytes
+print-type-size     field `.port`: 2 bytes
+print-type-size     padding: 2 bytes
+print-type-size     field `.ip`: 4 bytes, alignment: 4 bytes
+print-type-size type: `Pair<u8>`: 2 bytes, alignment: 1 bytes
+print-type-size     field `.a`: 1 bytes
+print-type-size     field `.b`: 1 bytes
+";
+
+    fn layout(size: usize, align: usize, fields: &[(&str, usize)]) -> TypeLayout {
+        TypeLayout {
+            size,
+            align,
+            fields: fields.iter().map(|&(x, y)| (x.to_owned(), y)).collect(),
+        }
+    }
+
+    #[test]
+    fn type_sizes() {
+        let types = parse_type_sizes(OUTPUT);
+        let names: Vec<&str> = types.iter().map(|x| x.0.as_str()).collect();
+        assert_eq!(names, ["Packet", "Header", "Shape", "Value", "net::Addr"]);
+
+        let get = |name: &str| &types.iter().find(|x| x.0 == name).unwrap().1;
+        // The padding moves the following fields, the end padding doesn't.
+        assert_eq!(*get("Packet"), layout(24, 8, &[("header", 0), ("crc", 16)]));
+        assert_eq!(
+            *get("Header"),
+            layout(12, 4, &[("tag", 0), ("len", 4), ("flags", 8)])
+        );
+        // Without the fields of the generic type which follows it.
+        assert_eq!(*get("net::Addr"), layout(8, 4, &[("port", 0), ("ip", 4)]));
+        // The fields of the enum variants and of the unions aren't kept.
+        assert_eq!(*get("Shape"), layout(8, 4, &[]));
+        assert_eq!(*get("Value"), layout(8, 8, &[]));
+    }
+
+    #[test]
+    fn type_sizes_ignore_other_lines() {
+        let output = "\
+warning: unused variable: `x`
+print-type-size     field `.orphan`: 4 bytes
+print-type-size type: `Flag`: 1 bytes, alignment: 1 bytes
+print-type-size     field `.on`: 1 bytes
+    Finished dev [unoptimized + debuginfo] target(s)
+";
+        assert_eq!(
+            parse_type_sizes(output),
+            [("Flag".to_owned(), layout(1, 1, &[("on", 0)]))]
+        );
+    }
+
+    #[test]
+    fn crate_layouts_by_path() {
+        let output = "\
+print-type-size type: `std::time::Duration`: 16 bytes, alignment: 8 bytes
+print-type-size type: `Socket`: 4 bytes, alignment: 4 bytes
+print-type-size type: `net::Socket`: 8 bytes, alignment: 8 bytes
+print-type-size type: `libc::Addr`: 4 bytes, alignment: 4 bytes
+print-type-size type: `libc::Addr`: 4 bytes, alignment: 4 bytes
+";
+        let layouts = crate_layouts("my-net", parse_type_sizes(output));
+        let mut paths: Vec<&str> = layouts.keys().map(|x| x.as_str()).collect();
+        paths.sort();
+        // The path printed twice could be a type of a dependency.
+        assert_eq!(
+            paths,
+            [
+                "my_net::Socket",
+                "my_net::net::Socket",
+                "my_net::std::time::Duration"
+            ]
+        );
+        assert_eq!(layouts["my_net::net::Socket"], layout(8, 8, &[]));
+    }
+}
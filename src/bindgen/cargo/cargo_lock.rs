@@ -37,6 +37,9 @@ pub struct Lock {
 pub struct Package {
     pub name: String,
     pub version: String,
+    /// Where the package comes from, like "registry+URL" or "git+URL#REV",
+    /// or `None` for path dependencies.
+    pub source: Option<String>,
     /// A list of dependencies formatted like "NAME VERSION-OPT REGISTRY-OPT"
     pub dependencies: Option<Vec<String>>,
 }
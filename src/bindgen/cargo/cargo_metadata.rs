@@ -28,6 +28,11 @@ pub struct Metadata {
     version: usize,
     /// path to the workspace containing the `Cargo.lock`
     pub workspace_root: String,
+    /// path to the directory of the build artifacts
+    pub target_directory: Option<String>,
+    /// The ids of the packages of the workspace
+    #[serde(default)]
+    pub workspace_members: Vec<String>,
 }
 
 /// A reference to a package including it's name and the specific version.
@@ -42,7 +47,7 @@ pub struct PackageRef {
 pub struct Package {
     #[serde(flatten)]
     pub name_and_version: PackageRef,
-    id: String,
+    pub id: String,
     source: Option<String>,
     /// List of dependencies of this particular package
     pub dependencies: HashSet<Dependency>,
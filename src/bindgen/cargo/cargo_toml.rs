@@ -50,6 +50,7 @@ impl error::Error for Error {
 #[derive(Clone, Deserialize, Debug)]
 pub struct Manifest {
     pub package: Package,
+    pub lib: Option<Lib>,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -57,6 +58,11 @@ pub struct Package {
     pub name: String,
 }
 
+#[derive(Clone, Deserialize, Debug)]
+pub struct Lib {
+    pub path: Option<String>,
+}
+
 /// Parse the Cargo.toml for a given path
 pub fn manifest(manifest_path: &Path) -> Result<Manifest, Error> {
     let mut s = String::new();
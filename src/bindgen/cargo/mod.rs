@@ -5,6 +5,7 @@
 #[allow(clippy::module_inception)]
 mod cargo;
 pub(crate) mod cargo_expand;
+pub(crate) mod cargo_layout;
 pub(crate) mod cargo_lock;
 pub(crate) mod cargo_metadata;
 pub(crate) mod cargo_toml;
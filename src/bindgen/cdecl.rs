@@ -5,9 +5,9 @@
 use std::io::Write;
 
 use crate::bindgen::declarationtyperesolver::DeclarationType;
-use crate::bindgen::ir::{ArrayLength, Function, Type};
+use crate::bindgen::ir::{ArrayLength, CallingConvention, Function, Type};
 use crate::bindgen::writer::{ListType, SourceWriter};
-use crate::bindgen::{Config, Language};
+use crate::bindgen::{Config, Language, PointerStyle};
 
 // This code is for translating Rust types into C declarations.
 // See Section 6.7, Declarations, in the C standard for background.
@@ -20,19 +20,38 @@ enum CDeclarator {
         is_ref: bool,
     },
     Array(String),
-    Func(Vec<(Option<String>, CDecl)>, bool),
+    Func {
+        args: Vec<(Option<String>, CDecl)>,
+        layout_vertical: bool,
+        /// Whether `...` follows the arguments.
+        variadic: bool,
+        calling_convention: Option<&'static str>,
+    },
 }
 
 impl CDeclarator {
     fn is_ptr(&self) -> bool {
         match self {
-            CDeclarator::Ptr { .. } | CDeclarator::Func(..) => true,
+            CDeclarator::Ptr { .. } | CDeclarator::Func { .. } => true,
             _ => false,
         }
     }
 }
 
+/// The keyword of a calling convention, which only C and C++ can declare.
+fn calling_convention_keyword(
+    calling_convention: CallingConvention,
+    config: &Config,
+) -> Option<&'static str> {
+    match config.language {
+        Language::C | Language::Cxx => calling_convention.keyword(),
+        _ => None,
+    }
+}
+
 struct CDecl {
+    /// A comment or an attribute written before the declaration.
+    prefix: Option<String>,
     type_qualifers: String,
     type_name: String,
     type_generic_args: Vec<Type>,
@@ -43,6 +62,7 @@ struct CDecl {
 impl CDecl {
     fn new() -> CDecl {
         CDecl {
+            prefix: None,
             type_qualifers: String::new(),
             type_name: String::new(),
             type_generic_args: Vec::new(),
@@ -86,15 +106,20 @@ impl CDecl {
             .args
             .iter()
             .map(|arg| {
-                (
-                    arg.name.clone(),
-                    CDecl::from_func_arg(&arg.ty, arg.array_length.as_deref(), config),
-                )
+                let mut cdecl = CDecl::from_func_arg(&arg.ty, arg.array_length.as_deref(), config);
+                cdecl.prefix = f.transfer_marker(config, Some(arg));
+                (arg.name.clone(), cdecl)
             })
             .collect();
-        self.declarators
-            .push(CDeclarator::Func(args, layout_vertical));
+        let calling_convention = calling_convention_keyword(f.calling_convention(config), config);
+        self.declarators.push(CDeclarator::Func {
+            args,
+            layout_vertical,
+            variadic: f.variadic,
+            calling_convention,
+        });
         self.build_type(&f.ret, false, config);
+        self.prefix = f.transfer_marker(config, None);
     }
 
     fn build_type(&mut self, t: &Type, is_const: bool, config: &Config) {
@@ -154,14 +179,15 @@ impl CDecl {
                 self.build_type(ty, *ptr_is_const, config);
             }
             Type::Array(ref t, ref constant) => {
-                let len = constant.as_str().to_owned();
+                let len = constant.to_string();
                 self.declarators.push(CDeclarator::Array(len));
                 self.build_type(t, is_const, config);
             }
             Type::FuncPtr {
                 ref ret,
                 ref args,
-                is_nullable: _,
+                is_nullable,
+                calling_convention,
             } => {
                 let args = args
                     .iter()
@@ -169,16 +195,30 @@ impl CDecl {
                     .collect();
                 self.declarators.push(CDeclarator::Ptr {
                     is_const: false,
-                    is_nullable: true,
+                    is_nullable: *is_nullable,
                     is_ref: false,
                 });
-                self.declarators.push(CDeclarator::Func(args, false));
+                let calling_convention = config
+                    .function
+                    .calling_convention
+                    .unwrap_or(*calling_convention);
+                let calling_convention = calling_convention_keyword(calling_convention, config);
+                self.declarators.push(CDeclarator::Func {
+                    args,
+                    layout_vertical: false,
+                    variadic: false,
+                    calling_convention,
+                });
                 self.build_type(ret, false, config);
             }
         }
     }
 
     fn write<F: Write>(&self, out: &mut SourceWriter<F>, ident: Option<&str>, config: &Config) {
+        if let Some(ref prefix) = self.prefix {
+            write!(out, "{} ", prefix);
+        }
+
         // Write the type-specifier and type-qualifier first
         if !self.type_qualifers.is_empty() {
             write!(out, "{} ", self.type_qualifers);
@@ -198,14 +238,36 @@ impl CDecl {
             out.write(">");
         }
 
+        // Write the left part of declarators before the identifier
+        let mut iter_rev = self.declarators.iter().rev().peekable();
+
+        // With the left pointer style, the pointers right before the
+        // identifier stick to the type instead.
+        if ident.is_some() && config.pointer_style == PointerStyle::Left {
+            while let Some(&&CDeclarator::Ptr {
+                is_const,
+                is_nullable,
+                is_ref,
+            }) = iter_rev.peek()
+            {
+                iter_rev.next();
+                out.write(if is_ref { "&" } else { "*" });
+                if is_const {
+                    out.write(" const");
+                }
+                if !is_ref && config.language != Language::Cython {
+                    if let Some(qualifier) = config.nullability_qualifier(is_nullable) {
+                        write!(out, " {}", qualifier);
+                    }
+                }
+            }
+        }
+
         // When we have an identifier, put a space between the type and the declarators
         if ident.is_some() {
             out.write(" ");
         }
 
-        // Write the left part of declarators before the identifier
-        let mut iter_rev = self.declarators.iter().rev().peekable();
-
         #[allow(clippy::while_let_on_iterator)]
         while let Some(declarator) = iter_rev.next() {
             let next_is_pointer = iter_rev.peek().map_or(false, |x| x.is_ptr());
@@ -220,9 +282,9 @@ impl CDecl {
                     if is_const {
                         out.write("const ");
                     }
-                    if !is_nullable && !is_ref && config.language != Language::Cython {
-                        if let Some(attr) = &config.pointer.non_null_attribute {
-                            write!(out, "{} ", attr);
+                    if !is_ref && config.language != Language::Cython {
+                        if let Some(qualifier) = config.nullability_qualifier(is_nullable) {
+                            write!(out, "{} ", qualifier);
                         }
                     }
                 }
@@ -231,10 +293,15 @@ impl CDecl {
                         out.write("(");
                     }
                 }
-                CDeclarator::Func(..) => {
+                CDeclarator::Func {
+                    calling_convention, ..
+                } => {
                     if next_is_pointer {
                         out.write("(");
                     }
+                    if let Some(keyword) = calling_convention {
+                        write!(out, "{} ", keyword);
+                    }
                 }
             }
         }
@@ -262,13 +329,18 @@ impl CDecl {
 
                     last_was_pointer = false;
                 }
-                CDeclarator::Func(ref args, layout_vertical) => {
+                CDeclarator::Func {
+                    ref args,
+                    layout_vertical,
+                    variadic,
+                    ..
+                } => {
                     if last_was_pointer {
                         out.write(")");
                     }
 
                     out.write("(");
-                    if args.is_empty() && config.language == Language::C {
+                    if args.is_empty() && !variadic && config.language == Language::C {
                         out.write("void");
                     }
                     if layout_vertical {
@@ -285,6 +357,13 @@ impl CDecl {
 
                             arg_ty.write(out, arg_ident, config);
                         }
+                        if variadic {
+                            if !args.is_empty() {
+                                out.write(",");
+                                out.new_line();
+                            }
+                            out.write("...");
+                        }
                         out.pop_tab();
                     } else {
                         for (i, &(ref arg_ident, ref arg_ty)) in args.iter().enumerate() {
@@ -297,6 +376,12 @@ impl CDecl {
 
                             arg_ty.write(out, arg_ident, config);
                         }
+                        if variadic {
+                            if !args.is_empty() {
+                                out.write(", ");
+                            }
+                            out.write("...");
+                        }
                     }
                     out.write(")");
 
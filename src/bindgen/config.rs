@@ -5,24 +5,32 @@
 use std::collections::{BTreeMap, HashMap};
 use std::default::Default;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::{fmt, fs, path::Path as StdPath};
 
 use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
 use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 
 use crate::bindgen::ir::annotation::AnnotationSet;
+use crate::bindgen::ir::function::CallingConvention;
 use crate::bindgen::ir::path::Path;
 use crate::bindgen::ir::repr::ReprAlign;
 pub use crate::bindgen::rename::RenameRule;
+use crate::bindgen::utilities::closest_match;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// A language type to generate bindings for.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum Language {
     Cxx,
     C,
     Cython,
+    Gir,
+    CSharp,
+    Vapi,
+    Napi,
+    Ctypes,
 }
 
 impl FromStr for Language {
@@ -42,6 +50,21 @@ impl FromStr for Language {
             "C" => Ok(Language::C),
             "cython" => Ok(Language::Cython),
             "Cython" => Ok(Language::Cython),
+            "gir" => Ok(Language::Gir),
+            "Gir" => Ok(Language::Gir),
+            "GIR" => Ok(Language::Gir),
+            "csharp" => Ok(Language::CSharp),
+            "CSharp" => Ok(Language::CSharp),
+            "C#" => Ok(Language::CSharp),
+            "vala" => Ok(Language::Vapi),
+            "Vala" => Ok(Language::Vapi),
+            "vapi" => Ok(Language::Vapi),
+            "napi" => Ok(Language::Napi),
+            "Napi" => Ok(Language::Napi),
+            "N-API" => Ok(Language::Napi),
+            "ctypes" => Ok(Language::Ctypes),
+            "python" => Ok(Language::Ctypes),
+            "Python" => Ok(Language::Ctypes),
             _ => Err(format!("Unrecognized Language: '{}'.", s)),
         }
     }
@@ -54,12 +77,17 @@ impl Language {
         match self {
             Language::Cxx | Language::C => "typedef",
             Language::Cython => "ctypedef",
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
     }
 }
 
 /// Controls what type of line endings are used in the generated code.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum LineEndingStyle {
     /// Use Unix-style linefeed characters
@@ -115,7 +143,7 @@ impl FromStr for LineEndingStyle {
 deserialize_enum_str!(LineEndingStyle);
 
 /// A style of braces to use for generating code.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Braces {
     SameLine,
     NextLine,
@@ -137,8 +165,32 @@ impl FromStr for Braces {
 
 deserialize_enum_str!(Braces);
 
+/// Which side of the space between a type and a name the `*` of a pointer
+/// is written on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum PointerStyle {
+    /// Next to the type, as in `char* name`.
+    Left,
+    /// Next to the name, as in `char *name`.
+    Right,
+}
+
+impl FromStr for PointerStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<PointerStyle, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "left" => Ok(PointerStyle::Left),
+            "right" => Ok(PointerStyle::Right),
+            _ => Err(format!("Unrecognized pointer style: '{}'.", s)),
+        }
+    }
+}
+
+deserialize_enum_str!(PointerStyle);
+
 /// A type of layout to use when generating long lines of code.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum Layout {
     Horizontal,
     Vertical,
@@ -164,11 +216,18 @@ impl FromStr for Layout {
 deserialize_enum_str!(Layout);
 
 /// How the comments containing documentation should be styled.
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy, Serialize)]
 pub enum DocumentationStyle {
     C,
     C99,
     Doxy,
+    /// Doxygen, with the `# Arguments`, `# Returns` and `# Safety` sections
+    /// turned into `\param`, `\return` and `\warning` commands.
+    DoxyStructured,
+    /// gtk-doc comment blocks for the functions, with the introspection
+    /// annotations of their arguments and return value, and "doxy" for the
+    /// other items.
+    GtkDoc,
     Cxx,
     Auto,
 }
@@ -183,6 +242,8 @@ impl FromStr for DocumentationStyle {
             "cxx" => Ok(DocumentationStyle::Cxx),
             "c++" => Ok(DocumentationStyle::Cxx),
             "doxy" => Ok(DocumentationStyle::Doxy),
+            "doxygen_structured" => Ok(DocumentationStyle::DoxyStructured),
+            "gtk_doc" => Ok(DocumentationStyle::GtkDoc),
             "auto" => Ok(DocumentationStyle::Auto),
             _ => Err(format!("Unrecognized documentation style: '{}'.", s)),
         }
@@ -191,8 +252,72 @@ impl FromStr for DocumentationStyle {
 
 deserialize_enum_str!(DocumentationStyle);
 
+/// How the items marked `#[deprecated]` are declared deprecated.
+#[derive(Debug, Clone, PartialEq, Copy, Serialize)]
+pub enum DeprecatedStyle {
+    /// `__attribute__((deprecated("note")))`
+    Attribute,
+    /// `[[deprecated("note")]]`, from C++14 and C2x.
+    Standard,
+    /// `G_DEPRECATED`, which doesn't have a note.
+    GObject,
+    None,
+    /// "gobject" in GObject mode, "standard" for C++ and "attribute" for C.
+    Auto,
+}
+
+impl FromStr for DeprecatedStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DeprecatedStyle, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "attribute" => Ok(DeprecatedStyle::Attribute),
+            "standard" => Ok(DeprecatedStyle::Standard),
+            "gobject" => Ok(DeprecatedStyle::GObject),
+            "none" => Ok(DeprecatedStyle::None),
+            "auto" => Ok(DeprecatedStyle::Auto),
+            _ => Err(format!("Unrecognized deprecated style: '{}'.", s)),
+        }
+    }
+}
+
+deserialize_enum_str!(DeprecatedStyle);
+
+/// How the transfer of ownership of the arguments and return values of the
+/// functions is written.
+#[derive(Debug, Clone, PartialEq, Copy, Serialize)]
+pub enum TransferStyle {
+    /// `(transfer ...)` annotations in the gtk-doc comment blocks.
+    GtkDoc,
+    /// `/* transfer ... */` before the arguments and the return type.
+    Comment,
+    /// `__attribute__((noescape))` on the borrowed pointer arguments.
+    Attribute,
+    None,
+    /// "gtk_doc" if the functions are documented with gtk-doc blocks,
+    /// "none" otherwise.
+    Auto,
+}
+
+impl FromStr for TransferStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TransferStyle, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "gtk_doc" => Ok(TransferStyle::GtkDoc),
+            "comment" => Ok(TransferStyle::Comment),
+            "attribute" => Ok(TransferStyle::Attribute),
+            "none" => Ok(TransferStyle::None),
+            "auto" => Ok(TransferStyle::Auto),
+            _ => Err(format!("Unrecognized transfer style: '{}'.", s)),
+        }
+    }
+}
+
+deserialize_enum_str!(TransferStyle);
+
 /// A style of Style to use when generating structs and enums.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum Style {
     Both,
     Tag,
@@ -249,7 +374,7 @@ impl FromStr for Style {
 deserialize_enum_str!(Style);
 
 /// Different item types that we can generate and filter.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ItemType {
     Constants,
     Globals,
@@ -282,11 +407,46 @@ impl FromStr for ItemType {
 
 deserialize_enum_str!(ItemType);
 
+/// An item written to the bindings, given to the hook set with
+/// `Builder::with_item_written_hook`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemKind {
+    /// The type of the item.
+    pub item_type: ItemType,
+    /// The name of the item in the bindings.
+    pub name: String,
+}
+
+/// A function rewriting the text written for an item, set with
+/// `Builder::with_item_written_hook`.
+#[derive(Clone)]
+pub struct ItemWrittenFn(pub(crate) Arc<ItemWrittenCallback>);
+
+type ItemWrittenCallback = dyn Fn(&ItemKind, &mut String) + Send + Sync;
+
+impl ItemWrittenFn {
+    pub(crate) fn new<F>(hook: F) -> ItemWrittenFn
+    where
+        F: Fn(&ItemKind, &mut String) + Send + Sync + 'static,
+    {
+        ItemWrittenFn(Arc::new(hook))
+    }
+}
+
+impl fmt::Debug for ItemWrittenFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ItemWrittenFn")
+    }
+}
+
 /// Type which specifies the sort order of functions
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum SortKey {
     Name,
     None,
+    /// Sorts the types so that each one follows the ones it uses, breaking
+    /// the ties by name, and the other items by name.
+    Topological,
 }
 
 impl FromStr for SortKey {
@@ -297,6 +457,7 @@ impl FromStr for SortKey {
         Ok(match &*s.to_lowercase() {
             "name" => Name,
             "none" => None,
+            "topological" => Topological,
             _ => return Err(format!("Unrecognized sort option: '{}'.", s)),
         })
     }
@@ -304,8 +465,94 @@ impl FromStr for SortKey {
 
 deserialize_enum_str!(SortKey);
 
+/// What to do when the written items use types which couldn't be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Unresolved {
+    /// Fail to generate the bindings.
+    Error,
+    /// Warn about them, and write the bindings anyway.
+    Warn,
+    /// Write the bindings without a word.
+    Ignore,
+}
+
+impl FromStr for Unresolved {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Unresolved::*;
+        Ok(match &*s.to_lowercase() {
+            "error" => Error,
+            "warn" => Warn,
+            "ignore" => Ignore,
+            _ => return Err(format!("Unrecognized unresolved option: '{}'.", s)),
+        })
+    }
+}
+
+deserialize_enum_str!(Unresolved);
+
+/// How to write the anonymous members of structs and unions, which bindgen
+/// declares as `__bindgen_anon_N` fields of generated types.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum AnonymousMembers {
+    /// As anonymous members of their struct or union, which needs C11.
+    Inline,
+    /// As named fields of types named after their struct or union.
+    Lift,
+}
+
+impl FromStr for AnonymousMembers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &*s.to_lowercase() {
+            "inline" => Ok(AnonymousMembers::Inline),
+            "lift" => Ok(AnonymousMembers::Lift),
+            _ => Err(format!("Unrecognized anonymous members option: '{}'.", s)),
+        }
+    }
+}
+
+deserialize_enum_str!(AnonymousMembers);
+
+/// How to declare the exported functions returning a `Result<T, E>`, whose
+/// runtime glue lowers them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum ResultConvention {
+    /// As returning the opaque `Result`.
+    None,
+    /// As returning the `E` status, 0 on success, and writing the `T` value
+    /// to an `out` argument added last.
+    OutParam,
+    /// As returning a `NameResult` tagged union, declared like a
+    /// `#[repr(C)] enum NameResult { Ok(T), Err(E) }`.
+    Struct,
+}
+
+impl Default for ResultConvention {
+    fn default() -> ResultConvention {
+        ResultConvention::None
+    }
+}
+
+impl FromStr for ResultConvention {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ResultConvention, Self::Err> {
+        match s {
+            "None" | "none" => Ok(ResultConvention::None),
+            "OutParam" | "out_param" => Ok(ResultConvention::OutParam),
+            "Struct" | "struct" => Ok(ResultConvention::Struct),
+            _ => Err(format!("Unrecognized result convention: '{}'.", s)),
+        }
+    }
+}
+
+deserialize_enum_str!(ResultConvention);
+
 /// Settings to apply when exporting items.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -325,14 +572,71 @@ pub struct ExportConfig {
     pub prefix: Option<String>,
     /// Types of items to generate.
     pub item_types: Vec<ItemType>,
+    /// Whether the constants are only written if the functions, the globals
+    /// or the included items use their types, instead of adding them.
+    pub prune_unused: bool,
     /// Whether renaming overrides or extends prefixing.
     pub renaming_overrides_prefixing: bool,
     /// Mangling configuration.
     pub mangle: MangleConfig,
+    /// Table of the C types to write for the primitive Rust types, like
+    /// `gchar` for `c_char`.
+    pub type_map: HashMap<String, String>,
+    /// Table of the types defined outside of the crate, with the headers
+    /// declaring them, written if the bindings use them.
+    pub extern_types: HashMap<String, ExternTypeConfig>,
+    /// The generic wrappers written as the type they wrap, or `None` for
+    /// the default ones.
+    pub transparent_wrappers: Option<Vec<String>>,
+    /// How the exported functions returning a `Result<T, E>` are declared.
+    pub result_convention: ResultConvention,
+    /// A function computing the names missing from `rename`.
+    #[serde(skip)]
+    pub rename_fn: Option<RenameFn>,
+    /// The namespaces of the items declared in nested namespaces, by name,
+    /// which the references to them are qualified with.
+    #[serde(skip)]
+    pub(crate) namespaces: HashMap<String, String>,
+}
+
+/// How to declare a type which isn't defined by the crate.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct ExternTypeConfig {
+    /// The header declaring the type, included with quotes.
+    pub include: Option<String>,
+    /// The system header declaring the type, included with angle brackets.
+    pub sys_include: Option<String>,
+    /// Whether to forward declare the type as an opaque struct.
+    pub forward_declaration: bool,
+}
+
+/// A function returning the name to export an item as, or `None` to keep its
+/// name, set with `Builder::with_rename_fn`.
+#[derive(Clone)]
+pub struct RenameFn(Arc<RenameCallback>);
+
+type RenameCallback = dyn Fn(ItemType, &str) -> Option<String> + Send + Sync;
+
+impl RenameFn {
+    pub(crate) fn new<F>(rename: F) -> RenameFn
+    where
+        F: Fn(ItemType, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        RenameFn(Arc::new(rename))
+    }
+}
+
+impl fmt::Debug for RenameFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RenameFn")
+    }
 }
 
 /// Mangling-specific configuration.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -341,6 +645,45 @@ pub struct MangleConfig {
     pub rename_types: RenameRule,
     /// Remove the underscores used for name mangling.
     pub remove_underscores: bool,
+    /// The separator used for name mangling, instead of underscores.
+    pub separator: Option<String>,
+    /// The rename rule to apply to the whole mangled names.
+    pub rename_mangled: RenameRule,
+    /// A function computing the mangled names.
+    #[serde(skip)]
+    pub mangle_fn: Option<MangleFn>,
+}
+
+impl MangleConfig {
+    pub(crate) fn separator(&self) -> &str {
+        if self.remove_underscores {
+            return "";
+        }
+        self.separator.as_deref().unwrap_or("_")
+    }
+}
+
+/// A function returning the mangled name of an instantiation of a generic
+/// type, given its name and the mangled names of its generic arguments, or
+/// `None` to mangle it as configured. Set with `Builder::with_mangle_fn`.
+#[derive(Clone)]
+pub struct MangleFn(pub(crate) Arc<MangleCallback>);
+
+type MangleCallback = dyn Fn(&str, &[String]) -> Option<String> + Send + Sync;
+
+impl MangleFn {
+    pub(crate) fn new<F>(mangle: F) -> MangleFn
+    where
+        F: Fn(&str, &[String]) -> Option<String> + Send + Sync + 'static,
+    {
+        MangleFn(Arc::new(mangle))
+    }
+}
+
+impl fmt::Debug for MangleFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MangleFn")
+    }
 }
 
 impl ExportConfig {
@@ -348,6 +691,20 @@ impl ExportConfig {
         self.item_types.is_empty() || self.item_types.contains(&item_type)
     }
 
+    /// Whether a `Wrapper<T>` type is written as `T`. The cells are seen
+    /// through by default, as well as `ManuallyDrop` and `MaybeUninit`
+    /// except in C++, where they can be declared as templates.
+    pub(crate) fn is_transparent_wrapper(&self, name: &str, language: Language) -> bool {
+        match self.transparent_wrappers {
+            Some(ref wrappers) => wrappers.iter().any(|wrapper| wrapper == name),
+            None => match name {
+                "Cell" | "UnsafeCell" => true,
+                "ManuallyDrop" | "MaybeUninit" => language != Language::Cxx,
+                _ => false,
+            },
+        }
+    }
+
     pub(crate) fn pre_body(&self, path: &Path) -> Option<&str> {
         self.pre_body.get(path.name()).map(|s| s.trim_matches('\n'))
     }
@@ -356,6 +713,21 @@ impl ExportConfig {
         self.body.get(path.name()).map(|s| s.trim_matches('\n'))
     }
 
+    /// Adds the name `rename_fn` gives to an item to `rename`, unless it's
+    /// already there.
+    pub(crate) fn add_fn_rename(&mut self, item_type: ItemType, item_name: &str) {
+        let rename = match self.rename_fn {
+            Some(ref rename) => rename,
+            None => return,
+        };
+        if self.rename.contains_key(item_name) {
+            return;
+        }
+        if let Some(name) = (rename.0)(item_type, item_name) {
+            self.rename.insert(item_name.to_owned(), name);
+        }
+    }
+
     pub(crate) fn rename(&self, item_name: &mut String) {
         if let Some(name) = self.rename.get(item_name) {
             *item_name = name.clone();
@@ -367,22 +739,70 @@ impl ExportConfig {
             item_name.insert_str(0, &prefix);
         }
     }
+
+    /// Renames a reference to an item, qualified with its namespaces when
+    /// they're not the ones of the bindings, like `net::Socket`.
+    pub(crate) fn rename_reference(&self, item_name: &mut String) {
+        let namespace = self.namespaces.get(item_name.as_str()).cloned();
+        self.rename(item_name);
+        if let Some(namespace) = namespace {
+            item_name.insert_str(0, &format!("{}::", namespace));
+        }
+    }
 }
 
 /// Settings to apply to generated types with layout modifiers.
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
 pub struct LayoutConfig {
-    /// The way to annotate C types as #[repr(packed)].
+    /// The way to annotate C types as #[repr(packed)], or `portable` to wrap
+    /// them in `#pragma pack`.
     pub packed: Option<String>,
     /// The way to annotate C types as #[repr(align(...))]. This is assumed to be a functional
-    /// macro which takes a single argument (the alignment).
+    /// macro which takes a single argument (the alignment), or `portable` to
+    /// use `alignas` in C++ and the attribute of the compiler in C.
     pub aligned_n: Option<String>,
 }
 
+/// The value of `layout.packed` and `layout.aligned_n` which has cbindgen
+/// write the spelling of the compiler.
+const PORTABLE_LAYOUT: &str = "portable";
+
+/// The macro aligning the C types with `layout.aligned_n = "portable"`.
+pub(crate) const ALIGNED_MACRO: &str = "CBINDGEN_ALIGNED";
+
 impl LayoutConfig {
+    /// Whether the packed types are wrapped in `#pragma pack(push, 1)` and
+    /// `#pragma pack(pop)`, which GCC, Clang and MSVC all support.
+    pub(crate) fn packed_pragma(&self) -> bool {
+        self.packed.as_deref() == Some(PORTABLE_LAYOUT)
+    }
+
+    /// The annotation of the packed types, unless they're wrapped in pragmas.
+    pub(crate) fn packed_annotation(&self) -> Option<&str> {
+        match self.packed.as_deref() {
+            Some(PORTABLE_LAYOUT) => None,
+            packed => packed,
+        }
+    }
+
+    /// The annotation of the types aligned to `n` bytes.
+    pub(crate) fn aligned_annotation(&self, language: Language, n: u64) -> Option<String> {
+        match self.aligned_n.as_deref() {
+            Some(PORTABLE_LAYOUT) if language == Language::Cxx => Some(format!("alignas({})", n)),
+            Some(PORTABLE_LAYOUT) => Some(format!("{}({})", ALIGNED_MACRO, n)),
+            Some(aligned_n) => Some(format!("{}({})", aligned_n, n)),
+            None => None,
+        }
+    }
+
+    /// Whether `ALIGNED_MACRO` is defined.
+    pub(crate) fn defines_aligned_macro(&self, language: Language) -> bool {
+        language == Language::C && self.aligned_n.as_deref() == Some(PORTABLE_LAYOUT)
+    }
+
     pub(crate) fn ensure_safe_to_represent(&self, align: &ReprAlign) -> Result<(), String> {
         match (align, &self.packed, &self.aligned_n) {
             (ReprAlign::Packed, None, _) => Err("Cannot safely represent #[repr(packed)] type without configured 'packed' annotation.".to_string()),
@@ -393,7 +813,7 @@ impl LayoutConfig {
 }
 
 /// Settings to apply to generated functions.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -402,7 +822,8 @@ pub struct FunctionConfig {
     pub prefix: Option<String>,
     /// Optional text to output after each function declaration
     pub postfix: Option<String>,
-    /// The way to annotation this function as #[must_use]
+    /// The way to annotation this function as #[must_use], or nothing if
+    /// empty. Defaults to the attribute of the language.
     pub must_use: Option<String>,
     /// The style to layout the args
     pub args: Layout,
@@ -414,6 +835,28 @@ pub struct FunctionConfig {
     pub sort_by: Option<SortKey>,
     /// Optional text to output after functions which return `!`.
     pub no_return: Option<String>,
+    /// Optional text to output before functions which return `!`, or nothing
+    /// if empty. Defaults to the attribute of the language, unless
+    /// `no_return` is set.
+    pub no_return_prefix: Option<String>,
+    /// Optional text to output before functions marked `#[cold]`, or nothing
+    /// if empty. Defaults to `__attribute__((cold))`.
+    pub cold: Option<String>,
+    /// How the transfer of ownership of the arguments and return values is
+    /// written.
+    pub transfer: TransferStyle,
+    /// Optional macro to output before each function and global declaration,
+    /// defined to export or import the symbol.
+    pub export_macro: Option<String>,
+    /// The calling convention of all the functions and function pointers,
+    /// instead of the one of their ABI.
+    pub calling_convention: Option<CallingConvention>,
+    /// Whether to declare a `NAME_valist` variant of the variadic functions,
+    /// taking a `va_list` instead of `...`. Defaults to true in gobject mode.
+    pub valist: Option<bool>,
+    /// The bodies of the functions to define as inline functions in C and
+    /// C++, by name.
+    pub inline_bodies: HashMap<String, String>,
 }
 
 impl Default for FunctionConfig {
@@ -427,6 +870,13 @@ impl Default for FunctionConfig {
             swift_name_macro: None,
             sort_by: None,
             no_return: None,
+            no_return_prefix: None,
+            cold: None,
+            transfer: TransferStyle::Auto,
+            export_macro: None,
+            calling_convention: None,
+            valist: None,
+            inline_bodies: HashMap::new(),
         }
     }
 }
@@ -445,10 +895,33 @@ impl FunctionConfig {
         }
         self.postfix.clone()
     }
+
+    pub(crate) fn cold(&self, language: Language) -> Option<&str> {
+        match self.cold {
+            Some(ref anno) if anno.is_empty() => None,
+            Some(ref anno) => Some(anno),
+            None => match language {
+                Language::C | Language::Cxx => Some("__attribute__((cold))"),
+                _ => None,
+            },
+        }
+    }
+
+    pub(crate) fn must_use(&self, language: Language) -> Option<&str> {
+        match self.must_use {
+            Some(ref anno) if anno.is_empty() => None,
+            Some(ref anno) => Some(anno),
+            None => match language {
+                Language::C => Some("__attribute__((warn_unused_result))"),
+                Language::Cxx => Some("[[nodiscard]]"),
+                _ => None,
+            },
+        }
+    }
 }
 
 /// Settings to apply to generated structs.
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -477,6 +950,32 @@ pub struct StructConfig {
     pub associated_constants_in_body: bool,
     /// The way to annotate this struct as #[must_use].
     pub must_use: Option<String>,
+    /// Whether `#[repr(transparent)]` structs are typedefs of the type they
+    /// wrap, rather than structs with a single field.
+    pub transparent_typedef: bool,
+    /// How to write the anonymous members generated by bindgen, as they are
+    /// by default.
+    pub anonymous_members: Option<AnonymousMembers>,
+}
+
+impl Default for StructConfig {
+    fn default() -> StructConfig {
+        StructConfig {
+            rename_fields: RenameRule::None,
+            derive_constructor: false,
+            derive_eq: false,
+            derive_neq: false,
+            derive_lt: false,
+            derive_lte: false,
+            derive_gt: false,
+            derive_gte: false,
+            derive_ostream: false,
+            associated_constants_in_body: false,
+            must_use: None,
+            transparent_typedef: true,
+            anonymous_members: None,
+        }
+    }
 }
 
 impl StructConfig {
@@ -528,10 +1027,16 @@ impl StructConfig {
         }
         self.derive_ostream
     }
+    pub(crate) fn transparent_typedef(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("transparent-typedef") {
+            return x;
+        }
+        self.transparent_typedef
+    }
 }
 
 /// Settings to apply to generated enums.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -573,6 +1078,16 @@ pub struct EnumConfig {
     /// Whether to generate empty, private default-constructors for tagged
     /// enums.
     pub private_default_tagged_enum_constructor: bool,
+    /// The suffix of the structs holding the fields of the variants of
+    /// tagged enums, appended to the variant name.
+    pub variant_body_suffix: String,
+    /// Whether to generate inline functions building, checking and accessing
+    /// the variants of tagged enums. Only relevant when targeting C, C++ has
+    /// `derive_helper_methods` instead.
+    pub helper_functions: bool,
+    /// Whether to define the bitwise operators of flags enums, which can
+    /// then be enum classes. Only relevant when targeting C++.
+    pub flag_operators: bool,
 }
 
 impl Default for EnumConfig {
@@ -592,6 +1107,9 @@ impl Default for EnumConfig {
             derive_ostream: false,
             enum_class: true,
             private_default_tagged_enum_constructor: false,
+            variant_body_suffix: "_Body".to_owned(),
+            helper_functions: false,
+            flag_operators: false,
         }
     }
 }
@@ -609,6 +1127,12 @@ impl EnumConfig {
         }
         self.derive_helper_methods
     }
+    pub(crate) fn helper_functions(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("helper-functions") {
+            return x;
+        }
+        self.helper_functions
+    }
     pub(crate) fn derive_const_casts(&self, annotations: &AnnotationSet) -> bool {
         if let Some(x) = annotations.bool("derive-const-casts") {
             return x;
@@ -651,6 +1175,12 @@ impl EnumConfig {
         }
         self.enum_class
     }
+    pub(crate) fn flag_operators(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("flag-operators") {
+            return x;
+        }
+        self.flag_operators
+    }
     pub(crate) fn private_default_tagged_enum_constructor(
         &self,
         annotations: &AnnotationSet,
@@ -663,7 +1193,7 @@ impl EnumConfig {
 }
 
 /// Settings to apply to generated constants.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -672,8 +1202,16 @@ pub struct ConstantConfig {
     pub allow_static_const: bool,
     /// Whether a generated constant should be constexpr in C++ mode.
     pub allow_constexpr: bool,
+    /// Whether to generate the string and byte string constants.
+    pub allow_static_strings: bool,
     /// Sort key for constants
     pub sort_by: Option<SortKey>,
+    /// Whether the associated constants written outside of their type are
+    /// prefixed with the name of the type.
+    pub prefix_associated: bool,
+    /// The rename rule to apply to the name of the associated constants
+    /// written outside of their type, after prefixing them.
+    pub rename_associated: RenameRule,
 }
 
 impl Default for ConstantConfig {
@@ -681,23 +1219,29 @@ impl Default for ConstantConfig {
         ConstantConfig {
             allow_static_const: true,
             allow_constexpr: false,
+            allow_static_strings: false,
             sort_by: None,
+            prefix_associated: true,
+            rename_associated: RenameRule::None,
         }
     }
 }
 
 /// Settings for custom macro expansion.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
 pub struct MacroExpansionConfig {
     /// Whether the `bitflags` macro should be expanded.
     pub bitflags: bool,
+    /// Whether the `bitflags` macro should be expanded to a flag enum rather
+    /// than to a struct with associated constants.
+    pub bitflags_as_enum: bool,
 }
 
 /// Controls which Cargo profile is used for macro expansion.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum Profile {
     Debug,
     Release,
@@ -718,7 +1262,7 @@ impl FromStr for Profile {
 deserialize_enum_str!(Profile);
 
 /// Settings to apply when running `rustc --pretty=expanded`
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -734,6 +1278,12 @@ pub struct ParseExpandConfig {
     pub features: Option<Vec<String>>,
     /// Controls whether or not to pass `--release` when expanding.
     pub profile: Profile,
+    /// Settings overriding the ones above when expanding a given crate. A
+    /// crate listed here is expanded even if it's not in `crates`.
+    pub per_crate: HashMap<String, ParseExpandCrateConfig>,
+    /// Whether the crates were given with the `expand = ["crate"]` shorthand.
+    #[serde(skip)]
+    pub shorthand: bool,
 }
 
 impl Default for ParseExpandConfig {
@@ -744,8 +1294,80 @@ impl Default for ParseExpandConfig {
             default_features: true,
             features: None,
             profile: Profile::Debug,
+            per_crate: HashMap::new(),
+            shorthand: false,
+        }
+    }
+}
+
+impl ParseExpandConfig {
+    pub(crate) fn should_expand(&self, crate_name: &str) -> bool {
+        self.crates.iter().any(|name| name == crate_name) || self.per_crate.contains_key(crate_name)
+    }
+
+    pub(crate) fn all_features(&self, crate_name: &str) -> bool {
+        if let Some(all_features) = self.per_crate.get(crate_name).and_then(|c| c.all_features) {
+            return all_features;
         }
+        self.all_features
     }
+
+    pub(crate) fn default_features(&self, crate_name: &str) -> bool {
+        if let Some(default_features) = self
+            .per_crate
+            .get(crate_name)
+            .and_then(|c| c.default_features)
+        {
+            return default_features;
+        }
+        self.default_features
+    }
+
+    pub(crate) fn features(&self, crate_name: &str) -> &Option<Vec<String>> {
+        if let Some(config) = self.per_crate.get(crate_name) {
+            if config.features.is_some() {
+                return &config.features;
+            }
+        }
+        &self.features
+    }
+
+    pub(crate) fn profile(&self, crate_name: &str) -> Profile {
+        if let Some(profile) = self.per_crate.get(crate_name).and_then(|c| c.profile) {
+            return profile;
+        }
+        self.profile
+    }
+}
+
+/// Settings to apply when running `rustc --pretty=expanded` on a given crate.
+/// Unset options fall back to the ones of `ParseExpandConfig`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct ParseExpandCrateConfig {
+    /// Whether to enable all the features when expanding.
+    pub all_features: Option<bool>,
+    /// Whether to use the default feature set when expanding.
+    pub default_features: Option<bool>,
+    /// List of features to use when expanding.
+    pub features: Option<Vec<String>>,
+    /// Controls whether or not to pass `--release` when expanding.
+    pub profile: Option<Profile>,
+}
+
+/// The field closest to the unknown one of a deserialization error, like
+/// "unknown field `exlude`, expected one of `include`, `exclude`, ...".
+#[allow(unused)]
+fn unknown_field_suggestion(message: &str) -> Option<String> {
+    let unknown = message.split("unknown field `").nth(1)?;
+    let end = unknown.find('`')?;
+    let (field, expected) = (&unknown[..end], &unknown[end + 1..]);
+    let expected = expected.split(" for key").next().unwrap_or(expected);
+    // The expected fields are the odd parts between the backquotes.
+    let fields = expected.split('`').skip(1).step_by(2);
+    closest_match(field, fields).map(|field| field.to_owned())
 }
 
 // Backwards-compatibility deserializer for ParseExpandConfig. This allows accepting both the
@@ -776,6 +1398,8 @@ fn retrocomp_parse_expand_config_deserialize<'de, D: Deserializer<'de>>(
                 default_features: true,
                 features: None,
                 profile: Profile::Debug,
+                per_crate: HashMap::new(),
+                shorthand: true,
             })
         }
 
@@ -788,7 +1412,7 @@ fn retrocomp_parse_expand_config_deserialize<'de, D: Deserializer<'de>>(
 }
 
 /// Settings to apply when parsing.
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -812,9 +1436,37 @@ pub struct ParseConfig {
     /// Whether to use a new temporary target directory when running `rustc --pretty=expanded`.
     /// This may be required for some build processes.
     pub clean: bool,
+    /// Whether to expand the invocations of the `macro_rules!` macros of a
+    /// crate defining items, without running `rustc --pretty=expanded`.
+    pub expand_local_macros: bool,
+    /// Whether to keep the items loaded from the source files of a crate in
+    /// its target directory, to only parse the files that changed next time.
+    pub cache: bool,
+    /// The number of threads loading the crates and their files.
+    pub jobs: usize,
     /// List of crate names which generate consts, statics, and fns. By default
     /// no dependent crates generate them.
     pub extra_bindings: Vec<String>,
+    /// What to do when the written items use types which couldn't be
+    /// resolved, usually because they were incompatible or not found.
+    pub unresolved: Unresolved,
+}
+
+impl Default for ParseConfig {
+    fn default() -> ParseConfig {
+        ParseConfig {
+            parse_deps: false,
+            include: None,
+            exclude: Vec::new(),
+            expand: ParseExpandConfig::default(),
+            clean: false,
+            expand_local_macros: false,
+            cache: false,
+            jobs: 4,
+            extra_bindings: Vec::new(),
+            unresolved: Unresolved::Warn,
+        }
+    }
 }
 
 impl ParseConfig {
@@ -832,8 +1484,67 @@ impl ParseConfig {
     }
 }
 
+/// Settings for the structs exported as integer handles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct HandleConfig {
+    /// The structs exported as handles, besides those annotated with
+    /// `cbindgen:handle`.
+    pub types: Vec<String>,
+    /// The Rust integer type of the handles.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The suffix appended to the name of the structs to name their handles.
+    pub suffix: String,
+    /// Whether to declare `<name>_create` and `<name>_destroy` functions
+    /// for each handle, when the crate doesn't export them.
+    pub functions: bool,
+}
+
+impl Default for HandleConfig {
+    fn default() -> HandleConfig {
+        HandleConfig {
+            types: Vec::new(),
+            ty: "usize".to_owned(),
+            suffix: "Handle".to_owned(),
+            functions: true,
+        }
+    }
+}
+
+impl HandleConfig {
+    pub(crate) fn is_handle(&self, name: &str, annotations: &AnnotationSet) -> bool {
+        annotations.bool("handle").unwrap_or(false) || self.types.iter().any(|ty| ty == name)
+    }
+}
+
+/// Settings to write a header for the crate compiled to WebAssembly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct WasmConfig {
+    /// Whether to write the WebAssembly attributes of the functions, and
+    /// `usize` as 32 bits.
+    pub enabled: bool,
+    /// The module of the imported functions whose `extern` block has no
+    /// `#[link(wasm_import_module)]`.
+    pub import_module: String,
+}
+
+impl Default for WasmConfig {
+    fn default() -> WasmConfig {
+        WasmConfig {
+            enabled: false,
+            import_module: "env".to_owned(),
+        }
+    }
+}
+
 /// Settings to apply to pointers
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -843,7 +1554,7 @@ pub struct PtrConfig {
 }
 
 /// Settings specific to Cython bindings.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -855,8 +1566,348 @@ pub struct CythonConfig {
     pub cimports: BTreeMap<String, Vec<String>>,
 }
 
+/// Settings specific to GObject introspection output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct GirConfig {
+    /// The introspection namespace, defaults to the export prefix.
+    pub namespace: Option<String>,
+    /// The version of the namespace.
+    pub version: String,
+    /// The shared library providing the symbols.
+    pub shared_library: Option<String>,
+    /// The repositories this one depends on, such as `GObject-2.0`.
+    pub includes: Vec<String>,
+    /// The C headers declaring the namespace.
+    pub c_includes: Vec<String>,
+}
+
+impl Default for GirConfig {
+    fn default() -> GirConfig {
+        GirConfig {
+            namespace: None,
+            version: "1.0".to_owned(),
+            shared_library: None,
+            includes: vec!["GObject-2.0".to_owned()],
+            c_includes: Vec::new(),
+        }
+    }
+}
+
+/// Settings specific to GObject bindings, set with `gobject = true` or a
+/// `[gobject]` table, which enables them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "GObjectOption")]
+pub struct GObjectConfig {
+    /// Whether to generate GObject bindings.
+    pub enabled: bool,
+    /// Whether to write the GLib types of the primitive types, like `gint32`
+    /// for `i32`, in C and C++.
+    pub glib_types: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GObjectOption {
+    Enabled(bool),
+    Table(GObjectTable),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+struct GObjectTable {
+    #[serde(default = "GObjectTable::enabled")]
+    enabled: bool,
+    #[serde(default)]
+    glib_types: bool,
+}
+
+impl GObjectTable {
+    fn enabled() -> bool {
+        true
+    }
+}
+
+impl From<GObjectOption> for GObjectConfig {
+    fn from(option: GObjectOption) -> GObjectConfig {
+        match option {
+            GObjectOption::Enabled(enabled) => GObjectConfig {
+                enabled,
+                glib_types: false,
+            },
+            GObjectOption::Table(table) => GObjectConfig {
+                enabled: table.enabled,
+                glib_types: table.glib_types,
+            },
+        }
+    }
+}
+
+impl GObjectConfig {
+    /// Whether the GLib types are written for the primitive types.
+    pub(crate) fn glib_types(&self, language: Language) -> bool {
+        self.enabled && self.glib_types && (language == Language::C || language == Language::Cxx)
+    }
+}
+
+/// Settings specific to C# bindings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct CSharpConfig {
+    /// The namespace to declare the bindings in.
+    pub namespace: Option<String>,
+    /// The static class holding the constants and the extern functions.
+    pub class_name: String,
+    /// The library to import the functions from, the one of the crate by
+    /// default.
+    pub library: Option<String>,
+}
+
+impl Default for CSharpConfig {
+    fn default() -> CSharpConfig {
+        CSharpConfig {
+            namespace: None,
+            class_name: "NativeMethods".to_owned(),
+            library: None,
+        }
+    }
+}
+
+/// Settings specific to Python ctypes modules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct CtypesConfig {
+    /// The library to load the functions from.
+    pub library: Option<String>,
+}
+
+/// Settings specific to Node-API native modules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct NapiConfig {
+    /// The C header declaring the wrapped functions.
+    pub header: Option<String>,
+}
+
+/// Settings specific to Vala API files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct VapiConfig {
+    /// The C headers declaring the bindings, defaults to `gir.c_includes`.
+    pub headers: Vec<String>,
+}
+
+/// Settings to turn the cfgs of the target platform, like
+/// `target_os = "windows"`, into the preprocessor conditions telling whether
+/// the header is compiled for it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct PlatformConfig {
+    /// Whether to use the built-in conditions, which test the macros
+    /// predefined by GCC, Clang and MSVC.
+    pub builtin: bool,
+    /// The conditions of the cfgs, written like the keys of `defines`,
+    /// overriding the built-in ones.
+    pub conditions: HashMap<String, String>,
+}
+
+/// Settings specific to C++ bindings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct CppConfig {
+    /// How to write classes owning the opaque types.
+    pub wrappers: CppWrappersConfig,
+    /// How to give the functions and globals C linkage.
+    pub extern_c: ExternC,
+    /// An inline namespace to declare the bindings in, inside the other
+    /// namespaces, to version their ABI.
+    pub inline_namespace: Option<String>,
+}
+
+/// How the functions and globals are given C linkage in C++.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum ExternC {
+    /// In a single `extern "C" { ... }` block.
+    Block,
+    /// With an `extern "C"` before each of them.
+    PerItem,
+    /// Not at all, for headers included in an `extern "C"` block.
+    None,
+}
+
+impl Default for ExternC {
+    fn default() -> ExternC {
+        ExternC::Block
+    }
+}
+
+impl FromStr for ExternC {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ExternC, Self::Err> {
+        match s {
+            "Block" | "block" => Ok(ExternC::Block),
+            "PerItem" | "per_item" => Ok(ExternC::PerItem),
+            "None" | "none" => Ok(ExternC::None),
+            _ => Err(format!("Unrecognized extern \"C\" style: '{}'.", s)),
+        }
+    }
+}
+
+deserialize_enum_str!(ExternC);
+
+/// Settings to write a C++ class owning each opaque type that has functions
+/// creating and freeing it, to a `.hpp` header next to the bindings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct CppWrappersConfig {
+    /// Whether to write the wrappers.
+    pub enabled: bool,
+    /// The namespace of the classes.
+    pub namespace: String,
+    /// The name of the function creating a type, where `{}` is replaced with
+    /// the name of the type in snake_case.
+    pub constructor: String,
+    /// The name of the function freeing a type, where `{}` is replaced with
+    /// the name of the type in snake_case.
+    pub destructor: String,
+}
+
+impl Default for CppWrappersConfig {
+    fn default() -> CppWrappersConfig {
+        CppWrappersConfig {
+            enabled: false,
+            namespace: "wrappers".to_owned(),
+            constructor: "{}_new".to_owned(),
+            destructor: "{}_free".to_owned(),
+        }
+    }
+}
+
+/// Settings for how the bindings are written to files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct OutputConfig {
+    /// How to split the bindings into several headers.
+    pub split: SplitConfig,
+}
+
+/// Settings to pipe the bindings through a code formatter before they're
+/// written or verified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct FormatConfig {
+    /// The formatter to run, like `clang-format`. The bindings are left as is
+    /// if unset.
+    pub command: Option<String>,
+    /// The arguments of the formatter, in which `{}` is replaced by the path
+    /// of the file being written. It's run from the directory of the file.
+    pub args: Vec<String>,
+}
+
+impl Default for FormatConfig {
+    fn default() -> FormatConfig {
+        FormatConfig {
+            command: None,
+            args: vec!["--style=file".to_owned(), "--assume-filename={}".to_owned()],
+        }
+    }
+}
+
+/// Settings to write each module to its own header, with an umbrella header
+/// including them all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct SplitConfig {
+    /// Whether to split the bindings.
+    pub enabled: bool,
+    /// The directory of the headers, relative to the umbrella header. Defaults
+    /// to the name of the umbrella header without its extension.
+    pub directory: Option<String>,
+    /// The header of the items outside of the top-level modules of the crate.
+    pub default_group: String,
+}
+
+impl Default for SplitConfig {
+    fn default() -> SplitConfig {
+        SplitConfig {
+            enabled: false,
+            directory: None,
+            default_group: "common".to_owned(),
+        }
+    }
+}
+
+impl SplitConfig {
+    /// The header of an item, which is its `header` annotation, set to the
+    /// top-level module it's declared in if it has none.
+    pub(crate) fn group(&self, annotations: &AnnotationSet) -> String {
+        match annotations.atom("header") {
+            Some(Some(group)) => group,
+            _ => self.default_group.clone(),
+        }
+    }
+}
+
+/// The placeholders of `include_guard`, replaced with the name of the crate
+/// and of the file the bindings are written to.
+pub(crate) const INCLUDE_GUARD_PLACEHOLDERS: [&str; 2] = ["{crate}", "{file}"];
+
+/// The `header` annotation of the items written to the private header.
+pub(crate) const PRIVATE_HEADER: &str = "private";
+
+/// Other bindings to write in the same run, as a `[[bindings]]` entry. Unset
+/// options fall back to the ones of the rest of the config.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct BindingsConfig {
+    /// The path of the file to write the bindings to, relative to the config
+    /// file.
+    pub output: String,
+    /// The crate of the workspace to write the bindings of, instead of the
+    /// binding crate.
+    #[serde(rename = "crate")]
+    pub crate_name: Option<String>,
+    /// The language to write the bindings in.
+    pub language: Option<Language>,
+    /// The name to use for an include guard.
+    pub include_guard: Option<String>,
+    /// The types to write even when no function uses them, instead of
+    /// `export.include`.
+    pub include: Option<Vec<String>>,
+    /// The items to leave out, instead of `export.exclude`.
+    pub exclude: Option<Vec<String>>,
+    /// The kinds of items to write, instead of `export.item_types`.
+    pub item_types: Option<Vec<ItemType>>,
+}
+
 /// A collection of settings to customize the generated bindings.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -871,7 +1922,13 @@ pub struct Config {
     pub after_includes: Option<String>,
     /// Optional text to output at the end of the file
     pub trailer: Option<String>,
-    /// Optional name to use for an include guard
+    /// Optional path of a file to write the bindings in, replacing its
+    /// `{{includes}}`, `{{constants}}`, `{{typedefs}}` and `{{functions}}`
+    /// placeholders, relative to the config file.
+    pub template: Option<String>,
+    /// Optional name to use for an include guard, where `{crate}` and
+    /// `{file}` are replaced with the names of the crate and of the file the
+    /// bindings are written to
     pub include_guard: Option<String>,
     /// Add a `#pragma once` guard
     pub pragma_once: bool,
@@ -890,8 +1947,13 @@ pub struct Config {
     pub namespaces: Option<Vec<String>>,
     /// An optional list of namespaces to declare as using. Only applicable when language="C++"
     pub using_namespaces: Option<Vec<String>>,
+    /// Whether to declare the items of each module of the crate in nested
+    /// namespaces named after it. Only applicable when language="C++"
+    pub namespaces_from_modules: bool,
     /// The style to use for braces
     pub braces: Braces,
+    /// The side the `*` of pointers is written on
+    pub pointer_style: PointerStyle,
     /// The preferred length of a line, used for auto breaking function arguments
     pub line_length: usize,
     /// The amount of spaces in a tab
@@ -904,11 +1966,22 @@ pub struct Config {
     pub cpp_compat: bool,
     /// The style to declare structs, enums and unions in for C
     pub style: Style,
-    /// Default sort key for functions and constants.
+    /// Default sort key for functions and constants, and sort key for types.
     pub sort_by: SortKey,
     /// If this option is true `usize` and `isize` will be converted into `size_t` and `ptrdiff_t`
     /// instead of `uintptr_t` and `intptr_t` respectively.
     pub usize_is_size_t: bool,
+    /// Whether to qualify pointers with `_Nonnull` or `_Nullable`.
+    pub nullability: bool,
+    /// Whether to adjust the output for Swift to import it: function names
+    /// and nullability qualifiers.
+    pub swift_friendly: bool,
+    /// Whether to check the size and alignment of structs and unions against
+    /// the layout computed by rustc. Requires a nightly compiler.
+    pub layout_asserts: bool,
+    /// Whether to write `#define FOO_OFFSETOF_BAR n` constants with the
+    /// offsets of the fields of structs, as computed by rustc.
+    pub field_offset_defines: bool,
     /// The configuration options for parsing
     pub parse: ParseConfig,
     /// The configuration options for exporting
@@ -931,17 +2004,49 @@ pub struct Config {
     pub constant: ConstantConfig,
     /// Preprocessor defines to use when generating #ifdef's for #[cfg]
     pub defines: HashMap<String, String>,
+    /// Preprocessor conditions to use for the target #[cfg]s
+    pub platform: PlatformConfig,
     /// Include doc comments from Rust as documentation
     pub documentation: bool,
     /// How documentation comments should be styled.
     pub documentation_style: DocumentationStyle,
+    /// Whether the documentation notes which integers are never zero.
+    pub document_nonzero: bool,
+    /// How the items marked `#[deprecated]` are declared deprecated.
+    pub deprecated_style: DeprecatedStyle,
     /// Configuration options for pointers
     #[serde(rename = "ptr")]
     pub pointer: PtrConfig,
+    /// Configuration options for the integer handles
+    pub handle: HandleConfig,
+    /// Configuration options for WebAssembly
+    pub wasm: WasmConfig,
     /// Configuration options specific to Cython.
     pub cython: CythonConfig,
-    /// Enable GObject generation
-    pub gobject: bool,
+    /// Configuration options specific to GObject introspection.
+    pub gir: GirConfig,
+    /// Configuration options specific to C#.
+    pub csharp: CSharpConfig,
+    /// Configuration options specific to Vala.
+    pub vapi: VapiConfig,
+    /// Configuration options specific to Node-API.
+    pub napi: NapiConfig,
+    /// Configuration options specific to Python ctypes.
+    pub ctypes: CtypesConfig,
+    /// Configuration options specific to C++.
+    pub cpp: CppConfig,
+    /// Configuration options for the output files.
+    pub output: OutputConfig,
+    /// Configuration options for formatting the bindings.
+    pub format: FormatConfig,
+    /// Configuration options specific to GObject bindings.
+    pub gobject: GObjectConfig,
+    /// Other bindings to write in the same run, with some settings
+    /// overridden.
+    pub bindings: Vec<BindingsConfig>,
+    /// A function rewriting the text written for each item.
+    #[serde(skip)]
+    pub item_written_hook: Option<ItemWrittenFn>,
 }
 
 impl Default for Config {
@@ -951,6 +2056,7 @@ impl Default for Config {
             includes: Vec::new(),
             sys_includes: Vec::new(),
             after_includes: None,
+            template: None,
             trailer: None,
             include_guard: None,
             pragma_once: false,
@@ -960,7 +2066,9 @@ impl Default for Config {
             namespace: None,
             namespaces: None,
             using_namespaces: None,
+            namespaces_from_modules: false,
             braces: Braces::SameLine,
+            pointer_style: PointerStyle::Right,
             line_length: 100,
             tab_width: 2,
             line_endings: LineEndingStyle::default(),
@@ -968,6 +2076,10 @@ impl Default for Config {
             cpp_compat: false,
             style: Style::default(),
             usize_is_size_t: false,
+            nullability: false,
+            layout_asserts: false,
+            field_offset_defines: false,
+            swift_friendly: false,
             sort_by: SortKey::None,
             macro_expansion: Default::default(),
             parse: ParseConfig::default(),
@@ -978,11 +2090,26 @@ impl Default for Config {
             enumeration: EnumConfig::default(),
             constant: ConstantConfig::default(),
             defines: HashMap::new(),
+            platform: PlatformConfig::default(),
             documentation: true,
             documentation_style: DocumentationStyle::Auto,
+            document_nonzero: false,
+            deprecated_style: DeprecatedStyle::Auto,
             pointer: PtrConfig::default(),
+            handle: HandleConfig::default(),
+            wasm: WasmConfig::default(),
             cython: CythonConfig::default(),
-            gobject: false,
+            gir: GirConfig::default(),
+            csharp: CSharpConfig::default(),
+            vapi: VapiConfig::default(),
+            napi: NapiConfig::default(),
+            ctypes: CtypesConfig::default(),
+            cpp: CppConfig::default(),
+            output: OutputConfig::default(),
+            format: FormatConfig::default(),
+            gobject: GObjectConfig::default(),
+            bindings: Vec::new(),
+            item_written_hook: None,
         }
     }
 }
@@ -992,11 +2119,26 @@ impl Config {
         self.language == Language::C && self.cpp_compat
     }
 
+    /// The include guard, unless it has a placeholder which couldn't be
+    /// replaced, like `{file}` when writing to stdout.
     pub(crate) fn include_guard(&self) -> Option<&str> {
         if self.language == Language::Cython {
-            None
-        } else {
-            self.include_guard.as_deref()
+            return None;
+        }
+        self.include_guard
+            .as_deref()
+            .filter(|guard| !INCLUDE_GUARD_PLACEHOLDERS.iter().any(|x| guard.contains(x)))
+    }
+
+    /// Replaces the `placeholder` of the include guard with `name`, upper
+    /// cased, with the characters which can't be in an identifier replaced
+    /// with `_`.
+    pub(crate) fn resolve_include_guard(&mut self, placeholder: &str, name: &str) {
+        if let Some(ref mut guard) = self.include_guard {
+            let name = name
+                .to_uppercase()
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+            *guard = guard.replace(placeholder, &name);
         }
     }
 
@@ -1008,6 +2150,168 @@ impl Config {
         }
     }
 
+    /// The header an item goes in, when the bindings are written to several:
+    /// its group when splitting them, else `PRIVATE_HEADER` for the private
+    /// items and an empty string for the public ones.
+    pub(crate) fn header_group(&self, annotations: &AnnotationSet) -> String {
+        if self.output.split.enabled {
+            self.output.split.group(annotations)
+        } else if annotations.atom("header") == Some(Some(PRIVATE_HEADER.to_owned())) {
+            PRIVATE_HEADER.to_owned()
+        } else {
+            String::new()
+        }
+    }
+
+    /// Whether the functions and globals are written in an `extern "C"`
+    /// block. C++ compatible C headers can't write `extern "C"` before each
+    /// item, so they use a block for `cpp.extern_c = "per_item"` too.
+    pub(crate) fn extern_c_block(&self) -> bool {
+        match self.cpp.extern_c {
+            ExternC::Block => self.language == Language::Cxx || self.cpp_compatible_c(),
+            ExternC::PerItem => self.cpp_compatible_c(),
+            ExternC::None => false,
+        }
+    }
+
+    /// Whether each function and global is written with `extern "C"`.
+    pub(crate) fn extern_c_per_item(&self) -> bool {
+        self.language == Language::Cxx && self.cpp.extern_c == ExternC::PerItem
+    }
+
+    /// The nested namespaces an item is declared in, from its `namespace`
+    /// annotation, like `net::tcp`, which `namespaces_from_modules` sets to
+    /// its module.
+    pub(crate) fn item_namespaces(&self, annotations: &AnnotationSet) -> Vec<String> {
+        if self.language != Language::Cxx {
+            return Vec::new();
+        }
+        match annotations.atom("namespace") {
+            Some(Some(namespace)) => namespace
+                .split("::")
+                .filter(|x| !x.is_empty())
+                .map(|x| x.to_owned())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the layout of the types has to be computed by rustc.
+    pub(crate) fn needs_layouts(&self) -> bool {
+        self.layout_asserts || self.field_offset_defines
+    }
+
+    /// The qualifier stating whether a pointer may be null, if any.
+    pub(crate) fn nullability_qualifier(&self, is_nullable: bool) -> Option<&str> {
+        let nullability = self.nullability || self.swift_friendly;
+        if is_nullable {
+            if nullability {
+                Some("_Nullable")
+            } else {
+                None
+            }
+        } else {
+            match self.pointer.non_null_attribute {
+                Some(ref attr) => Some(attr),
+                None if nullability => Some("_Nonnull"),
+                None => None,
+            }
+        }
+    }
+
+    /// The attribute written before the functions which return `!`.
+    pub(crate) fn no_return_prefix(&self) -> Option<&str> {
+        match self.function.no_return_prefix {
+            Some(ref anno) if anno.is_empty() => None,
+            Some(ref anno) => Some(anno),
+            None if self.function.no_return.is_some() => None,
+            None if self.gobject.enabled => Some("G_GNUC_NORETURN"),
+            None => match self.language {
+                Language::C => Some("_Noreturn"),
+                Language::Cxx => Some("[[noreturn]]"),
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether to declare the `va_list` variant of a variadic function.
+    pub(crate) fn valist(&self, annotations: &AnnotationSet) -> bool {
+        annotations
+            .bool("valist")
+            .unwrap_or_else(|| self.function.valist.unwrap_or(self.gobject.enabled))
+    }
+
+    /// The attribute checking the arguments of a function against the
+    /// printf-style format string at the 1-based index `format`, the checked
+    /// arguments starting at `first`.
+    pub(crate) fn format_attribute(&self, format: usize, first: usize) -> Option<String> {
+        if self.gobject.enabled {
+            return Some(format!("G_GNUC_PRINTF({}, {})", format, first));
+        }
+        match self.language {
+            Language::C | Language::Cxx => Some(format!(
+                "__attribute__((format(printf, {}, {})))",
+                format, first
+            )),
+            _ => None,
+        }
+    }
+
+    /// The attribute declaring an item deprecated, with `note` unless it's
+    /// empty.
+    pub(crate) fn deprecated_attribute(&self, note: &str) -> Option<String> {
+        if self.language != Language::C && self.language != Language::Cxx {
+            return None;
+        }
+        let style = match self.deprecated_style {
+            DeprecatedStyle::Auto if self.gobject.enabled => DeprecatedStyle::GObject,
+            DeprecatedStyle::Auto if self.language == Language::Cxx => DeprecatedStyle::Standard,
+            DeprecatedStyle::Auto => DeprecatedStyle::Attribute,
+            style => style,
+        };
+        let note = note
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        Some(match style {
+            DeprecatedStyle::Attribute if note.is_empty() => {
+                "__attribute__((deprecated))".to_owned()
+            }
+            DeprecatedStyle::Attribute => format!("__attribute__((deprecated(\"{}\")))", note),
+            DeprecatedStyle::Standard if note.is_empty() => "[[deprecated]]".to_owned(),
+            DeprecatedStyle::Standard => format!("[[deprecated(\"{}\")]]", note),
+            DeprecatedStyle::GObject => "G_DEPRECATED".to_owned(),
+            DeprecatedStyle::None | DeprecatedStyle::Auto => return None,
+        })
+    }
+
+    /// The attribute naming a function in Swift, either with the configured
+    /// macro or, in Swift friendly mode, with the attribute itself.
+    pub(crate) fn swift_name_attribute(&self, swift_name: &str) -> Option<String> {
+        match self.function.swift_name_macro {
+            Some(ref swift_name_macro) => Some(format!("{}({})", swift_name_macro, swift_name)),
+            None if self.swift_friendly => {
+                Some(format!("__attribute__((swift_name(\"{}\")))", swift_name))
+            }
+            None => None,
+        }
+    }
+
+    /// The macro exporting the functions and globals, which Cython can't
+    /// declare.
+    pub(crate) fn export_macro(&self) -> Option<&str> {
+        match self.language {
+            Language::C | Language::Cxx => self.function.export_macro.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Whether to write the WebAssembly attributes of the functions, which
+    /// Cython can't declare.
+    pub(crate) fn wasm_attributes(&self) -> bool {
+        self.wasm.enabled && (self.language == Language::C || self.language == Language::Cxx)
+    }
+
     pub(crate) fn sys_includes(&self) -> &[String] {
         if self.language == Language::Cython {
             &[]
@@ -1024,10 +2328,58 @@ impl Config {
             )
         })?;
 
-        match toml::from_str::<Config>(&config_text) {
-            Ok(x) => Ok(x),
-            Err(e) => Err(format!("Couldn't parse config file: {}.", e)),
+        let mut config = match toml::from_str::<Config>(&config_text) {
+            Ok(x) => x,
+            Err(e) => {
+                let e = e.to_string();
+                return Err(match unknown_field_suggestion(&e) {
+                    Some(field) => format!(
+                        "Couldn't parse config file: {}. Did you mean `{}`?",
+                        e, field
+                    ),
+                    None => format!("Couldn't parse config file: {}.", e),
+                });
+            }
+        };
+        if let Some(dir) = file_name.as_ref().parent() {
+            if let Some(ref template) = config.template {
+                config.template = Some(dir.join(template).to_string_lossy().into_owned());
+            }
+            for bindings in &mut config.bindings {
+                bindings.output = dir.join(&bindings.output).to_string_lossy().into_owned();
+            }
         }
+        for bindings in &config.bindings {
+            if bindings.output.is_empty() {
+                return Err(
+                    "Couldn't parse config file: [[bindings]] without an output.".to_owned(),
+                );
+            }
+        }
+        Ok(config)
+    }
+
+    /// The config of one of the `[[bindings]]` entries.
+    #[allow(unused)]
+    pub fn for_bindings(&self, bindings: &BindingsConfig) -> Config {
+        let mut config = self.clone();
+        config.bindings = Vec::new();
+        if let Some(language) = bindings.language {
+            config.language = language;
+        }
+        if let Some(ref include_guard) = bindings.include_guard {
+            config.include_guard = Some(include_guard.clone());
+        }
+        if let Some(ref include) = bindings.include {
+            config.export.include = include.clone();
+        }
+        if let Some(ref exclude) = bindings.exclude {
+            config.export.exclude = exclude.clone();
+        }
+        if let Some(ref item_types) = bindings.item_types {
+            config.export.item_types = item_types.clone();
+        }
+        config
     }
 
     pub fn from_root_or_default<P: AsRef<StdPath>>(root: P) -> Config {
@@ -1040,3 +2392,110 @@ impl Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_config(text: &str) -> ParseExpandConfig {
+        toml::from_str::<Config>(text).unwrap().parse.expand
+    }
+
+    #[test]
+    fn expand_per_crate_defaults() {
+        let expand = expand_config(
+            r#"
+            [parse.expand]
+            crates = ["listed"]
+
+            [parse.expand.per_crate.configured]
+            "#,
+        );
+        assert!(expand.should_expand("listed"));
+        // A crate with its own settings is expanded even if it's not listed.
+        assert!(expand.should_expand("configured"));
+        assert!(!expand.should_expand("other"));
+
+        // Without settings of its own, a crate gets the global ones.
+        for name in &["listed", "configured"] {
+            assert!(!expand.all_features(name));
+            assert!(expand.default_features(name));
+            assert_eq!(*expand.features(name), None);
+            assert_eq!(expand.profile(name), Profile::Debug);
+        }
+    }
+
+    #[test]
+    fn expand_per_crate_overrides() {
+        let expand = expand_config(
+            r#"
+            [parse.expand]
+            crates = ["global", "overridden"]
+            all_features = false
+            default_features = false
+            features = ["global-feature"]
+            profile = "release"
+
+            [parse.expand.per_crate.overridden]
+            all_features = true
+            default_features = true
+            features = ["crate-feature"]
+            profile = "debug"
+
+            [parse.expand.per_crate.partial]
+            features = []
+            "#,
+        );
+
+        assert!(!expand.all_features("global"));
+        assert!(!expand.default_features("global"));
+        assert_eq!(
+            *expand.features("global"),
+            Some(vec!["global-feature".to_owned()])
+        );
+        assert_eq!(expand.profile("global"), Profile::Release);
+
+        assert!(expand.all_features("overridden"));
+        assert!(expand.default_features("overridden"));
+        assert_eq!(
+            *expand.features("overridden"),
+            Some(vec!["crate-feature".to_owned()])
+        );
+        assert_eq!(expand.profile("overridden"), Profile::Debug);
+
+        // Only the settings a crate sets override the global ones.
+        assert!(expand.should_expand("partial"));
+        assert!(!expand.all_features("partial"));
+        assert!(!expand.default_features("partial"));
+        assert_eq!(*expand.features("partial"), Some(Vec::new()));
+        assert_eq!(expand.profile("partial"), Profile::Release);
+    }
+
+    #[test]
+    fn expand_shorthand() {
+        // The `expand = ["crate"]` form enables all the features.
+        let expand = expand_config(
+            r#"
+            [parse]
+            expand = ["listed"]
+            "#,
+        );
+        assert!(expand.shorthand);
+        assert!(expand.should_expand("listed"));
+        assert!(expand.all_features("listed"));
+        assert!(expand.default_features("listed"));
+        assert!(expand.per_crate.is_empty());
+    }
+
+    #[test]
+    fn expand_per_crate_unknown_field() {
+        let err = toml::from_str::<Config>(
+            r#"
+            [parse.expand.per_crate.configured]
+            all_feature = true
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown field `all_feature`"));
+    }
+}
@@ -0,0 +1,230 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::io::Write;
+
+use heck::SnakeCase;
+
+use crate::bindgen::cdecl;
+use crate::bindgen::config::Config;
+use crate::bindgen::ir::{Function, Item, ItemContainer, Type};
+use crate::bindgen::writer::{ListType, Source, SourceWriter};
+
+/// An opaque type with the functions creating and freeing it.
+struct Wrapper<'a> {
+    name: &'a str,
+    constructor: &'a Function,
+    destructor: &'a Function,
+}
+
+/// Whether `ty` is a pointer to the type named `name`.
+pub(crate) fn points_to(ty: &Type, name: &str) -> bool {
+    match *ty {
+        Type::Ptr { ref ty, .. } => match **ty {
+            Type::Path(ref path) => path.export_name() == name,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Writes C++ classes owning the opaque types that have a constructor and a
+/// destructor function, to include after the header declaring them.
+pub struct CppWrappersWriter<'a> {
+    config: &'a Config,
+    wrappers: Vec<Wrapper<'a>>,
+}
+
+impl<'a> CppWrappersWriter<'a> {
+    pub fn new(config: &'a Config, items: &'a [ItemContainer], functions: &'a [Function]) -> Self {
+        let wrappers = &config.cpp.wrappers;
+        let find = |pattern: &str, name: &str| {
+            let function_name = pattern.replace("{}", &name.to_snake_case());
+            functions.iter().find(|f| f.path().name() == function_name)
+        };
+
+        let wrappers = items
+            .iter()
+            .filter_map(|item| match *item {
+                ItemContainer::OpaqueItem(ref o) => Some(o.export_name()),
+                _ => None,
+            })
+            .filter_map(|name| {
+                let constructor =
+                    find(&wrappers.constructor, name).filter(|f| points_to(&f.ret, name))?;
+                let destructor = find(&wrappers.destructor, name)
+                    .filter(|f| f.args.len() == 1 && points_to(&f.args[0].ty, name))?;
+                Some(Wrapper {
+                    name,
+                    constructor,
+                    destructor,
+                })
+            })
+            .collect();
+
+        CppWrappersWriter { config, wrappers }
+    }
+
+    /// Writes the classes in the configured namespace. `header` is the header
+    /// declaring the wrapped types, declared in `namespaces`.
+    pub fn write<F: Write>(&self, out: &mut SourceWriter<F>, header: &str, namespaces: &[&str]) {
+        let config = self.config;
+
+        if let Some(ref f) = config.header {
+            write!(out, "{}", f);
+            out.new_line();
+        }
+        let guard = config.include_guard().map(|x| format!("{}_WRAPPERS", x));
+        if let Some(ref guard) = guard {
+            out.new_line_if_not_start();
+            write!(out, "#ifndef {}", guard);
+            out.new_line();
+            write!(out, "#define {}", guard);
+            out.new_line();
+        }
+        if config.pragma_once {
+            out.new_line_if_not_start();
+            out.write("#pragma once");
+            out.new_line();
+        }
+        if let Some(ref f) = config.autogen_warning {
+            out.new_line_if_not_start();
+            write!(out, "{}", f);
+            out.new_line();
+        }
+
+        out.new_line_if_not_start();
+        out.write("#include <utility>");
+        out.new_line();
+        write!(out, "#include \"{}\"", header);
+        out.new_line();
+
+        let qualifier: String = namespaces.iter().map(|x| format!("::{}", x)).collect();
+        let namespace = &config.cpp.wrappers.namespace;
+        out.new_line();
+        write!(out, "namespace {} {{", namespace);
+        out.new_line();
+        for wrapper in &self.wrappers {
+            out.new_line();
+            self.write_wrapper(out, wrapper, &qualifier);
+            out.new_line();
+        }
+        out.new_line();
+        write!(out, "}} // namespace {}", namespace);
+        out.new_line();
+
+        if let Some(ref guard) = guard {
+            out.new_line();
+            write!(out, "#endif // {}", guard);
+            out.new_line();
+        }
+    }
+
+    /// Writes the class of `wrapper`, the type and the functions it wraps
+    /// being qualified with `qualifier`, since the class shadows the type.
+    fn write_wrapper<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        wrapper: &Wrapper,
+        qualifier: &str,
+    ) {
+        let name = wrapper.name;
+        let ty = format!("{}::{}", qualifier, name);
+        let constructor = format!("{}::{}", qualifier, wrapper.constructor.path().name());
+        let destructor = format!("{}::{}", qualifier, wrapper.destructor.path().name());
+
+        write!(out, "class {}", name);
+        out.open_brace();
+        out.pop_tab();
+        out.write("public:");
+        out.push_tab();
+        out.new_line();
+
+        write!(out, "explicit {}({} *ptr) : ptr_(ptr) {{}}", name, ty);
+        out.new_line();
+        out.new_line();
+
+        let args: Vec<_> = wrapper
+            .constructor
+            .args
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| {
+                let name = arg.name.clone().unwrap_or_else(|| format!("arg{}", i));
+                (name, arg.ty.clone())
+            })
+            .collect();
+        let names: Vec<_> = args.iter().map(|(name, _)| &**name).collect();
+        if args.len() == 1 {
+            out.write("explicit ");
+        }
+        write!(out, "{}(", name);
+        let params: Vec<_> = args.iter().map(|(name, ty)| Param(name, ty)).collect();
+        out.write_horizontal_source_list(&params, ListType::Join(", "));
+        write!(out, ") : ptr_({}({})) {{}}", constructor, names.join(", "));
+        out.new_line();
+        out.new_line();
+
+        write!(out, "~{}()", name);
+        out.open_brace();
+        out.write("if (ptr_)");
+        out.open_brace();
+        write!(out, "{}(ptr_);", destructor);
+        out.close_brace(false);
+        out.close_brace(false);
+        out.new_line();
+        out.new_line();
+
+        write!(out, "{}(const {} &) = delete;", name, name);
+        out.new_line();
+        write!(out, "{} &operator=(const {} &) = delete;", name, name);
+        out.new_line();
+        out.new_line();
+
+        write!(
+            out,
+            "{}({} &&other) noexcept : ptr_(other.release()) {{}}",
+            name, name
+        );
+        out.new_line();
+        write!(out, "{} &operator=({} &&other) noexcept", name, name);
+        out.open_brace();
+        out.write("std::swap(ptr_, other.ptr_);");
+        out.new_line();
+        out.write("return *this;");
+        out.close_brace(false);
+        out.new_line();
+        out.new_line();
+
+        write!(out, "{} *get() const {{ return ptr_; }}", ty);
+        out.new_line();
+        out.new_line();
+        write!(out, "{} *release()", ty);
+        out.open_brace();
+        write!(out, "{} *ptr = ptr_;", ty);
+        out.new_line();
+        out.write("ptr_ = nullptr;");
+        out.new_line();
+        out.write("return ptr;");
+        out.close_brace(false);
+        out.new_line();
+        out.new_line();
+
+        out.pop_tab();
+        out.write("private:");
+        out.push_tab();
+        out.new_line();
+        write!(out, "{} *ptr_;", ty);
+        out.close_brace(true);
+    }
+}
+
+/// A parameter of a constructor.
+struct Param<'a>(&'a str, &'a Type);
+
+impl<'a> Source for Param<'a> {
+    fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
+        cdecl::write_field(out, self.1, self.0, config);
+    }
+}
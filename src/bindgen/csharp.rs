@@ -0,0 +1,511 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use crate::bindgen::config::Config;
+use crate::bindgen::ir::{
+    CallingConvention, Constant, Documentation, Enum, Field, Function, IntKind, Item,
+    ItemContainer, Literal, PrimitiveType, Type,
+};
+use crate::bindgen::writer::SourceWriter;
+
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// Escapes C# keywords used as identifiers.
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("@{}", name)
+    } else {
+        name.to_owned()
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The C# type of a primitive, `long` following the LP64 data model.
+fn primitive_name(primitive: &PrimitiveType) -> &'static str {
+    match *primitive {
+        PrimitiveType::Void => "void",
+        PrimitiveType::Bool => "bool",
+        PrimitiveType::Char | PrimitiveType::SChar => "sbyte",
+        PrimitiveType::UChar => "byte",
+        PrimitiveType::Char32 => "uint",
+        PrimitiveType::Float => "float",
+        PrimitiveType::Double => "double",
+        PrimitiveType::VaList | PrimitiveType::PtrDiffT => "IntPtr",
+        PrimitiveType::Integer { kind, signed, .. } => match (kind, signed) {
+            (IntKind::Short, true) | (IntKind::B16, true) => "short",
+            (IntKind::Short, false) | (IntKind::B16, false) => "ushort",
+            (IntKind::Int, true) | (IntKind::B32, true) => "int",
+            (IntKind::Int, false) | (IntKind::B32, false) => "uint",
+            (IntKind::LongLong, true) | (IntKind::B64, true) => "long",
+            (IntKind::LongLong, false) | (IntKind::B64, false) => "ulong",
+            (IntKind::B8, true) => "sbyte",
+            (IntKind::B8, false) => "byte",
+            (IntKind::Long, true) | (IntKind::SizeT, true) | (IntKind::Size, true) => "IntPtr",
+            (IntKind::Long, false) | (IntKind::SizeT, false) | (IntKind::Size, false) => "UIntPtr",
+        },
+    }
+}
+
+/// Whether `ty` is a `const char*`, which is marshalled as a string when
+/// passed to a function.
+fn is_c_string(ty: &Type) -> bool {
+    match *ty {
+        Type::Ptr {
+            ref ty,
+            is_const: true,
+            ..
+        } => **ty == Type::Primitive(PrimitiveType::Char),
+        _ => false,
+    }
+}
+
+/// Writes C# P/Invoke declarations for the bindings.
+pub struct CSharpWriter<'a> {
+    config: &'a Config,
+    /// The aliased types, C# not having typedefs.
+    typedefs: HashMap<String, Type>,
+    /// The opaque items, only handled through pointers.
+    opaque_items: HashSet<String>,
+}
+
+impl<'a> CSharpWriter<'a> {
+    pub fn new(config: &'a Config, items: &[ItemContainer]) -> Self {
+        let mut typedefs = HashMap::new();
+        let mut opaque_items = HashSet::new();
+        for item in items {
+            match *item {
+                ItemContainer::Typedef(ref t) => {
+                    typedefs.insert(t.export_name().to_owned(), t.aliased.clone());
+                }
+                ItemContainer::OpaqueItem(ref o) => {
+                    opaque_items.insert(o.export_name().to_owned());
+                }
+                _ => {}
+            }
+        }
+
+        CSharpWriter {
+            config,
+            typedefs,
+            opaque_items,
+        }
+    }
+
+    /// Replaces typedefs by the type they alias.
+    fn resolve<'t>(&'t self, ty: &'t Type) -> &'t Type {
+        let mut ty = ty;
+        while let Type::Path(ref generic) = *ty {
+            match self.typedefs.get(generic.export_name()) {
+                Some(aliased) if aliased != ty => ty = aliased,
+                _ => break,
+            }
+        }
+        ty
+    }
+
+    fn type_name(&self, ty: &Type) -> String {
+        match *self.resolve(ty) {
+            Type::Primitive(ref primitive) => primitive_name(primitive).to_owned(),
+            Type::Path(ref generic) if self.opaque_items.contains(generic.export_name()) => {
+                "IntPtr".to_owned()
+            }
+            Type::Path(ref generic) => identifier(generic.export_name()),
+            Type::Array(ref element, _) => format!("{}[]", self.type_name(element)),
+            Type::Ptr { .. } | Type::FuncPtr { .. } => "IntPtr".to_owned(),
+        }
+    }
+
+    /// The marshalling of arrays, and of booleans which are otherwise four
+    /// bytes wide.
+    fn marshal_as(&self, ty: &Type) -> Option<String> {
+        match *self.resolve(ty) {
+            Type::Primitive(PrimitiveType::Bool) => Some("UnmanagedType.I1".to_owned()),
+            Type::Array(_, ref length) => {
+                let class_name = &self.config.csharp.class_name;
+                let length = length.to_expr(&|name| format!("(int){}.{}", class_name, name));
+                Some(format!("UnmanagedType.ByValArray, SizeConst = {}", length))
+            }
+            _ => None,
+        }
+    }
+
+    fn write_doc<F: Write>(&self, out: &mut SourceWriter<F>, documentation: &Documentation) {
+        if documentation.doc_comment.is_empty() || !self.config.documentation {
+            return;
+        }
+
+        out.write("/// <summary>");
+        out.new_line();
+        for line in &documentation.doc_comment {
+            write!(out, "/// {}", escape_xml(line.trim()));
+            out.new_line();
+        }
+        out.write("/// </summary>");
+        out.new_line();
+    }
+
+    fn write_fields<F: Write>(&self, out: &mut SourceWriter<F>, fields: &[Field], explicit: bool) {
+        for (i, field) in fields.iter().enumerate() {
+            if i != 0 {
+                out.new_line();
+            }
+            self.write_doc(out, &field.documentation);
+            if explicit {
+                out.write("[FieldOffset(0)]");
+                out.new_line();
+            }
+            if let Some(marshal_as) = self.marshal_as(&field.ty) {
+                write!(out, "[MarshalAs({})]", marshal_as);
+                out.new_line();
+            }
+            write!(
+                out,
+                "public {} {};",
+                self.type_name(&field.ty),
+                identifier(&field.name)
+            );
+        }
+    }
+
+    fn write_enum<F: Write>(&self, out: &mut SourceWriter<F>, e: &Enum) {
+        if e.tag.is_some() {
+            warn!("Skipping {}: C# enums can't carry data.", e.export_name());
+            return;
+        }
+
+        self.write_doc(out, &e.documentation);
+        write!(out, "public enum {}", identifier(e.export_name()));
+        if let Some(repr) = e.repr.ty {
+            // Enums can't be based on native integers.
+            let underlying = match primitive_name(&repr.to_primitive()) {
+                "IntPtr" => "long",
+                "UIntPtr" => "ulong",
+                underlying => underlying,
+            };
+            write!(out, " : {}", underlying);
+        }
+        out.new_line();
+        out.write("{");
+        out.push_tab();
+        for variant in &e.variants {
+            out.new_line();
+            self.write_doc(out, &variant.documentation);
+            write!(out, "{}", identifier(&variant.export_name));
+            if let Some(Literal::Expr(ref discriminant)) = variant.discriminant {
+                write!(out, " = {}", discriminant.trim_end_matches("ULL"));
+            }
+            out.write(",");
+        }
+        out.pop_tab();
+        out.new_line();
+        out.write("}");
+        out.new_line();
+    }
+
+    fn write_item<F: Write>(&self, out: &mut SourceWriter<F>, item: &ItemContainer) {
+        match *item {
+            ItemContainer::Enum(ref e) => self.write_enum(out, e),
+            ItemContainer::Struct(ref s) => {
+                self.write_doc(out, &s.documentation);
+                out.write("[StructLayout(LayoutKind.Sequential)]");
+                out.new_line();
+                write!(out, "public struct {}", identifier(s.export_name()));
+                out.new_line();
+                out.write("{");
+                out.push_tab();
+                out.new_line();
+                self.write_fields(out, &s.fields, false);
+                out.pop_tab();
+                out.new_line();
+                out.write("}");
+                out.new_line();
+            }
+            ItemContainer::Union(ref u) => {
+                self.write_doc(out, &u.documentation);
+                out.write("[StructLayout(LayoutKind.Explicit)]");
+                out.new_line();
+                write!(out, "public struct {}", identifier(u.export_name()));
+                out.new_line();
+                out.write("{");
+                out.push_tab();
+                out.new_line();
+                self.write_fields(out, &u.fields, true);
+                out.pop_tab();
+                out.new_line();
+                out.write("}");
+                out.new_line();
+            }
+            // Opaque items are pointers, and typedefs are resolved.
+            ItemContainer::OpaqueItem(..) | ItemContainer::Typedef(..) => {}
+            ItemContainer::Constant(..)
+            | ItemContainer::Static(..)
+            | ItemContainer::GObject(..) => unreachable!(),
+        }
+    }
+
+    fn write_constant<F: Write>(&self, out: &mut SourceWriter<F>, constant: &Constant) {
+        let value = match constant.value {
+            Literal::Expr(ref value) => value,
+            _ => return,
+        };
+
+        let (ty, value) = match *self.resolve(&constant.ty) {
+            ref ty if is_c_string(ty) && value.starts_with('"') => {
+                ("string".to_owned(), value.clone())
+            }
+            Type::Primitive(PrimitiveType::Float) => ("float".to_owned(), format!("{}f", value)),
+            Type::Primitive(PrimitiveType::Char)
+            | Type::Primitive(PrimitiveType::Char32)
+            | Type::Primitive(PrimitiveType::VaList)
+            | Type::Primitive(PrimitiveType::Void) => return,
+            Type::Primitive(ref primitive) => {
+                let ty = primitive_name(primitive);
+                // Native integers can't be constants.
+                if ty == "IntPtr" || ty == "UIntPtr" {
+                    return;
+                }
+                let value = if value.ends_with("ULL") {
+                    format!("{}UL", value.trim_end_matches("ULL"))
+                } else {
+                    value.clone()
+                };
+                (ty.to_owned(), value)
+            }
+            _ => return,
+        };
+
+        self.write_doc(out, &constant.documentation);
+        write!(
+            out,
+            "public const {} {} = {};",
+            ty,
+            identifier(constant.export_name()),
+            value
+        );
+        out.new_line();
+    }
+
+    fn write_function<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        function: &Function,
+        library: &str,
+    ) {
+        self.write_doc(out, &function.documentation);
+        let calling_convention = match function.calling_convention(self.config) {
+            CallingConvention::C | CallingConvention::Cdecl => "Cdecl",
+            CallingConvention::Stdcall => "StdCall",
+            CallingConvention::Fastcall => "FastCall",
+        };
+        write!(
+            out,
+            "[DllImport({}, CallingConvention = CallingConvention.{}, EntryPoint = \"{}\")]",
+            library,
+            calling_convention,
+            function.path().name()
+        );
+        out.new_line();
+        if let Some(marshal_as) = self.marshal_as(&function.ret) {
+            write!(out, "[return: MarshalAs({})]", marshal_as);
+            out.new_line();
+        }
+        write!(
+            out,
+            "public static extern {} {}(",
+            self.type_name(&function.ret),
+            identifier(function.path().name())
+        );
+        for (i, arg) in function.args.iter().enumerate() {
+            if i != 0 {
+                out.write(", ");
+            }
+            let name = match arg.name {
+                Some(ref name) => identifier(name),
+                None => format!("arg{}", i),
+            };
+            if is_c_string(self.resolve(&arg.ty)) {
+                write!(out, "[MarshalAs(UnmanagedType.LPUTF8Str)] string {}", name);
+                continue;
+            }
+            if let Some(marshal_as) = self.marshal_as(&arg.ty) {
+                write!(out, "[MarshalAs({})] ", marshal_as);
+            }
+            write!(out, "{} {}", self.type_name(&arg.ty), name);
+        }
+        out.write(");");
+        out.new_line();
+    }
+
+    pub fn write<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        items: &[ItemContainer],
+        constants: &[Constant],
+        functions: &[Function],
+    ) {
+        let csharp = &self.config.csharp;
+
+        if let Some(ref header) = self.config.header {
+            write!(out, "{}", header);
+            out.new_line();
+            out.new_line();
+        }
+        if let Some(ref warning) = self.config.autogen_warning {
+            write!(out, "{}", warning);
+            out.new_line();
+            out.new_line();
+        }
+
+        out.write("using System;");
+        out.new_line();
+        out.write("using System.Runtime.InteropServices;");
+        out.new_line();
+
+        if let Some(ref namespace) = csharp.namespace {
+            out.new_line();
+            write!(out, "namespace {}", namespace);
+            out.new_line();
+            out.write("{");
+            out.push_tab();
+        }
+
+        for item in items {
+            if item
+                .deref()
+                .annotations()
+                .bool("no-export")
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            if let ItemContainer::OpaqueItem(..) | ItemContainer::Typedef(..) = *item {
+                continue;
+            }
+            out.new_line();
+            self.write_item(out, item);
+        }
+
+        out.new_line();
+        write!(out, "public static partial class {}", csharp.class_name);
+        out.new_line();
+        out.write("{");
+        out.push_tab();
+        out.new_line();
+
+        // The builder defaults it to the crate.
+        let library = csharp.library.as_ref().expect("No C# library");
+        write!(out, "public const string LibraryName = \"{}\";", library);
+        out.new_line();
+
+        let associated_constants = items.iter().filter_map(|item| match *item {
+            ItemContainer::Struct(ref s) => Some(&s.associated_constants),
+            _ => None,
+        });
+        for constant in constants.iter().chain(associated_constants.flatten()) {
+            out.new_line_if_not_start();
+            self.write_constant(out, constant);
+        }
+
+        for function in functions {
+            out.new_line();
+            self.write_function(out, function, "LibraryName");
+        }
+
+        out.pop_tab();
+        out.write("}");
+
+        if csharp.namespace.is_some() {
+            out.pop_tab();
+            out.new_line();
+            out.write("}");
+        }
+        out.new_line();
+    }
+}
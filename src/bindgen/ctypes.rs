@@ -0,0 +1,442 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use crate::bindgen::config::Config;
+use crate::bindgen::ir::{
+    Constant, Documentation, Enum, Field, Function, IntKind, Item, ItemContainer, Literal,
+    PrimitiveType, ReprAlign, Type,
+};
+use crate::bindgen::writer::SourceWriter;
+
+/// The ctypes type of a primitive, `None` for `void`.
+fn primitive_name(primitive: &PrimitiveType) -> Option<&'static str> {
+    Some(match *primitive {
+        PrimitiveType::Void | PrimitiveType::VaList => return None,
+        PrimitiveType::Bool => "ctypes.c_bool",
+        PrimitiveType::Char => "ctypes.c_char",
+        PrimitiveType::SChar => "ctypes.c_byte",
+        PrimitiveType::UChar => "ctypes.c_ubyte",
+        PrimitiveType::Char32 => "ctypes.c_uint32",
+        PrimitiveType::Float => "ctypes.c_float",
+        PrimitiveType::Double => "ctypes.c_double",
+        PrimitiveType::PtrDiffT => "ctypes.c_ssize_t",
+        PrimitiveType::Integer { kind, signed, .. } => match (kind, signed) {
+            (IntKind::Short, true) => "ctypes.c_short",
+            (IntKind::Short, false) => "ctypes.c_ushort",
+            (IntKind::Int, true) => "ctypes.c_int",
+            (IntKind::Int, false) => "ctypes.c_uint",
+            (IntKind::Long, true) => "ctypes.c_long",
+            (IntKind::Long, false) => "ctypes.c_ulong",
+            (IntKind::LongLong, true) => "ctypes.c_longlong",
+            (IntKind::LongLong, false) => "ctypes.c_ulonglong",
+            // ctypes has no intptr_t, which is as wide as size_t.
+            (IntKind::SizeT, true) | (IntKind::Size, true) => "ctypes.c_ssize_t",
+            (IntKind::SizeT, false) | (IntKind::Size, false) => "ctypes.c_size_t",
+            (IntKind::B8, true) => "ctypes.c_int8",
+            (IntKind::B8, false) => "ctypes.c_uint8",
+            (IntKind::B16, true) => "ctypes.c_int16",
+            (IntKind::B16, false) => "ctypes.c_uint16",
+            (IntKind::B32, true) => "ctypes.c_int32",
+            (IntKind::B32, false) => "ctypes.c_uint32",
+            (IntKind::B64, true) => "ctypes.c_int64",
+            (IntKind::B64, false) => "ctypes.c_uint64",
+        },
+    })
+}
+
+/// The Python spelling of a C literal, if it's a number, a boolean or a
+/// string.
+fn python_literal(value: &str) -> Option<String> {
+    let value = value.trim();
+    match value {
+        "true" => return Some("True".to_owned()),
+        "false" => return Some("False".to_owned()),
+        _ => {}
+    }
+    if value.starts_with('"') && value.ends_with('"') && value.len() > 1 {
+        return Some(value.to_owned());
+    }
+
+    let (sign, digits) = if value.starts_with('-') {
+        ("-", &value[1..])
+    } else {
+        ("", value)
+    };
+    let is_hex = digits.starts_with("0x") || digits.starts_with("0X");
+    let digits = if is_hex {
+        digits.trim_end_matches(|c| c == 'u' || c == 'U' || c == 'l' || c == 'L')
+    } else {
+        digits.trim_end_matches(|c| c == 'u' || c == 'U' || c == 'l' || c == 'L' || c == 'f')
+    };
+    let is_number = if is_hex {
+        digits.len() > 2 && u64::from_str_radix(&digits[2..], 16).is_ok()
+    } else {
+        digits.parse::<u64>().is_ok() || digits.parse::<f64>().is_ok()
+    };
+    if is_number {
+        Some(format!("{}{}", sign, digits))
+    } else {
+        None
+    }
+}
+
+/// Writes a Python module declaring the bindings with ctypes.
+pub struct CtypesWriter<'a> {
+    config: &'a Config,
+    /// The structs, unions and opaque items, declared as classes.
+    classes: HashSet<String>,
+    /// The type of the fieldless enums, which are integers.
+    enums: HashMap<String, &'static str>,
+    /// The typedefs, declared as aliases.
+    typedefs: HashSet<String>,
+}
+
+impl<'a> CtypesWriter<'a> {
+    pub fn new(config: &'a Config, items: &[ItemContainer]) -> Self {
+        let mut classes = HashSet::new();
+        let mut enums = HashMap::new();
+        let mut typedefs = HashSet::new();
+        for item in items {
+            match *item {
+                ItemContainer::Struct(ref s) => {
+                    classes.insert(s.export_name().to_owned());
+                }
+                ItemContainer::Union(ref u) => {
+                    classes.insert(u.export_name().to_owned());
+                }
+                ItemContainer::OpaqueItem(ref o) => {
+                    classes.insert(o.export_name().to_owned());
+                }
+                ItemContainer::Enum(ref e) if e.tag.is_none() => {
+                    let ty = match e.repr.ty {
+                        Some(repr) => primitive_name(&repr.to_primitive()).unwrap(),
+                        None => "ctypes.c_int",
+                    };
+                    enums.insert(e.export_name().to_owned(), ty);
+                }
+                ItemContainer::Typedef(ref t) => {
+                    typedefs.insert(t.export_name().to_owned());
+                }
+                _ => {}
+            }
+        }
+
+        CtypesWriter {
+            config,
+            classes,
+            enums,
+            typedefs,
+        }
+    }
+
+    /// The ctypes type of `ty`, `None` if it isn't declared or if it's `void`.
+    fn type_name(&self, ty: &Type) -> Option<String> {
+        match *ty {
+            Type::Primitive(ref primitive) => primitive_name(primitive).map(str::to_owned),
+            Type::Path(ref generic) => {
+                let name = generic.export_name();
+                if let Some(ty) = self.enums.get(name) {
+                    Some((*ty).to_owned())
+                } else if self.classes.contains(name) || self.typedefs.contains(name) {
+                    Some(name.to_owned())
+                } else {
+                    None
+                }
+            }
+            Type::Ptr {
+                ref ty, is_const, ..
+            } => match **ty {
+                Type::Primitive(PrimitiveType::Void) => Some("ctypes.c_void_p".to_owned()),
+                Type::Primitive(PrimitiveType::Char) if is_const => {
+                    Some("ctypes.c_char_p".to_owned())
+                }
+                Type::FuncPtr { .. } => self.type_name(ty),
+                ref ty => Some(format!("ctypes.POINTER({})", self.type_name(ty)?)),
+            },
+            Type::Array(ref element, ref length) if length.is_simple() => {
+                Some(format!("{} * {}", self.type_name(element)?, length))
+            }
+            Type::Array(ref element, ref length) => {
+                Some(format!("{} * ({})", self.type_name(element)?, length))
+            }
+            Type::FuncPtr {
+                ref ret, ref args, ..
+            } => {
+                let mut types = vec![self.return_type_name(ret)?];
+                for (_, arg) in args {
+                    types.push(self.type_name(arg)?);
+                }
+                Some(format!("ctypes.CFUNCTYPE({})", types.join(", ")))
+            }
+        }
+    }
+
+    /// The ctypes type of a return value, `None` for `void`.
+    fn return_type_name(&self, ty: &Type) -> Option<String> {
+        match *ty {
+            Type::Primitive(PrimitiveType::Void) => Some("None".to_owned()),
+            ref ty => self.type_name(ty),
+        }
+    }
+
+    fn write_doc<F: Write>(&self, out: &mut SourceWriter<F>, documentation: &Documentation) {
+        if documentation.doc_comment.is_empty() || !self.config.documentation {
+            return;
+        }
+
+        for line in &documentation.doc_comment {
+            let line = line.trim();
+            if line.is_empty() {
+                out.write("#");
+            } else {
+                write!(out, "# {}", line);
+            }
+            out.new_line();
+        }
+    }
+
+    fn write_constant<F: Write>(&self, out: &mut SourceWriter<F>, constant: &Constant) {
+        let value = match constant.value {
+            Literal::Expr(ref value) => python_literal(value),
+            _ => None,
+        };
+        let value = match value {
+            Some(value) => value,
+            None => return,
+        };
+
+        self.write_doc(out, &constant.documentation);
+        write!(out, "{} = {}", constant.export_name(), value);
+        out.new_line();
+    }
+
+    fn write_enum<F: Write>(&self, out: &mut SourceWriter<F>, e: &Enum) {
+        out.new_line();
+        out.new_line();
+        self.write_doc(out, &e.documentation);
+        write!(out, "class {}(enum.IntEnum):", e.export_name());
+        out.new_line();
+        if e.variants.is_empty() {
+            out.write("    pass");
+            out.new_line();
+            return;
+        }
+
+        let mut value = 0;
+        for variant in &e.variants {
+            if let Some(Literal::Expr(ref discriminant)) = variant.discriminant {
+                match python_literal(discriminant).and_then(|x| x.parse::<i64>().ok()) {
+                    Some(discriminant) => value = discriminant,
+                    None => warn!(
+                        "Can't compute the value of {}::{}.",
+                        e.export_name(),
+                        variant.export_name
+                    ),
+                }
+            }
+            write!(out, "    {} = {}", variant.export_name, value);
+            out.new_line();
+            value += 1;
+        }
+    }
+
+    /// Writes the `_fields_` of a struct or union, once all the classes are
+    /// declared, as they may point to each other.
+    fn write_fields<F: Write>(&self, out: &mut SourceWriter<F>, name: &str, fields: &[Field]) {
+        let mut types = Vec::new();
+        for field in fields {
+            match self.type_name(&field.ty) {
+                Some(ty) => types.push((&field.name, ty)),
+                None => {
+                    warn!(
+                        "Declaring {} opaque: the type of its field {} can't be declared.",
+                        name, field.name
+                    );
+                    return;
+                }
+            }
+        }
+
+        out.new_line();
+        write!(out, "{}._fields_ = [", name);
+        out.new_line();
+        for (field, ty) in types {
+            write!(out, "    (\"{}\", {}),", field, ty);
+            out.new_line();
+        }
+        out.write("]");
+        out.new_line();
+    }
+
+    fn write_function<F: Write>(&self, out: &mut SourceWriter<F>, function: &Function) {
+        let name = function.path().name();
+        let ret = self.return_type_name(&function.ret);
+        let args: Option<Vec<String>> = function
+            .args
+            .iter()
+            .map(|arg| self.type_name(&arg.ty))
+            .collect();
+        let (ret, args) = match (ret, args) {
+            (Some(ret), Some(args)) => (ret, args),
+            _ => {
+                warn!("Skipping {}: its types can't be declared.", name);
+                return;
+            }
+        };
+
+        out.new_line();
+        self.write_doc(out, &function.documentation);
+        // Functions behind a cfg may not be exported by the library.
+        let indent = if function.cfg.is_some() {
+            write!(out, "if hasattr(lib, \"{}\"):", name);
+            out.new_line();
+            "    "
+        } else {
+            ""
+        };
+        // Variadic functions only declare their fixed arguments.
+        if !function.variadic {
+            write!(
+                out,
+                "{}lib.{}.argtypes = [{}]",
+                indent,
+                name,
+                args.join(", ")
+            );
+            out.new_line();
+        }
+        write!(out, "{}lib.{}.restype = {}", indent, name, ret);
+        out.new_line();
+    }
+
+    pub fn write<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        items: &[ItemContainer],
+        constants: &[Constant],
+        functions: &[Function],
+    ) {
+        if let Some(ref header) = self.config.header {
+            write!(out, "{}", header);
+            out.new_line();
+            out.new_line();
+        }
+        if let Some(ref warning) = self.config.autogen_warning {
+            write!(out, "{}", warning);
+            out.new_line();
+            out.new_line();
+        }
+
+        out.write("import ctypes");
+        out.new_line();
+        out.write("import enum");
+        out.new_line();
+        out.new_line();
+        match self.config.ctypes.library {
+            Some(ref library) => write!(out, "lib = ctypes.CDLL(\"{}\")", library),
+            None => {
+                warn!("No library to load the functions from, set `ctypes.library`.");
+                out.write("lib = ctypes.CDLL(None)");
+            }
+        }
+        out.new_line();
+
+        let items: Vec<&ItemContainer> = items
+            .iter()
+            .filter(|item| {
+                !item
+                    .deref()
+                    .annotations()
+                    .bool("no-export")
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let associated_constants = items.iter().filter_map(|item| match **item {
+            ItemContainer::Struct(ref s) => Some(&s.associated_constants),
+            _ => None,
+        });
+        let constants: Vec<&Constant> = constants
+            .iter()
+            .chain(associated_constants.flatten())
+            .collect();
+        if !constants.is_empty() {
+            out.new_line();
+            for constant in constants {
+                self.write_constant(out, constant);
+            }
+        }
+
+        for item in &items {
+            match **item {
+                ItemContainer::Enum(ref e) if e.tag.is_none() => self.write_enum(out, e),
+                ItemContainer::Enum(ref e) => {
+                    warn!(
+                        "Skipping {}: enums with data can't be declared.",
+                        e.export_name()
+                    );
+                }
+                ItemContainer::Struct(ref s) => {
+                    out.new_line();
+                    out.new_line();
+                    self.write_doc(out, &s.documentation);
+                    write!(out, "class {}(ctypes.Structure):", s.export_name());
+                    out.new_line();
+                    if s.alignment == Some(ReprAlign::Packed) {
+                        out.write("    _pack_ = 1");
+                    } else {
+                        out.write("    pass");
+                    }
+                    out.new_line();
+                }
+                ItemContainer::Union(ref u) => {
+                    out.new_line();
+                    out.new_line();
+                    self.write_doc(out, &u.documentation);
+                    write!(out, "class {}(ctypes.Union):", u.export_name());
+                    out.new_line();
+                    out.write("    pass");
+                    out.new_line();
+                }
+                ItemContainer::OpaqueItem(ref o) => {
+                    out.new_line();
+                    out.new_line();
+                    self.write_doc(out, &o.documentation);
+                    write!(out, "class {}(ctypes.Structure):", o.export_name());
+                    out.new_line();
+                    out.write("    pass");
+                    out.new_line();
+                }
+                ItemContainer::Typedef(..) => {}
+                ItemContainer::Constant(..)
+                | ItemContainer::Static(..)
+                | ItemContainer::GObject(..) => unreachable!(),
+            }
+        }
+
+        out.new_line();
+        for item in &items {
+            match **item {
+                ItemContainer::Struct(ref s) => self.write_fields(out, s.export_name(), &s.fields),
+                ItemContainer::Union(ref u) => self.write_fields(out, u.export_name(), &u.fields),
+                ItemContainer::Typedef(ref t) => match self.type_name(&t.aliased) {
+                    Some(aliased) => {
+                        out.new_line();
+                        write!(out, "{} = {}", t.export_name(), aliased);
+                        out.new_line();
+                    }
+                    None => warn!("Skipping {}: its type can't be declared.", t.export_name()),
+                },
+                _ => {}
+            }
+        }
+
+        for function in functions {
+            self.write_function(out, function);
+        }
+    }
+}
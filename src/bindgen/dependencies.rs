@@ -3,15 +3,23 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use crate::bindgen::ir::{ItemContainer, Path};
+use crate::bindgen::library::Library;
 
 /// A dependency list is used for gathering what order to output the types.
 #[derive(Default)]
 pub struct Dependencies {
     pub order: Vec<ItemContainer>,
     pub items: HashSet<Path>,
+    /// The used types of `export.extern_types`.
+    pub extern_types: BTreeSet<String>,
+    /// The used types which aren't known, with the location of the first
+    /// item using them.
+    pub unresolved: BTreeMap<Path, Option<String>>,
+    /// The location of the item whose dependencies are being added.
+    pub location: Option<String>,
 }
 
 impl Dependencies {
@@ -19,9 +27,62 @@ impl Dependencies {
         Dependencies {
             order: Vec::new(),
             items: HashSet::new(),
+            extern_types: BTreeSet::new(),
+            unresolved: BTreeMap::new(),
+            location: None,
         }
     }
 
+    /// Orders the items so that each one follows the ones it uses, and which
+    /// don't depend on each other by path, regardless of the order they were
+    /// found in. Only the items in a cycle can come before their dependencies.
+    pub fn sort_topologically(&mut self, library: &Library) {
+        let mut items = std::mem::take(&mut self.order);
+        items.sort_by(|x, y| x.deref().path().cmp(y.deref().path()));
+
+        let mut uses: HashMap<Path, Vec<Path>> = HashMap::new();
+        for item in &items {
+            let path = item.deref().path();
+            if uses.contains_key(path) {
+                continue;
+            }
+            let mut dependencies = Dependencies::new();
+            item.deref().add_dependencies(library, &mut dependencies);
+            let mut paths: Vec<Path> = dependencies.items.into_iter().collect();
+            paths.retain(|x| x != path);
+            paths.sort();
+            uses.insert(path.clone(), paths);
+        }
+
+        fn visit(
+            path: &Path,
+            uses: &HashMap<Path, Vec<Path>>,
+            visited: &mut HashSet<Path>,
+            order: &mut Vec<Path>,
+        ) {
+            if !visited.insert(path.clone()) {
+                return;
+            }
+            for dependency in uses.get(path).into_iter().flatten() {
+                // Items used through a type which isn't written aren't sorted.
+                if uses.contains_key(dependency) {
+                    visit(dependency, uses, visited, order);
+                }
+            }
+            order.push(path.clone());
+        }
+
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        for item in &items {
+            visit(item.deref().path(), &uses, &mut visited, &mut order);
+        }
+
+        let rank: HashMap<&Path, usize> = order.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        items.sort_by_key(|x| rank[x.deref().path()]);
+        self.order = items;
+    }
+
     pub fn sort(&mut self) {
         // Sort untagged enums and opaque structs into their own layers because they don't
         // depend on each other or anything else.
@@ -0,0 +1,277 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Diagnostics pointing at the Rust source of the item which caused them.
+//!
+//! They are logged like the other messages, but their records have the
+//! `cbindgen::diagnostic` target, and their `file` and `line` are the ones of
+//! the item in the parsed sources rather than the ones of the logging call.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+use std::path::Path;
+
+use log::{Level, Record};
+use syn::spanned::Spanned;
+
+/// The target of the log records of the diagnostics with a location.
+pub const TARGET: &str = "cbindgen::diagnostic";
+
+thread_local! {
+    static SOURCE_FILE: RefCell<Option<String>> = RefCell::new(None);
+    static RECORDED: RefCell<Option<Vec<Diagnostic>>> = RefCell::new(None);
+}
+
+/// A diagnostic logged while loading a source file, which the parse cache
+/// keeps to log it again when the file isn't parsed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Diagnostic {
+    level: String,
+    line: Option<u32>,
+    message: String,
+}
+
+/// Runs `f` while loading the items of the source file at `path`, so that
+/// the diagnostics about them point at it.
+pub(crate) fn with_source_file<R, F: FnOnce() -> R>(path: &Path, f: F) -> R {
+    let path = path.to_string_lossy().into_owned();
+    let previous = SOURCE_FILE.with(|file| file.replace(Some(path)));
+    let result = f();
+    SOURCE_FILE.with(|file| *file.borrow_mut() = previous);
+    result
+}
+
+/// The location of `node`, as `file:line`, or `None` when the node doesn't
+/// come from a source file.
+pub(crate) fn location<T: Spanned + ?Sized>(node: &T) -> Option<String> {
+    SOURCE_FILE.with(|file| {
+        file.borrow()
+            .as_ref()
+            .map(|file| format!("{}:{}", file, node.span().start().line))
+    })
+}
+
+/// Logs `args` at the location of `node`, or without a location when the
+/// node doesn't come from a source file, like extra declarations.
+pub(crate) fn log_at<T: Spanned + ?Sized>(level: Level, node: &T, args: fmt::Arguments) {
+    let line = SOURCE_FILE.with(|file| file.borrow().as_ref().map(|_| node.span().start().line));
+    let line = line.map(|line| line as u32);
+    RECORDED.with(|recorded| {
+        if let Some(ref mut recorded) = *recorded.borrow_mut() {
+            recorded.push(Diagnostic {
+                level: level.to_string(),
+                line,
+                message: args.to_string(),
+            });
+        }
+    });
+    log_line(level, line, args);
+}
+
+/// Logs `args` at `location`, the `file:line` of an item kept in its
+/// annotations, or without a location when it has none.
+pub(crate) fn log_in(level: Level, location: Option<&str>, args: fmt::Arguments) {
+    if level > log::max_level() {
+        return;
+    }
+    let location = location.and_then(|location| {
+        let colon = location.rfind(':')?;
+        let line = location[colon + 1..].parse().ok()?;
+        Some((&location[..colon], line))
+    });
+    match location {
+        Some((file, line)) => log_record(level, file, line, args),
+        None => log!(level, "{}", args),
+    }
+}
+
+fn log_line(level: Level, line: Option<u32>, args: fmt::Arguments) {
+    if level > log::max_level() {
+        return;
+    }
+    SOURCE_FILE.with(|file| match (file.borrow().as_ref(), line) {
+        (Some(file), Some(line)) => log_record(level, file, line, args),
+        _ => log!(level, "{}", args),
+    });
+}
+
+fn log_record(level: Level, file: &str, line: u32, args: fmt::Arguments) {
+    log::logger().log(
+        &Record::builder()
+            .level(level)
+            .target(TARGET)
+            .file(Some(file))
+            .line(Some(line))
+            .args(args)
+            .build(),
+    );
+}
+
+/// Runs `f`, returning the diagnostics it logs along with its result.
+pub(crate) fn record<R, F: FnOnce() -> R>(f: F) -> (R, Vec<Diagnostic>) {
+    let previous = RECORDED.with(|recorded| recorded.replace(Some(Vec::new())));
+    let result = f();
+    let diagnostics = RECORDED.with(|recorded| mem::replace(&mut *recorded.borrow_mut(), previous));
+    (result, diagnostics.unwrap_or_default())
+}
+
+/// Logs the diagnostics recorded while loading the current source file
+/// again.
+pub(crate) fn replay(diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        let level = diagnostic.level.parse().unwrap_or(Level::Warn);
+        log_line(
+            level,
+            diagnostic.line,
+            format_args!("{}", diagnostic.message),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Once;
+
+    use log::{LevelFilter, Metadata};
+
+    /// The target, file, line and message of a logged record.
+    type Logged = (String, Option<String>, Option<u32>, String);
+
+    thread_local! {
+        static LOGGED: RefCell<Vec<Logged>> = RefCell::new(Vec::new());
+    }
+
+    /// Keeps the records logged by each test thread, so that the tests can
+    /// check the level, location and message of the diagnostics.
+    struct CaptureLogger;
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            LOGGED.with(|logged| {
+                logged.borrow_mut().push((
+                    record.target().to_owned(),
+                    record.file().map(String::from),
+                    record.line(),
+                    record.args().to_string(),
+                ))
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn capture<F: FnOnce()>(f: F) -> Vec<Logged> {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&CaptureLogger).unwrap();
+            log::set_max_level(LevelFilter::Trace);
+        });
+        LOGGED.with(|logged| logged.borrow_mut().clear());
+        f();
+        LOGGED.with(|logged| logged.replace(Vec::new()))
+    }
+
+    fn item() -> syn::Item {
+        syn::parse_str("\n\nstruct Foo(u8);").unwrap()
+    }
+
+    #[test]
+    fn log_at_source_file() {
+        let item = item();
+        let logged = capture(|| {
+            with_source_file(Path::new("src/foo.rs"), || {
+                log_at(Level::Warn, &item, format_args!("Skip Foo."))
+            })
+        });
+        assert_eq!(
+            logged,
+            vec![(
+                TARGET.to_owned(),
+                Some("src/foo.rs".to_owned()),
+                Some(3),
+                "Skip Foo.".to_owned()
+            )]
+        );
+
+        // Extra declarations don't come from a source file.
+        let logged = capture(|| log_at(Level::Warn, &item, format_args!("Skip Foo.")));
+        assert_eq!(logged.len(), 1);
+        assert_ne!(logged[0].0, TARGET);
+        assert_eq!(logged[0].3, "Skip Foo.");
+    }
+
+    #[test]
+    fn log_in_location() {
+        let logged = capture(|| {
+            log_in(Level::Warn, Some("src/foo.rs:7"), format_args!("Skip Foo."));
+            log_in(Level::Warn, None, format_args!("Skip Bar."));
+        });
+        assert_eq!(logged[0].0, TARGET);
+        assert_eq!(logged[0].1.as_deref(), Some("src/foo.rs"));
+        assert_eq!(logged[0].2, Some(7));
+        assert_ne!(logged[1].0, TARGET);
+        assert_eq!(logged[1].3, "Skip Bar.");
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let item = item();
+        let ((), diagnostics) = record(|| {
+            with_source_file(Path::new("src/foo.rs"), || {
+                log_at(Level::Warn, &item, format_args!("Skip Foo."));
+                log_at(Level::Info, &item, format_args!("Take Foo."));
+            })
+        });
+        // The parse cache writes the diagnostics along with the items.
+        assert_eq!(
+            serde_json::to_value(&diagnostics).unwrap(),
+            serde_json::json!([
+                { "level": "WARN", "line": 3, "message": "Skip Foo." },
+                { "level": "INFO", "line": 3, "message": "Take Foo." },
+            ])
+        );
+
+        // Replaying them while loading the cached file logs them again at
+        // the same location, with their level.
+        let cached: Vec<Diagnostic> =
+            serde_json::from_value(serde_json::to_value(&diagnostics).unwrap()).unwrap();
+        let logged = capture(|| with_source_file(Path::new("src/foo.rs"), || replay(&cached)));
+        assert_eq!(
+            logged,
+            vec![
+                (
+                    TARGET.to_owned(),
+                    Some("src/foo.rs".to_owned()),
+                    Some(3),
+                    "Skip Foo.".to_owned()
+                ),
+                (
+                    TARGET.to_owned(),
+                    Some("src/foo.rs".to_owned()),
+                    Some(3),
+                    "Take Foo.".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn record_nested() {
+        let item = item();
+        let ((), outer) = record(|| {
+            let ((), inner) = record(|| log_at(Level::Warn, &item, format_args!("Skip Foo.")));
+            assert_eq!(inner.len(), 1);
+            log_at(Level::Warn, &item, format_args!("Skip Bar."));
+        });
+        assert_eq!(outer.len(), 1);
+        assert_eq!(outer[0].message, "Skip Bar.");
+    }
+}
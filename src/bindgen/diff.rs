@@ -0,0 +1,157 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::cmp;
+
+/// The number of unchanged lines shown around the changed ones.
+const CONTEXT: usize = 3;
+
+/// The largest table of common subsequence lengths to compute. Above it, the
+/// changed lines are all removed and added back.
+const MAX_TABLE_SIZE: usize = 1 << 22;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Splits `text` in lines, keeping their `\n`, so a missing one at the end
+/// is a change.
+fn lines(text: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for (i, _) in text.match_indices('\n') {
+        lines.push(&text[start..=i]);
+        start = i + 1;
+    }
+    if start < text.len() {
+        lines.push(&text[start..]);
+    }
+    lines
+}
+
+/// The line edits turning `old` into `new`, keeping their longest common
+/// subsequence.
+fn edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_changed = &old[prefix..old.len() - suffix];
+    let new_changed = &new[prefix..new.len() - suffix];
+
+    let mut edits: Vec<_> = old[..prefix].iter().map(|x| (Op::Equal, *x)).collect();
+    let (mut i, mut j) = (0, 0);
+    if old_changed.len() * new_changed.len() <= MAX_TABLE_SIZE {
+        // The length of the common subsequence of `old_changed[i..]` and
+        // `new_changed[j..]` is at `i * width + j`.
+        let width = new_changed.len() + 1;
+        let mut lengths = vec![0u32; (old_changed.len() + 1) * width];
+        for i in (0..old_changed.len()).rev() {
+            for j in (0..new_changed.len()).rev() {
+                lengths[i * width + j] = if old_changed[i] == new_changed[j] {
+                    lengths[(i + 1) * width + j + 1] + 1
+                } else {
+                    cmp::max(lengths[(i + 1) * width + j], lengths[i * width + j + 1])
+                };
+            }
+        }
+
+        while i < old_changed.len() && j < new_changed.len() {
+            if old_changed[i] == new_changed[j] {
+                edits.push((Op::Equal, old_changed[i]));
+                i += 1;
+                j += 1;
+            } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+                edits.push((Op::Delete, old_changed[i]));
+                i += 1;
+            } else {
+                edits.push((Op::Insert, new_changed[j]));
+                j += 1;
+            }
+        }
+    }
+    edits.extend(old_changed[i..].iter().map(|x| (Op::Delete, *x)));
+    edits.extend(new_changed[j..].iter().map(|x| (Op::Insert, *x)));
+    edits.extend(old[old.len() - suffix..].iter().map(|x| (Op::Equal, *x)));
+    edits
+}
+
+/// The range of a hunk, where an empty one starts at the line before it.
+fn range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start - 1),
+        1 => format!("{}", start),
+        _ => format!("{},{}", start, count),
+    }
+}
+
+/// Returns the unified diff turning `old` into `new`, the contents of the
+/// file at `path`, or an empty string if they have the same lines.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines = lines(old);
+    let new_lines = lines(new);
+    let edits = edits(&old_lines, &new_lines);
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != Op::Equal)
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut out = String::new();
+    if changes.is_empty() {
+        return out;
+    }
+    out.push_str(&format!("--- {}\n+++ {}\n", path, path));
+
+    let mut first = 0;
+    while first < changes.len() {
+        // Changes whose contexts touch go in the same hunk.
+        let mut last = first;
+        while last + 1 < changes.len() && changes[last + 1] - changes[last] <= 2 * CONTEXT + 1 {
+            last += 1;
+        }
+        let start = changes[first].saturating_sub(CONTEXT);
+        let end = cmp::min(changes[last] + 1 + CONTEXT, edits.len());
+
+        let old_start = edits[..start]
+            .iter()
+            .filter(|(op, _)| *op != Op::Insert)
+            .count()
+            + 1;
+        let new_start = edits[..start]
+            .iter()
+            .filter(|(op, _)| *op != Op::Delete)
+            .count()
+            + 1;
+        let hunk = &edits[start..end];
+        let old_count = hunk.iter().filter(|(op, _)| *op != Op::Insert).count();
+        let new_count = hunk.iter().filter(|(op, _)| *op != Op::Delete).count();
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_count),
+            range(new_start, new_count)
+        ));
+
+        for (op, line) in hunk {
+            out.push(match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            });
+            out.push_str(line);
+            if !line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+        first = last + 1;
+    }
+    out
+}
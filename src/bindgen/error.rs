@@ -4,8 +4,10 @@
 
 use std::error;
 use std::fmt;
+use std::io;
 
 pub use crate::bindgen::cargo::cargo_expand::Error as CargoExpandError;
+pub use crate::bindgen::cargo::cargo_layout::Error as CargoLayoutError;
 pub use crate::bindgen::cargo::cargo_metadata::Error as CargoMetadataError;
 pub use crate::bindgen::cargo::cargo_toml::Error as CargoTomlError;
 pub use syn::parse::Error as ParseError;
@@ -15,6 +17,7 @@ pub enum Error {
     CargoMetadata(String, CargoMetadataError),
     CargoToml(String, CargoTomlError),
     CargoExpand(String, CargoExpandError),
+    CargoLayout(String, CargoLayoutError),
     ParseSyntaxError {
         crate_name: String,
         src_path: String,
@@ -24,6 +27,14 @@ pub enum Error {
         crate_name: String,
         src_path: String,
     },
+    IrFile(String, serde_json::Error),
+    TargetCfgs(String, String),
+    Template(String, io::Error),
+    UnresolvedTypes(Vec<String>),
+    SkippedItems(Vec<String>),
+    NameCollisions(Vec<String>),
+    InvalidSymbols(Vec<String>),
+    Config(String),
 }
 
 impl fmt::Display for Error {
@@ -42,6 +53,11 @@ impl fmt::Display for Error {
                 "Parsing crate `{}`: couldn't run `cargo rustc --pretty=expanded`: {:?}",
                 crate_name, error
             ),
+            Error::CargoLayout(ref crate_name, ref error) => write!(
+                f,
+                "Computing layouts of crate `{}`: couldn't run `cargo rustc -Zprint-type-sizes`: {:?}",
+                crate_name, error
+            ),
             Error::ParseSyntaxError {
                 ref crate_name,
                 ref src_path,
@@ -70,6 +86,32 @@ impl fmt::Display for Error {
                 "Parsing crate `{}`: cannot open file `{}`.",
                 crate_name, src_path
             ),
+            Error::IrFile(ref path, ref error) => {
+                write!(f, "Couldn't load IR file {:?}: {}", path, error)
+            }
+            Error::TargetCfgs(ref target, ref error) => write!(
+                f,
+                "Couldn't run `rustc --print cfg --target {}`: {}",
+                target, error
+            ),
+            Error::Template(ref path, ref error) => {
+                write!(f, "Couldn't read template file {:?}: {}", path, error)
+            }
+            Error::UnresolvedTypes(ref names) => {
+                write!(f, "Couldn't resolve types: {}", names.join(", "))
+            }
+            Error::SkippedItems(ref names) => {
+                write!(f, "Couldn't use exported items: {}", names.join(", "))
+            }
+            Error::NameCollisions(ref names) => {
+                write!(f, "Items written with the same name: {}", names.join(", "))
+            }
+            Error::InvalidSymbols(ref names) => write!(
+                f,
+                "Symbols which can't be declared in C: {}",
+                names.join(", ")
+            ),
+            Error::Config(ref msg) => write!(f, "{}", msg),
         }
     }
 }
@@ -80,8 +122,17 @@ impl error::Error for Error {
             Error::CargoMetadata(_, ref error) => Some(error),
             Error::CargoToml(_, ref error) => Some(error),
             Error::CargoExpand(_, ref error) => Some(error),
+            Error::CargoLayout(_, ref error) => Some(error),
             Error::ParseSyntaxError { ref error, .. } => Some(error),
             Error::ParseCannotOpenFile { .. } => None,
+            Error::IrFile(_, ref error) => Some(error),
+            Error::TargetCfgs(..) => None,
+            Error::Template(_, ref error) => Some(error),
+            Error::UnresolvedTypes(..) => None,
+            Error::SkippedItems(..) => None,
+            Error::NameCollisions(..) => None,
+            Error::InvalidSymbols(..) => None,
+            Error::Config(..) => None,
         }
     }
 }
@@ -0,0 +1,555 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::io::Write;
+
+use heck::SnakeCase;
+
+use crate::bindgen::cdecl;
+use crate::bindgen::config::{Config, Language, Style};
+use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
+use crate::bindgen::ir::{
+    Constant, Documentation, Enum, Field, Function, GObject, GType, Item, ItemContainer, Literal,
+    PrimitiveType, Transfer, Type,
+};
+use crate::bindgen::writer::SourceWriter;
+
+/// Escapes `text` to be used as XML character data or attribute value.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn primitive_name(primitive: &PrimitiveType, config: &Config) -> &'static str {
+    match *primitive {
+        PrimitiveType::Void => "none",
+        PrimitiveType::Bool => "gboolean",
+        PrimitiveType::VaList => "va_list",
+        _ => primitive.to_repr_glib(config).unwrap(),
+    }
+}
+
+/// Writes a GObject introspection repository describing the bindings.
+pub struct GirWriter<'a> {
+    config: &'a Config,
+    /// The configuration used to spell the C types, GIR describing a C API.
+    c_config: Config,
+    namespace: String,
+    symbol_prefix: String,
+}
+
+impl<'a> GirWriter<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        let mut c_config = config.clone();
+        c_config.language = Language::C;
+        c_config.style = Style::Type;
+
+        let namespace = match config.gir.namespace {
+            Some(ref namespace) => namespace.clone(),
+            None => config.export.prefix.clone().unwrap_or_else(|| {
+                warn!("No GIR namespace, set `gir.namespace` or `export.prefix`.");
+                String::new()
+            }),
+        };
+        let symbol_prefix = namespace.to_snake_case();
+
+        GirWriter {
+            config,
+            c_config,
+            namespace,
+            symbol_prefix,
+        }
+    }
+
+    /// The introspection name of a type, without its namespace prefix.
+    fn local_name<'n>(&self, name: &'n str) -> &'n str {
+        if name.len() > self.namespace.len() && name.starts_with(&self.namespace) {
+            &name[self.namespace.len()..]
+        } else {
+            name
+        }
+    }
+
+    /// The introspection name of a function, without its symbol prefix.
+    fn local_symbol<'n>(&self, name: &'n str) -> &'n str {
+        if self.symbol_prefix.is_empty() {
+            return name;
+        }
+        let prefix_len = self.symbol_prefix.len() + 1;
+        if name.len() > prefix_len
+            && name.starts_with(&self.symbol_prefix)
+            && name[self.symbol_prefix.len()..].starts_with('_')
+        {
+            &name[prefix_len..]
+        } else {
+            name
+        }
+    }
+
+    /// The introspection name of a parent class, which may come from GObject.
+    fn parent_name(&self, parent: &str) -> String {
+        match parent {
+            "Object" | "GObject" => "GObject.Object".to_owned(),
+            "InitiallyUnowned" | "GInitiallyUnowned" => "GObject.InitiallyUnowned".to_owned(),
+            parent => self.local_name(parent).to_owned(),
+        }
+    }
+
+    fn type_name(&self, ty: &Type) -> String {
+        match *ty {
+            Type::Primitive(ref primitive) => primitive_name(primitive, self.config).to_owned(),
+            Type::Ptr { ref ty, .. } => match **ty {
+                Type::Primitive(PrimitiveType::Char) => "utf8".to_owned(),
+                Type::Path(ref generic) => self.local_name(generic.export_name()).to_owned(),
+                _ => "gpointer".to_owned(),
+            },
+            Type::Path(ref generic) => self.local_name(generic.export_name()).to_owned(),
+            Type::Array(..) | Type::FuncPtr { .. } => "gpointer".to_owned(),
+        }
+    }
+
+    fn c_type<F: Write>(&self, out: &SourceWriter<F>, ty: &Type) -> String {
+        // C bindings may have resolved `struct`/`enum` keywords, which the
+        // introspection C types never spell.
+        let mut ty = ty.clone();
+        ty.resolve_declaration_types(&DeclarationTypeResolver::default());
+
+        let mut c_type = Vec::new();
+        {
+            let mut writer = SourceWriter::new(&mut c_type, out.bindings());
+            cdecl::write_type(&mut writer, &ty, &self.c_config);
+        }
+        String::from_utf8(c_type).unwrap()
+    }
+
+    fn write_type<F: Write>(&self, out: &mut SourceWriter<F>, ty: &Type) {
+        if let Type::Array(ref element, ref length) = *ty {
+            write!(
+                out,
+                "<array zero-terminated=\"0\" fixed-size=\"{}\">",
+                escape(&length.to_string())
+            );
+            out.push_tab();
+            out.new_line();
+            self.write_type(out, element);
+            out.pop_tab();
+            out.new_line();
+            out.write("</array>");
+            return;
+        }
+
+        let c_type = self.c_type(out, ty);
+        write!(
+            out,
+            "<type name=\"{}\" c:type=\"{}\"/>",
+            escape(&self.type_name(ty)),
+            escape(&c_type)
+        );
+    }
+
+    /// Writes the `<doc>` element, returning whether there was one.
+    fn write_doc<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        documentation: &Documentation,
+    ) -> bool {
+        if documentation.doc_comment.is_empty() || !self.config.documentation {
+            return false;
+        }
+
+        let lines: Vec<&str> = documentation
+            .doc_comment
+            .iter()
+            .map(|line| line.trim())
+            .collect();
+        // The blank lines before the annotations aren't part of the text.
+        write!(
+            out,
+            "<doc xml:space=\"preserve\">{}</doc>",
+            escape(lines.join("\n").trim_end())
+        );
+        true
+    }
+
+    fn write_fields<F: Write>(&self, out: &mut SourceWriter<F>, fields: &[Field]) {
+        for field in fields {
+            out.new_line();
+            write!(out, "<field name=\"{}\">", escape(&field.name));
+            out.push_tab();
+            out.new_line();
+            self.write_type(out, &field.ty);
+            out.pop_tab();
+            out.new_line();
+            out.write("</field>");
+        }
+    }
+
+    fn write_enum<F: Write>(&self, out: &mut SourceWriter<F>, e: &Enum) {
+        // Enums with data are tagged unions, which can't be introspected.
+        if e.tag.is_some() {
+            return;
+        }
+
+        write!(
+            out,
+            "<enumeration name=\"{}\" c:type=\"{}\"",
+            escape(self.local_name(e.export_name())),
+            escape(e.export_name())
+        );
+        if self.config.gobject.enabled && e.has_gtype() {
+            write!(
+                out,
+                " glib:type-name=\"{}\" glib:get-type=\"{}\"",
+                escape(e.export_name()),
+                e.get_type_function()
+            );
+        }
+        out.write(">");
+        out.push_tab();
+        out.new_line();
+        if self.write_doc(out, &e.documentation) {
+            out.new_line();
+        }
+
+        let mut value = 0;
+        for (i, variant) in e.variants.iter().enumerate() {
+            if let Some(Literal::Expr(ref discriminant)) = variant.discriminant {
+                if let Ok(discriminant) = discriminant.parse::<i64>() {
+                    value = discriminant;
+                }
+            }
+            if i != 0 {
+                out.new_line();
+            }
+            let name = e.member_name(variant);
+            write!(
+                out,
+                "<member name=\"{}\" value=\"{}\" c:identifier=\"{}\"/>",
+                escape(&name),
+                value,
+                escape(&variant.export_name)
+            );
+            value += 1;
+        }
+
+        out.pop_tab();
+        out.new_line();
+        out.write("</enumeration>");
+        out.new_line();
+    }
+
+    fn write_item<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        item: &ItemContainer,
+        gobjects: &[GObject],
+    ) {
+        match *item {
+            ItemContainer::Enum(ref e) => self.write_enum(out, e),
+            ItemContainer::Struct(ref s) => {
+                write!(
+                    out,
+                    "<record name=\"{}\" c:type=\"{}\">",
+                    escape(self.local_name(s.export_name())),
+                    escape(s.export_name())
+                );
+                out.push_tab();
+                if !s.documentation.doc_comment.is_empty() && self.config.documentation {
+                    out.new_line();
+                    self.write_doc(out, &s.documentation);
+                }
+                self.write_fields(out, &s.fields);
+                out.pop_tab();
+                out.new_line();
+                out.write("</record>");
+                out.new_line();
+            }
+            ItemContainer::Union(ref u) => {
+                write!(
+                    out,
+                    "<union name=\"{}\" c:type=\"{}\">",
+                    escape(self.local_name(u.export_name())),
+                    escape(u.export_name())
+                );
+                out.push_tab();
+                self.write_fields(out, &u.fields);
+                out.pop_tab();
+                out.new_line();
+                out.write("</union>");
+                out.new_line();
+            }
+            ItemContainer::OpaqueItem(ref o) => {
+                let boxed = gobjects
+                    .iter()
+                    .find(|gobject| gobject.is_boxed() && gobject.path() == o.path());
+                match boxed {
+                    Some(boxed) => {
+                        let snake = boxed.name.to_snake_case();
+                        write!(
+                            out,
+                            "<record name=\"{}\" c:type=\"{}\" glib:type-name=\"{}\" \
+                             glib:get-type=\"{}_get_type\" c:symbol-prefix=\"{}\"/>",
+                            escape(self.local_name(o.export_name())),
+                            escape(o.export_name()),
+                            escape(&boxed.name),
+                            snake,
+                            self.local_symbol(&snake)
+                        );
+                    }
+                    None => {
+                        write!(
+                            out,
+                            "<record name=\"{}\" c:type=\"{}\" disguised=\"1\"/>",
+                            escape(self.local_name(o.export_name())),
+                            escape(o.export_name())
+                        );
+                    }
+                }
+                out.new_line();
+            }
+            ItemContainer::Typedef(ref t) => {
+                write!(
+                    out,
+                    "<alias name=\"{}\" c:type=\"{}\">",
+                    escape(self.local_name(t.export_name())),
+                    escape(t.export_name())
+                );
+                out.push_tab();
+                out.new_line();
+                self.write_type(out, &t.aliased);
+                out.pop_tab();
+                out.new_line();
+                out.write("</alias>");
+                out.new_line();
+            }
+            ItemContainer::Constant(..) | ItemContainer::Static(..) => unreachable!(),
+            ItemContainer::GObject(..) => unreachable!(),
+        }
+    }
+
+    fn write_constant<F: Write>(&self, out: &mut SourceWriter<F>, constant: &Constant) {
+        // Only literal values can be described, not expressions.
+        let value = match constant.value {
+            Literal::Expr(ref value) => value.trim_matches(|c| c == '"' || c == '\''),
+            _ => return,
+        };
+
+        write!(
+            out,
+            "<constant name=\"{}\" value=\"{}\" c:type=\"{}\">",
+            escape(self.local_name(constant.export_name())),
+            escape(value),
+            escape(constant.export_name())
+        );
+        out.push_tab();
+        out.new_line();
+        if self.write_doc(out, &constant.documentation) {
+            out.new_line();
+        }
+        self.write_type(out, &constant.ty);
+        out.pop_tab();
+        out.new_line();
+        out.write("</constant>");
+        out.new_line();
+    }
+
+    fn write_function<F: Write>(&self, out: &mut SourceWriter<F>, function: &Function) {
+        write!(
+            out,
+            "<function name=\"{}\" c:identifier=\"{}\"{}>",
+            escape(self.local_symbol(function.path().name())),
+            escape(function.path().name()),
+            if function.throws() {
+                " throws=\"1\""
+            } else {
+                ""
+            }
+        );
+        out.push_tab();
+        out.new_line();
+        if self.write_doc(out, &function.documentation) {
+            out.new_line();
+        }
+
+        write!(
+            out,
+            "<return-value transfer-ownership=\"{}\">",
+            function.ret_transfer().unwrap_or(Transfer::None).as_str()
+        );
+        out.push_tab();
+        out.new_line();
+        self.write_type(out, &function.ret);
+        out.pop_tab();
+        out.new_line();
+        out.write("</return-value>");
+
+        // The error isn't a parameter of the throwing functions.
+        let args = if function.throws() {
+            &function.args[..function.args.len() - 1]
+        } else {
+            &function.args[..]
+        };
+        if !args.is_empty() {
+            out.new_line();
+            out.write("<parameters>");
+            out.push_tab();
+            for (i, arg) in args.iter().enumerate() {
+                let name = match arg.name {
+                    Some(ref name) => name.clone(),
+                    None => format!("arg{}", i),
+                };
+                out.new_line();
+                write!(
+                    out,
+                    "<parameter name=\"{}\" transfer-ownership=\"{}\">",
+                    escape(&name),
+                    function
+                        .arg_transfer(arg)
+                        .unwrap_or(Transfer::None)
+                        .as_str()
+                );
+                out.push_tab();
+                out.new_line();
+                self.write_type(out, &arg.ty);
+                out.pop_tab();
+                out.new_line();
+                out.write("</parameter>");
+            }
+            out.pop_tab();
+            out.new_line();
+            out.write("</parameters>");
+        }
+
+        out.pop_tab();
+        out.new_line();
+        out.write("</function>");
+        out.new_line();
+    }
+
+    pub fn write<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        items: &[ItemContainer],
+        constants: &[Constant],
+        functions: &[Function],
+        gobjects: &[GObject],
+    ) {
+        let gir = &self.config.gir;
+
+        out.write("<?xml version=\"1.0\"?>");
+        out.new_line();
+        out.write("<repository version=\"1.2\"");
+        out.write(" xmlns=\"http://www.gtk.org/introspection/core/1.0\"");
+        out.write(" xmlns:c=\"http://www.gtk.org/introspection/c/1.0\"");
+        out.write(" xmlns:glib=\"http://www.gtk.org/introspection/glib/1.0\">");
+        out.push_tab();
+
+        for include in &gir.includes {
+            let mut parts = include.rsplitn(2, '-');
+            let version = parts.next().unwrap();
+            let name = parts.next().unwrap_or(version);
+            out.new_line();
+            write!(
+                out,
+                "<include name=\"{}\" version=\"{}\"/>",
+                escape(name),
+                escape(version)
+            );
+        }
+        for include in &gir.c_includes {
+            out.new_line();
+            write!(out, "<c:include name=\"{}\"/>", escape(include));
+        }
+
+        out.new_line();
+        write!(
+            out,
+            "<namespace name=\"{}\" version=\"{}\"",
+            escape(&self.namespace),
+            escape(&gir.version)
+        );
+        if let Some(ref shared_library) = gir.shared_library {
+            write!(out, " shared-library=\"{}\"", escape(shared_library));
+        }
+        write!(
+            out,
+            " c:identifier-prefixes=\"{}\" c:symbol-prefixes=\"{}\">",
+            escape(&self.namespace),
+            escape(&self.symbol_prefix)
+        );
+        out.push_tab();
+        out.new_line();
+
+        for item in items {
+            if item
+                .deref()
+                .annotations()
+                .bool("no-export")
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            self.write_item(out, item, gobjects);
+        }
+
+        // Boxed types are declared alongside their opaque item.
+        for gobject in gobjects {
+            let (element, parent) = match gobject.gtype {
+                GType::Object {
+                    ref parent_type, ..
+                } => (
+                    "class",
+                    parent_type
+                        .get_root_path()
+                        .map(|parent| parent.name().to_owned()),
+                ),
+                GType::Final { ref parent } => ("class", Some(parent.clone())),
+                GType::Interface { .. } | GType::TraitInterface { .. } => ("interface", None),
+                GType::Boxed { .. } => continue,
+            };
+            write!(
+                out,
+                "<{} name=\"{}\" c:type=\"{}\"",
+                element,
+                escape(self.local_name(&gobject.name)),
+                escape(&gobject.name)
+            );
+            if let Some(parent) = parent {
+                write!(out, " parent=\"{}\"", escape(&self.parent_name(&parent)));
+            }
+            write!(
+                out,
+                " glib:type-name=\"{}\" glib:get-type=\"{}_get_type\"/>",
+                escape(&gobject.name),
+                gobject.name.to_snake_case()
+            );
+            out.new_line();
+        }
+
+        for constant in constants {
+            self.write_constant(out, constant);
+        }
+
+        for function in functions {
+            self.write_function(out, function);
+        }
+
+        out.pop_tab();
+        out.write("</namespace>");
+        out.pop_tab();
+        out.new_line();
+        out.write("</repository>");
+        out.new_line();
+    }
+}
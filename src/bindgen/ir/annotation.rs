@@ -23,7 +23,7 @@ use crate::bindgen::utilities::SynAttributeHelpers;
 //  * cbindgen:function-postfix=WR_DESTRUCTOR_SAFE
 
 /// A value specified by an annotation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AnnotationValue {
     List(Vec<String>),
     Atom(Option<String>),
@@ -31,10 +31,18 @@ pub enum AnnotationValue {
 }
 
 /// A set of annotations specified by a document comment.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct AnnotationSet {
     annotations: HashMap<String, AnnotationValue>,
     pub must_use: bool,
+    /// The note of `#[deprecated]`, empty if it has none.
+    pub deprecated: Option<String>,
+    /// Where the item is declared, as `file:line`, if it's in a source file.
+    pub location: Option<String>,
+    /// The path of the item in Rust, like `mycrate::net::Socket`, if it's in
+    /// a crate.
+    #[serde(skip)]
+    pub rust_path: Option<String>,
 }
 
 impl AnnotationSet {
@@ -42,6 +50,9 @@ impl AnnotationSet {
         AnnotationSet {
             annotations: HashMap::new(),
             must_use: false,
+            deprecated: None,
+            location: None,
+            rust_path: None,
         }
     }
 
@@ -53,6 +64,26 @@ impl AnnotationSet {
         self.must_use && config.language != Language::Cython
     }
 
+    /// The attributes of the item in C and C++: the one declaring it
+    /// deprecated if it's marked `#[deprecated]`, and the ones written
+    /// verbatim with the `attributes` annotation.
+    pub(crate) fn attributes(&self, config: &Config) -> Option<String> {
+        let deprecated = self
+            .deprecated
+            .as_ref()
+            .and_then(|x| config.deprecated_attribute(x));
+        let verbatim = match self.atom("attributes") {
+            Some(Some(x)) if config.language == Language::C || config.language == Language::Cxx => {
+                Some(x)
+            }
+            _ => None,
+        };
+        match (deprecated, verbatim) {
+            (Some(deprecated), Some(verbatim)) => Some(format!("{} {}", deprecated, verbatim)),
+            (deprecated, verbatim) => deprecated.or(verbatim),
+        }
+    }
+
     pub fn load(attrs: &[syn::Attribute]) -> Result<AnnotationSet, String> {
         let lines = attrs.get_comment_lines();
         let lines: Vec<&str> = lines
@@ -68,6 +99,7 @@ impl AnnotationSet {
             .collect();
 
         let must_use = attrs.has_attr_word("must_use");
+        let deprecated = attrs.deprecated_note();
 
         let mut annotations = HashMap::new();
 
@@ -78,12 +110,9 @@ impl AnnotationSet {
             // Remove the "cbindgen:" prefix
             let annotation = &line[9..];
 
-            // Split the annotation in two
-            let parts: Vec<&str> = annotation.split('=').map(|x| x.trim()).collect();
-
-            if parts.len() > 2 {
-                return Err(format!("Couldn't parse {}.", line));
-            }
+            // Split the annotation in two, the value being verbatim text for
+            // some annotations.
+            let parts: Vec<&str> = annotation.splitn(2, '=').map(|x| x.trim()).collect();
 
             // Grab the name that this annotation is modifying
             let name = parts[0];
@@ -118,6 +147,9 @@ impl AnnotationSet {
         Ok(AnnotationSet {
             annotations,
             must_use,
+            deprecated,
+            location: None,
+            rust_path: None,
         })
     }
 
@@ -128,6 +160,15 @@ impl AnnotationSet {
         }
     }
 
+    /// Sets an annotation value, replacing the specified one.
+    pub fn set(&mut self, name: &str, value: AnnotationValue) {
+        self.annotations.insert(name.to_string(), value);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<AnnotationValue> {
+        self.annotations.remove(name)
+    }
+
     pub fn list(&self, name: &str) -> Option<Vec<String>> {
         match self.annotations.get(name) {
             Some(&AnnotationValue::List(ref x)) => Some(x.clone()),
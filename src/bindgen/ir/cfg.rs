@@ -42,7 +42,87 @@ impl<'a> DefineKey<'a> {
     }
 }
 
-#[derive(Debug, Clone)]
+/// The conditions of `[platform]` when `builtin` is set, testing the macros
+/// predefined by GCC, Clang and MSVC.
+const PLATFORM_CONDITIONS: &[(&str, &str)] = &[
+    ("unix", "defined(__unix__) || defined(__APPLE__)"),
+    ("windows", "defined(_WIN32)"),
+    (
+        "target_family = unix",
+        "defined(__unix__) || defined(__APPLE__)",
+    ),
+    ("target_family = windows", "defined(_WIN32)"),
+    ("target_family = wasm", "defined(__wasm__)"),
+    ("target_os = windows", "defined(_WIN32)"),
+    (
+        "target_os = linux",
+        "defined(__linux__) && !defined(__ANDROID__)",
+    ),
+    ("target_os = android", "defined(__ANDROID__)"),
+    (
+        "target_os = macos",
+        "defined(__APPLE__) && defined(__MACH__)",
+    ),
+    ("target_os = freebsd", "defined(__FreeBSD__)"),
+    ("target_os = netbsd", "defined(__NetBSD__)"),
+    ("target_os = openbsd", "defined(__OpenBSD__)"),
+    ("target_os = dragonfly", "defined(__DragonFly__)"),
+    ("target_os = emscripten", "defined(__EMSCRIPTEN__)"),
+    ("target_env = msvc", "defined(_MSC_VER)"),
+    (
+        "target_env = gnu",
+        "defined(__GLIBC__) || defined(__MINGW32__)",
+    ),
+    ("target_arch = x86", "defined(__i386__) || defined(_M_IX86)"),
+    (
+        "target_arch = x86_64",
+        "defined(__x86_64__) || defined(_M_X64)",
+    ),
+    ("target_arch = arm", "defined(__arm__) || defined(_M_ARM)"),
+    (
+        "target_arch = aarch64",
+        "defined(__aarch64__) || defined(_M_ARM64)",
+    ),
+    ("target_arch = wasm32", "defined(__wasm32__)"),
+    (
+        "target_pointer_width = 32",
+        "__SIZEOF_POINTER__ == 4 || (defined(_WIN32) && !defined(_WIN64))",
+    ),
+    (
+        "target_pointer_width = 64",
+        "__SIZEOF_POINTER__ == 8 || defined(_WIN64)",
+    ),
+    (
+        "target_endian = little",
+        "__BYTE_ORDER__ == __ORDER_LITTLE_ENDIAN__ || defined(_MSC_VER)",
+    ),
+    (
+        "target_endian = big",
+        "__BYTE_ORDER__ == __ORDER_BIG_ENDIAN__",
+    ),
+];
+
+/// The `[platform]` condition of `key`, which is only used in C and C++.
+fn platform_condition(key: DefineKey, config: &Config) -> Option<Condition> {
+    if config.language != Language::C && config.language != Language::Cxx {
+        return None;
+    }
+    let platform = &config.platform;
+    let builtin = if platform.builtin {
+        PLATFORM_CONDITIONS
+    } else {
+        &[]
+    };
+    let (_, condition) = platform
+        .conditions
+        .iter()
+        .map(|(key, condition)| (&**key, &**condition))
+        .chain(builtin.iter().cloned())
+        .find(|(x, ..)| DefineKey::load(x) == key)?;
+    Some(Condition::Expr(condition.to_owned()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Cfg {
     Boolean(String),
     Named(String, String),
@@ -222,7 +302,7 @@ impl<'a> ToCondition for Cfg {
                     //     "Missing `[defines]` entry for `{}` in cbindgen config.",
                     //     self,
                     // );
-                    None
+                    platform_condition(DefineKey::Boolean(cfg_name), config)
                 }
             }
             Cfg::Named(ref cfg_name, ref cfg_value) => {
@@ -236,7 +316,7 @@ impl<'a> ToCondition for Cfg {
                     //     "Missing `[defines]` entry for `{}` in cbindgen config.",
                     //     self,
                     // );
-                    None
+                    platform_condition(DefineKey::Named(cfg_name, cfg_value), config)
                 }
             }
             Cfg::Any(ref children) => {
@@ -268,16 +348,18 @@ impl<'a> ToCondition for Cfg {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Condition {
     Define(String),
+    /// A preprocessor expression, from `[platform]`.
+    Expr(String),
     Any(Vec<Condition>),
     All(Vec<Condition>),
     Not(Box<Condition>),
 }
 
 impl Condition {
-    fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
+    pub(crate) fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
         match *self {
             Condition::Define(ref define) => {
                 if config.language == Language::Cython {
@@ -288,6 +370,7 @@ impl Condition {
                     out.write(")");
                 }
             }
+            Condition::Expr(ref expr) => write!(out, "({})", expr),
             Condition::Any(ref conditions) => {
                 out.write("(");
                 for (i, condition) in conditions.iter().enumerate() {
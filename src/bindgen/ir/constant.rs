@@ -6,30 +6,34 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::Write;
 
+use serde::de::{Deserialize, Deserializer, Error as _};
 use syn::{self, UnOp};
 
 use crate::bindgen::config::{Config, Language};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
 use crate::bindgen::ir::{
-    AnnotationSet, Cfg, ConditionWrite, Documentation, GenericParams, Item, ItemContainer, Path,
-    Struct, ToCondition, Type,
+    AnnotationSet, Cfg, ConditionWrite, Documentation, GenericParams, IntKind, Item, ItemContainer,
+    Path, PrimitiveType, Struct, ToCondition, Type,
 };
 use crate::bindgen::library::Library;
+use crate::bindgen::rename::IdentifierType;
 use crate::bindgen::writer::{Source, SourceWriter};
 use crate::bindgen::Bindings;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Literal {
     Expr(String),
     Path(String),
     PostfixUnaryOp {
-        op: &'static str,
+        #[serde(deserialize_with = "deserialize_operator")]
+        op: Operator,
         value: Box<Literal>,
     },
     BinOp {
         left: Box<Literal>,
-        op: &'static str,
+        #[serde(deserialize_with = "deserialize_operator")]
+        op: Operator,
         right: Box<Literal>,
     },
     Struct {
@@ -43,6 +47,24 @@ pub enum Literal {
     },
 }
 
+/// An operator of a `Literal`, one of `OPERATORS`.
+type Operator = &'static str;
+
+/// The operators a `Literal` can be made of.
+const OPERATORS: &[&str] = &[
+    "+", "-", "*", "/", "%", "&&", "||", "^", "&", "|", "<<", ">>", "==", "<", "<=", "!=", ">=",
+    ">", "+=", "-=", "*=", "/=", "%=", "^=", "&=", "|=", "<<=", ">>=", "!", "~",
+];
+
+fn deserialize_operator<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Operator, D::Error> {
+    let op = String::deserialize(deserializer)?;
+    OPERATORS
+        .iter()
+        .find(|known| **known == op)
+        .cloned()
+        .ok_or_else(|| D::Error::custom(format!("unknown operator {}", op)))
+}
+
 impl Literal {
     fn replace_self_with(&mut self, self_ty: &Path) {
         match *self {
@@ -76,14 +98,88 @@ impl Literal {
                 ty.replace_self_with(self_ty);
                 value.replace_self_with(self_ty);
             }
-            Literal::Expr(..) | Literal::Path(..) => {}
+            Literal::Path(ref mut name) => {
+                if name.starts_with("Self::") {
+                    *name = format!("{}{}", self_ty.name(), &name["Self".len()..]);
+                }
+            }
+            Literal::Expr(..) => {}
+        }
+    }
+
+    /// Whether the literal refers to a qualified path, like `Foo::BAR` or
+    /// `u32::MAX`, which has to be evaluated to be written.
+    pub(crate) fn has_qualified_path(&self) -> bool {
+        match *self {
+            Literal::Expr(..) => false,
+            Literal::Path(ref name) => name.contains("::"),
+            Literal::PostfixUnaryOp { ref value, .. } => value.has_qualified_path(),
+            Literal::BinOp {
+                ref left,
+                ref right,
+                ..
+            } => left.has_qualified_path() || right.has_qualified_path(),
+            Literal::Struct { ref fields, .. } => fields.values().any(|x| x.has_qualified_path()),
+            Literal::Cast { ref value, .. } => value.has_qualified_path(),
+        }
+    }
+
+    /// Evaluates an integer or boolean literal, `lookup` giving the value of
+    /// the paths it refers to. Returns `None` if it isn't one or overflows.
+    pub(crate) fn eval(&self, lookup: &dyn Fn(&str) -> Option<i128>) -> Option<i128> {
+        match *self {
+            Literal::Expr(ref v) => match &**v {
+                "true" => Some(1),
+                "false" => Some(0),
+                v => v.trim_end_matches("ULL").parse().ok(),
+            },
+            Literal::Path(ref name) => lookup(name),
+            Literal::PostfixUnaryOp { op, ref value } => {
+                let value = value.eval(lookup)?;
+                match op {
+                    "-" => value.checked_neg(),
+                    "~" => Some(!value),
+                    "!" => Some((value == 0) as i128),
+                    _ => None,
+                }
+            }
+            Literal::BinOp {
+                ref left,
+                op,
+                ref right,
+            } => {
+                let (l, r) = (left.eval(lookup)?, right.eval(lookup)?);
+                match op {
+                    "+" => l.checked_add(r),
+                    "-" => l.checked_sub(r),
+                    "*" => l.checked_mul(r),
+                    "/" => l.checked_div(r),
+                    "%" => l.checked_rem(r),
+                    "&" => Some(l & r),
+                    "|" => Some(l | r),
+                    "^" => Some(l ^ r),
+                    "<<" if (0..128).contains(&r) => l.checked_shl(r as u32),
+                    ">>" if (0..128).contains(&r) => l.checked_shr(r as u32),
+                    "&&" => Some((l != 0 && r != 0) as i128),
+                    "||" => Some((l != 0 || r != 0) as i128),
+                    "==" => Some((l == r) as i128),
+                    "!=" => Some((l != r) as i128),
+                    "<" => Some((l < r) as i128),
+                    "<=" => Some((l <= r) as i128),
+                    ">" => Some((l > r) as i128),
+                    ">=" => Some((l >= r) as i128),
+                    _ => None,
+                }
+            }
+            Literal::Struct { .. } => None,
+            Literal::Cast { ref ty, ref value } => Some(wrap_to_type(value.eval(lookup)?, ty)),
         }
     }
 
     fn is_valid(&self, bindings: &Bindings) -> bool {
         match *self {
             Literal::Expr(..) => true,
-            Literal::Path(..) => true,
+            Literal::Path(ref name) => !name.contains("::"),
             Literal::PostfixUnaryOp { ref value, .. } => value.is_valid(bindings),
             Literal::BinOp {
                 ref left,
@@ -121,13 +217,13 @@ impl Literal {
                 fields,
                 ..
             } => {
-                config.export.rename(export_name);
+                config.export.rename_reference(export_name);
                 for lit in fields.values_mut() {
                     lit.rename_for_config(config);
                 }
             }
             Literal::Path(ref mut name) => {
-                config.export.rename(name);
+                config.export.rename_reference(name);
             }
             Literal::PostfixUnaryOp { ref mut value, .. } => {
                 value.rename_for_config(config);
@@ -214,7 +310,14 @@ impl Literal {
                         Ok(Literal::Expr(value.base10_digits().to_string()))
                     }
                     syn::Lit::Bool(ref value) => Ok(Literal::Expr(format!("{}", value.value))),
-                    // TODO: Add support for byte string and Verbatim
+                    syn::Lit::Str(ref value) => {
+                        Ok(Literal::Expr(c_string_literal(value.value().as_bytes())))
+                    }
+                    syn::Lit::ByteStr(ref value) => Ok(Literal::Cast {
+                        ty: byte_string_type(),
+                        value: Box::new(Literal::Expr(c_string_literal(&value.value()))),
+                    }),
+                    // TODO: Add support for Verbatim
                     _ => Err(format!("Unsupported literal expression. {:?}", *lit)),
                 }
             }
@@ -252,17 +355,26 @@ impl Literal {
                         value: Box::new(val),
                     })
                 }
+                UnOp::Not(_) => {
+                    let val = Self::load(expr)?;
+                    Ok(Literal::PostfixUnaryOp {
+                        op: if val.is_bool() { "!" } else { "~" },
+                        value: Box::new(val),
+                    })
+                }
                 _ => Err(format!("Unsupported Unary expression. {:?}", *op)),
             },
 
-            // Match identifiers, like `5 << SHIFT`
+            // Match identifiers, like `5 << SHIFT`, and the associated
+            // constants, like `Self::MAX`, which are evaluated later.
             syn::Expr::Path(syn::ExprPath {
                 path: syn::Path { ref segments, .. },
                 ..
             }) => {
                 // Handle only the simplest identifiers and error for anything else.
-                if segments.len() == 1 {
-                    Ok(Literal::Path(format!("{}", segments.last().unwrap().ident)))
+                if segments.len() <= 2 && segments.iter().all(|x| x.arguments.is_empty()) {
+                    let names: Vec<_> = segments.iter().map(|x| x.ident.to_string()).collect();
+                    Ok(Literal::Path(names.join("::")))
                 } else {
                     Err(format!("Unsupported path expression. {:?}", *segments))
                 }
@@ -287,6 +399,28 @@ impl Literal {
         }
     }
 
+    /// Whether the literal is a string or a byte string.
+    pub(crate) fn is_string(&self) -> bool {
+        match *self {
+            Literal::Expr(ref v) => v.starts_with('"'),
+            Literal::Cast { ref value, .. } => value.is_string(),
+            _ => false,
+        }
+    }
+
+    /// Whether the literal is known to be a boolean, for `!` to be written
+    /// as a logical rather than a bitwise not.
+    fn is_bool(&self) -> bool {
+        match *self {
+            Literal::Expr(ref v) => v == "true" || v == "false",
+            Literal::PostfixUnaryOp { op, .. } => op == "!",
+            Literal::BinOp { op, .. } => {
+                ["&&", "||", "==", "!=", "<", "<=", ">", ">="].contains(&op)
+            }
+            _ => false,
+        }
+    }
+
     pub(crate) fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
         match self {
             Literal::Expr(v) => match (&**v, config.language) {
@@ -333,6 +467,13 @@ impl Literal {
                     Language::C => write!(out, "({})", export_name),
                     Language::Cxx => write!(out, "{}", export_name),
                     Language::Cython => write!(out, "<{}>", export_name),
+                    Language::Gir
+                    | Language::CSharp
+                    | Language::Vapi
+                    | Language::Napi
+                    | Language::Ctypes => {
+                        unreachable!()
+                    }
                 }
 
                 write!(out, "{{ ");
@@ -350,6 +491,13 @@ impl Literal {
                             Language::Cxx => write!(out, "/* .{} = */ ", ordered_key),
                             Language::C => write!(out, ".{} = ", ordered_key),
                             Language::Cython => {}
+                            Language::Gir
+                            | Language::CSharp
+                            | Language::Vapi
+                            | Language::Napi
+                            | Language::Ctypes => {
+                                unreachable!()
+                            }
                         }
                         lit.write(config, out);
                     }
@@ -360,7 +508,7 @@ impl Literal {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Constant {
     pub path: Path,
     pub export_name: String,
@@ -381,7 +529,10 @@ impl Constant {
         attrs: &[syn::Attribute],
         associated_to: Option<Path>,
     ) -> Result<Constant, String> {
-        let ty = Type::load(ty)?;
+        let ty = match load_string_type(ty) {
+            Some(ty) => Some(ty),
+            None => Type::load(ty)?,
+        };
         let mut ty = match ty {
             Some(ty) => ty,
             None => {
@@ -390,6 +541,13 @@ impl Constant {
         };
 
         let mut lit = Literal::load(&expr)?;
+        if let (Literal::PostfixUnaryOp { ref mut op, .. }, Type::Primitive(PrimitiveType::Bool)) =
+            (&mut lit, &ty)
+        {
+            if *op == "~" {
+                *op = "!";
+            }
+        }
 
         if let Some(ref associated_to) = associated_to {
             ty.replace_self_with(associated_to);
@@ -432,6 +590,133 @@ impl Constant {
     pub fn uses_only_primitive_types(&self) -> bool {
         self.value.uses_only_primitive_types() && self.ty.is_primitive_or_ptr_primitive()
     }
+
+    /// Evaluates the value of the constant, `lookup` giving the value of the
+    /// paths it refers to.
+    pub(crate) fn eval(&self, lookup: &dyn Fn(&str) -> Option<i128>) -> Option<i128> {
+        Some(wrap_to_type(self.value.eval(lookup)?, &self.ty))
+    }
+
+    /// Replaces the value of the constant with its evaluation. Returns `false`
+    /// if it can't be evaluated.
+    pub(crate) fn fold(&mut self, lookup: &dyn Fn(&str) -> Option<i128>) -> bool {
+        let value = match self.eval(lookup) {
+            Some(value) => value,
+            None => return false,
+        };
+        self.value = Literal::Expr(match self.ty {
+            Type::Primitive(PrimitiveType::Bool) => (value != 0).to_string(),
+            _ if value > i128::from(std::i64::MAX) => format!("{}ULL", value),
+            _ => value.to_string(),
+        });
+        true
+    }
+}
+
+/// Writes `bytes` as a C string literal. The characters which aren't printable
+/// ASCII are written as octal escapes, which unlike hexadecimal ones can't
+/// swallow the digits following them, and `?` is escaped after another `?`
+/// to avoid trigraphs.
+fn c_string_literal(bytes: &[u8]) -> String {
+    let mut literal = String::from("\"");
+    let mut previous = 0;
+    for &byte in bytes {
+        match byte {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            b'\n' => literal.push_str("\\n"),
+            b'\r' => literal.push_str("\\r"),
+            b'\t' => literal.push_str("\\t"),
+            b'?' if previous == b'?' => literal.push_str("\\?"),
+            0x20..=0x7e => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\{:03o}", byte)),
+        }
+        previous = byte;
+    }
+    literal.push('"');
+    literal
+}
+
+/// The type of the byte strings, `const uint8_t*`.
+fn byte_string_type() -> Type {
+    Type::Ptr {
+        ty: Box::new(Type::Primitive(PrimitiveType::maybe("u8").unwrap())),
+        is_const: true,
+        is_nullable: false,
+        is_ref: false,
+    }
+}
+
+/// Loads the types of the string constants, `&str` as `const char*`, and
+/// `&[u8]` and `&[u8; N]` as `const uint8_t*`.
+fn load_string_type(ty: &syn::Type) -> Option<Type> {
+    let reference = match *ty {
+        syn::Type::Reference(ref reference) if reference.mutability.is_none() => reference,
+        _ => return None,
+    };
+    let is_u8 = |ty: &syn::Type| match *ty {
+        syn::Type::Path(ref path) => path.path.is_ident("u8"),
+        _ => false,
+    };
+    match *reference.elem {
+        syn::Type::Path(ref path) if path.path.is_ident("str") => Some(Type::Ptr {
+            ty: Box::new(Type::Primitive(PrimitiveType::Char)),
+            is_const: true,
+            is_nullable: false,
+            is_ref: false,
+        }),
+        syn::Type::Slice(ref slice) if is_u8(&slice.elem) => Some(byte_string_type()),
+        syn::Type::Array(ref array) if is_u8(&array.elem) => Some(byte_string_type()),
+        _ => None,
+    }
+}
+
+/// The signedness and width of `ty`, if it's a fixed size integer.
+fn int_bits(ty: &PrimitiveType) -> Option<(bool, u32)> {
+    match *ty {
+        PrimitiveType::Integer { signed, kind, .. } => match kind {
+            IntKind::B8 => Some((signed, 8)),
+            IntKind::B16 => Some((signed, 16)),
+            IntKind::B32 => Some((signed, 32)),
+            IntKind::B64 => Some((signed, 64)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Wraps `value` around the range of `ty`, as `as` does, if it's a boolean or
+/// a fixed size integer.
+fn wrap_to_type(value: i128, ty: &Type) -> i128 {
+    let (signed, bits) = match *ty {
+        Type::Primitive(PrimitiveType::Bool) => return (value != 0) as i128,
+        Type::Primitive(ref ty) => match int_bits(ty) {
+            Some(x) => x,
+            None => return value,
+        },
+        _ => return value,
+    };
+    let unsigned = value & ((1i128 << bits) - 1);
+    if signed && unsigned >= 1i128 << (bits - 1) {
+        unsigned - (1i128 << bits)
+    } else {
+        unsigned
+    }
+}
+
+/// The value of the `MIN` and `MAX` constants of the fixed size integers,
+/// like `u32::MAX`.
+pub(crate) fn primitive_constant(path: &str) -> Option<i128> {
+    let mut parts = path.splitn(2, "::");
+    let (ty, name) = (parts.next()?, parts.next()?);
+    let (signed, bits) = int_bits(&PrimitiveType::maybe(ty)?)?;
+    match (name, signed) {
+        ("MIN", true) => Some(-(1i128 << (bits - 1))),
+        ("MIN", false) => Some(0),
+        ("MAX", true) => Some((1i128 << (bits - 1)) - 1),
+        ("MAX", false) => Some((1i128 << bits) - 1),
+        _ => None,
+    }
 }
 
 impl Item for Constant {
@@ -495,6 +780,9 @@ impl Constant {
             out.write("static const ");
         }
         self.ty.write(config, out);
+        if self.value.is_string() {
+            out.write(" const");
+        }
         write!(out, " {};", self.export_name())
     }
 
@@ -534,16 +822,27 @@ impl Constant {
         } else if self.associated_to.is_none() {
             Cow::Borrowed(self.export_name())
         } else {
-            let associated_name = match associated_to_struct {
-                Some(s) => Cow::Borrowed(s.export_name()),
-                None => {
-                    let mut name = self.associated_to.as_ref().unwrap().name().to_owned();
-                    config.export.rename(&mut name);
-                    Cow::Owned(name)
-                }
+            let name = if config.constant.prefix_associated {
+                let associated_name = match associated_to_struct {
+                    Some(s) => Cow::Borrowed(s.export_name()),
+                    None => {
+                        let mut name = self.associated_to.as_ref().unwrap().name().to_owned();
+                        config.export.rename(&mut name);
+                        Cow::Owned(name)
+                    }
+                };
+                format!("{}_{}", associated_name, self.export_name())
+            } else {
+                self.export_name().to_owned()
             };
 
-            Cow::Owned(format!("{}_{}", associated_name, self.export_name()))
+            Cow::Owned(
+                config
+                    .constant
+                    .rename_associated
+                    .apply(&name, IdentifierType::Type)
+                    .into_owned(),
+            )
         };
 
         let value = match self.value {
@@ -580,6 +879,10 @@ impl Constant {
                 }
 
                 self.ty.write(config, out);
+                if self.value.is_string() {
+                    // The string is constant, but so has to be the pointer.
+                    out.write(" const");
+                }
                 write!(out, " {} = ", name);
                 value.write(config, out);
                 write!(out, ";");
@@ -589,13 +892,22 @@ impl Constant {
                 value.write(config, out);
             }
             Language::Cython => {
-                out.write("const ");
+                if let Type::Ptr { is_const: true, .. } = self.ty {
+                    // Nothing.
+                } else {
+                    out.write("const ");
+                }
                 self.ty.write(config, out);
                 // For extern Cython declarations the initializer is ignored,
                 // but still useful as documentation, so we write it as a comment.
                 write!(out, " {} # = ", name);
                 value.write(config, out);
             }
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
 
         condition.write_after(config, out);
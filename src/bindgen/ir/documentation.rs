@@ -8,7 +8,7 @@ use crate::bindgen::config::{Config, DocumentationStyle, Language};
 use crate::bindgen::utilities::SynAttributeHelpers;
 use crate::bindgen::writer::{Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Documentation {
     pub doc_comment: Vec<String>,
 }
@@ -35,6 +35,183 @@ impl Documentation {
             doc_comment: Vec::new(),
         }
     }
+
+    /// Whether the documentation of functions is written as gtk-doc blocks.
+    pub fn uses_gtk_doc(config: &Config) -> bool {
+        config.documentation
+            && config.language != Language::Cython
+            && resolve_style(config) == DocumentationStyle::GtkDoc
+    }
+
+    /// Writes the gtk-doc block of the function `name`, given the
+    /// introspection annotations of its named arguments, and of its return
+    /// value unless it returns nothing.
+    ///
+    /// The `# Arguments` and `# Returns` sections become the descriptions of
+    /// the arguments and of the return value. Undocumented functions without
+    /// annotations get no block.
+    pub fn write_gtk_doc<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        name: &str,
+        args: &[(&str, Vec<String>)],
+        ret: Option<&[String]>,
+        throws: bool,
+    ) {
+        let has_annotations = args.iter().any(|(_, annotations)| !annotations.is_empty())
+            || ret.map_or(false, |annotations| !annotations.is_empty());
+        if self.doc_comment.is_empty() && !has_annotations && !throws {
+            return;
+        }
+
+        let sections = FunctionSections::parse(&self.doc_comment);
+
+        out.write("/**");
+        out.new_line();
+        write!(out, " * {}:", name);
+        out.new_line();
+        for (i, &(arg, ref annotations)) in args.iter().enumerate() {
+            let mut description = sections
+                .arguments
+                .iter()
+                .find(|&&(name, _)| name == arg)
+                .map_or(&[][..], |(_, description)| &description[..]);
+            if description.is_empty() && throws && i == args.len() - 1 {
+                description = &["return location for a #GError, or %NULL"];
+            }
+            write_gtk_doc_tag(out, &format!("@{}", arg), annotations, description);
+        }
+
+        if !sections.body.is_empty() {
+            out.write(" *");
+            out.new_line();
+            for line in &sections.body {
+                write!(out, " *{}", line);
+                out.new_line();
+            }
+        }
+
+        if let Some(annotations) = ret {
+            let mut returns = &sections.returns[..];
+            if returns.is_empty() && throws {
+                returns = if annotations.iter().any(|x| x == "(nullable)") {
+                    &["%NULL if @error is set"]
+                } else {
+                    &["%TRUE on success, %FALSE if @error is set"]
+                };
+            }
+            if !annotations.is_empty() || !returns.is_empty() {
+                out.write(" *");
+                out.new_line();
+                write_gtk_doc_tag(out, "Returns", annotations, returns);
+            }
+        }
+
+        out.write(" */");
+        out.new_line();
+    }
+}
+
+/// The style of the comments, with "auto" resolved for the language.
+fn resolve_style(config: &Config) -> DocumentationStyle {
+    match config.documentation_style {
+        DocumentationStyle::Auto if config.language == Language::C && config.gobject.enabled => {
+            DocumentationStyle::GtkDoc
+        }
+        DocumentationStyle::Auto if config.language == Language::C => DocumentationStyle::Doxy,
+        DocumentationStyle::Auto if config.language == Language::Cxx => DocumentationStyle::Cxx,
+        DocumentationStyle::Auto => DocumentationStyle::C, // Fallback if `Language` gets extended.
+        other => other,
+    }
+}
+
+/// Writes a ` * @arg: (annotations): description` line, the description
+/// going on on the following lines.
+fn write_gtk_doc_tag<F: Write>(
+    out: &mut SourceWriter<F>,
+    tag: &str,
+    annotations: &[String],
+    description: &[&str],
+) {
+    write!(out, " * {}:", tag);
+    if !annotations.is_empty() {
+        write!(out, " {}:", annotations.join(" "));
+    }
+    for (i, line) in description.iter().enumerate() {
+        if i > 0 {
+            out.new_line();
+            out.write(" *  ");
+        }
+        write!(out, " {}", line);
+    }
+    out.new_line();
+}
+
+/// The documentation of a function, split into the `# Arguments` and
+/// `# Returns` sections and the remaining lines, without the blank lines
+/// around them.
+struct FunctionSections<'a> {
+    arguments: Vec<(&'a str, Vec<&'a str>)>,
+    returns: Vec<&'a str>,
+    body: Vec<&'a str>,
+}
+
+impl<'a> FunctionSections<'a> {
+    fn parse(lines: &'a [String]) -> Self {
+        let mut sections = FunctionSections {
+            arguments: Vec::new(),
+            returns: Vec::new(),
+            body: Vec::new(),
+        };
+        let mut section = Section::Other;
+        for line in lines {
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') {
+                let heading = trimmed.trim_start_matches('#').trim().to_lowercase();
+                section = match heading.as_str() {
+                    "arguments" | "parameters" => Section::Arguments(false),
+                    "returns" | "return value" => Section::Command("Returns", false),
+                    _ => Section::Other,
+                };
+                if section == Section::Other {
+                    sections.body.push(line);
+                }
+                continue;
+            }
+
+            match section {
+                Section::Arguments(_) | Section::Command(..) if trimmed.is_empty() => {}
+                Section::Arguments(_) => match parse_argument(trimmed) {
+                    Some((name, description)) => {
+                        sections.arguments.push((name, vec![description]));
+                    }
+                    None => {
+                        if let Some(&mut (_, ref mut description)) = sections.arguments.last_mut() {
+                            description.push(trimmed);
+                        }
+                    }
+                },
+                Section::Command(..) => sections.returns.push(trimmed),
+                Section::Other => sections.body.push(line),
+            }
+        }
+
+        while sections
+            .body
+            .first()
+            .map_or(false, |line| line.trim().is_empty())
+        {
+            sections.body.remove(0);
+        }
+        while sections
+            .body
+            .last()
+            .map_or(false, |line| line.trim().is_empty())
+        {
+            sections.body.pop();
+        }
+        sections
+    }
 }
 
 impl Source for Documentation {
@@ -52,10 +229,9 @@ impl Source for Documentation {
             return;
         }
 
-        let style = match config.documentation_style {
-            DocumentationStyle::Auto if config.language == Language::C => DocumentationStyle::Doxy,
-            DocumentationStyle::Auto if config.language == Language::Cxx => DocumentationStyle::Cxx,
-            DocumentationStyle::Auto => DocumentationStyle::C, // Fallback if `Language` gets extended.
+        // Only functions get gtk-doc blocks, see `write_gtk_doc`.
+        let style = match resolve_style(config) {
+            DocumentationStyle::GtkDoc => DocumentationStyle::Doxy,
             other => other,
         };
 
@@ -68,7 +244,7 @@ impl Source for Documentation {
                 out.new_line();
             }
 
-            DocumentationStyle::Doxy => {
+            DocumentationStyle::Doxy | DocumentationStyle::DoxyStructured => {
                 out.write("/**");
                 out.new_line();
             }
@@ -76,13 +252,21 @@ impl Source for Documentation {
             _ => (),
         }
 
-        for line in &self.doc_comment {
+        let structured;
+        let lines = if style == DocumentationStyle::DoxyStructured {
+            structured = doxygen_structured(&self.doc_comment);
+            &structured
+        } else {
+            &self.doc_comment
+        };
+
+        for line in lines {
             match style {
                 DocumentationStyle::C => out.write(""),
-                DocumentationStyle::Doxy => out.write(" *"),
+                DocumentationStyle::Doxy | DocumentationStyle::DoxyStructured => out.write(" *"),
                 DocumentationStyle::C99 => out.write("//"),
                 DocumentationStyle::Cxx => out.write("///"),
-                DocumentationStyle::Auto => unreachable!(), // Auto case should always be covered
+                DocumentationStyle::Auto | DocumentationStyle::GtkDoc => unreachable!(), // Auto case should always be covered
             }
 
             write!(out, "{}", line);
@@ -95,7 +279,7 @@ impl Source for Documentation {
                 out.new_line();
             }
 
-            DocumentationStyle::Doxy => {
+            DocumentationStyle::Doxy | DocumentationStyle::DoxyStructured => {
                 out.write(" */");
                 out.new_line();
             }
@@ -104,3 +288,88 @@ impl Source for Documentation {
         }
     }
 }
+
+/// The sections of Rust documentation with a Doxygen counterpart.
+/// The converted ones know whether their first line was seen, to drop the
+/// blank lines after the heading.
+#[derive(PartialEq)]
+enum Section {
+    Arguments(bool),
+    /// A section becoming the paragraph of a command.
+    Command(&'static str, bool),
+    Other,
+}
+
+/// Turns the `# Arguments`, `# Returns` and `# Safety` sections of the
+/// documentation into Doxygen commands, keeping the other lines.
+fn doxygen_structured(lines: &[String]) -> Vec<String> {
+    let mut out = Vec::with_capacity(lines.len());
+    let mut section = Section::Other;
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            let heading = trimmed.trim_start_matches('#').trim().to_lowercase();
+            section = match heading.as_str() {
+                "arguments" | "parameters" => Section::Arguments(false),
+                "returns" | "return value" => Section::Command("\\return", false),
+                "safety" => Section::Command("\\warning", false),
+                _ => Section::Other,
+            };
+            if section == Section::Other {
+                out.push(line.clone());
+            }
+            continue;
+        }
+
+        match section {
+            Section::Arguments(false) | Section::Command(_, false) if trimmed.is_empty() => {}
+            Section::Arguments(ref mut started) => {
+                *started = true;
+                match parse_argument(trimmed) {
+                    Some((name, description)) => {
+                        out.push(format!(" \\param {} {}", name, description));
+                    }
+                    None if trimmed.is_empty() => out.push(String::new()),
+                    // The description of the previous argument goes on.
+                    None => out.push(format!("        {}", trimmed)),
+                }
+            }
+            Section::Command(command, ref mut started) => {
+                if *started {
+                    out.push(line.clone());
+                } else {
+                    out.push(format!(" {} {}", command, trimmed));
+                    *started = true;
+                }
+            }
+            Section::Other => out.push(line.clone()),
+        }
+    }
+    out
+}
+
+/// Splits a ``* `name` - description`` list item into the name of the
+/// argument and its description.
+fn parse_argument(line: &str) -> Option<(&str, &str)> {
+    if !line.starts_with("* ") && !line.starts_with("- ") {
+        return None;
+    }
+    let line = line[2..].trim_start();
+    let (name, rest) = if line.starts_with('`') {
+        let end = line[1..].find('`')? + 1;
+        (&line[1..end], &line[end + 1..])
+    } else {
+        let end = line
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(line.len());
+        (&line[..end], &line[end..])
+    };
+    if name.is_empty() {
+        return None;
+    }
+    let description = rest
+        .trim_start()
+        .trim_start_matches(|c| c == '-' || c == ':' || c == '—')
+        .trim_start();
+    Some((name, description))
+}
@@ -4,6 +4,9 @@
 
 use std::io::Write;
 
+use heck::{ShoutySnakeCase, SnakeCase};
+
+use crate::bindgen::cdecl;
 use crate::bindgen::config::{Config, Language};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
@@ -19,7 +22,7 @@ use crate::bindgen::reserved;
 use crate::bindgen::writer::{ListType, Source, SourceWriter};
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum VariantBody {
     Empty(AnnotationSet),
     Body {
@@ -80,7 +83,7 @@ impl VariantBody {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumVariant {
     pub name: String,
     pub export_name: String,
@@ -150,7 +153,10 @@ impl EnumVariant {
         let body = match variant.fields {
             syn::Fields::Unit => VariantBody::Empty(annotations),
             syn::Fields::Named(ref fields) => {
-                let path = Path::new(format!("{}_Body", variant.ident));
+                let path = Path::new(format!(
+                    "{}{}",
+                    variant.ident, config.enumeration.variant_body_suffix
+                ));
                 let name = RenameRule::SnakeCase
                     .apply(&variant.ident.to_string(), IdentifierType::StructMember)
                     .into_owned();
@@ -173,7 +179,10 @@ impl EnumVariant {
                 }
             }
             syn::Fields::Unnamed(ref fields) => {
-                let path = Path::new(format!("{}_Body", variant.ident));
+                let path = Path::new(format!(
+                    "{}{}",
+                    variant.ident, config.enumeration.variant_body_suffix
+                ));
                 let name = RenameRule::SnakeCase
                     .apply(&variant.ident.to_string(), IdentifierType::StructMember)
                     .into_owned();
@@ -280,7 +289,11 @@ impl Source for EnumVariant {
             condition.write_before(config, out);
         }
         self.documentation.write(config, out);
-        write!(out, "{}", self.export_name);
+        if config.language == Language::Cython {
+            write!(out, "{}", reserved::cython_name(&self.export_name));
+        } else {
+            write!(out, "{}", self.export_name);
+        }
         if let Some(discriminant) = &self.discriminant {
             if config.language == Language::Cython {
                 // For extern Cython declarations the enumerator value is ignored,
@@ -297,7 +310,7 @@ impl Source for EnumVariant {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Enum {
     pub path: Path,
     pub export_name: String,
@@ -368,7 +381,8 @@ impl Enum {
         let mut variants = Vec::new();
         let mut has_data = false;
 
-        let annotations = AnnotationSet::load(&item.attrs)?;
+        let mut annotations = AnnotationSet::load(&item.attrs)?;
+        let is_bitflags = annotations.bool("bitflags").unwrap_or(false);
 
         for variant in item.variants.iter() {
             let variant = EnumVariant::load(
@@ -384,6 +398,29 @@ impl Enum {
             variants.push(variant);
         }
 
+        if is_bitflags {
+            if has_data {
+                return Err("Bitflags enum can't have fields.".to_owned());
+            }
+            if !variants.iter().any(|variant| variant.name == "NONE") {
+                variants.insert(
+                    0,
+                    EnumVariant::new(
+                        "NONE".to_owned(),
+                        Some(Literal::Expr("0".to_owned())),
+                        VariantBody::empty(),
+                        None,
+                        Documentation::none(),
+                    ),
+                );
+            }
+            // Flags have to be combined, which C++ only allows for unscoped
+            // enums, unless their operators are defined.
+            if !config.enumeration.flag_operators(&annotations) {
+                annotations.add_default("enum-class", AnnotationValue::Bool(false));
+            }
+        }
+
         if let Some(names) = annotations.list("enum-trailing-values") {
             for name in names {
                 variants.push(EnumVariant::new(
@@ -530,9 +567,18 @@ impl Item for Enum {
             }
         }
 
-        if config.enumeration.prefix_with_name
-            || self.annotations.bool("prefix-with-name").unwrap_or(false)
-        {
+        let prefix_with_name = config.enumeration.prefix_with_name
+            || self.annotations.bool("prefix-with-name").unwrap_or(false);
+        if !prefix_with_name && self.annotations.bool("bitflags").unwrap_or(false) {
+            // All the flag enums have a `NONE` flag, which would clash.
+            for variant in &mut self.variants {
+                if variant.export_name == "NONE" {
+                    variant.export_name = format!("{}_NONE", self.export_name);
+                }
+            }
+        }
+
+        if prefix_with_name {
             for variant in &mut self.variants {
                 variant.export_name = format!("{}_{}", self.export_name, variant.export_name);
                 if let VariantBody::Body { ref mut body, .. } = variant.body {
@@ -662,6 +708,9 @@ impl Source for Enum {
 
         // Emit the tag enum and everything related to it.
         self.write_tag_enum(config, out, size, has_data, tag_name);
+        if config.gobject.enabled && self.has_gtype() {
+            self.write_gtype_macro(out);
+        }
 
         // If the enum has data, we need to emit structs for the variants and gather them together.
         if has_data {
@@ -711,10 +760,21 @@ impl Source for Enum {
             // Close the struct or union opened either at (*) or at (**).
             if config.language == Language::C && config.style.generate_typedef() {
                 out.close_brace(false);
-                write!(out, " {};", self.export_name);
+                write!(out, " {}", self.export_name);
+                if let Some(attributes) = self.annotations.attributes(config) {
+                    write!(out, " {}", attributes);
+                }
+                out.write(";");
             } else {
                 out.close_brace(true);
             }
+
+            // C++ has the helper methods instead.
+            if config.language == Language::C
+                && config.enumeration.helper_functions(&self.annotations)
+            {
+                self.write_helper_functions(config, out, inline_tag_field);
+            }
         }
 
         condition.write_after(config, out);
@@ -722,6 +782,107 @@ impl Source for Enum {
 }
 
 impl Enum {
+    /// Whether the enum holds flags, from `bitflags!` or annotated with
+    /// `cbindgen:bitflags`.
+    pub fn is_bitflags(&self) -> bool {
+        self.annotations.bool("bitflags").unwrap_or(false)
+    }
+
+    /// Whether the enum is registered as a `G_TYPE_ENUM`, or `G_TYPE_FLAGS`
+    /// for bitflags, in gobject mode: the fieldless enums are.
+    pub fn has_gtype(&self) -> bool {
+        self.tag.is_none() && self.generic_params.is_empty()
+    }
+
+    /// The function registering the `GType` of the enum.
+    pub fn get_type_function(&self) -> String {
+        format!("{}_get_type", self.export_name.to_snake_case())
+    }
+
+    /// The name of a variant without the name of the enum, in snake case,
+    /// like `bar` for `FOO_BAR`.
+    pub fn member_name(&self, variant: &EnumVariant) -> String {
+        let name = variant.export_name.to_snake_case();
+        let enum_prefix = format!("{}_", self.export_name.to_snake_case());
+        if name.len() > enum_prefix.len() && name.starts_with(&enum_prefix) {
+            name[enum_prefix.len()..].to_owned()
+        } else {
+            name
+        }
+    }
+
+    /// Defines the function registering the `GType` of the enum, as
+    /// glib-mkenums does.
+    #[allow(unused)]
+    pub fn write_get_type<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
+        let (value_type, register) = if self.is_bitflags() {
+            ("GFlagsValue", "g_flags_register_static")
+        } else {
+            ("GEnumValue", "g_enum_register_static")
+        };
+
+        let condition = self.cfg.to_condition(config);
+        condition.write_before(config, out);
+
+        write!(out, "GType {}(void)", self.get_type_function());
+        out.open_brace();
+        out.write("static gsize gtype_id = 0;");
+        out.new_line();
+        write!(out, "static const {} values[] =", value_type);
+        out.open_brace();
+        for variant in &self.variants {
+            let condition = variant.cfg.to_condition(config);
+            condition.write_before(config, out);
+            write!(
+                out,
+                "{{ {}, \"{}\", \"{}\" }},",
+                variant.export_name,
+                variant.export_name,
+                self.member_name(variant).replace('_', "-")
+            );
+            out.new_line();
+            condition.write_after(config, out);
+        }
+        out.write("{ 0, NULL, NULL }");
+        out.close_brace(true);
+        out.new_line();
+        out.write("if (g_once_init_enter(&gtype_id))");
+        out.open_brace();
+        write!(
+            out,
+            "GType new_type = {}(g_intern_static_string(\"{}\"), values);",
+            register, self.export_name
+        );
+        out.new_line();
+        out.write("g_once_init_leave(&gtype_id, new_type);");
+        out.close_brace(false);
+        out.new_line();
+        out.write("return gtype_id;");
+        out.close_brace(false);
+
+        condition.write_after(config, out);
+    }
+
+    fn write_gtype_macro<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let name = self.path.name();
+        let prefix = if self.export_name.ends_with(name) {
+            &self.export_name[..self.export_name.len() - name.len()]
+        } else {
+            ""
+        };
+        let type_up = if prefix.is_empty() {
+            format!("TYPE_{}", name.to_shouty_snake_case())
+        } else {
+            format!(
+                "{}_TYPE_{}",
+                prefix.to_shouty_snake_case(),
+                name.to_shouty_snake_case()
+            )
+        };
+        out.new_line();
+        write!(out, "#define {} ({}())", type_up, self.get_type_function());
+    }
+
     /// Emit the tag enum and convenience methods for it.
     /// For enums with data this is only a part of the output,
     /// but for enums without data it's the whole output (modulo doc comments etc.).
@@ -733,6 +894,14 @@ impl Enum {
         has_data: bool,
         tag_name: &str,
     ) {
+        // Enums with data have their attributes on their struct or union
+        // instead.
+        let attributes = if has_data {
+            None
+        } else {
+            self.annotations.attributes(config)
+        };
+
         // Open the tag enum.
         match config.language {
             Language::C => {
@@ -755,6 +924,11 @@ impl Enum {
                         out.write("typedef ");
                     }
                     out.write("enum");
+                    if !config.style.generate_typedef() {
+                        if let Some(ref attributes) = attributes {
+                            write!(out, " {}", attributes);
+                        }
+                    }
                     if config.style.generate_tag() {
                         write!(out, " {}", tag_name);
                     }
@@ -773,6 +947,10 @@ impl Enum {
                     }
                 }
 
+                if let Some(ref attributes) = attributes {
+                    write!(out, " {}", attributes);
+                }
+
                 write!(out, " {}", tag_name);
                 if let Some(prim) = size {
                     write!(out, " : {}", prim);
@@ -787,6 +965,11 @@ impl Enum {
                     write!(out, "{}enum {}", config.style.cython_def(), tag_name);
                 }
             }
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
         out.open_brace();
 
@@ -801,7 +984,11 @@ impl Enum {
         // Close the tag enum.
         if config.language == Language::C && size.is_none() && config.style.generate_typedef() {
             out.close_brace(false);
-            write!(out, " {};", tag_name);
+            write!(out, " {}", tag_name);
+            if let Some(ref attributes) = attributes {
+                write!(out, " {}", attributes);
+            }
+            out.write(";");
         } else {
             out.close_brace(true);
         }
@@ -817,7 +1004,11 @@ impl Enum {
 
             if config.language != Language::Cxx {
                 out.new_line();
-                write!(out, "{} {} {};", config.language.typedef(), prim, tag_name);
+                write!(out, "{} {} {}", config.language.typedef(), prim, tag_name);
+                if let Some(ref attributes) = attributes {
+                    write!(out, " {}", attributes);
+                }
+                out.write(";");
             }
 
             if config.cpp_compatible_c() {
@@ -828,6 +1019,53 @@ impl Enum {
 
         // Emit convenience methods for the tag enum.
         self.write_derived_functions_enum(config, out, has_data, tag_name);
+
+        if config.language == Language::Cxx
+            && self.is_bitflags()
+            && config.enumeration.flag_operators(&self.annotations)
+        {
+            self.write_flag_operators(out, size.unwrap_or("int"), tag_name);
+        }
+    }
+
+    /// Defines the bitwise operators combining the flags.
+    fn write_flag_operators<F: Write>(&self, out: &mut SourceWriter<F>, prim: &str, name: &str) {
+        for op in &["|", "&", "^"] {
+            out.new_line();
+            out.new_line();
+            write!(
+                out,
+                "inline constexpr {} operator{}({} a, {} b)",
+                name, op, name, name
+            );
+            out.open_brace();
+            write!(
+                out,
+                "return static_cast<{}>(static_cast<{}>(a) {} static_cast<{}>(b));",
+                name, prim, op, prim
+            );
+            out.close_brace(false);
+            out.new_line();
+            out.new_line();
+            write!(
+                out,
+                "inline {}& operator{}=({}& a, {} b)",
+                name, op, name, name
+            );
+            out.open_brace();
+            write!(out, "return a = a {} b;", op);
+            out.close_brace(false);
+        }
+        out.new_line();
+        out.new_line();
+        write!(out, "inline constexpr {} operator~({} a)", name, name);
+        out.open_brace();
+        write!(
+            out,
+            "return static_cast<{}>(~static_cast<{}>(a));",
+            name, prim
+        );
+        out.close_brace(false);
     }
 
     /// The code here mirrors the beginning of `Struct::write` and `Union::write`.
@@ -841,6 +1079,11 @@ impl Enum {
             Language::C if config.style.generate_typedef() => out.write("typedef "),
             Language::C | Language::Cxx => {}
             Language::Cython => out.write(config.style.cython_def()),
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
 
         out.write(if inline_tag_field { "union" } else { "struct" });
@@ -851,6 +1094,12 @@ impl Enum {
             }
         }
 
+        if config.language == Language::Cxx || !config.style.generate_typedef() {
+            if let Some(attributes) = self.annotations.attributes(config) {
+                write!(out, " {}", attributes);
+            }
+        }
+
         if config.language != Language::C || config.style.generate_tag() {
             write!(out, " {}", self.export_name());
         }
@@ -1108,7 +1357,158 @@ impl Enum {
         }
     }
 
-    // Emit convenience methods for structs or unions produced for enums with data.
+    /// Emit inline functions building the enum from the fields of a variant,
+    /// checking its variant, and accessing its fields.
+    fn write_helper_functions<F: Write>(
+        &self,
+        config: &Config,
+        out: &mut SourceWriter<F>,
+        inline_tag_field: bool,
+    ) {
+        let c_type = |keyword: &str, name: &str| {
+            if config.style.generate_typedef() {
+                name.to_owned()
+            } else {
+                format!("{} {}", keyword, name)
+            }
+        };
+        let self_type = c_type(
+            if inline_tag_field { "union" } else { "struct" },
+            &self.export_name,
+        );
+        let arg_renamer = |name: &str| {
+            config
+                .function
+                .rename_args
+                .apply(name, IdentifierType::FunctionArg)
+                .into_owned()
+        };
+
+        for variant in &self.variants {
+            let condition = variant.cfg.to_condition(config);
+            let fields: &[Field] = match variant.body {
+                VariantBody::Body { ref body, .. } => {
+                    &body.fields[if body.has_tag_field { 1 } else { 0 }..]
+                }
+                VariantBody::Empty(..) => &[],
+            };
+
+            out.new_line();
+            out.new_line();
+            condition.write_before(config, out);
+
+            write!(
+                out,
+                "static inline {} {}_{}_new(",
+                self_type, self.export_name, variant.name
+            );
+            if fields.is_empty() {
+                out.write("void");
+            } else {
+                let args: Vec<_> = fields
+                    .iter()
+                    .map(|field| {
+                        Field::from_name_and_type(arg_renamer(&field.name), field.ty.clone())
+                    })
+                    .collect();
+                out.write_vertical_source_list(&args[..], ListType::Join(","));
+            }
+            out.write(")");
+            out.open_brace();
+            write!(out, "{} result;", self_type);
+            out.new_line();
+            write!(out, "result.tag = {};", variant.export_name);
+            if let VariantBody::Body {
+                ref name, inline, ..
+            } = variant.body
+            {
+                for field in fields {
+                    let member = if inline {
+                        field.name.clone()
+                    } else {
+                        format!("{}.{}", name, field.name)
+                    };
+                    out.new_line();
+                    match field.ty {
+                        Type::Array(_, ref length) => {
+                            // Arrays are not assignable.
+                            write!(out, "for (int i = 0; i < {}; i++)", length);
+                            out.open_brace();
+                            write!(
+                                out,
+                                "result.{}[i] = {}[i];",
+                                member,
+                                arg_renamer(&field.name)
+                            );
+                            out.close_brace(false);
+                        }
+                        _ => write!(out, "result.{} = {};", member, arg_renamer(&field.name)),
+                    }
+                }
+            }
+            out.new_line();
+            out.write("return result;");
+            out.close_brace(false);
+
+            out.new_line();
+            out.new_line();
+            write!(
+                out,
+                "static inline bool {}_is_{}(const {} *self)",
+                self.export_name, variant.name, self_type
+            );
+            out.open_brace();
+            write!(out, "return self->tag == {};", variant.export_name);
+            out.close_brace(false);
+
+            // Accessors return NULL when the enum holds another variant.
+            if let VariantBody::Body {
+                ref name,
+                ref body,
+                inline,
+                ..
+            } = variant.body
+            {
+                out.new_line();
+                out.new_line();
+                out.write("static inline ");
+                let accessor = format!(
+                    "{}_get_{}(const {} *self)",
+                    self.export_name, variant.name, self_type
+                );
+                let member = if inline {
+                    // The declarator spells pointers to arrays and functions.
+                    let field = &fields[0];
+                    let ty = Type::Ptr {
+                        ty: Box::new(field.ty.clone()),
+                        is_const: true,
+                        is_ref: false,
+                        is_nullable: true,
+                    };
+                    cdecl::write_field(out, &ty, &accessor, config);
+                    &field.name
+                } else {
+                    write!(
+                        out,
+                        "const {} *{}",
+                        c_type("struct", body.export_name()),
+                        accessor
+                    );
+                    name
+                };
+                out.open_brace();
+                write!(
+                    out,
+                    "return self->tag == {} ? &self->{} : NULL;",
+                    variant.export_name, member
+                );
+                out.close_brace(false);
+            }
+
+            condition.write_after(config, out);
+        }
+    }
+
     fn write_derived_functions_data<F: Write>(
         &self,
         config: &Config,
@@ -1186,7 +1586,7 @@ impl Enum {
                             Type::Array(ref ty, ref length) => {
                                 // arrays are not assignable in C++ so we
                                 // need to manually copy the elements
-                                write!(out, "for (int i = 0; i < {}; i++)", length.as_str());
+                                write!(out, "for (int i = 0; i < {}; i++)", length);
                                 out.open_brace();
                                 write!(out, "::new (&result.{}.{}[i]) (", variant_name, field.name);
                                 ty.write(config, out);
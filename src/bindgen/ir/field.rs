@@ -2,11 +2,12 @@ use std::io::Write;
 
 use crate::bindgen::cdecl;
 use crate::bindgen::config::{Config, Language};
-use crate::bindgen::ir::{AnnotationSet, Cfg, ConditionWrite};
-use crate::bindgen::ir::{Documentation, Path, ToCondition, Type};
-use crate::bindgen::writer::{Source, SourceWriter};
+use crate::bindgen::ir::{AnnotationSet, ArrayLength, Cfg, ConditionWrite};
+use crate::bindgen::ir::{Documentation, ItemContainer, Path, ToCondition, Type};
+use crate::bindgen::reserved;
+use crate::bindgen::writer::{ListType, Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Field {
     pub name: String,
     pub ty: Type,
@@ -26,6 +27,30 @@ impl Field {
         }
     }
 
+    /// The number of an anonymous member declared by bindgen, whose field is
+    /// named `__bindgen_anon_N`.
+    pub fn anonymous_index(&self) -> Option<&str> {
+        if self.name.starts_with("__bindgen_anon_") {
+            Some(&self.name["__bindgen_anon_".len()..])
+        } else {
+            None
+        }
+    }
+
+    /// Whether the field is written as a flexible array member, which
+    /// `Struct::load` only allows for a trailing `[T; 0]` or `[T; 1]` array.
+    pub fn is_flexible_array(&self) -> bool {
+        self.annotations.bool("flexible-array").unwrap_or(false)
+    }
+
+    /// The width of the field when it's declared as a bitfield with a
+    /// `bitfield=N` annotation.
+    pub fn bitfield(&self) -> Option<String> {
+        self.annotations
+            .atom("bitfield")
+            .map(Option::unwrap_or_default)
+    }
+
     pub fn load(field: &syn::Field, self_path: &Path) -> Result<Option<Field>, String> {
         Ok(if let Some(mut ty) = Type::load(&field.ty)? {
             ty.replace_self_with(self_path);
@@ -46,6 +71,15 @@ impl Field {
     }
 }
 
+/// Writes an inlined anonymous member, followed by the semicolon of its
+/// struct's list of fields.
+fn write_anonymous<F: Write>(out: &mut SourceWriter<F>, keyword: &'static str, fields: &[Field]) {
+    out.write(keyword);
+    out.open_brace();
+    out.write_vertical_source_list(fields, ListType::Cap(";"));
+    out.close_brace(false);
+}
+
 impl Source for Field {
     fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
         // Cython doesn't support conditional fields.
@@ -55,12 +89,33 @@ impl Source for Field {
         }
 
         self.documentation.write(config, out);
-        cdecl::write_field(out, &self.ty, &self.name, config);
+        let bindings = out.bindings();
+        let inlined = match self.ty {
+            Type::Path(ref generic) => bindings.inline_member(generic.path()),
+            _ => None,
+        };
+        match inlined {
+            Some(ItemContainer::Struct(ref x)) => write_anonymous(out, "struct", &x.fields),
+            Some(ItemContainer::Union(ref x)) => write_anonymous(out, "union", &x.fields),
+            _ => match self.ty {
+                // Cython doesn't declare layouts.
+                Type::Array(ref element, _)
+                    if self.is_flexible_array() && config.language != Language::Cython =>
+                {
+                    let ty = Type::Array(element.clone(), ArrayLength::Value(String::new()));
+                    cdecl::write_field(out, &ty, &self.name, config)
+                }
+                _ if config.language == Language::Cython => {
+                    cdecl::write_field(out, &self.ty, &reserved::cython_name(&self.name), config)
+                }
+                _ => cdecl::write_field(out, &self.ty, &self.name, config),
+            },
+        }
         // Cython extern declarations don't manage layouts, layouts are defined entierly by the
         // corresponding C code. So we can omit bitfield sizes which are not supported by Cython.
         if config.language != Language::Cython {
-            if let Some(bitfield) = self.annotations.atom("bitfield") {
-                write!(out, ": {}", bitfield.unwrap_or_default());
+            if let Some(bitfield) = self.bitfield() {
+                write!(out, ": {}", bitfield);
             }
         }
 
@@ -4,42 +4,64 @@
 
 use std::collections::HashMap;
 use std::io::Write;
+use std::str::FromStr;
+
+use heck::CamelCase;
 
 use crate::bindgen::cdecl;
-use crate::bindgen::config::{Config, Language, Layout};
+use crate::bindgen::config::{Config, Language, Layout, ResultConvention, TransferStyle};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
 use crate::bindgen::ir::{
-    AnnotationSet, Cfg, ConditionWrite, Documentation, GenericPath, Path, PrimitiveType,
-    ToCondition, Type,
+    AnnotationSet, AnnotationValue, Cfg, ConditionWrite, Documentation, GenericPath, Path,
+    PrimitiveType, ToCondition, Type,
 };
 use crate::bindgen::library::Library;
 use crate::bindgen::monomorph::Monomorphs;
 use crate::bindgen::rename::{IdentifierType, RenameRule};
 use crate::bindgen::reserved;
-use crate::bindgen::utilities::IterHelpers;
+use crate::bindgen::utilities::{IterHelpers, SynAbiHelpers, SynAttributeHelpers};
 use crate::bindgen::writer::{Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+/// The introspection annotations of the named arguments of a function.
+pub type ArgumentAnnotations<'a> = Vec<(&'a str, Vec<String>)>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionArgument {
     pub name: Option<String>,
     pub ty: Type,
     pub array_length: Option<String>,
+    /// The transfer of ownership inferred from the Rust type.
+    pub transfer: Option<Transfer>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Function {
     pub path: Path,
     /// Path to the self-type of the function
     /// If the function is a method, this will contain the path of the type in the impl block
     pub self_type_path: Option<Path>,
     pub ret: Type,
+    /// The transfer of ownership of the return value inferred from its Rust
+    /// type.
+    pub ret_transfer: Option<Transfer>,
     pub args: Vec<FunctionArgument>,
     pub extern_decl: bool,
     pub cfg: Option<Cfg>,
     pub annotations: AnnotationSet,
     pub documentation: Documentation,
     pub never_return: bool,
+    /// Whether the function is marked `#[cold]`.
+    pub cold: bool,
+    /// Whether the function takes variable arguments after `args`.
+    pub variadic: bool,
+    /// The calling convention of the ABI of the function.
+    pub calling_convention: CallingConvention,
+    /// The `#[link(wasm_import_module)]` of the `extern` block declaring the
+    /// function.
+    pub import_module: Option<String>,
+    /// The `#[link_name]` of the function declared in an `extern` block.
+    pub link_name: Option<String>,
 }
 
 impl Function {
@@ -51,12 +73,19 @@ impl Function {
         attrs: &[syn::Attribute],
         mod_cfg: Option<&Cfg>,
     ) -> Result<Function, String> {
-        let mut args = sig.inputs.iter().try_skip_map(|x| x.as_argument())?;
+        let mut annotations = AnnotationSet::load(attrs)?;
+        let mut args = if annotations.bool("lower-slices").unwrap_or(false) {
+            lower_slices(sig, &mut annotations)?
+        } else {
+            sig.inputs.iter().try_skip_map(|x| x.as_argument())?
+        };
 
         let mut never_return = false;
+        let mut ret_transfer = None;
         let mut ret = match sig.output {
             syn::ReturnType::Default => Type::Primitive(PrimitiveType::Void),
             syn::ReturnType::Type(_, ref ty) => {
+                ret_transfer = infer_transfer(ty);
                 if let syn::Type::Never(_) = ty.as_ref() {
                     never_return = true;
                     Type::Primitive(PrimitiveType::Void)
@@ -73,19 +102,394 @@ impl Function {
             ret.replace_self_with(self_path);
         }
 
+        let variadic = sig.variadic.is_some() || annotations.bool("variadic").unwrap_or(false);
+
         Ok(Function {
             path,
             self_type_path: self_type_path.cloned(),
             ret,
+            ret_transfer,
             args,
             extern_decl,
             cfg: Cfg::append(mod_cfg, Cfg::load(attrs)),
-            annotations: AnnotationSet::load(attrs)?,
+            annotations,
             documentation: Documentation::load(attrs),
             never_return,
+            cold: attrs.has_attr_word("cold"),
+            variadic,
+            calling_convention: sig.abi.calling_convention().unwrap_or(CallingConvention::C),
+            import_module: None,
+            link_name: attrs.attr_name_value_lookup("link_name"),
         })
     }
 
+    /// Loads an `async fn` annotated `cbindgen:gasync`, or returns `None` if
+    /// it isn't. Its errors are reported in a `GError`, so the output of a
+    /// function returning a `Result` is its value.
+    pub fn load_gasync(
+        path: Path,
+        self_type_path: Option<&Path>,
+        sig: &syn::Signature,
+        attrs: &[syn::Attribute],
+        mod_cfg: Option<&Cfg>,
+    ) -> Result<Option<Function>, String> {
+        let mut function = Function::load(path, self_type_path, sig, false, attrs, mod_cfg)?;
+        if !function.is_gasync() {
+            return Ok(None);
+        }
+        if let syn::ReturnType::Type(_, ref ty) = sig.output {
+            if let Some(value) = result_value(ty) {
+                function.ret_transfer = infer_transfer(value);
+                function.ret = Type::load(value)?.unwrap_or(Type::Primitive(PrimitiveType::Void));
+                if let Some(self_path) = self_type_path {
+                    function.ret.replace_self_with(self_path);
+                }
+            }
+        }
+        Ok(Some(function))
+    }
+
+    /// Lowers the `Result<T, E>` returned by the function as
+    /// `export.result_convention` says, returning the `NameResult` enum to
+    /// declare with the `struct` convention.
+    pub(crate) fn lower_result(
+        &mut self,
+        sig: &syn::Signature,
+        convention: ResultConvention,
+    ) -> Result<Option<syn::ItemEnum>, String> {
+        let (ok, err) = match sig.output {
+            syn::ReturnType::Type(_, ref ty) => match result_types(ty) {
+                Some(types) => types,
+                None => return Ok(None),
+            },
+            syn::ReturnType::Default => return Ok(None),
+        };
+        match convention {
+            ResultConvention::None => Ok(None),
+            ResultConvention::OutParam => {
+                self.ret = Type::load(err)?.unwrap_or(Type::Primitive(PrimitiveType::Void));
+                self.ret_transfer = None;
+                let ok_ty = Type::load(ok)?;
+                // The glue returns the zero value of `E` on success.
+                self.documentation.doc_comment.push(
+                    if ok_ty.is_some() {
+                        " Returns 0 on success, having written the value to `*out`, or the error."
+                    } else {
+                        " Returns 0 on success, or the error."
+                    }
+                    .to_owned(),
+                );
+                if let Some(ty) = ok_ty {
+                    self.args.push(FunctionArgument {
+                        name: Some("out".to_owned()),
+                        ty: Type::Ptr {
+                            ty: Box::new(ty),
+                            is_const: false,
+                            is_nullable: false,
+                            is_ref: false,
+                        },
+                        array_length: None,
+                        transfer: infer_transfer(ok),
+                    });
+                }
+                if let Some(ref self_path) = self.self_type_path {
+                    self.ret.replace_self_with(self_path);
+                    for arg in &mut self.args {
+                        arg.ty.replace_self_with(self_path);
+                    }
+                }
+                Ok(None)
+            }
+            ResultConvention::Struct => {
+                let name = format!("{}Result", self.path.name().to_camel_case());
+                let ident = syn::Ident::new(&name, proc_macro2::Span::call_site());
+                let doc = format!(" The result of `{}`.", self.path.name());
+                let is_unit = match *ok {
+                    syn::Type::Tuple(ref tuple) => tuple.elems.is_empty(),
+                    _ => false,
+                };
+                let item = if is_unit {
+                    parse_quote! {
+                        #[doc = #doc]
+                        #[doc = "cbindgen:prefix-with-name"]
+                        #[repr(C)]
+                        pub enum #ident { Ok, Err(#err) }
+                    }
+                } else {
+                    parse_quote! {
+                        #[doc = #doc]
+                        #[doc = "cbindgen:prefix-with-name"]
+                        #[repr(C)]
+                        pub enum #ident { Ok(#ok), Err(#err) }
+                    }
+                };
+                self.ret = Type::Path(GenericPath::new(Path::new(name), Vec::new()));
+                self.ret_transfer = None;
+                Ok(Some(item))
+            }
+        }
+    }
+
+    /// The attribute importing the function declared in an `extern` block
+    /// from the WebAssembly host.
+    fn wasm_import(&self, config: &Config) -> Option<String> {
+        if !self.extern_decl || !config.wasm_attributes() {
+            return None;
+        }
+        Some(format!(
+            "__attribute__((import_module(\"{}\"), import_name(\"{}\")))",
+            self.import_module
+                .as_ref()
+                .unwrap_or(&config.wasm.import_module),
+            self.link_name
+                .as_ref()
+                .map_or(self.path.name(), |name| name.as_str())
+        ))
+    }
+
+    /// The calling convention of the function, which can be set with the
+    /// `calling-convention` annotation or for all the functions in the
+    /// config.
+    pub(crate) fn calling_convention(&self, config: &Config) -> CallingConvention {
+        if let Some(Some(name)) = self.annotations.atom("calling-convention") {
+            match name.parse() {
+                Ok(calling_convention) => return calling_convention,
+                Err(msg) => warn_in!(
+                    self.annotations.location,
+                    "{} in {}.",
+                    msg,
+                    self.path.name()
+                ),
+            }
+        }
+        config
+            .function
+            .calling_convention
+            .unwrap_or(self.calling_convention)
+    }
+
+    /// The 1-based indices of the printf-style format string and of the
+    /// first argument it formats, from the `format-arg` annotation, or for
+    /// variadic functions and those taking a trailing `va_list`, from an
+    /// argument named `fmt`.
+    fn format_args(&self) -> Option<(usize, usize)> {
+        if let Some(Some(value)) = self.annotations.atom("format-arg") {
+            let indices: Vec<_> = value.split(',').map(|x| x.trim().parse()).collect();
+            match indices[..] {
+                [Ok(format), Ok(first)] if format > 0 && format <= self.args.len() => {
+                    return Some((format, first));
+                }
+                _ => warn_in!(
+                    self.annotations.location,
+                    "Invalid format-arg \"{}\" in {}.",
+                    value,
+                    self.path.name()
+                ),
+            }
+        }
+        let first = if self.variadic {
+            self.args.len() + 1
+        } else {
+            match self.args.last() {
+                Some(arg) if arg.ty == Type::Primitive(PrimitiveType::VaList) => 0,
+                _ => return None,
+            }
+        };
+        let format = self
+            .args
+            .iter()
+            .position(|arg| arg.name.as_ref().map_or(false, |x| x == "fmt"))?;
+        Some((format + 1, first))
+    }
+
+    /// The variant of a variadic function taking its variable arguments as a
+    /// `va_list`, named with the `_valist` suffix.
+    /// Whether the function reports its errors in a `GError **` last
+    /// argument, annotated `throws` or typed `*mut *mut GError`.
+    pub fn throws(&self) -> bool {
+        if let Some(throws) = self.annotations.bool("throws") {
+            return throws && !self.args.is_empty();
+        }
+        self.args
+            .last()
+            .map_or(false, |arg| is_gerror_location(&arg.ty))
+    }
+
+    /// Warns if the function throws but returns neither a boolean nor a
+    /// pointer, as GLib expects to tell the failures.
+    pub(crate) fn check_throws(&self) {
+        if !self.throws() {
+            return;
+        }
+        let tells_failures = match self.ret {
+            Type::Ptr { .. } | Type::FuncPtr { .. } | Type::Primitive(PrimitiveType::Bool) => true,
+            Type::Path(ref path) => path.path().name() == "gboolean",
+            _ => false,
+        };
+        if !tells_failures {
+            warn_in!(
+                self.annotations.location,
+                "{} throws a GError but returns neither a boolean nor a pointer.",
+                self.path.name()
+            );
+        }
+    }
+
+    /// The type of a pointer to the function.
+    pub(crate) fn pointer_type(&self) -> Type {
+        Type::FuncPtr {
+            ret: Box::new(self.ret.clone()),
+            args: self
+                .args
+                .iter()
+                .map(|arg| (arg.name.clone(), arg.ty.clone()))
+                .collect(),
+            is_nullable: false,
+            calling_convention: self.calling_convention,
+        }
+    }
+
+    pub(crate) fn valist_variant(&self) -> Function {
+        let mut function = self.clone();
+        function.path = Path::new(format!("{}_valist", self.path.name()));
+        function.variadic = false;
+        function.args.push(FunctionArgument {
+            name: Some("args".to_owned()),
+            ty: Type::Primitive(PrimitiveType::VaList),
+            array_length: None,
+            transfer: None,
+        });
+        // The formatted arguments are in the `va_list`.
+        if let Some((format, _)) = self.format_args() {
+            function.annotations.set(
+                "format-arg",
+                AnnotationValue::Atom(Some(format!("{},0", format))),
+            );
+        }
+        function.documentation = Documentation::simple(&format!(
+            " Like `{}`, with the variable arguments in a `va_list`.",
+            self.path.name()
+        ));
+        function
+    }
+
+    /// Whether the function is an `async fn` annotated `cbindgen:gasync`,
+    /// which its runtime glue exports as a pair of GIO-style functions.
+    pub fn is_gasync(&self) -> bool {
+        self.annotations.bool("gasync").unwrap_or(false)
+            || self.annotations.atom("gasync").is_some()
+    }
+
+    /// The `name_async` and `name_finish` functions exported by the runtime
+    /// glue of an `async fn` annotated `cbindgen:gasync`, or
+    /// `cbindgen:gasync=name` to give their prefix.
+    ///
+    /// The first one starts the operation, taking a `GCancellable`, a
+    /// `GAsyncReadyCallback` and its data after the arguments. The second one
+    /// takes the `GAsyncResult` given to the callback, after the source
+    /// object when the first argument is a pointer to one, and returns the
+    /// output of the `async fn`, or a boolean if it's empty, reporting its
+    /// errors in a `GError`.
+    pub(crate) fn gasync_pair(&self) -> (Function, Function) {
+        let name = match self.annotations.atom("gasync") {
+            Some(Some(name)) => name,
+            _ => self.path.name().to_owned(),
+        };
+        let mut annotations = self.annotations.clone();
+        annotations.remove("gasync");
+        let gio_arg = |name: &str, ty: &str, is_nullable: bool| FunctionArgument {
+            name: Some(name.to_owned()),
+            ty: Type::Ptr {
+                ty: Box::new(Type::Path(GenericPath::new(Path::new(ty), vec![]))),
+                is_const: false,
+                is_nullable,
+                is_ref: false,
+            },
+            array_length: None,
+            transfer: None,
+        };
+
+        let mut start = self.clone();
+        start.path = Path::new(format!("{}_async", name));
+        start.ret = Type::Primitive(PrimitiveType::Void);
+        start.ret_transfer = None;
+        start
+            .args
+            .push(gio_arg("cancellable", "GCancellable", true));
+        start.args.push(FunctionArgument {
+            name: Some("callback".to_owned()),
+            ty: Type::Path(GenericPath::new(Path::new("GAsyncReadyCallback"), vec![])),
+            array_length: None,
+            transfer: None,
+        });
+        start.args.push(FunctionArgument {
+            name: Some("user_data".to_owned()),
+            ty: Type::Ptr {
+                ty: Box::new(Type::Primitive(PrimitiveType::Void)),
+                is_const: false,
+                is_nullable: true,
+                is_ref: false,
+            },
+            array_length: None,
+            transfer: None,
+        });
+        start.annotations = annotations.clone();
+        start.annotations.set(
+            "callbacks",
+            AnnotationValue::List(vec!["[callback; user_data]".to_owned()]),
+        );
+
+        let mut finish = self.clone();
+        finish.path = Path::new(format!("{}_finish", name));
+        finish.args = match self.args.first() {
+            Some(arg) if is_source_object(&arg.ty) => vec![arg.clone()],
+            _ => Vec::new(),
+        };
+        finish.args.push(gio_arg("result", "GAsyncResult", false));
+        finish.args.push(FunctionArgument {
+            name: Some("error".to_owned()),
+            ty: gerror_location(),
+            array_length: None,
+            transfer: None,
+        });
+        if finish.ret == Type::Primitive(PrimitiveType::Void) {
+            finish.ret = Type::Primitive(PrimitiveType::Bool);
+        }
+        finish.variadic = false;
+        finish.annotations = annotations;
+        finish.documentation = Documentation::simple(&format!(
+            " Finishes the operation started with `{}()`.",
+            start.path.name()
+        ));
+
+        (start, finish)
+    }
+
+    /// The body of the function, to define it as an inline function, from
+    /// the `inline-body` annotation or `fn.inline_bodies`.
+    fn inline_body(&self, config: &Config) -> Option<String> {
+        if self.extern_decl || (config.language != Language::C && config.language != Language::Cxx)
+        {
+            return None;
+        }
+        if let Some(Some(body)) = self.annotations.atom("inline-body") {
+            return Some(body);
+        }
+        config.function.inline_bodies.get(self.path.name()).cloned()
+    }
+
+    /// Writes the body of an inline function.
+    fn write_inline_body<F: Write>(out: &mut SourceWriter<F>, body: &str) {
+        out.open_brace();
+        for (i, line) in body.trim().lines().enumerate() {
+            if i != 0 {
+                out.new_line();
+            }
+            write!(out, "{}", line.trim_end());
+        }
+        out.close_brace(false);
+    }
+
     pub(crate) fn never_return(&self, config: &Config) -> bool {
         self.never_return && config.language != Language::Cython
     }
@@ -127,6 +531,246 @@ impl Function {
         &self.path
     }
 
+    /// The `[a; b; ...]` items of a list annotation, split.
+    fn annotation_lists(&self, annotation: &str) -> Option<Vec<Vec<String>>> {
+        let lists = self.annotations.list(annotation)?;
+        Some(
+            lists
+                .iter()
+                .map(|list| {
+                    list[1..list.len() - 1]
+                        .split(';')
+                        .map(|x| x.trim().to_owned())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// The `[name; value]` items of a list annotation.
+    fn annotation_tuples(&self, annotation: &str) -> Option<HashMap<String, String>> {
+        let mut map = HashMap::new();
+        for mut parts in self.annotation_lists(annotation)? {
+            if parts.len() != 2 {
+                warn_in!(
+                    self.annotations.location,
+                    "{:?} does not follow the correct syntax, so the annotation is being ignored",
+                    parts
+                );
+                continue;
+            }
+            let value = parts.pop().unwrap();
+            map.insert(parts.pop().unwrap(), value);
+        }
+        Some(map)
+    }
+
+    /// The gtk-doc annotations of the callback arguments listed by the
+    /// `callbacks=[[callback; user_data], [callback; user_data; destroy]]`
+    /// annotation: their scope, given by the `scope` annotation or else
+    /// `notified` with a destroy function and `async` without, their
+    /// closure data, and their destroy function.
+    fn callback_annotations(&self) -> HashMap<String, Vec<String>> {
+        let mut callbacks = HashMap::new();
+        let lists = match self.annotation_lists("callbacks") {
+            Some(lists) => lists,
+            None => return callbacks,
+        };
+        let scopes = self.annotation_tuples("scope").unwrap_or_default();
+        let is_arg = |name: &str| {
+            self.args
+                .iter()
+                .any(|arg| arg.name.as_deref() == Some(name))
+        };
+        for parts in lists {
+            if parts.len() != 2 && parts.len() != 3 {
+                warn_in!(
+                    self.annotations.location,
+                    "{:?} does not follow the correct syntax, so the annotation is being ignored",
+                    parts
+                );
+                continue;
+            }
+            if let Some(unknown) = parts.iter().find(|name| !is_arg(name)) {
+                warn_in!(
+                    self.annotations.location,
+                    "{} has no {:?} argument, so its callback annotation is being ignored.",
+                    self.path.name(),
+                    unknown
+                );
+                continue;
+            }
+            let destroy = parts.get(2);
+            let scope = match scopes.get(&parts[0]) {
+                Some(scope) => match scope.as_str() {
+                    "call" | "async" | "notified" | "forever" => scope.as_str(),
+                    _ => {
+                        warn_in!(
+                            self.annotations.location,
+                            "Unknown scope {:?} for {:?} in {}.",
+                            scope,
+                            parts[0],
+                            self.path.name()
+                        );
+                        continue;
+                    }
+                },
+                None if destroy.is_some() => "notified",
+                None => "async",
+            };
+            let mut annotations = vec![
+                format!("(scope {})", scope),
+                format!("(closure {})", parts[1]),
+            ];
+            if let Some(destroy) = destroy {
+                annotations.push(format!("(destroy {})", destroy));
+            }
+            callbacks.insert(parts[0].clone(), annotations);
+        }
+        callbacks
+    }
+
+    /// The transfer of ownership of the argument or return value `name`,
+    /// given by the `transfer` annotation, or else inferred from its Rust
+    /// type.
+    fn annotated_transfer(&self, name: &str, inferred: Option<Transfer>) -> Option<Transfer> {
+        let annotated = if name == "return" {
+            self.annotations.atom("transfer").and_then(|mode| mode)
+        } else {
+            None
+        };
+        let annotated = annotated.or_else(|| {
+            self.annotation_tuples("transfer")
+                .and_then(|mut tuples| tuples.remove(name))
+        });
+        match annotated {
+            Some(mode) => match mode.parse() {
+                Ok(transfer) => Some(transfer),
+                Err(err) => {
+                    warn_in!(
+                        self.annotations.location,
+                        "{} for {:?} in {}.",
+                        err,
+                        name,
+                        self.path.name()
+                    );
+                    inferred
+                }
+            },
+            None => inferred,
+        }
+    }
+
+    /// The transfer of ownership of an argument.
+    pub fn arg_transfer(&self, arg: &FunctionArgument) -> Option<Transfer> {
+        match arg.name {
+            Some(ref name) => self.annotated_transfer(name, arg.transfer),
+            None => arg.transfer,
+        }
+    }
+
+    /// The transfer of ownership of the return value.
+    pub fn ret_transfer(&self) -> Option<Transfer> {
+        self.annotated_transfer("return", self.ret_transfer)
+    }
+
+    /// The `/* transfer ... */` comment or `noescape` attribute to write
+    /// before an argument, or before the return type for `None`.
+    pub(crate) fn transfer_marker(
+        &self,
+        config: &Config,
+        arg: Option<&FunctionArgument>,
+    ) -> Option<String> {
+        let transfer = match arg {
+            Some(arg) => self.arg_transfer(arg),
+            None => self.ret_transfer(),
+        }?;
+        match transfer_style(config) {
+            TransferStyle::Comment => Some(format!("/* transfer {} */", transfer.as_str())),
+            TransferStyle::Attribute => match arg {
+                Some(&FunctionArgument {
+                    ty: Type::Ptr { .. },
+                    ..
+                }) if transfer == Transfer::None => Some("__attribute__((noescape))".to_owned()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The gtk-doc introspection annotations of the named arguments, and of
+    /// the return value unless it's `void`.
+    ///
+    /// Nullable pointers are `(nullable)`, `ptrs-as-arrays` arguments are
+    /// arrays, `callbacks` get their scope, closure and destroy function,
+    /// and the transfer of ownership is given unless it's written
+    /// some other way.
+    pub fn introspection_annotations(
+        &self,
+        config: &Config,
+    ) -> (ArgumentAnnotations<'_>, Option<Vec<String>>) {
+        let gtk_doc_transfer = transfer_style(config) == TransferStyle::GtkDoc;
+        let transfer_of = |transfer: Option<Transfer>| match transfer {
+            Some(transfer) if gtk_doc_transfer => Some(format!("(transfer {})", transfer.as_str())),
+            _ => None,
+        };
+        let nullable = |ty: &Type| match *ty {
+            Type::Ptr { is_nullable, .. } | Type::FuncPtr { is_nullable, .. } if is_nullable => {
+                Some("(nullable)".to_owned())
+            }
+            _ => None,
+        };
+
+        let mut callbacks = self.callback_annotations();
+        let slice_lengths = self.annotation_tuples("slice-lengths").unwrap_or_default();
+        let mut args = Vec::new();
+        let throws = self.throws();
+        for (i, arg) in self.args.iter().enumerate() {
+            let name = match arg.name {
+                Some(ref name) => name.as_str(),
+                None => continue,
+            };
+            // The error needs no annotations.
+            if throws && i == self.args.len() - 1 {
+                args.push((name, Vec::new()));
+                continue;
+            }
+            let mut annotations: Vec<String> = nullable(&arg.ty).into_iter().collect();
+            if let Some(ref length) = arg.array_length {
+                annotations.push(if length.is_empty() {
+                    "(array)".to_owned()
+                } else if length.parse::<u64>().is_ok() {
+                    format!("(array fixed-size={})", length)
+                } else if self.args.iter().any(|a| a.name.as_ref() == Some(length)) {
+                    format!("(array length={})", length)
+                } else {
+                    "(array)".to_owned()
+                });
+            }
+            if let Some(length) = slice_lengths.get(name) {
+                annotations.push(format!("(array length={})", length));
+            }
+            annotations.extend(callbacks.remove(name).unwrap_or_default());
+            annotations.extend(transfer_of(self.arg_transfer(arg)));
+            args.push((name, annotations));
+        }
+
+        let ret = if self.ret == Type::Primitive(PrimitiveType::Void) {
+            None
+        } else {
+            let mut annotations: Vec<String> = nullable(&self.ret).into_iter().collect();
+            // The pointers are null on errors.
+            if let Type::Ptr { .. } = self.ret {
+                if annotations.is_empty() && self.throws() {
+                    annotations.push("(nullable)".to_owned());
+                }
+            }
+            annotations.extend(transfer_of(self.ret_transfer()));
+            Some(annotations)
+        };
+        (args, ret)
+    }
+
     pub fn simplify_standard_types(&mut self, config: &Config) {
         self.ret.simplify_standard_types(config);
         for arg in &mut self.args {
@@ -136,7 +780,13 @@ impl Function {
 
     pub fn add_dependencies(&self, library: &Library, out: &mut Dependencies) {
         self.ret.add_dependencies(library, out);
-        for arg in &self.args {
+        // `GError` is declared by GLib.
+        let args = if library.get_config().gobject.enabled && self.throws() {
+            &self.args[..self.args.len() - 1]
+        } else {
+            &self.args[..]
+        };
+        for arg in args {
             arg.ty.add_dependencies(library, out);
         }
     }
@@ -185,6 +835,7 @@ impl Function {
                         name,
                         ty: arg.ty,
                         array_length: None,
+                        transfer: arg.transfer,
                     }
                 })
                 .collect()
@@ -195,29 +846,21 @@ impl Function {
         for arg in &mut self.args {
             arg.ty.rename_for_config(config, &generic_params);
             if let Some(ref mut name) = arg.name {
-                reserved::escape(name);
+                reserved::escape_arg(name, config.language);
             }
         }
 
+        // Declare the error argument as GLib does, without the prefix of the
+        // exported types. Other bindings keep the argument as it's declared.
+        if config.gobject.enabled && self.throws() {
+            let error = self.args.last_mut().unwrap();
+            error.name = Some("error".to_owned());
+            error.ty = gerror_location();
+        }
+
         // Save the array length of the pointer arguments which need to use
         // the C-array notation
-        if let Some(tuples) = self.annotations.list("ptrs-as-arrays") {
-            let mut ptrs_as_arrays: HashMap<String, String> = HashMap::new();
-            for str_tuple in tuples {
-                let parts: Vec<&str> = str_tuple[1..str_tuple.len() - 1]
-                    .split(';')
-                    .map(|x| x.trim())
-                    .collect();
-                if parts.len() != 2 {
-                    warn!(
-                        "{:?} does not follow the correct syntax, so the annotation is being ignored",
-                        parts
-                    );
-                    continue;
-                }
-                ptrs_as_arrays.insert(parts[0].to_string(), parts[1].to_string());
-            }
-
+        if let Some(ptrs_as_arrays) = self.annotation_tuples("ptrs-as-arrays") {
             for arg in &mut self.args {
                 match arg.ty {
                     Type::Ptr { .. } => {}
@@ -233,40 +876,135 @@ impl Function {
     }
 }
 
+impl Function {
+    /// Writes the attributes going before the whole declaration, each on its
+    /// own line if `vertical`.
+    fn write_attributes<F: Write>(
+        &self,
+        config: &Config,
+        out: &mut SourceWriter<F>,
+        vertical: bool,
+    ) {
+        fn write_attribute<F: Write>(out: &mut SourceWriter<F>, attribute: &str, vertical: bool) {
+            write!(out, "{}", attribute);
+            if vertical {
+                out.new_line();
+            } else {
+                out.write(" ");
+            }
+        }
+
+        if let Some(attributes) = self.annotations.attributes(config) {
+            write_attribute(out, &attributes, vertical);
+        }
+        if self.never_return(config) {
+            match config.no_return_prefix() {
+                // `_Noreturn` is only C, while `[[noreturn]]` is only C++11
+                // and C2x.
+                Some("_Noreturn") if config.cpp_compatible_c() => {
+                    out.write("#ifdef __cplusplus");
+                    out.new_line();
+                    out.write("[[noreturn]]");
+                    out.new_line();
+                    out.write("#else");
+                    out.new_line();
+                    out.write("_Noreturn");
+                    out.new_line();
+                    out.write("#endif // __cplusplus");
+                    out.new_line();
+                }
+                Some(no_return) => write_attribute(out, no_return, vertical),
+                None => {}
+            }
+        }
+        if self.cold && config.language != Language::Cython {
+            if let Some(cold) = config.function.cold(config.language) {
+                write_attribute(out, cold, vertical);
+            }
+        }
+    }
+
+    fn write_documentation<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
+        if !Documentation::uses_gtk_doc(config) {
+            self.documentation.write(config, out);
+            return;
+        }
+        let (args, ret) = self.introspection_annotations(config);
+        self.documentation.write_gtk_doc(
+            out,
+            self.path.name(),
+            &args,
+            ret.as_deref(),
+            self.throws(),
+        );
+    }
+}
+
 impl Source for Function {
     fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
         fn write_1<W: Write>(func: &Function, config: &Config, out: &mut SourceWriter<W>) {
             let prefix = config.function.prefix(&func.annotations);
             let postfix = config.function.postfix(&func.annotations);
+            let body = func.inline_body(config);
 
             let condition = func.cfg.to_condition(config);
             condition.write_before(config, out);
 
-            func.documentation.write(config, out);
+            func.write_documentation(config, out);
 
+            if config.extern_c_per_item() {
+                out.write("extern \"C\" ");
+            }
+            func.write_attributes(config, out, false);
             if func.extern_decl {
-                out.write("extern ");
+                if let Some(import) = func.wasm_import(config) {
+                    write!(out, "{} ", import);
+                }
+                if !config.extern_c_per_item() {
+                    out.write("extern ");
+                }
             } else {
                 if let Some(ref prefix) = prefix {
                     write!(out, "{} ", prefix);
                 }
                 if func.annotations.must_use(config) {
-                    if let Some(ref anno) = config.function.must_use {
+                    if let Some(anno) = config.function.must_use(config.language) {
                         write!(out, "{} ", anno);
                     }
                 }
+                if body.is_some() {
+                    out.write(if config.language == Language::Cxx {
+                        "inline "
+                    } else {
+                        "static inline "
+                    });
+                } else {
+                    if config.wasm_attributes() {
+                        out.write("EMSCRIPTEN_KEEPALIVE ");
+                    }
+                    if let Some(export_macro) = config.export_macro() {
+                        write!(out, "{} ", export_macro);
+                    }
+                }
             }
             cdecl::write_func(out, &func, false, config);
 
+            // Attributes can't follow the declarator of a definition.
+            if let Some(ref body) = body {
+                Function::write_inline_body(out, body);
+                condition.write_after(config, out);
+                return;
+            }
+
             if !func.extern_decl {
                 if let Some(ref postfix) = postfix {
                     write!(out, " {}", postfix);
                 }
             }
 
-            if let Some(ref swift_name_macro) = config.function.swift_name_macro {
-                if let Some(swift_name) = func.swift_name(config) {
-                    write!(out, " {}({})", swift_name_macro, swift_name);
+            if let Some(swift_name) = func.swift_name(config) {
+                if let Some(attribute) = config.swift_name_attribute(&swift_name) {
+                    write!(out, " {}", attribute);
                 }
             }
 
@@ -276,6 +1014,12 @@ impl Source for Function {
                 }
             }
 
+            if let Some((format, first)) = func.format_args() {
+                if let Some(attribute) = config.format_attribute(format, first) {
+                    write!(out, " {}", attribute);
+                }
+            }
+
             out.write(";");
 
             condition.write_after(config, out);
@@ -284,28 +1028,60 @@ impl Source for Function {
         fn write_2<W: Write>(func: &Function, config: &Config, out: &mut SourceWriter<W>) {
             let prefix = config.function.prefix(&func.annotations);
             let postfix = config.function.postfix(&func.annotations);
+            let body = func.inline_body(config);
 
             let condition = func.cfg.to_condition(config);
 
             condition.write_before(config, out);
 
-            func.documentation.write(config, out);
+            func.write_documentation(config, out);
 
+            if config.extern_c_per_item() {
+                out.write("extern \"C\" ");
+            }
+            func.write_attributes(config, out, true);
             if func.extern_decl {
-                out.write("extern ");
+                if let Some(import) = func.wasm_import(config) {
+                    write!(out, "{}", import);
+                    out.new_line();
+                }
+                if !config.extern_c_per_item() {
+                    out.write("extern ");
+                }
             } else {
                 if let Some(ref prefix) = prefix {
                     write!(out, "{}", prefix);
                     out.new_line();
                 }
                 if func.annotations.must_use(config) {
-                    if let Some(ref anno) = config.function.must_use {
+                    if let Some(anno) = config.function.must_use(config.language) {
                         write!(out, "{}", anno);
                         out.new_line();
                     }
                 }
+                if body.is_some() {
+                    out.write(if config.language == Language::Cxx {
+                        "inline "
+                    } else {
+                        "static inline "
+                    });
+                } else {
+                    if config.wasm_attributes() {
+                        out.write("EMSCRIPTEN_KEEPALIVE ");
+                    }
+                    if let Some(export_macro) = config.export_macro() {
+                        write!(out, "{} ", export_macro);
+                    }
+                }
             }
             cdecl::write_func(out, &func, true, config);
+
+            if let Some(ref body) = body {
+                Function::write_inline_body(out, body);
+                condition.write_after(config, out);
+                return;
+            }
+
             if !func.extern_decl {
                 if let Some(ref postfix) = postfix {
                     out.new_line();
@@ -313,9 +1089,9 @@ impl Source for Function {
                 }
             }
 
-            if let Some(ref swift_name_macro) = config.function.swift_name_macro {
-                if let Some(swift_name) = func.swift_name(config) {
-                    write!(out, " {}({})", swift_name_macro, swift_name);
+            if let Some(swift_name) = func.swift_name(config) {
+                if let Some(attribute) = config.swift_name_attribute(&swift_name) {
+                    write!(out, " {}", attribute);
                 }
             }
 
@@ -325,6 +1101,12 @@ impl Source for Function {
                 }
             }
 
+            if let Some((format, first)) = func.format_args() {
+                if let Some(attribute) = config.format_attribute(format, first) {
+                    write!(out, " {}", attribute);
+                }
+            }
+
             out.write(";");
 
             condition.write_after(config, out);
@@ -342,6 +1124,313 @@ impl Source for Function {
     }
 }
 
+/// The index of the argument whose type selects the function of a
+/// `generic-group`: the only one which differs between the functions, all
+/// taking the same number of arguments, with a different type each.
+pub(crate) fn dispatch_argument(functions: &[&Function]) -> Result<usize, &'static str> {
+    let first = match functions.first() {
+        Some(first) if functions.len() > 1 => first,
+        _ => return Err("it has a single function"),
+    };
+    if functions.iter().any(|f| f.variadic) {
+        return Err("variadic functions can't be selected by a macro");
+    }
+    if functions.iter().any(|f| f.args.len() != first.args.len()) {
+        return Err("its functions take different numbers of arguments");
+    }
+    let differing: Vec<usize> = (0..first.args.len())
+        .filter(|&i| functions.iter().any(|f| f.args[i].ty != first.args[i].ty))
+        .collect();
+    let index = match differing[..] {
+        [index] => index,
+        [] => return Err("its functions take the same argument types"),
+        _ => return Err("its functions differ in more than one argument"),
+    };
+    let mut types: Vec<&Type> = functions.iter().map(|f| &f.args[index].ty).collect();
+    types.sort();
+    types.dedup();
+    if types.len() != functions.len() {
+        return Err("several of its functions take the same argument type");
+    }
+    Ok(index)
+}
+
+/// Whether `ty` is a `*mut *mut GError`, the location of the error of a
+/// function following the GLib conventions.
+fn is_gerror_location(ty: &Type) -> bool {
+    match *ty {
+        Type::Ptr {
+            ty: ref inner,
+            is_const: false,
+            ..
+        } => match **inner {
+            Type::Ptr {
+                ty: ref error,
+                is_const: false,
+                ..
+            } => match **error {
+                Type::Path(ref path) => path.path().name() == "GError",
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The `GError **` type of the error argument of a throwing function.
+/// The `T` of a `Result<T, E>` type.
+fn result_value(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match *ty {
+        syn::Type::Path(ref path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Result" {
+        return None;
+    }
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref arguments) => {
+            arguments.args.iter().find_map(|argument| match *argument {
+                syn::GenericArgument::Type(ref ty) => Some(ty),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Loads the arguments of a function annotated `lower-slices`, whose runtime
+/// glue takes each `&[T]` or `&mut [T]` argument as a pointer followed by a
+/// `usize` length named with the `_len` suffix. They're recorded in the
+/// `slice-lengths` annotation.
+fn lower_slices(
+    sig: &syn::Signature,
+    annotations: &mut AnnotationSet,
+) -> Result<Vec<FunctionArgument>, String> {
+    let mut args = Vec::new();
+    let mut lengths = Vec::new();
+    for input in &sig.inputs {
+        let slice = match *input {
+            syn::FnArg::Typed(syn::PatType {
+                ref pat, ref ty, ..
+            }) => match **ty {
+                syn::Type::Reference(ref reference) => match *reference.elem {
+                    syn::Type::Slice(ref slice) => Some((pat, reference, slice)),
+                    _ => None,
+                },
+                _ => None,
+            },
+            syn::FnArg::Receiver(..) => None,
+        };
+        let (pat, reference, slice) = match slice {
+            Some(slice) => slice,
+            None => {
+                args.extend(input.as_argument()?);
+                continue;
+            }
+        };
+        let name = match **pat {
+            syn::Pat::Ident(syn::PatIdent { ref ident, .. }) => Some(ident.to_string()),
+            _ => None,
+        };
+        let element = Type::load(&slice.elem)?
+            .ok_or_else(|| "Cannot have a slice of zero sized types.".to_owned())?;
+        let length = name.as_ref().map(|name| format!("{}_len", name));
+        if let (Some(name), Some(length)) = (&name, &length) {
+            lengths.push(format!("[{}; {}]", name, length));
+        }
+        args.push(FunctionArgument {
+            name,
+            ty: Type::Ptr {
+                ty: Box::new(element),
+                is_const: reference.mutability.is_none(),
+                is_nullable: false,
+                is_ref: false,
+            },
+            array_length: None,
+            transfer: Some(Transfer::None),
+        });
+        args.push(FunctionArgument {
+            name: length,
+            ty: Type::Primitive(PrimitiveType::maybe("usize").unwrap()),
+            array_length: None,
+            transfer: None,
+        });
+    }
+    if !lengths.is_empty() {
+        annotations.set("slice-lengths", AnnotationValue::List(lengths));
+    }
+    Ok(args)
+}
+
+/// The `T` and `E` of a `Result<T, E>` type.
+fn result_types(ty: &syn::Type) -> Option<(&syn::Type, &syn::Type)> {
+    let segment = match *ty {
+        syn::Type::Path(ref path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Result" {
+        return None;
+    }
+    let arguments = match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref arguments) => arguments,
+        _ => return None,
+    };
+    let types: Vec<&syn::Type> = arguments
+        .args
+        .iter()
+        .filter_map(|argument| match *argument {
+            syn::GenericArgument::Type(ref ty) => Some(ty),
+            _ => None,
+        })
+        .collect();
+    match types[..] {
+        [ok, err] => Some((ok, err)),
+        _ => None,
+    }
+}
+
+/// Whether a `gasync` function argument is the source object of the
+/// operation, a pointer to a type of the crate.
+fn is_source_object(ty: &Type) -> bool {
+    match *ty {
+        Type::Ptr { ty: ref inner, .. } => match **inner {
+            Type::Path(..) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn gerror_location() -> Type {
+    let error = Type::Path(GenericPath::new(Path::new("GError"), vec![]));
+    let ptr = |ty| Type::Ptr {
+        ty: Box::new(ty),
+        is_const: false,
+        is_nullable: true,
+        is_ref: false,
+    };
+    ptr(ptr(error))
+}
+
+/// The transfer of ownership of a value between the caller and the callee.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Transfer {
+    None,
+    /// Only the container, not its elements.
+    Container,
+    Full,
+}
+
+impl Transfer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Transfer::None => "none",
+            Transfer::Container => "container",
+            Transfer::Full => "full",
+        }
+    }
+}
+
+impl FromStr for Transfer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Transfer, Self::Err> {
+        match s {
+            "none" => Ok(Transfer::None),
+            "container" => Ok(Transfer::Container),
+            "full" => Ok(Transfer::Full),
+            _ => Err(format!("Unrecognized transfer mode: '{}'", s)),
+        }
+    }
+}
+
+/// The calling convention of a function or a function pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallingConvention {
+    /// The default convention of the compiler, which isn't written.
+    #[serde(rename = "C")]
+    C,
+    Cdecl,
+    Stdcall,
+    Fastcall,
+}
+
+impl CallingConvention {
+    /// Returns the convention of a Rust ABI, if it's supported.
+    pub(crate) fn from_abi(abi: &str) -> Option<CallingConvention> {
+        match abi {
+            "C" => Some(CallingConvention::C),
+            "cdecl" => Some(CallingConvention::Cdecl),
+            // "system" is only stdcall on 32-bit Windows, where the compilers
+            // ignore the keyword on other architectures.
+            "stdcall" | "system" => Some(CallingConvention::Stdcall),
+            "fastcall" => Some(CallingConvention::Fastcall),
+            _ => None,
+        }
+    }
+
+    /// The keyword written before the name of the function or the pointer.
+    pub(crate) fn keyword(self) -> Option<&'static str> {
+        match self {
+            CallingConvention::C => None,
+            CallingConvention::Cdecl => Some("__cdecl"),
+            CallingConvention::Stdcall => Some("__stdcall"),
+            CallingConvention::Fastcall => Some("__fastcall"),
+        }
+    }
+}
+
+impl FromStr for CallingConvention {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<CallingConvention, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "c" => Ok(CallingConvention::C),
+            "cdecl" => Ok(CallingConvention::Cdecl),
+            "stdcall" => Ok(CallingConvention::Stdcall),
+            "fastcall" => Ok(CallingConvention::Fastcall),
+            _ => Err(format!("Unrecognized calling convention: '{}'.", s)),
+        }
+    }
+}
+
+/// The transfer of ownership implied by a Rust type: references are
+/// borrowed, while boxes and owned strings are given away.
+fn infer_transfer(ty: &syn::Type) -> Option<Transfer> {
+    match *ty {
+        syn::Type::Reference(_) => Some(Transfer::None),
+        syn::Type::Paren(syn::TypeParen { ref elem, .. })
+        | syn::Type::Group(syn::TypeGroup { ref elem, .. }) => infer_transfer(elem),
+        syn::Type::Path(ref path) => {
+            let segment = path.path.segments.last()?;
+            match segment.ident.to_string().as_str() {
+                "Box" | "String" | "CString" => Some(Transfer::Full),
+                "Option" => match segment.arguments {
+                    syn::PathArguments::AngleBracketed(ref args) => match args.args.first()? {
+                        syn::GenericArgument::Type(ref ty) => infer_transfer(ty),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The style of the transfer of ownership, with "auto" resolved.
+fn transfer_style(config: &Config) -> TransferStyle {
+    match config.function.transfer {
+        _ if config.language == Language::Cython => TransferStyle::None,
+        TransferStyle::Auto if Documentation::uses_gtk_doc(config) => TransferStyle::GtkDoc,
+        TransferStyle::Auto => TransferStyle::None,
+        other => other,
+    }
+}
+
 trait SynFnArgHelpers {
     fn as_argument(&self) -> Result<Option<FunctionArgument>, String>;
 }
@@ -377,6 +1466,12 @@ impl SynFnArgHelpers for syn::FnArg {
                         ))
                     }
                 };
+                // The `args: ...` of a variadic function definition, which
+                // is `Signature::variadic`.
+                if let syn::Type::Verbatim(..) = **ty {
+                    return Ok(None);
+                }
+                let transfer = infer_transfer(ty);
                 let ty = match Type::load(ty)? {
                     Some(x) => x,
                     None => return Ok(None),
@@ -388,12 +1483,14 @@ impl SynFnArgHelpers for syn::FnArg {
                     name,
                     ty,
                     array_length: None,
+                    transfer,
                 }))
             }
             syn::FnArg::Receiver(ref receiver) => Ok(Some(FunctionArgument {
                 name: Some("self".to_string()),
                 ty: gen_self_type(receiver),
                 array_length: None,
+                transfer: receiver.reference.as_ref().map(|_| Transfer::None),
             })),
         }
     }
@@ -7,23 +7,44 @@ use crate::bindgen::ir::{Path, Type};
 use crate::bindgen::utilities::IterHelpers;
 use crate::bindgen::writer::{Source, SourceWriter};
 
-#[derive(Default, Debug, Clone)]
-pub struct GenericParams(pub Vec<Path>);
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct GenericParams {
+    params: Vec<Path>,
+    /// The type of each parameter which is a const generic.
+    const_types: Vec<Option<Type>>,
+}
 
 impl GenericParams {
     pub fn new(generics: &syn::Generics) -> Self {
-        GenericParams(
-            generics
-                .params
-                .iter()
-                .filter_map(|x| match *x {
-                    syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) => {
-                        Some(Path::new(ident.to_string()))
-                    }
-                    _ => None,
-                })
-                .collect(),
-        )
+        let mut params = Vec::new();
+        let mut const_types = Vec::new();
+        for param in &generics.params {
+            match *param {
+                syn::GenericParam::Type(syn::TypeParam { ref ident, .. }) => {
+                    params.push(Path::new(ident.to_string()));
+                    const_types.push(None);
+                }
+                syn::GenericParam::Const(syn::ConstParam {
+                    ref ident, ref ty, ..
+                }) => {
+                    params.push(Path::new(ident.to_string()));
+                    const_types.push(Type::load(ty).unwrap_or(None));
+                }
+                syn::GenericParam::Lifetime(..) => {}
+            }
+        }
+        GenericParams {
+            params,
+            const_types,
+        }
+    }
+
+    pub fn from_paths(params: Vec<Path>) -> Self {
+        let const_types = vec![None; params.len()];
+        GenericParams {
+            params,
+            const_types,
+        }
     }
 
     fn write_internal<F: Write>(
@@ -32,15 +53,23 @@ impl GenericParams {
         out: &mut SourceWriter<F>,
         with_default: bool,
     ) {
-        if !self.0.is_empty() && config.language == Language::Cxx {
+        if !self.params.is_empty() && config.language == Language::Cxx {
             out.write("template<");
-            for (i, item) in self.0.iter().enumerate() {
+            for (i, (item, const_type)) in self.params.iter().zip(&self.const_types).enumerate() {
                 if i != 0 {
                     out.write(", ");
                 }
-                write!(out, "typename {}", item);
-                if with_default {
-                    write!(out, " = void");
+                match *const_type {
+                    Some(ref ty) => {
+                        ty.write(config, out);
+                        write!(out, " {}", item);
+                    }
+                    None => {
+                        write!(out, "typename {}", item);
+                        if with_default {
+                            write!(out, " = void");
+                        }
+                    }
                 }
             }
             out.write(">");
@@ -57,7 +86,7 @@ impl Deref for GenericParams {
     type Target = [Path];
 
     fn deref(&self) -> &[Path] {
-        &self.0
+        &self.params
     }
 }
 
@@ -67,7 +96,7 @@ impl Source for GenericParams {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct GenericPath {
     path: Path,
     export_name: String,
@@ -117,6 +146,12 @@ impl GenericPath {
         self.path.name()
     }
 
+    /// Whether the path is the value of a const generic argument rather
+    /// than a type.
+    pub fn is_const_value(&self) -> bool {
+        self.name().starts_with(|c: char| c.is_ascii_digit())
+    }
+
     pub fn export_name(&self) -> &str {
         &self.export_name
     }
@@ -125,8 +160,8 @@ impl GenericPath {
         for generic in &mut self.generics {
             generic.rename_for_config(config, generic_params);
         }
-        if !generic_params.contains(&self.path) {
-            config.export.rename(&mut self.export_name);
+        if !generic_params.contains(&self.path) && !self.is_const_value() {
+            config.export.rename_reference(&mut self.export_name);
         }
     }
 
@@ -156,6 +191,15 @@ impl GenericPath {
             }) => args.iter().try_skip_map(|x| match *x {
                 syn::GenericArgument::Type(ref x) => Type::load(x),
                 syn::GenericArgument::Lifetime(_) => Ok(None),
+                // Const arguments are kept as paths named after their value,
+                // which specialization substitutes like type arguments.
+                syn::GenericArgument::Const(syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(ref value),
+                    ..
+                })) => Ok(Some(Type::Path(Self::new(
+                    Path::new(value.base10_digits()),
+                    Vec::new(),
+                )))),
                 _ => Err(format!("can't handle generic argument {:?}", x)),
             })?,
             syn::PathArguments::Parenthesized(_) => {
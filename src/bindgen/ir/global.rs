@@ -12,7 +12,7 @@ use crate::bindgen::ir::{AnnotationSet, Cfg, Documentation, Item, ItemContainer,
 use crate::bindgen::library::Library;
 use crate::bindgen::writer::{Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Static {
     pub path: Path,
     pub export_name: String,
@@ -106,7 +106,19 @@ impl Item for Static {
 
 impl Source for Static {
     fn write<F: Write>(&self, config: &Config, out: &mut SourceWriter<F>) {
-        out.write("extern ");
+        let extern_c = config.extern_c_per_item();
+        if extern_c {
+            out.write("extern \"C\" ");
+        }
+        if let Some(attributes) = self.annotations.attributes(config) {
+            write!(out, "{} ", attributes);
+        }
+        if let Some(export_macro) = config.export_macro() {
+            write!(out, "{} ", export_macro);
+        }
+        if !extern_c {
+            out.write("extern ");
+        }
         if let Type::Ptr { is_const: true, .. } = self.ty {
         } else if !self.mutable {
             out.write("const ");
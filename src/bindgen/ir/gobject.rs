@@ -4,19 +4,20 @@
 
 use std::io::Write;
 
-use heck::SnakeCase;
+use heck::{CamelCase, SnakeCase};
 use syn;
 
-use crate::bindgen::config::Config;
+use crate::bindgen::config::{Config, Language};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
 use crate::bindgen::ir::{
-    AnnotationSet, Cfg, Documentation, Item, ItemContainer, Path, Struct, Type,
+    AnnotationSet, CallingConvention, Cfg, Documentation, Field, Function, GenericParams,
+    GenericPath, Item, ItemContainer, OpaqueItem, Path, Struct, Type,
 };
 use crate::bindgen::library::Library;
-use crate::bindgen::writer::SourceWriter;
+use crate::bindgen::writer::{ListType, Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GType {
     Object {
         instance: Option<Type>,
@@ -26,9 +27,71 @@ pub enum GType {
     Interface {
         type_: Type,
     },
+    Boxed {
+        type_: Type,
+    },
+    Final {
+        parent: String,
+    },
+    /// An interface declared by a trait annotated `cbindgen:ginterface`,
+    /// whose methods are the virtual functions of its interface struct. They
+    /// take the instance as a `self` pointer to the trait's type.
+    TraitInterface {
+        prerequisite: String,
+        vfuncs: Vec<Field>,
+    },
+}
+
+/// A property declared with a `cbindgen:property=name:type` annotation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Property {
+    pub name: String,
+    pub ty: String,
+}
+
+impl Property {
+    fn load(property: &str) -> Result<Self, String> {
+        let mut parts = property.splitn(2, ':').map(|part| part.trim());
+        match (parts.next(), parts.next()) {
+            (Some(name), Some(ty)) if !name.is_empty() && !ty.is_empty() => Ok(Property {
+                name: name.replace('_', "-"),
+                ty: ty.to_owned(),
+            }),
+            _ => Err(format!(
+                "Property {} isn't in the name:type form.",
+                property
+            )),
+        }
+    }
+
+    fn is_pointer(&self) -> bool {
+        self.ty.ends_with('*')
+    }
+
+    /// Strings are passed and returned as const, the object keeping its copy.
+    fn is_string(&self) -> bool {
+        let ty = self.ty.trim_end_matches('*').trim();
+        self.ty.matches('*').count() == 1 && (ty == "gchar" || ty == "char")
+    }
+
+    /// The C type, with its pointer star attached to the name that follows.
+    fn c_type(&self, with_const: bool) -> String {
+        let ty = self.ty.trim_end_matches('*').trim_end();
+        let stars = self.ty[ty.len()..].trim_start();
+        let const_ = if with_const && self.is_string() {
+            "const "
+        } else {
+            ""
+        };
+        if stars.is_empty() {
+            format!("{}{} ", const_, ty)
+        } else {
+            format!("{}{} {}", const_, ty, stars)
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GObject {
     pub path: Path,
     pub name: String,
@@ -128,6 +191,120 @@ impl GObject {
         ))
     }
 
+    /// Load a struct annotated with `#[gobject(class)]`, declared as a final
+    /// type deriving from the type of its first field, or from GObject.
+    pub fn load_final(
+        item: &syn::ItemStruct,
+        name: String,
+        mod_cfg: Option<&Cfg>,
+    ) -> Result<Self, String> {
+        let path = Path::new(item.ident.to_string());
+        if !name.ends_with(path.name()) || name == path.name() {
+            return Err("A final type needs a prefix, see export.prefix.".to_owned());
+        }
+
+        let parent = match item.fields.iter().next() {
+            Some(field) => match Type::load(&field.ty)?.and_then(|ty| ty.get_root_path()) {
+                Some(parent) => parent.name().to_owned(),
+                None => return Err("Cannot find the parent type.".to_owned()),
+            },
+            None => "GObject".to_owned(),
+        };
+
+        Ok(Self::new(
+            path,
+            name,
+            GType::Final { parent },
+            Cfg::append(mod_cfg, Cfg::load(&item.attrs)),
+            AnnotationSet::load(&item.attrs)?,
+            Documentation::load(&item.attrs),
+        ))
+    }
+
+    /// Load a trait annotated with `cbindgen:ginterface`, or
+    /// `cbindgen:ginterface=Prerequisite` when its implementations have to
+    /// derive from another type than GObject.
+    pub fn load_trait_interface(
+        item: &syn::ItemTrait,
+        name: String,
+        mod_cfg: Option<&Cfg>,
+    ) -> Result<Self, String> {
+        let path = Path::new(item.ident.to_string());
+        if !name.ends_with(path.name()) || name == path.name() {
+            return Err("An interface needs a prefix, see export.prefix.".to_owned());
+        }
+
+        let annotations = AnnotationSet::load(&item.attrs)?;
+        let prerequisite = match annotations.atom("ginterface") {
+            Some(Some(prerequisite)) => prerequisite,
+            _ => "GObject".to_owned(),
+        };
+
+        let mut vfuncs = Vec::new();
+        for method in item.items.iter().filter_map(|item| match item {
+            syn::TraitItem::Method(method) => Some(method),
+            _ => None,
+        }) {
+            let function = Function::load(
+                Path::new(method.sig.ident.to_string()),
+                Some(&path),
+                &method.sig,
+                false,
+                &method.attrs,
+                mod_cfg,
+            )?;
+            let mut args: Vec<_> = function
+                .args
+                .into_iter()
+                .map(|arg| (arg.name, arg.ty))
+                .collect();
+            match args.first_mut() {
+                Some((Some(ref name), ref mut ty)) if name == "self" => {
+                    // GObject methods take a mutable instance.
+                    *ty = Type::Ptr {
+                        ty: Box::new(Type::Path(GenericPath::new(path.clone(), vec![]))),
+                        is_const: false,
+                        is_nullable: false,
+                        is_ref: false,
+                    };
+                }
+                _ => {
+                    warn_in!(
+                        function.annotations.location,
+                        "Skipping {}::{}, which isn't a method.",
+                        path,
+                        function.path.name()
+                    );
+                    continue;
+                }
+            }
+            vfuncs.push(Field {
+                name: function.path.name().to_owned(),
+                ty: Type::FuncPtr {
+                    ret: Box::new(function.ret),
+                    args,
+                    is_nullable: false,
+                    calling_convention: CallingConvention::C,
+                },
+                cfg: function.cfg,
+                annotations: AnnotationSet::new(),
+                documentation: function.documentation,
+            });
+        }
+
+        Ok(Self::new(
+            path,
+            name,
+            GType::TraitInterface {
+                prerequisite,
+                vfuncs,
+            },
+            Cfg::append(mod_cfg, Cfg::load(&item.attrs)),
+            annotations,
+            Documentation::load(&item.attrs),
+        ))
+    }
+
     pub fn new(
         path: Path,
         name: String,
@@ -145,6 +322,82 @@ impl GObject {
             documentation,
         }
     }
+
+    /// Declare an opaque item annotated with `cbindgen:gobject=boxed` as a
+    /// boxed type, `name` being its exported name.
+    pub fn new_boxed(item: &OpaqueItem, name: String) -> Self {
+        let type_ = Type::Path(GenericPath::new(item.path.clone(), vec![]));
+
+        Self::new(
+            item.path.clone(),
+            name,
+            GType::Boxed { type_ },
+            item.cfg.clone(),
+            AnnotationSet::new(),
+            Documentation::none(),
+        )
+    }
+
+    /// Whether the struct of the type is declared by `G_DECLARE_FINAL_TYPE`
+    /// or `G_DECLARE_INTERFACE` rather than by the crate.
+    pub fn declares_struct(&self) -> bool {
+        match self.gtype {
+            GType::Final { .. } | GType::TraitInterface { .. } => true,
+            GType::Object { .. } | GType::Interface { .. } | GType::Boxed { .. } => false,
+        }
+    }
+
+    pub fn is_boxed(&self) -> bool {
+        match self.gtype {
+            GType::Boxed { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// The properties listed by a `cbindgen:property=[title:gchar*, count:gint]`
+    /// annotation, a single one being also accepted as `cbindgen:property=title:gchar*`.
+    pub fn properties(&self) -> Vec<Property> {
+        let properties = match self.annotations.list("property") {
+            Some(properties) => properties,
+            None => match self.annotations.atom("property") {
+                Some(Some(property)) => vec![property],
+                _ => return Vec::new(),
+            },
+        };
+
+        properties
+            .iter()
+            .filter_map(|property| match Property::load(property) {
+                Ok(property) => Some(property),
+                Err(msg) => {
+                    warn_in!(
+                        self.annotations.location,
+                        "Skipping a property of {}: {}",
+                        self.name,
+                        msg
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// The signals listed by a `cbindgen:signals=changed,closed` annotation.
+    pub fn signals(&self) -> Vec<String> {
+        let signals = match self.annotations.list("signals") {
+            Some(signals) => signals,
+            None => match self.annotations.atom("signals") {
+                Some(Some(signals)) => signals.split(',').map(|s| s.to_owned()).collect(),
+                _ => return Vec::new(),
+            },
+        };
+
+        signals
+            .iter()
+            .map(|signal| signal.trim().replace('_', "-"))
+            .filter(|signal| !signal.is_empty())
+            .collect()
+    }
 }
 
 impl Item for GObject {
@@ -167,9 +420,24 @@ impl Item for GObject {
                     class.add_dependencies(library, out);
                 }
             }
-            GType::Interface { type_ } => {
+            GType::Interface { type_ } | GType::Boxed { type_ } => {
                 type_.add_dependencies(library, out);
             }
+            GType::Final { .. } => {}
+            GType::TraitInterface { ref vfuncs, .. } => {
+                // The `self` pointer is to the interface itself.
+                for vfunc in vfuncs {
+                    if let Type::FuncPtr {
+                        ref ret, ref args, ..
+                    } = vfunc.ty
+                    {
+                        ret.add_dependencies(library, out);
+                        for (_, ty) in args.iter().skip(1) {
+                            ty.add_dependencies(library, out);
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -193,7 +461,15 @@ impl Item for GObject {
         ItemContainer::GObject(self.clone())
     }
 
-    fn rename_for_config(&mut self, _config: &Config) {}
+    fn rename_for_config(&mut self, config: &Config) {
+        if let GType::TraitInterface { ref mut vfuncs, .. } = self.gtype {
+            for vfunc in vfuncs {
+                vfunc
+                    .ty
+                    .rename_for_config(config, &GenericParams::default());
+            }
+        }
+    }
 
     fn resolve_declaration_types(&mut self, resolver: &DeclarationTypeResolver) {
         match &mut self.gtype {
@@ -210,22 +486,64 @@ impl Item for GObject {
                     class.resolve_declaration_types(resolver);
                 }
             }
-            GType::Interface { type_ } => {
+            GType::Interface { type_ } | GType::Boxed { type_ } => {
                 type_.resolve_declaration_types(resolver);
             }
+            GType::Final { .. } => {}
+            GType::TraitInterface { ref mut vfuncs, .. } => {
+                for vfunc in vfuncs {
+                    vfunc.ty.resolve_declaration_types(resolver);
+                }
+            }
         }
     }
 }
 
 impl GObject {
+    /// Simplifies the standard types of the virtual functions.
+    pub fn simplify_standard_types(&mut self, config: &Config) {
+        if let GType::TraitInterface { ref mut vfuncs, .. } = self.gtype {
+            for vfunc in vfuncs {
+                vfunc.ty.simplify_standard_types(config);
+            }
+        }
+    }
+
     pub fn write<F: Write>(
         &self,
-        _config: &Config,
+        config: &Config,
         out: &mut SourceWriter<F>,
         _associated_to_struct: Option<&Struct>,
     ) {
+        if config.language == Language::Cython {
+            // Only the struct is declared, as an opaque one.
+            write!(out, "{}struct {}", config.style.cython_def(), self.name);
+            out.open_brace();
+            out.write("pass");
+            out.close_brace(false);
+            return;
+        }
+        if let GType::TraitInterface {
+            ref prerequisite,
+            ref vfuncs,
+        } = self.gtype
+        {
+            self.write_trait_interface(config, out, prerequisite, vfuncs);
+            self.write_signals(out);
+            return;
+        }
+        self.write_type_macros(out);
+        if !self.is_boxed() {
+            self.write_signals(out);
+        }
+    }
+
+    fn write_type_macros<F: Write>(&self, out: &mut SourceWriter<F>) {
         let (prefix, name) = match self.gtype {
-            GType::Object { .. } => {
+            GType::Object { .. }
+            | GType::Boxed { .. }
+            | GType::Final { .. }
+            | GType::TraitInterface { .. } => {
                 let prefix = self.name.strip_suffix(self.path.name()).unwrap();
                 let name = self.name.strip_prefix(prefix).unwrap();
                 (prefix, name)
@@ -247,7 +565,27 @@ impl GObject {
             "#define {}                    ({}_get_type())",
             type_up, snake
         );
+        if self.is_boxed() {
+            return;
+        }
         out.new_line();
+
+        if let GType::Final { ref parent } = self.gtype {
+            // G_DECLARE_FINAL_TYPE also provides the class struct and the
+            // cast helpers.
+            write!(
+                out,
+                "G_DECLARE_FINAL_TYPE({}, {}, {}, {}, {})",
+                self.name, snake, prefix_up, name_up, parent
+            );
+            out.new_line();
+            out.new_line();
+            write!(out, "struct _{}", self.name);
+            out.open_brace();
+            write!(out, "{} parent_instance;", parent);
+            out.close_brace(true);
+            return;
+        }
         write!(
             out,
             "#define {}_{}(obj)            (G_TYPE_CHECK_INSTANCE_CAST((obj),{},{}))",
@@ -281,6 +619,9 @@ impl GObject {
                     prefix_up, name_up, type_up, self.name
                 );
             }
+            GType::Boxed { .. } | GType::Final { .. } | GType::TraitInterface { .. } => {
+                unreachable!()
+            }
             GType::Interface { .. } => {
                 write!(
                     out,
@@ -294,3 +635,210 @@ impl GObject {
         }
     }
 }
+
+impl GObject {
+    /// Declares an interface with `G_DECLARE_INTERFACE`, which also declares
+    /// its `get_type` function, and defines its interface struct, with a
+    /// function pointer for each of its virtual functions.
+    fn write_trait_interface<F: Write>(
+        &self,
+        config: &Config,
+        out: &mut SourceWriter<F>,
+        prerequisite: &str,
+        vfuncs: &[Field],
+    ) {
+        // The name was checked to end with the one of the trait.
+        let prefix = &self.name[..self.name.len() - self.path.name().len()];
+        let prefix_up = prefix.to_uppercase();
+        let name_up = self.path.name().to_snake_case().to_uppercase();
+        let snake = self.name.to_snake_case();
+
+        self.documentation.write(config, out);
+        write!(
+            out,
+            "#define {}_TYPE_{} ({}_get_type())",
+            prefix_up, name_up, snake
+        );
+        out.new_line();
+        write!(
+            out,
+            "G_DECLARE_INTERFACE({}, {}, {}, {}, {})",
+            self.name, snake, prefix_up, name_up, prerequisite
+        );
+        out.new_line();
+        out.new_line();
+        write!(out, "struct _{}Interface", self.name);
+        out.open_brace();
+        out.write("GTypeInterface g_iface;");
+        if !vfuncs.is_empty() {
+            out.new_line();
+            out.new_line();
+            out.write_vertical_source_list(vfuncs, ListType::Cap(";"));
+        }
+        out.close_brace(true);
+    }
+
+    /// Write a documented handler prototype for each signal, to be cast with
+    /// `G_CALLBACK` when connecting it.
+    fn write_signals<F: Write>(&self, out: &mut SourceWriter<F>) {
+        for signal in self.signals() {
+            out.new_line();
+            out.new_line();
+            out.write("/**");
+            out.new_line();
+            write!(out, " * {}::{}:", self.name, signal);
+            out.new_line();
+            out.write(" * @self: the object which received the signal");
+            out.new_line();
+            out.write(" * @user_data: user data set when the signal handler was connected");
+            out.new_line();
+            out.write(" *");
+            out.new_line();
+            write!(
+                out,
+                " * Connect with g_signal_connect (self, \"{}\", G_CALLBACK (handler), user_data).",
+                signal
+            );
+            out.new_line();
+            out.write(" */");
+            out.new_line();
+            write!(
+                out,
+                "typedef void (*{}{}Handler)({} *self, gpointer user_data);",
+                self.name,
+                signal.to_camel_case(),
+                self.name
+            );
+        }
+    }
+
+    /// Whether prototypes have to be declared along with the exported functions.
+    pub fn has_functions(&self) -> bool {
+        self.is_boxed() || !self.properties().is_empty()
+    }
+
+    /// Write the prototypes that are part of the type API, skipping the ones
+    /// listed in `exported` since the crate already provides them.
+    pub fn write_functions<F: Write>(&self, out: &mut SourceWriter<F>, exported: &[&str]) {
+        if self.is_boxed() {
+            self.write_boxed_functions(out, exported);
+        } else {
+            self.write_property_accessors(out, exported);
+        }
+    }
+
+    /// Write a documented getter and setter for each property.
+    fn write_property_accessors<F: Write>(&self, out: &mut SourceWriter<F>, exported: &[&str]) {
+        let snake = self.name.to_snake_case();
+
+        for property in self.properties() {
+            let property_snake = property.name.replace('-', "_");
+            let transfer = if property.is_pointer() {
+                "(transfer none): "
+            } else {
+                ""
+            };
+
+            let getter = format!("{}_get_{}", snake, property_snake);
+            if !exported.contains(&getter.as_str()) {
+                out.new_line_if_not_start();
+                out.write("/**");
+                out.new_line();
+                write!(out, " * {}:", getter);
+                out.new_line();
+                write!(out, " * @self: a #{}", self.name);
+                out.new_line();
+                out.write(" *");
+                out.new_line();
+                write!(
+                    out,
+                    " * Returns: {}the value of the #{}:{} property",
+                    transfer, self.name, property.name
+                );
+                out.new_line();
+                out.write(" */");
+                out.new_line();
+                write!(
+                    out,
+                    "{}{}({} *self);",
+                    property.c_type(true),
+                    getter,
+                    self.name
+                );
+                out.new_line();
+            }
+
+            let setter = format!("{}_set_{}", snake, property_snake);
+            if !exported.contains(&setter.as_str()) {
+                out.new_line_if_not_start();
+                out.write("/**");
+                out.new_line();
+                write!(out, " * {}:", setter);
+                out.new_line();
+                write!(out, " * @self: a #{}", self.name);
+                out.new_line();
+                write!(
+                    out,
+                    " * @{}: {}the new value of the #{}:{} property",
+                    property_snake, transfer, self.name, property.name
+                );
+                out.new_line();
+                out.write(" */");
+                out.new_line();
+                write!(
+                    out,
+                    "void {}({} *self, {}{});",
+                    setter,
+                    self.name,
+                    property.c_type(true),
+                    property_snake
+                );
+                out.new_line();
+            }
+        }
+    }
+
+    /// Write the prototypes backing a boxed type, skipping the ones listed in
+    /// `exported` since the crate already provides them.
+    /// The function registering the type.
+    pub fn get_type_function(&self) -> String {
+        format!("{}_get_type", self.name.to_snake_case())
+    }
+
+    /// Defines the `get_type` function of a boxed type, with its `copy` and
+    /// `free` functions.
+    #[allow(unused)]
+    pub fn write_boxed_type_definition<F: Write>(&self, out: &mut SourceWriter<F>) {
+        let snake = self.name.to_snake_case();
+        write!(
+            out,
+            "G_DEFINE_BOXED_TYPE({}, {}, {}_copy, {}_free)",
+            self.name, snake, snake, snake
+        );
+    }
+
+    fn write_boxed_functions<F: Write>(&self, out: &mut SourceWriter<F>, exported: &[&str]) {
+        let snake = self.name.to_snake_case();
+
+        let get_type = self.get_type_function();
+        if !exported.contains(&get_type.as_str()) {
+            out.new_line_if_not_start();
+            write!(out, "GType {}(void);", get_type);
+            out.new_line();
+        }
+
+        let copy = format!("{}_copy", snake);
+        if !exported.contains(&copy.as_str()) {
+            out.new_line_if_not_start();
+            write!(out, "{} *{}(const {} *self);", self.name, copy, self.name);
+            out.new_line();
+        }
+
+        let free = format!("{}_free", snake);
+        if !exported.contains(&free.as_str()) {
+            out.new_line_if_not_start();
+            write!(out, "void {}({} *self);", free, self.name);
+            out.new_line();
+        }
+    }
+}
@@ -3,6 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::mem;
 
 use crate::bindgen::config::Config;
@@ -43,7 +44,7 @@ pub trait Item {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ItemContainer {
     Constant(Constant),
     Static(Static),
@@ -89,6 +90,23 @@ impl<T: Item> Default for ItemMap<T> {
     }
 }
 
+// An `ItemMap` is (de)serialized as the list of its items, in order.
+impl<T: Item + Clone + Serialize> Serialize for ItemMap<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_vec().serialize(serializer)
+    }
+}
+
+impl<'de, T: Item + Clone + Deserialize<'de>> Deserialize<'de> for ItemMap<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ItemMap<T>, D::Error> {
+        let mut map = ItemMap::default();
+        for item in Vec::<T>::deserialize(deserializer)? {
+            map.try_insert(item);
+        }
+        Ok(map)
+    }
+}
+
 impl<T: Item + Clone> ItemMap<T> {
     pub fn rebuild(&mut self) {
         let old = mem::take(self);
@@ -16,7 +16,7 @@ use crate::bindgen::mangle;
 use crate::bindgen::monomorph::Monomorphs;
 use crate::bindgen::writer::{Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpaqueItem {
     pub path: Path,
     pub export_name: String,
@@ -169,6 +169,11 @@ impl Source for OpaqueItem {
                 out.write("pass");
                 out.close_brace(false);
             }
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
 
         condition.write_after(config, out);
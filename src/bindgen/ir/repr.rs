@@ -4,7 +4,7 @@
 
 use crate::bindgen::ir::ty::{IntKind, PrimitiveType};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReprStyle {
     Rust,
     C,
@@ -17,7 +17,7 @@ impl Default for ReprStyle {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReprType {
     kind: IntKind,
     signed: bool,
@@ -33,13 +33,13 @@ impl ReprType {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReprAlign {
     Packed,
     Align(u64),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Repr {
     pub style: ReprStyle,
     pub ty: Option<ReprType>,
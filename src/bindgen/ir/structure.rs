@@ -8,8 +8,9 @@ use crate::bindgen::config::{Config, Language, LayoutConfig};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
 use crate::bindgen::ir::{
-    AnnotationSet, Cfg, ConditionWrite, Constant, Documentation, Field, GenericParams, Item,
-    ItemContainer, Path, Repr, ReprAlign, ReprStyle, ToCondition, Type, Typedef,
+    AnnotationSet, ArrayLength, Cfg, ConditionWrite, Constant, Documentation, Field, GenericParams,
+    Item, ItemContainer, Path, PrimitiveType, Repr, ReprAlign, ReprStyle, ToCondition, Type,
+    Typedef,
 };
 use crate::bindgen::library::Library;
 use crate::bindgen::mangle;
@@ -19,7 +20,126 @@ use crate::bindgen::reserved;
 use crate::bindgen::utilities::IterHelpers;
 use crate::bindgen::writer::{ListType, Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+/// Removes the `bitfield=N` annotations C can't declare, and warns about the
+/// runs of bitfields whose layout a Rust integer holding them can't mirror:
+/// adjacent bitfields of different types, which compilers pack differently,
+/// and bitfields which don't fit the bits left in their storage unit.
+fn check_bitfields(path: &Path, fields: &mut [Field]) {
+    // The type, the capacity and the bits used of the current storage unit,
+    // and the name of its last bitfield.
+    let mut unit: Option<(Type, Option<u32>, u32, String)> = None;
+    for field in fields.iter_mut() {
+        let width = match field.bitfield() {
+            Some(width) => width,
+            None => {
+                unit = None;
+                continue;
+            }
+        };
+        let capacity = match field.ty {
+            Type::Primitive(ref primitive) => primitive.bitfield_capacity(),
+            _ => None,
+        };
+        let capacity = match capacity {
+            Some(capacity) => capacity,
+            None => {
+                warn!(
+                    "Field {}::{} isn't an integer, it can't be a bitfield.",
+                    path, field.name
+                );
+                field.annotations.remove("bitfield");
+                unit = None;
+                continue;
+            }
+        };
+        let bits = match width.parse::<u32>() {
+            Ok(bits) if bits > 0 && capacity.map_or(true, |capacity| bits <= capacity) => bits,
+            _ => {
+                warn!(
+                    "Field {}::{} can't be a bitfield of {:?} bits.",
+                    path, field.name, width
+                );
+                field.annotations.remove("bitfield");
+                unit = None;
+                continue;
+            }
+        };
+        // Any bitfield can follow a full storage unit.
+        if let Some((_, Some(capacity), used, _)) = unit {
+            if used == capacity {
+                unit = None;
+            }
+        }
+        let used = match unit {
+            Some((ref ty, _, _, ref previous)) if *ty != field.ty => {
+                warn!(
+                    "Bitfields {}::{} and {}::{} have different types, their layout depends \
+                     on the C compiler and the Rust struct can't match it.",
+                    path, previous, path, field.name
+                );
+                bits
+            }
+            Some((_, Some(capacity), used, _)) if used + bits > capacity => {
+                warn!(
+                    "Bitfield {}::{} doesn't fit in the {} bits left of its storage unit, C \
+                     pads it to the next one and the Rust struct has to skip those bits.",
+                    path,
+                    field.name,
+                    capacity - used
+                );
+                bits
+            }
+            Some((_, _, used, _)) => used + bits,
+            None => bits,
+        };
+        unit = Some((field.ty.clone(), capacity, used, field.name.clone()));
+    }
+}
+
+/// Replaces the fields annotated with `skip-field` with `pad=N` bytes of
+/// padding, so that the struct keeps its size without showing them, or
+/// leaves them out without a `pad` annotation.
+fn skip_fields(path: &Path, fields: &mut Vec<Field>) {
+    let mut kept = Vec::with_capacity(fields.len());
+    for field in fields.drain(..) {
+        if !field.annotations.bool("skip-field").unwrap_or(false) {
+            if field.annotations.atom("pad").is_some() {
+                warn!(
+                    "Field {}::{} has a `pad` annotation but isn't skipped.",
+                    path, field.name
+                );
+            }
+            kept.push(field);
+            continue;
+        }
+        match field.annotations.atom("pad") {
+            Some(Some(ref bytes)) if bytes.parse::<u64>().unwrap_or(0) > 0 => {
+                let byte = Type::Primitive(PrimitiveType::maybe("u8").unwrap());
+                kept.push(Field {
+                    name: format!("_pad_{}", field.name),
+                    ty: Type::Array(Box::new(byte), ArrayLength::Value(bytes.clone())),
+                    cfg: field.cfg,
+                    annotations: AnnotationSet::new(),
+                    documentation: Documentation::none(),
+                });
+            }
+            Some(bytes) => warn!(
+                "Field {}::{} can't be replaced with {:?} bytes of padding, it's left out.",
+                path,
+                field.name,
+                bytes.unwrap_or_default()
+            ),
+            None => warn!(
+                "Field {}::{} is left out without a `pad` annotation, the C struct doesn't \
+                 have the layout of the Rust one.",
+                path, field.name
+            ),
+        }
+    }
+    *fields = kept;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Struct {
     pub path: Path,
     pub export_name: String,
@@ -95,6 +215,31 @@ impl Struct {
             }
         };
 
+        let mut fields = fields;
+        let count = fields.len();
+        for (i, field) in fields.iter_mut().enumerate() {
+            if !field.is_flexible_array() {
+                continue;
+            }
+            let valid = match field.ty {
+                Type::Array(_, ArrayLength::Value(ref length)) => length == "0" || length == "1",
+                _ => false,
+            };
+            if i + 1 != count || !valid {
+                warn_at!(
+                    item,
+                    "Field {}::{} isn't a trailing array of zero or one element, it can't be \
+                     a flexible array member.",
+                    path,
+                    field.name
+                );
+                field.annotations.remove("flexible-array");
+            }
+        }
+
+        skip_fields(&path, &mut fields);
+        check_bitfields(&path, &mut fields);
+
         let has_tag_field = false;
         let is_enum_variant_body = false;
 
@@ -418,12 +563,23 @@ impl Source for Struct {
         let condition = self.cfg.to_condition(config);
         condition.write_before(config, out);
 
+        let pragma_pack = self.alignment == Some(ReprAlign::Packed)
+            && config.layout.packed_pragma()
+            && config.language != Language::Cython;
+        if pragma_pack {
+            out.write("#pragma pack(push, 1)");
+            out.new_line();
+        }
+
         self.documentation.write(config, out);
 
         if !self.is_enum_variant_body {
             self.generic_params.write(config, out);
         }
 
+        // A forward declared struct is defined with its tag, after its typedef.
+        let forward_declared = out.bindings().is_forward_declared(&self.path);
+
         // The following results in
         // C++ or C with Tag as style:
         //   struct Name {
@@ -432,9 +588,16 @@ impl Source for Struct {
         // C with Both as style:
         //   typedef struct Name {
         match config.language {
-            Language::C if config.style.generate_typedef() => out.write("typedef "),
+            Language::C if config.style.generate_typedef() && !forward_declared => {
+                out.write("typedef ")
+            }
             Language::C | Language::Cxx => {}
             Language::Cython => out.write(config.style.cython_def()),
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
 
         // Cython extern declarations don't manage layouts, layouts are defined entierly by the
@@ -455,13 +618,13 @@ impl Source for Struct {
             if let Some(align) = self.alignment {
                 match align {
                     ReprAlign::Packed => {
-                        if let Some(ref anno) = config.layout.packed {
+                        if let Some(anno) = config.layout.packed_annotation() {
                             write!(out, " {}", anno);
                         }
                     }
                     ReprAlign::Align(n) => {
-                        if let Some(ref anno) = config.layout.aligned_n {
-                            write!(out, " {}({})", anno, n);
+                        if let Some(anno) = config.layout.aligned_annotation(config.language, n) {
+                            write!(out, " {}", anno);
                         }
                     }
                 }
@@ -474,7 +637,16 @@ impl Source for Struct {
             }
         }
 
-        if config.language != Language::C || config.style.generate_tag() {
+        // C typedefs are deprecated after their name instead, since using a
+        // deprecated type in the typedef would warn.
+        if config.language == Language::Cxx || !config.style.generate_typedef() || forward_declared
+        {
+            if let Some(attributes) = self.annotations.attributes(config) {
+                write!(out, " {}", attributes);
+            }
+        }
+
+        if config.language != Language::C || config.style.generate_tag() || forward_declared {
             write!(out, " {}", self.export_name());
         }
 
@@ -697,13 +869,22 @@ impl Source for Struct {
             }
         }
 
-        if config.language == Language::C && config.style.generate_typedef() {
+        if config.language == Language::C && config.style.generate_typedef() && !forward_declared {
             out.close_brace(false);
-            write!(out, " {};", self.export_name());
+            write!(out, " {}", self.export_name());
+            if let Some(attributes) = self.annotations.attributes(config) {
+                write!(out, " {}", attributes);
+            }
+            out.write(";");
         } else {
             out.close_brace(true);
         }
 
+        if pragma_pack {
+            out.new_line();
+            out.write("#pragma pack(pop)");
+        }
+
         for constant in &self.associated_constants {
             out.new_line();
             constant.write(config, out, Some(self));
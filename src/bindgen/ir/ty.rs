@@ -3,19 +3,22 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::borrow::Cow;
+use std::fmt;
 use std::io::Write;
+use std::mem;
 
 use crate::bindgen::cdecl;
 use crate::bindgen::config::{Config, Language};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
-use crate::bindgen::ir::{GenericParams, GenericPath, Path};
+use crate::bindgen::ir::{CallingConvention, GenericParams, GenericPath, Path};
 use crate::bindgen::library::Library;
 use crate::bindgen::monomorph::Monomorphs;
-use crate::bindgen::utilities::IterHelpers;
+use crate::bindgen::reserved;
+use crate::bindgen::utilities::{IterHelpers, SynAbiHelpers};
 use crate::bindgen::writer::{Source, SourceWriter};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum PrimitiveType {
     Void,
     Bool,
@@ -34,7 +37,7 @@ pub enum PrimitiveType {
     },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum IntKind {
     Short,
     Int,
@@ -100,6 +103,28 @@ impl PrimitiveType {
         })
     }
 
+    /// The number of bits a bitfield of this type can hold: `None` if the
+    /// type isn't integral, `Some(None)` if its width depends on the target.
+    pub fn bitfield_capacity(&self) -> Option<Option<u32>> {
+        Some(match *self {
+            PrimitiveType::Bool => Some(1),
+            PrimitiveType::Char | PrimitiveType::SChar | PrimitiveType::UChar => Some(8),
+            PrimitiveType::Integer { kind, .. } => match kind {
+                IntKind::B8 => Some(8),
+                IntKind::Short | IntKind::B16 => Some(16),
+                IntKind::Int | IntKind::B32 => Some(32),
+                IntKind::LongLong | IntKind::B64 => Some(64),
+                IntKind::Long | IntKind::SizeT | IntKind::Size => None,
+            },
+            PrimitiveType::Void
+            | PrimitiveType::Char32
+            | PrimitiveType::Float
+            | PrimitiveType::Double
+            | PrimitiveType::VaList
+            | PrimitiveType::PtrDiffT => return None,
+        })
+    }
+
     pub fn to_repr_rust(&self) -> &'static str {
         match *self {
             PrimitiveType::Bool => "bool",
@@ -191,7 +216,69 @@ impl PrimitiveType {
         }
     }
 
-    pub fn to_repr_c(&self, config: &Config) -> &'static str {
+    pub fn to_repr_c<'a>(&self, config: &'a Config) -> &'a str {
+        let type_map = &config.export.type_map;
+        if type_map.is_empty() {
+            return self.to_repr_c_builtin(config);
+        }
+        // The keys can be any name of the type, like `u8` or `uint8_t`.
+        let rust = self.to_repr_rust();
+        let mapped = type_map.get(rust).or_else(|| {
+            type_map
+                .iter()
+                .find(|&(name, _)| {
+                    PrimitiveType::maybe(name).map_or(false, |x| x.to_repr_rust() == rust)
+                })
+                .map(|(_, ty)| ty)
+        });
+        match mapped {
+            Some(ty) => ty,
+            None => self.to_repr_c_builtin(config),
+        }
+    }
+
+    /// The GLib typedef of the type, if it has one with the same layout.
+    pub fn to_repr_glib(&self, config: &Config) -> Option<&'static str> {
+        Some(match *self {
+            PrimitiveType::Void | PrimitiveType::Bool | PrimitiveType::VaList => return None,
+            PrimitiveType::Char => "gchar",
+            PrimitiveType::SChar => "gint8",
+            PrimitiveType::UChar => "guint8",
+            PrimitiveType::Char32 => "gunichar",
+            PrimitiveType::Float => "gfloat",
+            PrimitiveType::Double => "gdouble",
+            PrimitiveType::PtrDiffT => "gssize",
+            PrimitiveType::Integer { kind, signed, .. } => match (kind, signed) {
+                (IntKind::Short, true) => "gshort",
+                (IntKind::Short, false) => "gushort",
+                (IntKind::Int, true) => "gint",
+                (IntKind::Int, false) => "guint",
+                (IntKind::Long, true) => "glong",
+                (IntKind::Long, false) => "gulong",
+                (IntKind::LongLong, true) | (IntKind::B64, true) => "gint64",
+                (IntKind::LongLong, false) | (IntKind::B64, false) => "guint64",
+                (IntKind::SizeT, true) => "gssize",
+                (IntKind::SizeT, false) => "gsize",
+                (IntKind::Size, true) if config.usize_is_size_t => "gssize",
+                (IntKind::Size, false) if config.usize_is_size_t => "gsize",
+                (IntKind::Size, true) => "gintptr",
+                (IntKind::Size, false) => "guintptr",
+                (IntKind::B8, true) => "gint8",
+                (IntKind::B8, false) => "guint8",
+                (IntKind::B16, true) => "gint16",
+                (IntKind::B16, false) => "guint16",
+                (IntKind::B32, true) => "gint32",
+                (IntKind::B32, false) => "guint32",
+            },
+        })
+    }
+
+    fn to_repr_c_builtin(&self, config: &Config) -> &'static str {
+        if config.gobject.glib_types(config.language) {
+            if let Some(glib) = self.to_repr_glib(config) {
+                return glib;
+            }
+        }
         match *self {
             PrimitiveType::Void => "void",
             PrimitiveType::Bool => "bool",
@@ -248,7 +335,14 @@ impl PrimitiveType {
                     }
                 }
                 IntKind::Size => {
-                    if config.usize_is_size_t {
+                    // Pointers are 32 bits wide in wasm32.
+                    if config.wasm.enabled {
+                        if signed {
+                            "int32_t"
+                        } else {
+                            "uint32_t"
+                        }
+                    } else if config.usize_is_size_t {
                         if signed {
                             "ptrdiff_t"
                         } else {
@@ -309,27 +403,134 @@ impl PrimitiveType {
 }
 
 // The `U` part of `[T; U]`
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ArrayLength {
     Name(String),
     Value(String),
+    /// An arithmetic operation on two lengths, like `LEN * 2`.
+    BinOp(Box<ArrayLength>, String, Box<ArrayLength>),
 }
 
 impl ArrayLength {
-    pub fn as_str(&self) -> &str {
-        match self {
-            ArrayLength::Name(ref string) | ArrayLength::Value(ref string) => string,
+    fn load(expr: &syn::Expr) -> Result<ArrayLength, String> {
+        Ok(match *expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(ref len),
+                ..
+            }) => ArrayLength::Value(len.base10_digits().to_string()),
+            syn::Expr::Path(ref path) => {
+                let generic_path = GenericPath::load(&path.path)?;
+                ArrayLength::Name(generic_path.export_name().to_owned())
+            }
+            syn::Expr::Paren(ref paren) => ArrayLength::load(&paren.expr)?,
+            syn::Expr::Group(ref group) => ArrayLength::load(&group.expr)?,
+            // The lengths are all `usize`.
+            syn::Expr::Cast(ref cast) => ArrayLength::load(&cast.expr)?,
+            // The const generic arguments, like `[u8; { N }]`.
+            syn::Expr::Block(ref block) if block.block.stmts.len() == 1 => {
+                match block.block.stmts[0] {
+                    syn::Stmt::Expr(ref expr) => ArrayLength::load(expr)?,
+                    _ => return Err(format!("Unsupported array length: {:?}", expr)),
+                }
+            }
+            syn::Expr::Binary(ref bin) => {
+                let op = match bin.op {
+                    syn::BinOp::Add(..) => "+",
+                    syn::BinOp::Sub(..) => "-",
+                    syn::BinOp::Mul(..) => "*",
+                    syn::BinOp::Div(..) => "/",
+                    syn::BinOp::Rem(..) => "%",
+                    syn::BinOp::Shl(..) => "<<",
+                    syn::BinOp::Shr(..) => ">>",
+                    syn::BinOp::BitAnd(..) => "&",
+                    syn::BinOp::BitOr(..) => "|",
+                    syn::BinOp::BitXor(..) => "^",
+                    _ => return Err(format!("Unsupported array length: {:?}", expr)),
+                };
+                ArrayLength::BinOp(
+                    Box::new(ArrayLength::load(&bin.left)?),
+                    op.to_owned(),
+                    Box::new(ArrayLength::load(&bin.right)?),
+                )
+            }
+            _ => return Err(format!("Unsupported array length: {:?}", expr)),
+        })
+    }
+
+    /// The length as an expression, with `name` spelling the names of the
+    /// constants and the generic parameters, and the operands of the
+    /// operations in parentheses.
+    pub fn to_expr(&self, name: &dyn Fn(&str) -> String) -> String {
+        match *self {
+            ArrayLength::Name(ref string) => name(string),
+            ArrayLength::Value(ref string) => string.clone(),
+            ArrayLength::BinOp(ref left, ref op, ref right) => {
+                let operand = |length: &ArrayLength| match *length {
+                    ArrayLength::BinOp(..) => format!("({})", length.to_expr(name)),
+                    _ => length.to_expr(name),
+                };
+                format!("{} {} {}", operand(left), op, operand(right))
+            }
+        }
+    }
+
+    /// Whether the length is a number or a name, rather than an operation.
+    pub fn is_simple(&self) -> bool {
+        match *self {
+            ArrayLength::Name(..) | ArrayLength::Value(..) => true,
+            ArrayLength::BinOp(..) => false,
+        }
+    }
+
+    fn rename_for_config(&mut self, config: &Config, generic_params: &GenericParams) {
+        match *self {
+            ArrayLength::Name(ref mut name) => {
+                if !generic_params.iter().any(|param| param.name() == name) {
+                    config.export.rename_reference(name);
+                }
+            }
+            ArrayLength::Value(..) => {}
+            ArrayLength::BinOp(ref mut left, _, ref mut right) => {
+                left.rename_for_config(config, generic_params);
+                right.rename_for_config(config, generic_params);
+            }
         }
     }
 
-    fn rename_for_config(&mut self, config: &Config) {
-        if let ArrayLength::Name(ref mut name) = self {
-            config.export.rename(name);
+    fn specialize(&self, mappings: &[(&Path, &Type)]) -> ArrayLength {
+        match *self {
+            ArrayLength::Name(ref name) => {
+                for &(param, value) in mappings {
+                    if param.name() != name {
+                        continue;
+                    }
+                    if let Type::Path(ref value) = *value {
+                        return if value.is_const_value() {
+                            ArrayLength::Value(value.name().to_owned())
+                        } else {
+                            ArrayLength::Name(value.export_name().to_owned())
+                        };
+                    }
+                }
+                self.clone()
+            }
+            ArrayLength::Value(..) => self.clone(),
+            ArrayLength::BinOp(ref left, ref op, ref right) => ArrayLength::BinOp(
+                Box::new(left.specialize(mappings)),
+                op.clone(),
+                Box::new(right.specialize(mappings)),
+            ),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+impl fmt::Display for ArrayLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_expr(&|name| name.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Type {
     Ptr {
         ty: Box<Type>,
@@ -347,6 +548,7 @@ pub enum Type {
         ret: Box<Type>,
         args: Vec<(Option<String>, Type)>,
         is_nullable: bool,
+        calling_convention: CallingConvention,
     },
 }
 
@@ -412,9 +614,7 @@ impl Type {
                 }
             }
             syn::Type::Array(syn::TypeArray {
-                ref elem,
-                len: syn::Expr::Path(ref path),
-                ..
+                ref elem, ref len, ..
             }) => {
                 let converted = Type::load(elem)?;
 
@@ -422,29 +622,8 @@ impl Type {
                     Some(converted) => converted,
                     None => return Err("Cannot have an array of zero sized types.".to_owned()),
                 };
-                let generic_path = GenericPath::load(&path.path)?;
-                let len = ArrayLength::Name(generic_path.export_name().to_owned());
-                Type::Array(Box::new(converted), len)
-            }
-            syn::Type::Array(syn::TypeArray {
-                ref elem,
-                len:
-                    syn::Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Int(ref len),
-                        ..
-                    }),
-                ..
-            }) => {
-                let converted = Type::load(elem)?;
 
-                let converted = match converted {
-                    Some(converted) => converted,
-                    None => return Err("Cannot have an array of zero sized types.".to_owned()),
-                };
-
-                let len = ArrayLength::Value(len.base10_digits().to_string());
-                // panic!("panic -> value: {:?}", len);
-                Type::Array(Box::new(converted), len)
+                Type::Array(Box::new(converted), ArrayLength::load(len)?)
             }
             syn::Type::BareFn(ref function) => {
                 let mut wildcard_counter = 0;
@@ -484,6 +663,10 @@ impl Type {
                     ret: Box::new(ret),
                     args,
                     is_nullable: false,
+                    calling_convention: function
+                        .abi
+                        .calling_convention()
+                        .unwrap_or(CallingConvention::C),
                 }
             }
             syn::Type::Tuple(ref tuple) => {
@@ -509,6 +692,17 @@ impl Type {
         }
     }
 
+    /// Whether the type is an integer which is never zero, from a
+    /// `NonZero` type.
+    pub fn is_nonzero(&self) -> bool {
+        match *self {
+            Type::Primitive(PrimitiveType::Integer {
+                zeroable: false, ..
+            }) => true,
+            _ => false,
+        }
+    }
+
     pub fn make_zeroable(&self) -> Option<Self> {
         let (kind, signed) = match *self {
             Type::Primitive(PrimitiveType::Integer {
@@ -543,10 +737,12 @@ impl Type {
                 ref ret,
                 ref args,
                 is_nullable: false,
+                calling_convention,
             } => Some(Type::FuncPtr {
                 ret: ret.clone(),
                 args: args.clone(),
                 is_nullable: true,
+                calling_convention,
             }),
             _ => None,
         }
@@ -572,12 +768,12 @@ impl Type {
             "NonZeroU16" => (IntKind::B16, false),
             "NonZeroU32" => (IntKind::B32, false),
             "NonZeroU64" => (IntKind::B64, false),
-            "NonZeroUSize" => (IntKind::Size, false),
+            "NonZeroUsize" => (IntKind::Size, false),
             "NonZeroI8" => (IntKind::B8, true),
             "NonZeroI16" => (IntKind::B16, true),
             "NonZeroI32" => (IntKind::B32, true),
             "NonZeroI64" => (IntKind::B64, true),
-            "NonZeroISize" => (IntKind::Size, true),
+            "NonZeroIsize" => (IntKind::Size, true),
             _ => return None,
         };
 
@@ -629,8 +825,17 @@ impl Type {
                 is_nullable: false,
                 is_ref: false,
             }),
-            "Cell" => Some(generic.into_owned()),
-            "ManuallyDrop" | "MaybeUninit" if config.language != Language::Cxx => {
+            "NonZero" => match *generic {
+                Type::Primitive(PrimitiveType::Integer { kind, signed, .. }) => {
+                    Some(Type::Primitive(PrimitiveType::Integer {
+                        zeroable: false,
+                        signed,
+                        kind,
+                    }))
+                }
+                _ => None,
+            },
+            name if config.export.is_transparent_wrapper(name, config.language) => {
                 Some(generic.into_owned())
             }
             _ => None,
@@ -673,6 +878,25 @@ impl Type {
         }
     }
 
+    /// Names the arguments of a function pointer, or of an optional one,
+    /// returning whether this is one.
+    pub fn set_func_ptr_arg_names(&mut self, names: &[String]) -> bool {
+        match *self {
+            Type::FuncPtr { ref mut args, .. } => {
+                for (arg, name) in args.iter_mut().zip(names) {
+                    arg.0 = Some(name.clone());
+                }
+                true
+            }
+            Type::Path(ref mut generic)
+                if generic.name() == "Option" && generic.generics().len() == 1 =>
+            {
+                generic.generics_mut()[0].set_func_ptr_arg_names(names)
+            }
+            _ => false,
+        }
+    }
+
     pub fn get_root_path(&self) -> Option<Path> {
         let mut current = self;
         loop {
@@ -694,6 +918,29 @@ impl Type {
         }
     }
 
+    /// Calls `f` with the path of every type `self` uses, through pointers,
+    /// arrays, function pointers and generic arguments too.
+    pub fn visit_paths<F: FnMut(&Path)>(&self, f: &mut F) {
+        match *self {
+            Type::Ptr { ref ty, .. } | Type::Array(ref ty, _) => ty.visit_paths(f),
+            Type::Path(ref generic) => {
+                f(generic.path());
+                for generic_value in generic.generics() {
+                    generic_value.visit_paths(f);
+                }
+            }
+            Type::Primitive(..) => {}
+            Type::FuncPtr {
+                ref ret, ref args, ..
+            } => {
+                ret.visit_paths(f);
+                for (_, arg) in args {
+                    arg.visit_paths(f);
+                }
+            }
+        }
+    }
+
     pub fn specialize(&self, mappings: &[(&Path, &Type)]) -> Type {
         match *self {
             Type::Ptr {
@@ -725,13 +972,15 @@ impl Type {
                 Type::Path(specialized)
             }
             Type::Primitive(ref primitive) => Type::Primitive(primitive.clone()),
-            Type::Array(ref ty, ref constant) => {
-                Type::Array(Box::new(ty.specialize(mappings)), constant.clone())
-            }
+            Type::Array(ref ty, ref constant) => Type::Array(
+                Box::new(ty.specialize(mappings)),
+                constant.specialize(mappings),
+            ),
             Type::FuncPtr {
                 ref ret,
                 ref args,
                 is_nullable,
+                calling_convention,
             } => Type::FuncPtr {
                 ret: Box::new(ret.specialize(mappings)),
                 args: args
@@ -740,6 +989,7 @@ impl Type {
                     .map(|(name, ty)| (name, ty.specialize(mappings)))
                     .collect(),
                 is_nullable,
+                calling_convention,
             },
         }
     }
@@ -759,24 +1009,33 @@ impl Type {
                     generic_value.add_dependencies_ignoring_generics(generic_params, library, out);
                 }
                 let path = generic.path();
-                if !generic_params.contains(path) {
+                if !generic_params.contains(path) && !generic.is_const_value() {
                     if let Some(items) = library.get_items(path) {
                         if !out.items.contains(path) {
                             out.items.insert(path.clone());
 
                             for item in &items {
+                                let location = item.deref().annotations().location.clone();
+                                let location = mem::replace(&mut out.location, location);
                                 item.deref().add_dependencies(library, out);
+                                out.location = location;
                             }
                             for item in items {
                                 out.order.push(item);
                             }
                         }
-                    } else {
-                        warn!(
-                            "Can't find {}. This usually means that this type was incompatible or \
-                             not found.",
-                            path
-                        );
+                    } else if library
+                        .get_config()
+                        .export
+                        .extern_types
+                        .contains_key(path.name())
+                    {
+                        out.extern_types.insert(path.name().to_owned());
+                    } else if library.declares_gobject(path) {
+                        // The struct is declared with the GObject type.
+                    } else if !out.unresolved.contains_key(path) {
+                        let location = out.location.clone();
+                        out.unresolved.insert(path.clone(), location);
                     }
                 }
             }
@@ -842,7 +1101,7 @@ impl Type {
             Type::Primitive(_) => {}
             Type::Array(ref mut ty, ref mut len) => {
                 ty.rename_for_config(config, generic_params);
-                len.rename_for_config(config);
+                len.rename_for_config(config, generic_params);
             }
             Type::FuncPtr {
                 ref mut ret,
@@ -850,7 +1109,10 @@ impl Type {
                 ..
             } => {
                 ret.rename_for_config(config, generic_params);
-                for (_, arg) in args {
+                for &mut (ref mut name, ref mut arg) in args {
+                    if let Some(ref mut name) = *name {
+                        reserved::escape_arg(name, config.language);
+                    }
                     arg.rename_for_config(config, generic_params);
                 }
             }
@@ -18,7 +18,7 @@ use crate::bindgen::monomorph::Monomorphs;
 use crate::bindgen::writer::{Source, SourceWriter};
 
 /// A type alias that is represented as a C typedef
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Typedef {
     pub path: Path,
     pub export_name: String,
@@ -31,14 +31,24 @@ pub struct Typedef {
 
 impl Typedef {
     pub fn load(item: &syn::ItemType, mod_cfg: Option<&Cfg>) -> Result<Typedef, String> {
-        if let Some(x) = Type::load(&item.ty)? {
+        if let Some(mut x) = Type::load(&item.ty)? {
             let path = Path::new(item.ident.to_string());
+            let annotations = AnnotationSet::load(&item.attrs)?;
+            if let Some(names) = annotations.list("arg-names") {
+                if !x.set_func_ptr_arg_names(&names) {
+                    warn_at!(
+                        item,
+                        "Ignoring the arg-names of {}, which isn't a function pointer.",
+                        path
+                    );
+                }
+            }
             Ok(Typedef::new(
                 path,
                 GenericParams::new(&item.generics),
                 x,
                 Cfg::append(mod_cfg, Cfg::load(&item.attrs)),
-                AnnotationSet::load(&item.attrs)?,
+                annotations,
                 Documentation::load(&item.attrs),
             ))
         } else {
@@ -77,15 +87,25 @@ impl Typedef {
 
         if let Some(alias_path) = self.aliased.get_root_path() {
             if out.contains_key(&alias_path) {
-                warn!(
+                warn_in!(
+                    self.annotations.location,
                     "Multiple typedef's with annotations for {}. Ignoring annotations from {}.",
-                    alias_path, self.path
+                    alias_path,
+                    self.path
                 );
                 return;
             }
 
+            // The typedef stays deprecated and keeps its attributes, not the
+            // aliased type.
+            let deprecated = self.annotations.deprecated.take();
+            let attributes = self.annotations.remove("attributes");
             out.insert(alias_path, self.annotations.clone());
             self.annotations = AnnotationSet::new();
+            self.annotations.deprecated = deprecated;
+            if let Some(attributes) = attributes {
+                self.annotations.set("attributes", attributes);
+            }
         }
     }
 
@@ -209,14 +229,26 @@ impl Source for Typedef {
 
         match config.language {
             Language::Cxx => {
-                write!(out, "using {} = ", self.export_name());
+                write!(out, "using {}", self.export_name());
+                if let Some(attributes) = self.annotations.attributes(config) {
+                    write!(out, " {}", attributes);
+                }
+                out.write(" = ");
                 self.aliased.write(config, out);
             }
             Language::C | Language::Cython => {
                 write!(out, "{} ", config.language.typedef());
                 Field::from_name_and_type(self.export_name().to_owned(), self.aliased.clone())
                     .write(config, out);
+                if let Some(attributes) = self.annotations.attributes(config) {
+                    write!(out, " {}", attributes);
+                }
             }
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
 
         out.write(";");
@@ -18,7 +18,7 @@ use crate::bindgen::rename::{IdentifierType, RenameRule};
 use crate::bindgen::utilities::IterHelpers;
 use crate::bindgen::writer::{ListType, Source, SourceWriter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Union {
     pub path: Path,
     pub export_name: String,
@@ -282,10 +282,21 @@ impl Source for Union {
         let condition = self.cfg.to_condition(config);
         condition.write_before(config, out);
 
+        let pragma_pack = self.alignment == Some(ReprAlign::Packed)
+            && config.layout.packed_pragma()
+            && config.language != Language::Cython;
+        if pragma_pack {
+            out.write("#pragma pack(push, 1)");
+            out.new_line();
+        }
+
         self.documentation.write(config, out);
 
         self.generic_params.write(config, out);
 
+        // A forward declared union is defined with its tag, after its typedef.
+        let forward_declared = out.bindings().is_forward_declared(&self.path);
+
         // The following results in
         // C++ or C with Tag as style:
         //   union Name {
@@ -294,9 +305,16 @@ impl Source for Union {
         // C with Both as style:
         //   typedef union Name {
         match config.language {
-            Language::C if config.style.generate_typedef() => out.write("typedef "),
+            Language::C if config.style.generate_typedef() && !forward_declared => {
+                out.write("typedef ")
+            }
             Language::C | Language::Cxx => {}
             Language::Cython => out.write(config.style.cython_def()),
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
 
         out.write("union");
@@ -306,20 +324,27 @@ impl Source for Union {
             if let Some(align) = self.alignment {
                 match align {
                     ReprAlign::Packed => {
-                        if let Some(ref anno) = config.layout.packed {
+                        if let Some(anno) = config.layout.packed_annotation() {
                             write!(out, " {}", anno);
                         }
                     }
                     ReprAlign::Align(n) => {
-                        if let Some(ref anno) = config.layout.aligned_n {
-                            write!(out, " {}({})", anno, n);
+                        if let Some(anno) = config.layout.aligned_annotation(config.language, n) {
+                            write!(out, " {}", anno);
                         }
                     }
                 }
             }
         }
 
-        if config.language != Language::C || config.style.generate_tag() {
+        if config.language == Language::Cxx || !config.style.generate_typedef() || forward_declared
+        {
+            if let Some(attributes) = self.annotations.attributes(config) {
+                write!(out, " {}", attributes);
+            }
+        }
+
+        if config.language != Language::C || config.style.generate_tag() || forward_declared {
             write!(out, " {}", self.export_name);
         }
 
@@ -342,13 +367,22 @@ impl Source for Union {
             out.write_raw_block(body);
         }
 
-        if config.language == Language::C && config.style.generate_typedef() {
+        if config.language == Language::C && config.style.generate_typedef() && !forward_declared {
             out.close_brace(false);
-            write!(out, " {};", self.export_name);
+            write!(out, " {}", self.export_name);
+            if let Some(attributes) = self.annotations.attributes(config) {
+                write!(out, " {}", attributes);
+            }
+            out.write(";");
         } else {
             out.close_brace(true);
         }
 
+        if pragma_pack {
+            out.new_line();
+            out.write("#pragma pack(pop)");
+        }
+
         condition.write_after(config, out);
     }
 }
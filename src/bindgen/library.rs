@@ -2,16 +2,30 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::mem;
+
+use heck::SnakeCase;
 
 use crate::bindgen::bindings::Bindings;
-use crate::bindgen::config::{Config, Language, SortKey};
+use crate::bindgen::config::{
+    AnonymousMembers, Config, ExternTypeConfig, Language, SortKey, Unresolved, PRIVATE_HEADER,
+};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
 use crate::bindgen::error::Error;
-use crate::bindgen::ir::{Constant, Enum, Function, GObject, GType, Item, ItemContainer, ItemMap};
-use crate::bindgen::ir::{OpaqueItem, Path, Static, Struct, Typedef, Union};
+use crate::bindgen::ir::{
+    dispatch_argument, AnnotationSet, CallingConvention, Cfg, Constant, Documentation, Enum, Field,
+    Function, FunctionArgument, GObject, GType, GenericParams, GenericPath, Item, ItemContainer,
+    ItemMap, PrimitiveType, Transfer, Type,
+};
+use crate::bindgen::ir::{primitive_constant, OpaqueItem, Path, Static, Struct, Typedef, Union};
+use crate::bindgen::mangle;
 use crate::bindgen::monomorph::Monomorphs;
+use crate::bindgen::parser::ShadowedItem;
+use crate::bindgen::reserved;
+use crate::bindgen::targets::TargetCfgs;
+use crate::bindgen::utilities::closest_match;
 use crate::bindgen::ItemType;
 
 #[derive(Debug, Clone)]
@@ -26,6 +40,10 @@ pub struct Library {
     typedefs: ItemMap<Typedef>,
     functions: Vec<Function>,
     gobjects: ItemMap<GObject>,
+    /// The items of other modules that items with the same name shadowed.
+    shadowed: Vec<ShadowedItem>,
+    /// The exported items which couldn't be loaded.
+    skipped: Vec<String>,
 }
 
 impl Library {
@@ -41,6 +59,8 @@ impl Library {
         typedefs: ItemMap<Typedef>,
         functions: Vec<Function>,
         gobjects: ItemMap<GObject>,
+        shadowed: Vec<ShadowedItem>,
+        skipped: Vec<String>,
     ) -> Library {
         Library {
             config,
@@ -53,16 +73,28 @@ impl Library {
             typedefs,
             functions,
             gobjects,
+            shadowed,
+            skipped,
         }
     }
 
     pub fn generate(mut self) -> Result<Bindings, Error> {
+        self.mangle_identifiers()?;
+        self.fold_constants();
+        self.remove_string_constants();
         self.transfer_annotations();
+        self.must_use_return_types();
+        self.strong_transparent_structs();
         self.simplify_standard_types();
+        self.add_fn_renames();
         self.gobject_config();
+        self.integer_handles();
+        self.valist_functions();
 
         match self.config.function.sort_by.unwrap_or(self.config.sort_by) {
-            SortKey::Name => self.functions.sort_by(|x, y| x.path.cmp(&y.path)),
+            SortKey::Name | SortKey::Topological => {
+                self.functions.sort_by(|x, y| x.path.cmp(&y.path))
+            }
             SortKey::None => { /* keep input order */ }
         }
 
@@ -70,24 +102,42 @@ impl Library {
             self.instantiate_monomorphs();
         }
         self.remove_excluded();
+        self.check_throwing_functions();
+        self.vtables();
+        let inline_members = self.anonymous_members();
         if self.config.language == Language::C {
             self.resolve_declaration_types();
         }
 
+        // Again, for the monomorphs.
+        self.add_fn_renames();
+        self.item_namespaces();
         self.rename_items();
+        self.gasync_functions();
+        self.document_nonzero();
+        if self.config.language == Language::C {
+            self.generic_groups();
+        }
 
         let mut dependencies = Dependencies::new();
 
         for function in &self.functions {
+            dependencies.location = function.annotations.location.clone();
             function.add_dependencies(&self, &mut dependencies);
         }
         self.globals.for_all_items(|global| {
+            dependencies.location = global.annotations.location.clone();
             global.add_dependencies(&self, &mut dependencies);
         });
-        self.constants.for_all_items(|constant| {
-            constant.add_dependencies(&self, &mut dependencies);
-        });
+        let prune_unused = self.config.export.prune_unused;
+        if !prune_unused {
+            self.constants.for_all_items(|constant| {
+                dependencies.location = constant.annotations.location.clone();
+                constant.add_dependencies(&self, &mut dependencies);
+            });
+        }
         self.gobjects.for_all_items(|gobject| {
+            dependencies.location = gobject.annotations().location.clone();
             gobject.add_dependencies(&self, &mut dependencies);
         });
         for name in &self.config.export.include {
@@ -95,6 +145,7 @@ impl Library {
             if let Some(items) = self.get_items(&path) {
                 if dependencies.items.insert(path) {
                     for item in &items {
+                        dependencies.location = item.deref().annotations().location.clone();
                         item.deref().add_dependencies(&self, &mut dependencies);
                     }
                     for item in items {
@@ -103,14 +154,38 @@ impl Library {
                 }
             }
         }
+        self.check_unresolved(&dependencies)?;
+        if prune_unused {
+            self.prune_constants(&dependencies);
+        }
 
+        if self.config.sort_by == SortKey::Topological {
+            dependencies.sort_topologically(&self);
+        }
         dependencies.sort();
 
+        let extern_types = mem::take(&mut dependencies.extern_types);
         let items = dependencies.order;
+
+        // The fieldless enums written get a function returning their
+        // `GType`, which is declared by GLib too.
+        let has_gtype = |item: &ItemContainer| match *item {
+            ItemContainer::Enum(ref x) => x.has_gtype(),
+            _ => false,
+        };
+        let glib_object = "glib-object.h".to_owned();
+        if self.config.gobject.enabled
+            && !self.config.sys_includes.contains(&glib_object)
+            && items.iter().any(has_gtype)
+        {
+            self.config.sys_includes.push(glib_object);
+        }
         let constants = if self.config.export.should_generate(ItemType::Constants) {
             let mut constants = self.constants.to_vec();
             match self.config.constant.sort_by.unwrap_or(self.config.sort_by) {
-                SortKey::Name => constants.sort_by(|x, y| x.path.cmp(&y.path)),
+                SortKey::Name | SortKey::Topological => {
+                    constants.sort_by(|x, y| x.path.cmp(&y.path))
+                }
                 SortKey::None => { /* keep input order */ }
             }
             constants
@@ -121,7 +196,7 @@ impl Library {
         let globals = if self.config.export.should_generate(ItemType::Globals) {
             let mut globals = self.globals.to_vec();
             match self.config.constant.sort_by.unwrap_or(self.config.sort_by) {
-                SortKey::Name => globals.sort_by(|x, y| x.path.cmp(&y.path)),
+                SortKey::Name | SortKey::Topological => globals.sort_by(|x, y| x.path.cmp(&y.path)),
                 SortKey::None => { /* keep input order */ }
             }
             globals
@@ -129,12 +204,29 @@ impl Library {
             vec![]
         };
         let functions = if self.config.export.should_generate(ItemType::Functions) {
-            self.functions
+            mem::take(&mut self.functions)
         } else {
             vec![]
         };
 
-        Ok(Bindings::new(
+        self.check_name_collisions(&constants, &globals, &items, &functions)?;
+
+        let is_private = |annotations: &AnnotationSet| {
+            annotations.atom("header") == Some(Some(PRIVATE_HEADER.to_owned()))
+        };
+        let mut has_private_items = constants.iter().any(|x| is_private(&x.annotations))
+            || globals.iter().any(|x| is_private(&x.annotations))
+            || items.iter().any(|x| is_private(x.deref().annotations()))
+            || functions.iter().any(|x| is_private(&x.annotations));
+        self.gobjects
+            .for_all_items(|x| has_private_items |= is_private(x.annotations()));
+        let split_includes = if self.config.output.split.enabled || has_private_items {
+            self.split_includes(&constants, &globals, &items, &functions)
+        } else {
+            BTreeMap::new()
+        };
+
+        let mut bindings = Bindings::new(
             self.config,
             self.structs,
             constants,
@@ -142,7 +234,220 @@ impl Library {
             items,
             functions,
             self.gobjects.to_vec(),
-        ))
+        );
+        bindings.split_includes = split_includes;
+        bindings.inline_members = inline_members;
+        bindings.extern_types = extern_types;
+        Ok(bindings)
+    }
+
+    /// Reports the types used by the written items which couldn't be
+    /// resolved, failing with `parse.unresolved = "error"`, which also fails
+    /// when exported items couldn't be loaded.
+    fn check_unresolved(&self, dependencies: &Dependencies) -> Result<(), Error> {
+        let unresolved = self.config.parse.unresolved;
+        if unresolved == Unresolved::Ignore {
+            return Ok(());
+        }
+        for (path, location) in &dependencies.unresolved {
+            let message = format!(
+                "Can't find {}. This usually means that this type was incompatible or not found.",
+                path
+            );
+            if unresolved == Unresolved::Error {
+                error_in!(location, "{}", message);
+            } else {
+                warn_in!(location, "{}", message);
+            }
+        }
+        if unresolved == Unresolved::Error && !dependencies.unresolved.is_empty() {
+            let names = dependencies.unresolved.keys().map(|x| x.to_string());
+            return Err(Error::UnresolvedTypes(names.collect()));
+        }
+        // The exported items which couldn't be loaded were reported when
+        // parsing, and are missing from the header like unresolved types.
+        if unresolved == Unresolved::Error && !self.skipped.is_empty() {
+            return Err(Error::SkippedItems(self.skipped.clone()));
+        }
+        Ok(())
+    }
+
+    /// Fails when distinct items are written with the same name, after their
+    /// renaming, which wouldn't compile. In C, the tags of the structs,
+    /// unions and enums written without a typedef don't clash with the other
+    /// names. The items of other modules with the same name and definition
+    /// only warn, since writing either is the same.
+    fn check_name_collisions(
+        &self,
+        constants: &[Constant],
+        globals: &[Static],
+        items: &[ItemContainer],
+        functions: &[Function],
+    ) -> Result<(), Error> {
+        // The Rust path of an item, and where it's declared.
+        fn describe(path: &Path, annotations: &AnnotationSet) -> String {
+            let path = match annotations.rust_path {
+                Some(ref rust_path) => rust_path.clone(),
+                None => path.to_string(),
+            };
+            match annotations.location {
+                Some(ref location) => format!("{} ({})", path, location),
+                None => path,
+            }
+        }
+
+        let tags = self.config.language == Language::C && !self.config.style.generate_typedef();
+        let mut declared: Vec<(bool, &str, &Path, &AnnotationSet)> = Vec::new();
+        for constant in constants {
+            let name = constant.export_name();
+            declared.push((false, name, &constant.path, &constant.annotations));
+        }
+        for global in globals {
+            declared.push((
+                false,
+                global.export_name(),
+                &global.path,
+                &global.annotations,
+            ));
+        }
+        for item in items {
+            let tag = tags
+                && match *item {
+                    ItemContainer::Struct(..)
+                    | ItemContainer::Union(..)
+                    | ItemContainer::Enum(..)
+                    | ItemContainer::OpaqueItem(..) => true,
+                    _ => false,
+                };
+            let item = item.deref();
+            declared.push((tag, item.export_name(), item.path(), item.annotations()));
+        }
+        for function in functions {
+            let name = function.path.name();
+            declared.push((false, name, &function.path, &function.annotations));
+        }
+
+        let mut names: BTreeMap<(bool, &str), Vec<(&Path, &AnnotationSet)>> = BTreeMap::new();
+        for &(tag, name, path, annotations) in &declared {
+            let entry = names.entry((tag, name)).or_default();
+            // The items declared for several cfgs share their path.
+            if !entry.iter().any(|&(x, _)| x == path) {
+                entry.push((path, annotations));
+            }
+        }
+        // The shadowed items would be written with the name of the item that
+        // shadowed them, which is only a problem if they're defined
+        // differently.
+        for shadowed in &self.shadowed {
+            let (tag, name, path, annotations) =
+                match declared.iter().find(|x| x.2 == &shadowed.path) {
+                    Some(&declared) => declared,
+                    None => continue,
+                };
+            if shadowed.differs {
+                names
+                    .entry((tag, name))
+                    .or_default()
+                    .push((&shadowed.path, &shadowed.annotations));
+            } else {
+                warn_in!(
+                    shadowed.annotations.location.clone(),
+                    "{} has the same definition as {}, and is written as {} with it.",
+                    shadowed.annotations.rust_path.as_deref().unwrap_or(name),
+                    describe(path, annotations),
+                    name
+                );
+            }
+        }
+
+        let mut collisions = Vec::new();
+        for ((_, name), items) in names {
+            if items.len() < 2 {
+                continue;
+            }
+            let items: Vec<String> = items
+                .iter()
+                .map(|&(path, annotations)| describe(path, annotations))
+                .collect();
+            error!(
+                "Several items are written as {}: {}.",
+                name,
+                items.join(", ")
+            );
+            collisions.push(name.to_owned());
+        }
+        if !collisions.is_empty() {
+            return Err(Error::NameCollisions(collisions));
+        }
+        Ok(())
+    }
+
+    /// Removes the constants using types which aren't otherwise written.
+    fn prune_constants(&mut self, dependencies: &Dependencies) {
+        let mut pruned = Vec::new();
+        self.constants.for_all_items(|constant| {
+            let mut used = Dependencies::new();
+            constant.add_dependencies(self, &mut used);
+            if !used.items.is_subset(&dependencies.items) {
+                info!(
+                    "Leaving out {}, whose type isn't used.",
+                    constant.path.name()
+                );
+                pruned.push(constant.path.clone());
+            }
+        });
+        self.constants.filter(|x| pruned.contains(&x.path));
+    }
+
+    /// Lists the other headers the items of each header use, when writing the
+    /// bindings to several.
+    fn split_includes(
+        &self,
+        constants: &[Constant],
+        globals: &[Static],
+        items: &[ItemContainer],
+        functions: &[Function],
+    ) -> BTreeMap<String, BTreeSet<String>> {
+        let config = &self.config;
+        let mut includes: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut add = |annotations: &AnnotationSet, dependencies: Dependencies| {
+            let group = config.header_group(annotations);
+            let group_includes = includes.entry(group.clone()).or_default();
+            for dependency in dependencies.order {
+                let dependency_group = config.header_group(dependency.deref().annotations());
+                if dependency_group != group {
+                    group_includes.insert(dependency_group);
+                }
+            }
+        };
+
+        for item in items {
+            let mut dependencies = Dependencies::new();
+            item.deref().add_dependencies(self, &mut dependencies);
+            add(item.deref().annotations(), dependencies);
+        }
+        for function in functions {
+            let mut dependencies = Dependencies::new();
+            function.add_dependencies(self, &mut dependencies);
+            add(&function.annotations, dependencies);
+        }
+        for global in globals {
+            let mut dependencies = Dependencies::new();
+            global.add_dependencies(self, &mut dependencies);
+            add(&global.annotations, dependencies);
+        }
+        for constant in constants {
+            let mut dependencies = Dependencies::new();
+            constant.add_dependencies(self, &mut dependencies);
+            add(&constant.annotations, dependencies);
+        }
+        self.gobjects.for_all_items(|gobject| {
+            let mut dependencies = Dependencies::new();
+            gobject.add_dependencies(self, &mut dependencies);
+            add(gobject.annotations(), dependencies);
+        });
+
+        includes
     }
 
     pub fn get_items(&self, p: &Path) -> Option<Vec<ItemContainer>> {
@@ -165,6 +470,16 @@ impl Library {
         None
     }
 
+    /// Whether `p` is a GObject type whose struct is declared along with it.
+    pub fn declares_gobject(&self, p: &Path) -> bool {
+        self.gobjects.get_items(p).map_or(false, |items| {
+            items.iter().any(|x| match *x {
+                ItemContainer::GObject(ref x) => x.declares_struct(),
+                _ => false,
+            })
+        })
+    }
+
     pub fn get_config(&self) -> &Config {
         &self.config
     }
@@ -190,6 +505,88 @@ impl Library {
             .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
     }
 
+    /// The entries of the `[export]` section which don't name any parsed
+    /// item, with the closest item name as a suggestion when there is one.
+    pub(crate) fn config_issues(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .functions
+            .iter()
+            .map(|x| x.path().name().to_owned())
+            .collect();
+        self.constants
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+        self.globals
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+        self.enums
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+        self.structs
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+        self.unions
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+        self.opaque_items
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+        self.typedefs
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+        self.gobjects
+            .for_all_items(|x| names.push(x.path().name().to_owned()));
+
+        let export = &self.config.export;
+        let entries = export
+            .include
+            .iter()
+            .map(|x| ("include", x))
+            .chain(export.exclude.iter().map(|x| ("exclude", x)))
+            .chain(export.rename.keys().map(|x| ("rename", x)))
+            .chain(export.pre_body.keys().map(|x| ("pre_body", x)))
+            .chain(export.body.keys().map(|x| ("body", x)));
+
+        let mut issues = Vec::new();
+        for (key, entry) in entries {
+            // Monomorphs of generic items are named after them.
+            let matches = names.iter().any(|name| {
+                entry == name || (entry.starts_with(name) && entry[name.len()..].starts_with('_'))
+            });
+            if matches {
+                continue;
+            }
+            let mut issue = format!("export.{} entry `{}` doesn't match any item.", key, entry);
+            if let Some(name) = closest_match(entry, names.iter().map(|x| x.as_str())) {
+                issue.push_str(&format!(" Did you mean `{}`?", name));
+            }
+            issues.push(issue);
+        }
+        issues
+    }
+
+    /// Whether `ty` is a `#[must_use]` type, or an alias of one.
+    fn is_must_use(&self, ty: &Type) -> bool {
+        let path = match *ty {
+            Type::Path(ref generic) => generic.path(),
+            _ => return false,
+        };
+        self.get_items(path).map_or(false, |items| {
+            items.iter().any(|item| match *item {
+                ItemContainer::Typedef(ref x) => {
+                    x.annotations.must_use || self.is_must_use(&x.aliased)
+                }
+                _ => item.deref().annotations().must_use,
+            })
+        })
+    }
+
+    /// Marks the functions returning a `#[must_use]` type as must use too,
+    /// since Rust warns when their result is ignored.
+    fn must_use_return_types(&mut self) {
+        let must_use: Vec<bool> = self
+            .functions
+            .iter()
+            .map(|function| self.is_must_use(&function.ret))
+            .collect();
+        for (function, must_use) in self.functions.iter_mut().zip(must_use) {
+            function.annotations.must_use |= must_use;
+        }
+    }
+
     fn transfer_annotations(&mut self) {
         let mut annotations = HashMap::new();
 
@@ -206,7 +603,8 @@ impl Library {
                     *x.annotations_mut() = annotations.clone();
                     transferred = true;
                 } else {
-                    warn!(
+                    warn_in!(
+                        annotations.location,
                         "Can't transfer annotations from typedef to alias ({}) \
                          that already has annotations.",
                         alias_path
@@ -221,7 +619,8 @@ impl Library {
                     *x.annotations_mut() = annotations.clone();
                     transferred = true;
                 } else {
-                    warn!(
+                    warn_in!(
+                        annotations.location,
                         "Can't transfer annotations from typedef to alias ({}) \
                          that already has annotations.",
                         alias_path
@@ -236,7 +635,8 @@ impl Library {
                     *x.annotations_mut() = annotations.clone();
                     transferred = true;
                 } else {
-                    warn!(
+                    warn_in!(
+                        annotations.location,
                         "Can't transfer annotations from typedef to alias ({}) \
                          that already has annotations.",
                         alias_path
@@ -251,7 +651,8 @@ impl Library {
                     *x.annotations_mut() = annotations.clone();
                     transferred = true;
                 } else {
-                    warn!(
+                    warn_in!(
+                        annotations.location,
                         "Can't transfer annotations from typedef to alias ({}) \
                          that already has annotations.",
                         alias_path
@@ -266,7 +667,8 @@ impl Library {
                     *x.annotations_mut() = annotations.clone();
                     transferred = true;
                 } else {
-                    warn!(
+                    warn_in!(
+                        annotations.location,
                         "Can't transfer annotations from typedef to alias ({}) \
                          that already has annotations.",
                         alias_path
@@ -279,6 +681,64 @@ impl Library {
         }
     }
 
+    /// Records the names `export.rename_fn` gives to the items, so that the
+    /// references to them are renamed the same way.
+    fn add_fn_renames(&mut self) {
+        if self.config.export.rename_fn.is_none() {
+            return;
+        }
+
+        let mut names = Vec::new();
+        self.constants.for_all_items(|x| {
+            if x.associated_to.is_none() {
+                names.push((ItemType::Constants, x.path().name().to_owned()));
+            }
+        });
+        self.structs
+            .for_all_items(|x| names.push((ItemType::Structs, x.path().name().to_owned())));
+        self.unions
+            .for_all_items(|x| names.push((ItemType::Unions, x.path().name().to_owned())));
+        self.enums
+            .for_all_items(|x| names.push((ItemType::Enums, x.path().name().to_owned())));
+        self.opaque_items
+            .for_all_items(|x| names.push((ItemType::OpaqueItems, x.path().name().to_owned())));
+        self.typedefs
+            .for_all_items(|x| names.push((ItemType::Typedefs, x.path().name().to_owned())));
+
+        for (item_type, name) in names {
+            self.config.export.add_fn_rename(item_type, &name);
+        }
+    }
+
+    /// Records the namespaces of the items declared in nested namespaces, so
+    /// that the references to them are qualified with them.
+    fn item_namespaces(&mut self) {
+        if self.config.language != Language::Cxx {
+            return;
+        }
+        let config = &self.config;
+        let mut namespaces = HashMap::new();
+        let mut add = |path: &Path, annotations: &AnnotationSet| {
+            let namespace = config.item_namespaces(annotations);
+            if !namespace.is_empty() {
+                namespaces.insert(path.name().to_owned(), namespace.join("::"));
+            }
+        };
+        self.constants.for_all_items(|x| {
+            if x.associated_to.is_none() {
+                add(&x.path, &x.annotations);
+            }
+        });
+        self.structs.for_all_items(|x| add(&x.path, &x.annotations));
+        self.unions.for_all_items(|x| add(&x.path, &x.annotations));
+        self.enums.for_all_items(|x| add(&x.path, &x.annotations));
+        self.opaque_items
+            .for_all_items(|x| add(&x.path, &x.annotations));
+        self.typedefs
+            .for_all_items(|x| add(&x.path, &x.annotations));
+        self.config.export.namespaces = namespaces;
+    }
+
     fn rename_items(&mut self) {
         let config = &self.config;
 
@@ -310,11 +770,82 @@ impl Library {
             .for_all_items_mut(|x| x.rename_for_config(config));
         self.typedefs.rebuild();
 
+        self.gobjects
+            .for_all_items_mut(|x| x.rename_for_config(config));
+
         for item in &mut self.functions {
             item.rename_for_config(&self.config);
         }
     }
 
+    /// Replaces the `cbindgen:gasync` functions with the `name_async` and
+    /// `name_finish` functions their runtime glue exports. They are built
+    /// after renaming, so the GIO types keep their names, and are declared
+    /// by `<gio/gio.h>`.
+    fn gasync_functions(&mut self) {
+        if !self.functions.iter().any(|f| f.is_gasync()) {
+            return;
+        }
+        let mut functions = Vec::with_capacity(self.functions.len() + 1);
+        for function in self.functions.drain(..) {
+            if function.is_gasync() {
+                let (start, finish) = function.gasync_pair();
+                functions.push(start);
+                functions.push(finish);
+            } else {
+                functions.push(function);
+            }
+        }
+        self.functions = functions;
+        for name in &["GAsyncReadyCallback", "GAsyncResult", "GCancellable"] {
+            self.config
+                .export
+                .extern_types
+                .entry((*name).to_owned())
+                .or_insert_with(|| ExternTypeConfig {
+                    sys_include: Some("gio/gio.h".to_owned()),
+                    ..Default::default()
+                });
+        }
+    }
+
+    /// Notes in the documentation of the fields, the type aliases and the
+    /// functions which integers come from `NonZero` types, which are written
+    /// as plain integers.
+    fn document_nonzero(&mut self) {
+        if !self.config.document_nonzero {
+            return;
+        }
+        let never_zero = " Never zero.";
+        let note_fields = |fields: &mut Vec<Field>| {
+            for field in fields.iter_mut().filter(|field| field.ty.is_nonzero()) {
+                field.documentation.doc_comment.push(never_zero.to_owned());
+            }
+        };
+        self.structs
+            .for_all_items_mut(|x| note_fields(&mut x.fields));
+        self.unions
+            .for_all_items_mut(|x| note_fields(&mut x.fields));
+        self.typedefs.for_all_items_mut(|x| {
+            if x.aliased.is_nonzero() {
+                x.documentation.doc_comment.push(never_zero.to_owned());
+            }
+        });
+        for function in &mut self.functions {
+            let mut notes: Vec<_> = function
+                .args
+                .iter()
+                .filter(|arg| arg.ty.is_nonzero())
+                .filter_map(|arg| arg.name.as_ref())
+                .map(|name| format!(" `{}` is never zero.", name))
+                .collect();
+            if function.ret.is_nonzero() {
+                notes.push(" Never returns zero.".to_owned());
+            }
+            function.documentation.doc_comment.extend(notes);
+        }
+    }
+
     fn resolve_declaration_types(&mut self) {
         if !self.config.style.generate_tag() {
             return;
@@ -394,15 +925,618 @@ impl Library {
                         x.name,
                     );
                 }
+                GType::Final { .. } | GType::TraitInterface { .. } => {}
+                // Boxed types are only declared below.
+                GType::Boxed { .. } => unreachable!(),
+            }
+            needs_gobject = true;
+        }
+
+        if self.config.gobject.enabled {
+            let mut boxed = Vec::new();
+            self.opaque_items.for_all_items(|o| {
+                if o.annotations().atom("gobject") == Some(Some("boxed".to_owned())) {
+                    let mut name = o.path().name().to_owned();
+                    self.config.export.rename(&mut name);
+                    boxed.push(GObject::new_boxed(o, name));
+                }
+            });
+            for x in boxed {
+                self.gobjects.try_insert(x);
+                needs_gobject = true;
             }
+        }
+
+        // `GError` is declared by GLib.
+        if self.config.gobject.enabled && self.functions.iter().any(|f| f.throws()) {
             needs_gobject = true;
         }
 
+        // So is `G_DEFINE_AUTOPTR_CLEANUP_FUNC`, for the opaque types which
+        // have a free function.
+        if self.config.gobject.enabled {
+            let mut names = Vec::new();
+            self.opaque_items.for_all_items(|o| {
+                let mut name = o.path().name().to_owned();
+                self.config.export.rename(&mut name);
+                names.push(name.to_snake_case());
+            });
+            needs_gobject |= self.functions.iter().any(|f| {
+                names.iter().any(|name| {
+                    f.path.name() == format!("{}_free", name)
+                        || f.path.name() == format!("{}_unref", name)
+                })
+            });
+        }
+
         if needs_gobject {
             self.config.sys_includes.push("glib-object.h".into())
         }
     }
 
+    fn check_throwing_functions(&self) {
+        if !self.config.gobject.enabled {
+            return;
+        }
+        for function in &self.functions {
+            function.check_throws();
+        }
+    }
+
+    /// Declares the structs of function pointers named by the `vtable`
+    /// annotations of the functions, with a field per function, and exports
+    /// them. The bindings initialize a `static const` table of each one.
+    fn vtables(&mut self) {
+        let mut vtables: Vec<(String, Vec<Field>)> = Vec::new();
+        for function in &mut self.functions {
+            let name = match function.annotations.atom("vtable") {
+                Some(Some(name)) => name,
+                _ => continue,
+            };
+            if function.variadic {
+                warn_in!(
+                    function.annotations.location,
+                    "Leaving the variadic function {} out of the {} vtable.",
+                    function.path.name(),
+                    name
+                );
+                function.annotations.remove("vtable");
+                continue;
+            }
+            let field = Field {
+                name: function.path.name().to_owned(),
+                ty: function.pointer_type(),
+                cfg: function.cfg.clone(),
+                annotations: AnnotationSet::new(),
+                documentation: Documentation::none(),
+            };
+            match vtables.iter_mut().find(|x| x.0 == name) {
+                Some(vtable) => vtable.1.push(field),
+                None => vtables.push((name, vec![field])),
+            }
+        }
+
+        for (name, fields) in vtables {
+            let path = Path::new(name.clone());
+            if self.get_items(&path).is_some() {
+                warn!(
+                    "Can't declare the {} vtable, an item already has this name.",
+                    name
+                );
+                for function in &mut self.functions {
+                    if function.annotations.atom("vtable") == Some(Some(name.clone())) {
+                        function.annotations.remove("vtable");
+                    }
+                }
+                continue;
+            }
+            self.structs.try_insert(Struct::new(
+                path,
+                GenericParams::default(),
+                fields,
+                false,
+                false,
+                None,
+                false,
+                None,
+                AnnotationSet::new(),
+                Documentation::simple(&format!(" The functions of the {} vtable.", name)),
+            ));
+            self.config.export.include.push(name);
+        }
+    }
+
+    /// Checks that the functions of each `generic-group` can be selected by
+    /// the type of one of their arguments, leaving the other groups out.
+    fn generic_groups(&mut self) {
+        let mut groups: Vec<String> = Vec::new();
+        for function in &self.functions {
+            if let Some(Some(group)) = function.annotations.atom("generic-group") {
+                if !groups.contains(&group) {
+                    groups.push(group);
+                }
+            }
+        }
+
+        for group in groups {
+            let in_group =
+                |f: &Function| f.annotations.atom("generic-group") == Some(Some(group.clone()));
+            let error = if self.functions.iter().any(|f| f.path.name() == group) {
+                Some("a function has this name")
+            } else {
+                let functions: Vec<&Function> =
+                    self.functions.iter().filter(|f| in_group(f)).collect();
+                let cfg = |f: &Function| f.cfg.as_ref().map(|x| x.to_string());
+                if functions.iter().any(|f| cfg(f) != cfg(functions[0])) {
+                    Some("its functions have different cfgs")
+                } else {
+                    dispatch_argument(&functions).err()
+                }
+            };
+            if let Some(error) = error {
+                warn!("Can't define the {} generic macro, {}.", group, error);
+                for function in &mut self.functions {
+                    if in_group(function) {
+                        function.annotations.remove("generic-group");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Declares the `va_list` variants of the variadic functions after
+    /// them, unless they're already exported.
+    fn valist_functions(&mut self) {
+        let mut functions = Vec::with_capacity(self.functions.len());
+        for function in &self.functions {
+            functions.push(function.clone());
+            if !function.variadic || !self.config.valist(&function.annotations) {
+                continue;
+            }
+            let variant = function.valist_variant();
+            if !self.functions.iter().any(|f| f.path == variant.path) {
+                functions.push(variant);
+            }
+        }
+        self.functions = functions;
+    }
+
+    /// Names or inlines the types bindgen declares for the anonymous members
+    /// of structs and unions, as `structure.anonymous_members` says. Returns
+    /// the types to inline.
+    fn anonymous_members(&mut self) -> HashSet<Path> {
+        // Cython has no anonymous members, they are only lifted.
+        let mode = match self.config.structure.anonymous_members {
+            Some(AnonymousMembers::Inline) if self.config.language == Language::Cython => {
+                return HashSet::new()
+            }
+            Some(mode) => mode,
+            None => return HashSet::new(),
+        };
+
+        // The types of the anonymous members, with their struct or union
+        // and their number.
+        let mut members: HashMap<Path, (Path, String)> = HashMap::new();
+        let mut collect = |parent: &Path, fields: &[Field]| {
+            for field in fields {
+                match (field.anonymous_index(), &field.ty) {
+                    (Some(index), Type::Path(generic)) if generic.generics().is_empty() => {
+                        members.insert(generic.path().clone(), (parent.clone(), index.to_owned()));
+                    }
+                    _ => {}
+                }
+            }
+        };
+        self.structs.for_all_items(|x| collect(&x.path, &x.fields));
+        self.unions.for_all_items(|x| collect(&x.path, &x.fields));
+        let (structs, unions) = (&self.structs, &self.unions);
+        members.retain(|path, _| {
+            structs.get_items(path).is_some() || unions.get_items(path).is_some()
+        });
+
+        if mode == AnonymousMembers::Inline {
+            return members.into_iter().map(|(path, _)| path).collect();
+        }
+
+        fn lifted_name(
+            path: &Path,
+            members: &HashMap<Path, (Path, String)>,
+            config: &Config,
+        ) -> String {
+            if let Some(name) = config.export.rename.get(path.name()) {
+                return name.clone();
+            }
+            match members.get(path) {
+                Some((parent, index)) => {
+                    format!("{}_Anon{}", lifted_name(parent, members, config), index)
+                }
+                None => path.name().to_owned(),
+            }
+        }
+        let names: Vec<(String, String)> = members
+            .keys()
+            .map(|path| {
+                (
+                    path.name().to_owned(),
+                    lifted_name(path, &members, &self.config),
+                )
+            })
+            .collect();
+        self.config.export.rename.extend(names);
+
+        let rename_fields = |fields: &mut Vec<Field>| {
+            for field in fields {
+                let index = match (field.anonymous_index(), &field.ty) {
+                    (Some(index), Type::Path(generic)) if members.contains_key(generic.path()) => {
+                        index.to_owned()
+                    }
+                    _ => continue,
+                };
+                field.name = format!("anon{}", index);
+            }
+        };
+        self.structs
+            .for_all_items_mut(|x| rename_fields(&mut x.fields));
+        self.unions
+            .for_all_items_mut(|x| rename_fields(&mut x.fields));
+        HashSet::new()
+    }
+
+    /// Replaces the structs exported as integer handles by the typedefs of
+    /// their handles, which the functions take and return instead of
+    /// pointers to them, and declares their create and destroy functions.
+    fn integer_handles(&mut self) {
+        let config = &self.config;
+        let mut handles: Vec<(Path, Option<Cfg>, Documentation)> = Vec::new();
+        self.structs.for_all_items(|x| {
+            if config.handle.is_handle(x.path().name(), x.annotations()) {
+                handles.push((x.path().clone(), x.cfg.clone(), x.documentation.clone()));
+            }
+        });
+        self.opaque_items.for_all_items(|x| {
+            if config.handle.is_handle(x.path().name(), x.annotations()) {
+                handles.push((x.path().clone(), x.cfg.clone(), x.documentation.clone()));
+            }
+        });
+        if handles.is_empty() {
+            return;
+        }
+
+        let ty = match PrimitiveType::maybe(&config.handle.ty) {
+            Some(ty) => ty,
+            None => {
+                warn!(
+                    "Ignoring the integer handles, {} isn't a primitive type.",
+                    config.handle.ty
+                );
+                return;
+            }
+        };
+
+        let exported: Vec<String> = self
+            .functions
+            .iter()
+            .map(|f| f.path().name().to_owned())
+            .collect();
+        let exists = |name: &str| exported.iter().any(|exported| exported == name);
+        let mut handle_types = HashMap::new();
+        for (path, cfg, documentation) in handles {
+            let handle = Path::new(format!("{}{}", path.name(), config.handle.suffix));
+            let handle_type = Type::Path(GenericPath::new(handle.clone(), Vec::new()));
+            self.structs.filter(|x| x.path() == &path);
+            self.opaque_items.filter(|x| x.path() == &path);
+            self.typedefs.try_insert(Typedef::new(
+                handle,
+                GenericParams::default(),
+                Type::Primitive(ty.clone()),
+                cfg.clone(),
+                AnnotationSet::new(),
+                documentation,
+            ));
+
+            if config.handle.functions {
+                let mut name = path.name().to_owned();
+                config.export.rename(&mut name);
+                let name = name.to_snake_case();
+
+                let create = format!("{}_create", name);
+                if !exists(&create) {
+                    self.functions.push(Function {
+                        path: Path::new(create),
+                        self_type_path: None,
+                        ret: handle_type.clone(),
+                        ret_transfer: Some(Transfer::Full),
+                        args: Vec::new(),
+                        extern_decl: false,
+                        cfg: cfg.clone(),
+                        annotations: AnnotationSet::new(),
+                        documentation: Documentation::simple(&format!(
+                            " Creates a {} and returns its handle.",
+                            path
+                        )),
+                        never_return: false,
+                        cold: false,
+                        variadic: false,
+                        calling_convention: CallingConvention::C,
+                        import_module: None,
+                        link_name: None,
+                    });
+                }
+
+                let destroy = format!("{}_destroy", name);
+                if !exists(&destroy) {
+                    self.functions.push(Function {
+                        path: Path::new(destroy),
+                        self_type_path: None,
+                        ret: Type::Primitive(PrimitiveType::Void),
+                        ret_transfer: None,
+                        args: vec![FunctionArgument {
+                            name: Some("handle".to_owned()),
+                            ty: handle_type.clone(),
+                            array_length: None,
+                            transfer: Some(Transfer::Full),
+                        }],
+                        extern_decl: false,
+                        cfg,
+                        annotations: AnnotationSet::new(),
+                        documentation: Documentation::simple(&format!(
+                            " Destroys the {} of a handle.",
+                            path
+                        )),
+                        never_return: false,
+                        cold: false,
+                        variadic: false,
+                        calling_convention: CallingConvention::C,
+                        import_module: None,
+                        link_name: None,
+                    });
+                }
+            }
+            handle_types.insert(path, handle_type);
+        }
+
+        let replace = |ty: &mut Type| {
+            let handle_type = match *ty {
+                Type::Ptr {
+                    ty: ref pointee, ..
+                } => match **pointee {
+                    Type::Path(ref generic) => handle_types.get(generic.path()).cloned(),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(handle_type) = handle_type {
+                *ty = handle_type;
+            }
+        };
+        for function in &mut self.functions {
+            replace(&mut function.ret);
+            for arg in &mut function.args {
+                replace(&mut arg.ty);
+            }
+        }
+    }
+
+    /// Evaluates the constants referring to qualified paths, like `Self::MAX`
+    /// or `u32::MAX`, which can't be written as they are. The ones that can't
+    /// be evaluated are skipped.
+    fn fold_constants(&mut self) {
+        let mut constants = HashMap::new();
+        let mut add = |constant: &Constant| {
+            let name = match constant.associated_to {
+                Some(ref ty) => format!("{}::{}", ty.name(), constant.path.name()),
+                None => constant.path.name().to_owned(),
+            };
+            constants.insert(name, constant.clone());
+        };
+        self.constants.for_all_items(&mut add);
+        self.structs
+            .for_all_items(|x| x.associated_constants.iter().for_each(&mut add));
+
+        let lookup = |path: &str| eval_constant(&constants, path, 0);
+        let fold = |constant: &mut Constant| {
+            if !constant.value.has_qualified_path() || constant.fold(&lookup) {
+                return true;
+            }
+            warn_in!(
+                constant.annotations.location,
+                "Skip {} - (can't evaluate its value)",
+                constant.path.name()
+            );
+            false
+        };
+
+        let mut skipped = Vec::new();
+        self.constants.for_all_items_mut(|x| {
+            if !fold(x) {
+                skipped.push(x.path.clone());
+            }
+        });
+        self.constants.filter(|x| skipped.contains(&x.path));
+        self.structs.for_all_items_mut(|x| {
+            let associated_constants = mem::take(&mut x.associated_constants);
+            x.associated_constants = associated_constants
+                .into_iter()
+                .filter_map(|mut x| if fold(&mut x) { Some(x) } else { None })
+                .collect();
+        });
+    }
+
+    /// Removes the items which aren't built for `target`, and the cfgs of the
+    /// ones which are always built for it.
+    pub(crate) fn resolve_target_cfgs(&mut self, target: &TargetCfgs) {
+        self.constants.filter(|x| target.excludes(&x.cfg));
+        self.globals.filter(|x| target.excludes(&x.cfg));
+        self.enums.filter(|x| target.excludes(&x.cfg));
+        self.structs.filter(|x| target.excludes(&x.cfg));
+        self.unions.filter(|x| target.excludes(&x.cfg));
+        self.opaque_items.filter(|x| target.excludes(&x.cfg));
+        self.typedefs.filter(|x| target.excludes(&x.cfg));
+        self.gobjects.filter(|x| target.excludes(&x.cfg));
+        self.functions.retain(|x| !target.excludes(&x.cfg));
+
+        self.constants
+            .for_all_items_mut(|x| target.resolve(&mut x.cfg));
+        self.globals
+            .for_all_items_mut(|x| target.resolve(&mut x.cfg));
+        self.enums.for_all_items_mut(|x| {
+            target.resolve(&mut x.cfg);
+            x.variants.retain(|x| !target.excludes(&x.cfg));
+            x.variants
+                .iter_mut()
+                .for_each(|x| target.resolve(&mut x.cfg));
+        });
+        self.structs.for_all_items_mut(|x| {
+            target.resolve(&mut x.cfg);
+            x.fields.retain(|x| !target.excludes(&x.cfg));
+            x.fields.iter_mut().for_each(|x| target.resolve(&mut x.cfg));
+            x.associated_constants.retain(|x| !target.excludes(&x.cfg));
+            x.associated_constants
+                .iter_mut()
+                .for_each(|x| target.resolve(&mut x.cfg));
+        });
+        self.unions.for_all_items_mut(|x| {
+            target.resolve(&mut x.cfg);
+            x.fields.retain(|x| !target.excludes(&x.cfg));
+            x.fields.iter_mut().for_each(|x| target.resolve(&mut x.cfg));
+        });
+        self.opaque_items
+            .for_all_items_mut(|x| target.resolve(&mut x.cfg));
+        self.typedefs
+            .for_all_items_mut(|x| target.resolve(&mut x.cfg));
+        self.gobjects
+            .for_all_items_mut(|x| target.resolve(&mut x.cfg));
+        for function in &mut self.functions {
+            target.resolve(&mut function.cfg);
+        }
+    }
+
+    /// Gives C identifiers to the types and constants named with raw
+    /// identifiers or with non-ASCII characters, unless they're renamed in
+    /// `export.rename`. Fails if they collide with other items, or if a
+    /// function or global has such a name, which is its symbol.
+    fn mangle_identifiers(&mut self) -> Result<(), Error> {
+        let mut names = Vec::new();
+        fn add<T: Item>(item_type: ItemType, x: &T) -> (ItemType, String, Option<String>) {
+            let location = x.annotations().location.clone();
+            (item_type, x.path().name().to_owned(), location)
+        }
+        self.constants.for_all_items(|x| {
+            if x.associated_to.is_none() {
+                names.push(add(ItemType::Constants, x));
+            }
+        });
+        self.structs
+            .for_all_items(|x| names.push(add(ItemType::Structs, x)));
+        self.unions
+            .for_all_items(|x| names.push(add(ItemType::Unions, x)));
+        self.enums
+            .for_all_items(|x| names.push(add(ItemType::Enums, x)));
+        self.opaque_items
+            .for_all_items(|x| names.push(add(ItemType::OpaqueItems, x)));
+        self.typedefs
+            .for_all_items(|x| names.push(add(ItemType::Typedefs, x)));
+        self.globals
+            .for_all_items(|x| names.push(add(ItemType::Globals, x)));
+        for function in &self.functions {
+            let location = function.annotations.location.clone();
+            names.push((
+                ItemType::Functions,
+                function.path().name().to_owned(),
+                location,
+            ));
+        }
+
+        // The Rust name of the item with each C name.
+        let mut taken: HashMap<String, String> = names
+            .iter()
+            .map(|(_, x, _)| (x.clone(), x.clone()))
+            .collect();
+        let mut invalid_symbols = Vec::new();
+        let mut collisions = Vec::new();
+        for (item_type, name, location) in names {
+            // The symbols of the functions and globals are what's linked, so
+            // they can't be declared under another name.
+            if let ItemType::Functions | ItemType::Globals = item_type {
+                // The symbol of a raw identifier is the identifier.
+                let symbol = name.trim_start_matches("r#");
+                if mangle::mangle_ident(symbol).is_some() || reserved::is_keyword(symbol) {
+                    error_in!(
+                        location,
+                        "The symbol {} can't be declared in C, as it isn't an ASCII identifier or is a keyword.",
+                        name
+                    );
+                    invalid_symbols.push(name);
+                }
+                continue;
+            }
+            if self.config.export.rename.contains_key(&name) {
+                continue;
+            }
+            let mut ident = match mangle::mangle_ident(&name) {
+                Some(ident) => ident,
+                None => continue,
+            };
+            // Raw identifiers are often keywords.
+            reserved::escape(&mut ident);
+            if let Some(other) = taken.get(&ident) {
+                error_in!(
+                    location,
+                    "{} and {} are both named {} in C.",
+                    other,
+                    name,
+                    ident
+                );
+                collisions.push(ident.clone());
+            }
+            taken.insert(ident.clone(), name.clone());
+            self.config.export.rename.insert(name, ident);
+        }
+
+        if !invalid_symbols.is_empty() {
+            return Err(Error::InvalidSymbols(invalid_symbols));
+        }
+        if !collisions.is_empty() {
+            return Err(Error::NameCollisions(collisions));
+        }
+
+        // The symbols of the raw identifiers are written without their prefix.
+        for function in &mut self.functions {
+            if function.path.name().starts_with("r#") {
+                function.path = Path::new(&function.path.name()[2..]);
+            }
+        }
+        self.globals.for_all_items_mut(|x| {
+            if x.export_name.starts_with("r#") {
+                x.export_name = x.export_name[2..].to_owned();
+            }
+        });
+        Ok(())
+    }
+
+    /// Removes the string and byte string constants, unless
+    /// `const.allow_static_strings` is set.
+    fn remove_string_constants(&mut self) {
+        if self.config.constant.allow_static_strings {
+            return;
+        }
+        self.constants.filter(|x| x.value.is_string());
+        self.structs.for_all_items_mut(|x| {
+            x.associated_constants.retain(|x| !x.value.is_string());
+        });
+    }
+
+    /// Turns the `#[repr(transparent)]` structs which aren't typedefs into
+    /// plain structs.
+    fn strong_transparent_structs(&mut self) {
+        let config = &self.config;
+        self.structs.for_all_items_mut(|x| {
+            if x.is_transparent && !config.structure.transparent_typedef(&x.annotations) {
+                x.is_transparent = false;
+            }
+        });
+    }
+
     fn simplify_standard_types(&mut self) {
         let config = &self.config;
 
@@ -418,6 +1552,9 @@ impl Library {
         self.typedefs.for_all_items_mut(|x| {
             x.simplify_standard_types(config);
         });
+        self.gobjects.for_all_items_mut(|x| {
+            x.simplify_standard_types(config);
+        });
         for x in &mut self.functions {
             x.simplify_standard_types(config);
         }
@@ -481,3 +1618,17 @@ impl Library {
         }
     }
 }
+
+/// Evaluates the constant at `path`, either `NAME` or `Type::NAME`, among
+/// `constants`.
+fn eval_constant(constants: &HashMap<String, Constant>, path: &str, depth: usize) -> Option<i128> {
+    // Rust rejects cycles, but don't trust it for the constants of other
+    // crates.
+    if depth > 64 {
+        return None;
+    }
+    match constants.get(path) {
+        Some(constant) => constant.eval(&|x| eval_constant(constants, x, depth + 1)),
+        None => primitive_constant(path),
+    }
+}
@@ -0,0 +1,426 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A lightweight expander for the `macro_rules!` macros of a crate, so that
+//! the items they define can be found without expanding the whole crate.
+//!
+//! Matching is greedy and never backtracks, which covers the macros usually
+//! written to stamp out FFI items; anything fancier needs `parse.expand`.
+
+use std::collections::HashMap;
+
+use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+use syn::parse::{ParseStream, Parser};
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Token(TokenTree),
+    Group(Delimiter, Vec<Matcher>),
+    Fragment(String, String),
+    Repeat(Vec<Matcher>, Option<TokenTree>, char),
+}
+
+#[derive(Debug, Clone)]
+enum Transcriber {
+    Token(TokenTree),
+    Group(Delimiter, Vec<Transcriber>),
+    Var(String),
+    Repeat(Vec<Transcriber>, Option<TokenTree>),
+}
+
+#[derive(Debug, Clone)]
+enum Binding {
+    Fragment(Vec<TokenTree>),
+    Repeat(Vec<Binding>),
+}
+
+type Bindings = HashMap<String, Binding>;
+
+#[derive(Debug, Clone)]
+struct Rule {
+    matcher: Vec<Matcher>,
+    transcriber: Vec<Transcriber>,
+}
+
+/// The rules of a `macro_rules!` definition.
+#[derive(Debug, Clone)]
+pub struct MacroRules {
+    rules: Vec<Rule>,
+}
+
+fn is_punct(token: Option<&TokenTree>, ch: char) -> bool {
+    match token {
+        Some(TokenTree::Punct(ref punct)) => punct.as_char() == ch,
+        _ => false,
+    }
+}
+
+fn same_token(a: &TokenTree, b: &TokenTree) -> bool {
+    match (a, b) {
+        (TokenTree::Punct(a), TokenTree::Punct(b)) => a.as_char() == b.as_char(),
+        (TokenTree::Ident(a), TokenTree::Ident(b)) => a == b,
+        (TokenTree::Literal(a), TokenTree::Literal(b)) => a.to_string() == b.to_string(),
+        _ => false,
+    }
+}
+
+/// Parses the separator and the operator following `$(...)`.
+fn parse_repeat_op(
+    tokens: &[TokenTree],
+    i: &mut usize,
+) -> Result<(Option<TokenTree>, char), String> {
+    let op = |token: Option<&TokenTree>| {
+        ['*', '+', '?']
+            .iter()
+            .cloned()
+            .find(|&ch| is_punct(token, ch))
+    };
+    if let Some(ch) = op(tokens.get(*i)) {
+        *i += 1;
+        return Ok((None, ch));
+    }
+    match (tokens.get(*i), op(tokens.get(*i + 1))) {
+        (Some(separator), Some(ch)) => {
+            *i += 2;
+            Ok((Some(separator.clone()), ch))
+        }
+        _ => Err("Expected a repetition operator.".to_owned()),
+    }
+}
+
+fn parse_matcher(tokens: TokenStream) -> Result<Vec<Matcher>, String> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut matchers = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        i += 1;
+        if !is_punct(Some(token), '$') {
+            matchers.push(match *token {
+                TokenTree::Group(ref group) => {
+                    Matcher::Group(group.delimiter(), parse_matcher(group.stream())?)
+                }
+                ref token => Matcher::Token(token.clone()),
+            });
+            continue;
+        }
+        match tokens.get(i) {
+            Some(TokenTree::Ident(ref name)) if is_punct(tokens.get(i + 1), ':') => {
+                let kind = match tokens.get(i + 2) {
+                    Some(TokenTree::Ident(ref kind)) => kind.to_string(),
+                    _ => return Err(format!("Missing fragment specifier for ${}.", name)),
+                };
+                matchers.push(Matcher::Fragment(name.to_string(), kind));
+                i += 3;
+            }
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                i += 1;
+                let inner = parse_matcher(group.stream())?;
+                let (separator, op) = parse_repeat_op(&tokens, &mut i)?;
+                matchers.push(Matcher::Repeat(inner, separator, op));
+            }
+            _ => return Err("Unexpected `$` in macro matcher.".to_owned()),
+        }
+    }
+    Ok(matchers)
+}
+
+fn parse_transcriber(tokens: TokenStream) -> Result<Vec<Transcriber>, String> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut transcribers = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        i += 1;
+        if !is_punct(Some(token), '$') {
+            transcribers.push(match *token {
+                TokenTree::Group(ref group) => {
+                    Transcriber::Group(group.delimiter(), parse_transcriber(group.stream())?)
+                }
+                ref token => Transcriber::Token(token.clone()),
+            });
+            continue;
+        }
+        match tokens.get(i) {
+            Some(TokenTree::Ident(ref name)) => {
+                i += 1;
+                transcribers.push(if name == "crate" {
+                    Transcriber::Token(TokenTree::Ident(Ident::new("crate", Span::call_site())))
+                } else {
+                    Transcriber::Var(name.to_string())
+                });
+            }
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                i += 1;
+                let inner = parse_transcriber(group.stream())?;
+                // The number of repetitions comes from the matched fragments.
+                let (separator, _) = parse_repeat_op(&tokens, &mut i)?;
+                transcribers.push(Transcriber::Repeat(inner, separator));
+            }
+            _ => return Err("Unexpected `$` in macro transcriber.".to_owned()),
+        }
+    }
+    Ok(transcribers)
+}
+
+/// Parses the body of a `macro_rules!` definition.
+pub fn parse(tokens: TokenStream) -> Result<MacroRules, String> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut rules = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let matcher = match tokens.get(i) {
+            Some(TokenTree::Group(ref group)) => group.stream(),
+            _ => return Err("Expected a macro matcher.".to_owned()),
+        };
+        if !is_punct(tokens.get(i + 1), '=') || !is_punct(tokens.get(i + 2), '>') {
+            return Err("Expected `=>` after a macro matcher.".to_owned());
+        }
+        let transcriber = match tokens.get(i + 3) {
+            Some(TokenTree::Group(ref group)) => group.stream(),
+            _ => return Err("Expected a macro transcriber.".to_owned()),
+        };
+        rules.push(Rule {
+            matcher: parse_matcher(matcher)?,
+            transcriber: parse_transcriber(transcriber)?,
+        });
+        i += 4;
+        if is_punct(tokens.get(i), ';') {
+            i += 1;
+        }
+    }
+    Ok(MacroRules { rules })
+}
+
+/// Parses a fragment of the given kind at the start of `input`, returning
+/// the number of token trees it spans.
+fn match_fragment(kind: &str, input: &[TokenTree]) -> Option<usize> {
+    match kind {
+        "tt" => return if input.is_empty() { None } else { Some(1) },
+        "ident" => {
+            return match input.first() {
+                Some(TokenTree::Ident(..)) => Some(1),
+                _ => None,
+            }
+        }
+        "literal" => {
+            let skip = if is_punct(input.first(), '-') { 1 } else { 0 };
+            return match input.get(skip) {
+                Some(TokenTree::Literal(..)) => Some(skip + 1),
+                Some(TokenTree::Ident(ref ident)) if ident == "true" || ident == "false" => {
+                    Some(skip + 1)
+                }
+                _ => None,
+            };
+        }
+        _ => {}
+    }
+
+    fn rest<T: syn::parse::Parse>(input: ParseStream) -> syn::Result<TokenStream> {
+        input.parse::<T>()?;
+        input.parse()
+    }
+    let parser = match kind {
+        "ty" => rest::<syn::Type>,
+        "expr" => rest::<syn::Expr>,
+        "path" => rest::<syn::Path>,
+        "item" => rest::<syn::Item>,
+        "block" => rest::<syn::Block>,
+        "vis" => rest::<syn::Visibility>,
+        "meta" => rest::<syn::Meta>,
+        "pat" => rest::<syn::Pat>,
+        "stmt" => rest::<syn::Stmt>,
+        "lifetime" => rest::<syn::Lifetime>,
+        _ => return None,
+    };
+    let remaining = parser
+        .parse2(input.iter().cloned().collect())
+        .ok()?
+        .into_iter()
+        .count();
+    Some(input.len() - remaining)
+}
+
+/// Matches `matchers` against the start of `input`, returning the number of
+/// token trees they span.
+fn match_prefix(
+    matchers: &[Matcher],
+    input: &[TokenTree],
+    bindings: &mut Bindings,
+) -> Option<usize> {
+    let mut pos = 0;
+    for matcher in matchers {
+        match *matcher {
+            Matcher::Token(ref token) => {
+                if !same_token(token, input.get(pos)?) {
+                    return None;
+                }
+                pos += 1;
+            }
+            Matcher::Group(delimiter, ref inner) => {
+                let group = match input.get(pos)? {
+                    TokenTree::Group(ref group) if group.delimiter() == delimiter => group,
+                    _ => return None,
+                };
+                let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+                if match_prefix(inner, &tokens, bindings)? != tokens.len() {
+                    return None;
+                }
+                pos += 1;
+            }
+            Matcher::Fragment(ref name, ref kind) => {
+                let len = match_fragment(kind, &input[pos..])?;
+                bindings.insert(
+                    name.clone(),
+                    Binding::Fragment(input[pos..pos + len].to_vec()),
+                );
+                pos += len;
+            }
+            Matcher::Repeat(ref inner, ref separator, op) => {
+                let mut iterations: Vec<Bindings> = Vec::new();
+                loop {
+                    let mut next = pos;
+                    if !iterations.is_empty() {
+                        if let Some(ref separator) = *separator {
+                            match input.get(next) {
+                                Some(token) if same_token(separator, token) => next += 1,
+                                _ => break,
+                            }
+                        }
+                    }
+                    let mut iteration = Bindings::new();
+                    // Stop once an iteration doesn't make progress.
+                    match match_prefix(inner, &input[next..], &mut iteration) {
+                        Some(len) if len > 0 || next > pos => {
+                            pos = next + len;
+                            iterations.push(iteration);
+                        }
+                        _ => break,
+                    }
+                    if op == '?' {
+                        break;
+                    }
+                }
+                if op == '+' && iterations.is_empty() {
+                    return None;
+                }
+                let mut names = Vec::new();
+                matcher_names(inner, &mut names);
+                for name in names {
+                    let values = iterations
+                        .iter_mut()
+                        .filter_map(|iteration| iteration.remove(&name))
+                        .collect();
+                    bindings.insert(name, Binding::Repeat(values));
+                }
+            }
+        }
+    }
+    Some(pos)
+}
+
+fn matcher_names(matchers: &[Matcher], names: &mut Vec<String>) {
+    for matcher in matchers {
+        match *matcher {
+            Matcher::Token(..) => {}
+            Matcher::Group(_, ref inner) | Matcher::Repeat(ref inner, ..) => {
+                matcher_names(inner, names)
+            }
+            Matcher::Fragment(ref name, _) => names.push(name.clone()),
+        }
+    }
+}
+
+fn transcriber_names(transcribers: &[Transcriber], names: &mut Vec<String>) {
+    for transcriber in transcribers {
+        match *transcriber {
+            Transcriber::Token(..) => {}
+            Transcriber::Group(_, ref inner) | Transcriber::Repeat(ref inner, ..) => {
+                transcriber_names(inner, names)
+            }
+            Transcriber::Var(ref name) => names.push(name.clone()),
+        }
+    }
+}
+
+fn transcribe(
+    transcribers: &[Transcriber],
+    bindings: &Bindings,
+    out: &mut Vec<TokenTree>,
+) -> Result<(), String> {
+    for transcriber in transcribers {
+        match *transcriber {
+            Transcriber::Token(ref token) => out.push(token.clone()),
+            Transcriber::Group(delimiter, ref inner) => {
+                let mut tokens = Vec::new();
+                transcribe(inner, bindings, &mut tokens)?;
+                out.push(TokenTree::Group(Group::new(
+                    delimiter,
+                    tokens.into_iter().collect(),
+                )));
+            }
+            Transcriber::Var(ref name) => match bindings.get(name) {
+                Some(Binding::Fragment(ref tokens)) => out.extend(tokens.iter().cloned()),
+                Some(Binding::Repeat(..)) => {
+                    return Err(format!("${} is still repeating at this depth.", name))
+                }
+                None => {
+                    // Not a macro variable, so left as is.
+                    out.push(TokenTree::Punct(proc_macro2::Punct::new(
+                        '$',
+                        proc_macro2::Spacing::Alone,
+                    )));
+                    out.push(TokenTree::Ident(Ident::new(name, Span::call_site())));
+                }
+            },
+            Transcriber::Repeat(ref inner, ref separator) => {
+                let mut names = Vec::new();
+                transcriber_names(inner, &mut names);
+                let repeated: Vec<(&String, &Vec<Binding>)> = names
+                    .iter()
+                    .filter_map(|name| match bindings.get(name) {
+                        Some(Binding::Repeat(ref values)) => Some((name, values)),
+                        _ => None,
+                    })
+                    .collect();
+                let count = match repeated.first() {
+                    Some(&(_, values)) => values.len(),
+                    None => return Err("Repetition without repeated variables.".to_owned()),
+                };
+                if repeated.iter().any(|&(_, values)| values.len() != count) {
+                    return Err("Repeated variables have different lengths.".to_owned());
+                }
+                for i in 0..count {
+                    if i != 0 {
+                        if let Some(ref separator) = *separator {
+                            out.push(separator.clone());
+                        }
+                    }
+                    let mut iteration = bindings.clone();
+                    for &(name, values) in &repeated {
+                        iteration.insert(name.clone(), values[i].clone());
+                    }
+                    transcribe(inner, &iteration, out)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl MacroRules {
+    /// Expands an invocation of the macro with the first rule matching it.
+    pub fn expand(&self, input: TokenStream) -> Result<TokenStream, String> {
+        let input: Vec<TokenTree> = input.into_iter().collect();
+        for rule in &self.rules {
+            let mut bindings = Bindings::new();
+            if match_prefix(&rule.matcher, &input, &mut bindings) == Some(input.len()) {
+                let mut out = Vec::new();
+                transcribe(&rule.transcriber, &bindings, &mut out)?;
+                return Ok(out.into_iter().collect());
+            }
+        }
+        Err("No rule matches the invocation.".to_owned())
+    }
+}
@@ -11,7 +11,94 @@ pub fn mangle_path(path: &Path, generic_values: &[Type], config: &MangleConfig)
 }
 
 pub fn mangle_name(name: &str, generic_values: &[Type], config: &MangleConfig) -> String {
-    Mangler::new(name, generic_values, /* last = */ true, config).mangle()
+    if let Some(ref mangle) = config.mangle_fn {
+        let args: Vec<_> = generic_values
+            .iter()
+            .map(|ty| {
+                let mut mangler = Mangler::new(name, &[], /* last = */ true, config);
+                mangler.append_mangled_type(ty, /* last = */ true);
+                mangler.output
+            })
+            .collect();
+        if let Some(name) = (mangle.0)(name, &args) {
+            return name;
+        }
+    }
+    let name = Mangler::new(name, generic_values, /* last = */ true, config).mangle();
+    config
+        .rename_mangled
+        .apply(&name, IdentifierType::Type)
+        .into_owned()
+}
+
+/// The ASCII letters a non-ASCII one is written with.
+fn transliterate(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ď' | 'đ' | 'ð' => "d",
+        'Ď' | 'Đ' | 'Ð' => "D",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ł' | 'ľ' | 'ĺ' | 'ļ' => "l",
+        'Ł' | 'Ľ' | 'Ĺ' | 'Ļ' => "L",
+        'ñ' | 'ń' | 'ň' | 'ņ' => "n",
+        'Ñ' | 'Ń' | 'Ň' | 'Ņ' => "N",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'ŕ' | 'ř' | 'ŗ' => "r",
+        'Ŕ' | 'Ř' | 'Ŗ' => "R",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ß' => "ss",
+        'ť' | 'ţ' => "t",
+        'Ť' | 'Ţ' => "T",
+        'þ' => "th",
+        'Þ' => "TH",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ý' | 'ÿ' => "y",
+        'Ý' | 'Ÿ' => "Y",
+        'ź' | 'ż' | 'ž' => "z",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        _ => return None,
+    })
+}
+
+/// Returns the C identifier of a Rust one, if it's a raw identifier or has
+/// non-ASCII characters: the `r#` prefix is removed, and the characters are
+/// transliterated, or escaped as `_uXXXX` otherwise.
+pub fn mangle_ident(name: &str) -> Option<String> {
+    let unraw = if name.starts_with("r#") {
+        &name[2..]
+    } else {
+        name
+    };
+    if unraw.len() == name.len() && name.is_ascii() {
+        return None;
+    }
+
+    let mut ident = String::with_capacity(unraw.len());
+    for c in unraw.chars() {
+        if c.is_ascii() {
+            ident.push(c);
+        } else if let Some(letters) = transliterate(c) {
+            ident.push_str(letters);
+        } else {
+            ident.push_str(&format!("_u{:04X}", c as u32));
+        }
+    }
+    Some(ident)
 }
 
 enum Separator {
@@ -56,20 +143,21 @@ impl<'a> Mangler<'a> {
 
     fn push(&mut self, id: Separator) {
         let count = id as usize;
-        let separator = if self.config.remove_underscores {
-            ""
-        } else {
-            "_"
-        };
+        let separator = self.config.separator();
         self.output.extend(std::iter::repeat(separator).take(count));
     }
 
     fn append_mangled_type(&mut self, ty: &Type, last: bool) {
         match *ty {
             Type::Path(ref generic) => {
-                let sub_path =
+                // The names given by the callback are used for the nested
+                // instantiations too.
+                let sub_path = if self.config.mangle_fn.is_some() {
+                    mangle_name(generic.export_name(), generic.generics(), self.config)
+                } else {
                     Mangler::new(generic.export_name(), generic.generics(), last, self.config)
-                        .mangle();
+                        .mangle()
+                };
 
                 self.output.push_str(
                     &self
@@ -196,6 +284,7 @@ fn generics() {
             &MangleConfig {
                 remove_underscores: true,
                 rename_types: RenameRule::None,
+                ..MangleConfig::default()
             }
         ),
         Path::new("FooBar")
@@ -209,6 +298,7 @@ fn generics() {
             &MangleConfig {
                 remove_underscores: true,
                 rename_types: PascalCase,
+                ..MangleConfig::default()
             },
         ),
         Path::new("FooBarF32")
@@ -222,6 +312,7 @@ fn generics() {
             &MangleConfig {
                 remove_underscores: true,
                 rename_types: PascalCase,
+                ..MangleConfig::default()
             },
         ),
         Path::new("FooBarCChar")
@@ -268,6 +359,7 @@ fn generics() {
             &MangleConfig {
                 remove_underscores: true,
                 rename_types: PascalCase,
+                ..MangleConfig::default()
             },
         ),
         Path::new("FooBarTE")
@@ -284,8 +376,18 @@ fn generics() {
             &MangleConfig {
                 remove_underscores: true,
                 rename_types: PascalCase,
+                ..MangleConfig::default()
             },
         ),
         Path::new("FooBarTBarE")
     );
 }
+
+#[test]
+fn idents() {
+    assert_eq!(mangle_ident("Foo"), None);
+    assert_eq!(mangle_ident("r#type"), Some("type".to_owned()));
+    assert_eq!(mangle_ident("naïve"), Some("naive".to_owned()));
+    assert_eq!(mangle_ident("Größe"), Some("Grosse".to_owned()));
+    assert_eq!(mangle_ident("r#Δx"), Some("_u0394x".to_owned()));
+}
@@ -36,23 +36,73 @@ macro_rules! deserialize_enum_str {
     };
 }
 
+/// Logs a warning at the location of a syn node, see `diagnostic::log_at`.
+macro_rules! warn_at {
+    ($node:expr, $($arg:tt)+) => {
+        $crate::bindgen::diagnostic::log_at(::log::Level::Warn, &$node, format_args!($($arg)+))
+    };
+}
+
+/// Logs an error at the location of a syn node, see `diagnostic::log_at`.
+macro_rules! error_at {
+    ($node:expr, $($arg:tt)+) => {
+        $crate::bindgen::diagnostic::log_at(::log::Level::Error, &$node, format_args!($($arg)+))
+    };
+}
+
+/// Logs a warning at the location of an item, given as the `file:line` kept
+/// in its annotations, see `diagnostic::log_in`.
+macro_rules! warn_in {
+    ($location:expr, $($arg:tt)+) => {
+        $crate::bindgen::diagnostic::log_in(
+            ::log::Level::Warn,
+            $location.as_deref(),
+            format_args!($($arg)+),
+        )
+    };
+}
+
+/// Logs an error at the location of an item, given as the `file:line` kept
+/// in its annotations, see `diagnostic::log_in`.
+macro_rules! error_in {
+    ($location:expr, $($arg:tt)+) => {
+        $crate::bindgen::diagnostic::log_in(
+            ::log::Level::Error,
+            $location.as_deref(),
+            format_args!($($arg)+),
+        )
+    };
+}
+
 mod bindings;
 mod bitflags;
 mod builder;
 mod cargo;
 mod cdecl;
 mod config;
+mod cpp_wrappers;
+mod csharp;
+mod ctypes;
 mod declarationtyperesolver;
 mod dependencies;
+pub mod diagnostic;
+mod diff;
 mod error;
+mod gir;
 mod ir;
 mod library;
+mod local_macros;
 mod mangle;
 mod monomorph;
+mod napi;
+mod parse_cache;
 mod parser;
 mod rename;
 mod reserved;
+mod targets;
 mod utilities;
+mod vapi;
+mod work_queue;
 mod writer;
 
 #[allow(unused)]
@@ -60,6 +110,10 @@ pub(crate) use self::cargo::*;
 
 pub use self::bindings::Bindings;
 pub use self::builder::Builder;
+#[allow(unused)]
+pub use self::builder::ItemDecl;
 pub use self::config::Profile; // disambiguate with cargo::Profile
 pub use self::config::*;
 pub use self::error::Error;
+#[allow(unused)]
+pub use self::targets::TargetBindings;
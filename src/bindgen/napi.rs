@@ -0,0 +1,440 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use crate::bindgen::config::Config;
+use crate::bindgen::ir::{
+    ConditionWrite, Function, IntKind, Item, ItemContainer, PrimitiveType, ToCondition, Type,
+};
+use crate::bindgen::writer::SourceWriter;
+
+/// How a value is converted between JavaScript and C.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Marshal {
+    Bool,
+    Int32,
+    Uint32,
+    /// Integers wider than 32 bits, which JavaScript numbers hold up to 2^53.
+    Int64,
+    Double,
+    /// A `const char*`, as a string.
+    String,
+    /// Any other pointer, as an external value.
+    External,
+}
+
+impl Marshal {
+    /// The type of the C local holding the value.
+    fn c_type(self) -> &'static str {
+        match self {
+            Marshal::Bool => "bool",
+            Marshal::Int32 => "int32_t",
+            Marshal::Uint32 => "uint32_t",
+            Marshal::Int64 => "int64_t",
+            Marshal::Double => "double",
+            Marshal::String => "const char *",
+            Marshal::External => "void *",
+        }
+    }
+
+    /// The declaration of the C local `name` holding the value.
+    fn local(self, name: &str) -> String {
+        let c_type = self.c_type();
+        if c_type.ends_with('*') {
+            format!("{}{}", c_type, name)
+        } else {
+            format!("{} {}", c_type, name)
+        }
+    }
+
+    /// The N-API function getting the C value of a JavaScript one.
+    fn getter(self) -> &'static str {
+        match self {
+            Marshal::Bool => "napi_get_value_bool",
+            Marshal::Int32 => "napi_get_value_int32",
+            Marshal::Uint32 => "napi_get_value_uint32",
+            Marshal::Int64 => "napi_get_value_int64",
+            Marshal::Double => "napi_get_value_double",
+            Marshal::String => "napi_get_value_string_utf8",
+            Marshal::External => "napi_get_value_external",
+        }
+    }
+
+    /// The N-API function creating the JavaScript value of a C one.
+    fn creator(self) -> &'static str {
+        match self {
+            Marshal::Bool => "napi_get_boolean",
+            Marshal::Int32 => "napi_create_int32",
+            Marshal::Uint32 => "napi_create_uint32",
+            Marshal::Int64 => "napi_create_int64",
+            Marshal::Double => "napi_create_double",
+            Marshal::String => "napi_create_string_utf8",
+            Marshal::External => "napi_create_external",
+        }
+    }
+}
+
+fn primitive_marshal(primitive: &PrimitiveType) -> Option<Marshal> {
+    Some(match *primitive {
+        PrimitiveType::Void | PrimitiveType::VaList => return None,
+        PrimitiveType::Bool => Marshal::Bool,
+        PrimitiveType::Char | PrimitiveType::SChar => Marshal::Int32,
+        PrimitiveType::UChar | PrimitiveType::Char32 => Marshal::Uint32,
+        PrimitiveType::Float | PrimitiveType::Double => Marshal::Double,
+        PrimitiveType::PtrDiffT => Marshal::Int64,
+        PrimitiveType::Integer { kind, signed, .. } => match kind {
+            IntKind::Short | IntKind::Int | IntKind::B8 | IntKind::B16 | IntKind::B32 => {
+                if signed {
+                    Marshal::Int32
+                } else {
+                    Marshal::Uint32
+                }
+            }
+            IntKind::Long | IntKind::LongLong | IntKind::SizeT | IntKind::Size | IntKind::B64 => {
+                Marshal::Int64
+            }
+        },
+    })
+}
+
+/// Writes the C source of a Node-API native module wrapping the functions.
+pub struct NapiWriter<'a> {
+    config: &'a Config,
+    /// The aliased types, resolved to marshal them.
+    typedefs: HashMap<String, Type>,
+    /// The fieldless enums, marshalled as numbers.
+    enums: HashSet<String>,
+}
+
+impl<'a> NapiWriter<'a> {
+    pub fn new(config: &'a Config, items: &[ItemContainer]) -> Self {
+        let mut typedefs = HashMap::new();
+        let mut enums = HashSet::new();
+        for item in items {
+            match *item {
+                ItemContainer::Typedef(ref t) => {
+                    typedefs.insert(t.export_name().to_owned(), t.aliased.clone());
+                }
+                ItemContainer::Enum(ref e) if e.tag.is_none() => {
+                    enums.insert(e.export_name().to_owned());
+                }
+                _ => {}
+            }
+        }
+
+        NapiWriter {
+            config,
+            typedefs,
+            enums,
+        }
+    }
+
+    /// Replaces typedefs by the type they alias.
+    fn resolve<'t>(&'t self, ty: &'t Type) -> &'t Type {
+        let mut ty = ty;
+        while let Type::Path(ref generic) = *ty {
+            match self.typedefs.get(generic.export_name()) {
+                Some(aliased) if aliased != ty => ty = aliased,
+                _ => break,
+            }
+        }
+        ty
+    }
+
+    /// How a value of type `ty` is marshalled, if it can be.
+    fn marshal(&self, ty: &Type) -> Option<Marshal> {
+        match *self.resolve(ty) {
+            Type::Primitive(ref primitive) => primitive_marshal(primitive),
+            Type::Path(ref generic) if self.enums.contains(generic.export_name()) => {
+                Some(Marshal::Int32)
+            }
+            Type::Ptr {
+                ref ty,
+                is_const: true,
+                ..
+            } if **ty == Type::Primitive(PrimitiveType::Char) => Some(Marshal::String),
+            Type::Ptr { .. } => Some(Marshal::External),
+            Type::Path(..) | Type::Array(..) | Type::FuncPtr { .. } => None,
+        }
+    }
+
+    /// The marshalling of the arguments and the return value, `None` for a
+    /// `void` return value, if the function can be wrapped.
+    fn signature(&self, function: &Function) -> Option<(Vec<Marshal>, Option<Marshal>)> {
+        let args = function
+            .args
+            .iter()
+            .map(|arg| self.marshal(&arg.ty))
+            .collect::<Option<Vec<_>>>()?;
+        let ret = match function.ret {
+            Type::Primitive(PrimitiveType::Void) => None,
+            ref ret => Some(self.marshal(ret)?),
+        };
+        Some((args, ret))
+    }
+
+    fn write_arg<F: Write>(&self, out: &mut SourceWriter<F>, i: usize, marshal: Marshal) {
+        if marshal == Marshal::String {
+            write!(out, "size_t arg{}_len;", i);
+            out.new_line();
+            write!(
+                out,
+                "NAPI_CALL(env, napi_get_value_string_utf8(env, argv[{}], NULL, 0, &arg{}_len));",
+                i, i
+            );
+            out.new_line();
+            write!(out, "char *arg{} = malloc(arg{}_len + 1);", i, i);
+            out.new_line();
+            write!(
+                out,
+                "NAPI_CALL(env, napi_get_value_string_utf8(env, argv[{}], arg{}, arg{}_len + 1, \
+                 &arg{}_len));",
+                i, i, i, i
+            );
+            out.new_line();
+            return;
+        }
+
+        write!(out, "{};", marshal.local(&format!("arg{}", i)));
+        out.new_line();
+        write!(
+            out,
+            "NAPI_CALL(env, {}(env, argv[{}], &arg{}));",
+            marshal.getter(),
+            i,
+            i
+        );
+        out.new_line();
+    }
+
+    fn write_result<F: Write>(&self, out: &mut SourceWriter<F>, ret: Option<Marshal>) {
+        out.write("napi_value result;");
+        out.new_line();
+        match ret {
+            None => out.write("NAPI_CALL(env, napi_get_undefined(env, &result));"),
+            Some(Marshal::String) => {
+                out.write("if (ret == NULL) {");
+                out.push_tab();
+                out.new_line();
+                out.write("NAPI_CALL(env, napi_get_null(env, &result));");
+                out.pop_tab();
+                out.new_line();
+                out.write("} else {");
+                out.push_tab();
+                out.new_line();
+                out.write(
+                    "NAPI_CALL(env, napi_create_string_utf8(env, ret, NAPI_AUTO_LENGTH, &result));",
+                );
+                out.pop_tab();
+                out.new_line();
+                out.write("}");
+            }
+            Some(Marshal::External) => {
+                out.write("NAPI_CALL(env, napi_create_external(env, ret, NULL, NULL, &result));")
+            }
+            Some(marshal) => write!(
+                out,
+                "NAPI_CALL(env, {}(env, ret, &result));",
+                marshal.creator()
+            ),
+        }
+        out.new_line();
+        out.write("return result;");
+    }
+
+    /// Writes the callback calling `function` with the JavaScript arguments.
+    fn write_wrapper<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        function: &Function,
+        args: &[Marshal],
+        ret: Option<Marshal>,
+    ) {
+        let name = function.path().name();
+
+        let condition = function.cfg.to_condition(self.config);
+        condition.write_before(self.config, out);
+
+        write!(
+            out,
+            "static napi_value js_{}(napi_env env, napi_callback_info info) {{",
+            name
+        );
+        out.push_tab();
+        out.new_line();
+
+        if args.is_empty() {
+            out.write("(void)info;");
+            out.new_line();
+        } else {
+            write!(out, "size_t argc = {};", args.len());
+            out.new_line();
+            write!(out, "napi_value argv[{}];", args.len());
+            out.new_line();
+            out.write("NAPI_CALL(env, napi_get_cb_info(env, info, &argc, argv, NULL, NULL));");
+            out.new_line();
+            write!(out, "if (argc < {}) {{", args.len());
+            out.push_tab();
+            out.new_line();
+            write!(
+                out,
+                "napi_throw_type_error(env, NULL, \"{} expects {} argument{}\");",
+                name,
+                args.len(),
+                if args.len() == 1 { "" } else { "s" }
+            );
+            out.new_line();
+            out.write("return NULL;");
+            out.pop_tab();
+            out.new_line();
+            out.write("}");
+            out.new_line();
+        }
+        for (i, marshal) in args.iter().enumerate() {
+            self.write_arg(out, i, *marshal);
+        }
+
+        match ret {
+            // Pointers to const are returned as external values.
+            Some(Marshal::External) => out.write("void *ret = (void *)"),
+            Some(marshal) => write!(out, "{} = ", marshal.local("ret")),
+            None => {}
+        }
+        write!(out, "{}(", name);
+        for i in 0..args.len() {
+            if i != 0 {
+                out.write(", ");
+            }
+            write!(out, "arg{}", i);
+        }
+        out.write(");");
+        out.new_line();
+        for (i, marshal) in args.iter().enumerate() {
+            if *marshal == Marshal::String {
+                write!(out, "free(arg{});", i);
+                out.new_line();
+            }
+        }
+
+        self.write_result(out, ret);
+        out.pop_tab();
+        out.new_line();
+        out.write("}");
+        condition.write_after(self.config, out);
+        out.new_line();
+    }
+
+    pub fn write<F: Write>(&self, out: &mut SourceWriter<F>, functions: &[Function]) {
+        if let Some(ref header) = self.config.header {
+            write!(out, "{}", header);
+            out.new_line();
+            out.new_line();
+        }
+        if let Some(ref warning) = self.config.autogen_warning {
+            write!(out, "{}", warning);
+            out.new_line();
+            out.new_line();
+        }
+
+        out.write("#include <stdlib.h>");
+        out.new_line();
+        out.write("#include <node_api.h>");
+        out.new_line();
+        match self.config.napi.header {
+            Some(ref header) => {
+                write!(out, "#include \"{}\"", header);
+                out.new_line();
+            }
+            None => warn!("No header declaring the functions, set `napi.header`."),
+        }
+        out.new_line();
+
+        // Throws a JavaScript error when a call to N-API fails.
+        out.write("#define NAPI_CALL(env, call) \\");
+        out.new_line();
+        out.write("  do { \\");
+        out.new_line();
+        out.write("    if ((call) != napi_ok) { \\");
+        out.new_line();
+        out.write("      napi_throw_error((env), NULL, #call \" failed\"); \\");
+        out.new_line();
+        out.write("      return NULL; \\");
+        out.new_line();
+        out.write("    } \\");
+        out.new_line();
+        out.write("  } while (0)");
+        out.new_line();
+
+        let mut wrapped = Vec::new();
+        for function in functions {
+            // The functions of `extern` blocks aren't exported by the crate.
+            if function.extern_decl {
+                continue;
+            }
+            if function.variadic {
+                warn!(
+                    "Skipping {}: variadic functions can't be wrapped.",
+                    function.path().name()
+                );
+                continue;
+            }
+            let (args, ret) = match self.signature(function) {
+                Some(signature) => signature,
+                None => {
+                    warn!(
+                        "Skipping {}: its arguments or return value can't be marshalled.",
+                        function.path().name()
+                    );
+                    continue;
+                }
+            };
+            out.new_line();
+            self.write_wrapper(out, function, &args, ret);
+            wrapped.push(function);
+        }
+
+        out.new_line();
+        out.write("NAPI_MODULE_INIT() {");
+        out.push_tab();
+        out.new_line();
+        if wrapped.is_empty() {
+            out.write("(void)env;");
+            out.new_line();
+            out.write("return exports;");
+            out.pop_tab();
+            out.new_line();
+            out.write("}");
+            out.new_line();
+            return;
+        }
+        out.write("napi_property_descriptor properties[] = {");
+        out.push_tab();
+        for function in &wrapped {
+            let name = function.path().name();
+            let condition = function.cfg.to_condition(self.config);
+            out.new_line();
+            condition.write_before(self.config, out);
+            write!(
+                out,
+                "{{ \"{}\", NULL, js_{}, NULL, NULL, NULL, napi_default, NULL }},",
+                name, name
+            );
+            condition.write_after(self.config, out);
+        }
+        out.pop_tab();
+        out.new_line();
+        out.write("};");
+        out.new_line();
+        out.write("NAPI_CALL(env, napi_define_properties(env, exports, ");
+        out.write("sizeof(properties) / sizeof(properties[0]), properties));");
+        out.new_line();
+        out.write("return exports;");
+        out.pop_tab();
+        out.new_line();
+        out.write("}");
+        out.new_line();
+    }
+}
@@ -0,0 +1,137 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::bindgen::config::{Config, VERSION};
+use crate::bindgen::ir::Cfg;
+use crate::bindgen::parser::LoadedMod;
+
+/// The name of the cache file, in the target directory of the crate.
+pub const CACHE_FILE_NAME: &str = "cbindgen-cache.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile {
+    version: String,
+    config: u64,
+    modules: HashMap<String, LoadedMod>,
+}
+
+/// The modules loaded from the source files by previous runs, keyed on the
+/// hash of their source and of everything else their loading depends on.
+///
+/// Only the modules used by the current run are kept when saving, so the
+/// cache doesn't grow when the sources change.
+#[derive(Debug, Clone)]
+pub(crate) struct ParseCache {
+    path: PathBuf,
+    config: u64,
+    previous: HashMap<String, LoadedMod>,
+    current: HashMap<String, LoadedMod>,
+    dirty: bool,
+}
+
+impl ParseCache {
+    /// Loads the cache at `path`, which is empty if it doesn't exist or was
+    /// written by another version or with other settings.
+    pub(crate) fn load(path: &Path, config: &Config) -> ParseCache {
+        let config = hash_config(config);
+        let previous = match File::open(path) {
+            Ok(file) => match serde_json::from_reader::<_, CacheFile>(BufReader::new(file)) {
+                Ok(cache) if cache.version == VERSION && cache.config == config => cache.modules,
+                Ok(_) => HashMap::new(),
+                Err(err) => {
+                    warn!("Ignoring invalid cache {}: {}", path.display(), err);
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+
+        ParseCache {
+            path: path.to_owned(),
+            config,
+            previous,
+            current: HashMap::new(),
+            dirty: false,
+        }
+    }
+
+    /// The key of a module whose source is `src`.
+    pub(crate) fn key(
+        src: &str,
+        crate_name: &str,
+        binding_crate_name: &str,
+        mod_cfg: Option<&Cfg>,
+    ) -> String {
+        let mut hasher = DefaultHasher::new();
+        src.hash(&mut hasher);
+        crate_name.hash(&mut hasher);
+        binding_crate_name.hash(&mut hasher);
+        format!("{:?}", mod_cfg).hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    pub(crate) fn get(&mut self, key: &str) -> Option<LoadedMod> {
+        if let Some(module) = self.current.get(key) {
+            return Some(module.clone());
+        }
+        let module = self.previous.remove(key)?;
+        self.current.insert(key.to_owned(), module.clone());
+        Some(module)
+    }
+
+    pub(crate) fn insert(&mut self, key: String, module: LoadedMod) {
+        self.current.insert(key, module);
+        self.dirty = true;
+    }
+
+    /// Writes the modules used by this run, if they differ from the ones that
+    /// were loaded. The file is replaced at once, so that concurrent runs
+    /// never read a partial cache.
+    pub(crate) fn save(self) -> io::Result<()> {
+        if !self.dirty && self.previous.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let cache = CacheFile {
+            version: VERSION.to_owned(),
+            config: self.config,
+            modules: self.current,
+        };
+        let tmp_path = self
+            .path
+            .with_extension(format!("json.{}", std::process::id()));
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            serde_json::to_writer(&mut writer, &cache)?;
+            writer.flush()?;
+        }
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+/// Hashes the settings. They're serialized into a `serde_json::Value`, whose
+/// objects are sorted by key, since the `Debug` output of the maps depends on
+/// their random state.
+fn hash_config(config: &Config) -> u64 {
+    let mut config = config.clone();
+    // These don't change what's loaded.
+    config.parse.jobs = 0;
+    config.parse.cache = true;
+
+    let mut hasher = DefaultHasher::new();
+    // The maps are keyed by strings, so this can't fail.
+    let value = serde_json::to_value(&config).unwrap();
+    value.to_string().hash(&mut hasher);
+    hasher.finish()
+}
@@ -5,18 +5,28 @@
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufReader, Read};
 use std::path::{Path as FilePath, PathBuf as FilePathBuf};
+use std::sync::{Arc, Mutex};
 
+use serde::Serialize;
+
+use crate::bindgen::bindings::IrFile;
 use crate::bindgen::bitflags;
+use crate::bindgen::builder::ItemDecl;
 use crate::bindgen::cargo::{Cargo, PackageRef};
-use crate::bindgen::config::{Config, ParseConfig};
+use crate::bindgen::config::{Config, ParseConfig, VERSION};
+use crate::bindgen::diagnostic::{self, Diagnostic};
 use crate::bindgen::error::Error;
 use crate::bindgen::ir::{
-    AnnotationSet, Cfg, Constant, Documentation, Enum, Function, GObject, GType, GenericParams,
-    ItemMap, OpaqueItem, Path, Static, Struct, Type, Typedef, Union,
+    AnnotationSet, AnnotationValue, Cfg, Constant, Documentation, Enum, Function, GObject, GType,
+    GenericParams, Item, ItemContainer, ItemMap, OpaqueItem, Path, Static, Struct, Type, Typedef,
+    Union,
 };
+use crate::bindgen::local_macros::{self, MacroRules};
+use crate::bindgen::parse_cache::{self, ParseCache};
 use crate::bindgen::utilities::{SynAbiHelpers, SynAttributeHelpers, SynItemFnHelpers};
+use crate::bindgen::work_queue;
 
 const STD_CRATES: &[&str] = &[
     "std",
@@ -29,25 +39,35 @@ const STD_CRATES: &[&str] = &[
 
 type ParseResult = Result<Parse, Error>;
 
+/// How deep macros expanding to other macro invocations are followed.
+const MAX_MACRO_EXPANSION_DEPTH: usize = 64;
+
+/// Collects the Rust files in `dir` and its subdirectories.
+fn rust_files(dir: &FilePath, files: &mut Vec<FilePathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries.filter_map(|x| x.ok().map(|x| x.path())) {
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().map_or(false, |x| x == "rs") {
+            files.push(path);
+        }
+    }
+}
+
 /// Parses a single rust source file, not following `mod` or `extern crate`.
 pub fn parse_src(src_file: &FilePath, config: &Config) -> ParseResult {
     let mod_name = src_file.file_stem().unwrap().to_str().unwrap();
     let mut config = config.clone();
     config.parse = ParseConfig {
         parse_deps: true,
+        expand_local_macros: config.parse.expand_local_macros,
         ..ParseConfig::default()
     };
 
-    let mut context = Parser {
-        binding_crate_name: mod_name.to_owned(),
-        config: &config,
-        lib: None,
-        parsed_crates: HashSet::new(),
-        cache_src: HashMap::new(),
-        cache_expanded_crate: HashMap::new(),
-        cfg_stack: Vec::new(),
-        out: Parse::new(),
-    };
+    let mut context = Parser::new(mod_name.to_owned(), &config, None, None);
 
     let pkg_ref = PackageRef {
         name: mod_name.to_owned(),
@@ -58,44 +78,340 @@ pub fn parse_src(src_file: &FilePath, config: &Config) -> ParseResult {
     Ok(context.out)
 }
 
+/// Parses an item added with `Builder::with_extra_declaration`.
+pub(crate) fn parse_declaration(decl: &ItemDecl, config: &Config) -> ParseResult {
+    let src = match *decl {
+        ItemDecl::Rust(ref src) => src.clone(),
+        ItemDecl::Typedef { ref name, ref ty } => format!("pub type {} = {};", name, ty),
+        ItemDecl::Opaque(ref name) => {
+            let mut out = Parse::new();
+            out.opaque_items.try_insert(OpaqueItem::new(
+                Path::new(name.clone()),
+                GenericParams::default(),
+                None,
+                AnnotationSet::new(),
+                Documentation::none(),
+            ));
+            return Ok(out);
+        }
+        ItemDecl::Function(ref signature) => format!("extern \"C\" {{ {}; }}", signature),
+    };
+
+    let crate_name = "extra_declarations";
+    let i = syn::parse_file(&src).map_err(|x| Error::ParseSyntaxError {
+        crate_name: crate_name.to_owned(),
+        src_path: "<extra declaration>".to_owned(),
+        error: x,
+    })?;
+
+    let mut config = config.clone();
+    config.parse = ParseConfig::default();
+    let mut context = Parser::new(crate_name.to_owned(), &config, None, None);
+    let pkg_ref = PackageRef {
+        name: crate_name.to_owned(),
+        version: None,
+    };
+    let loaded_mod = context.load_mod(&pkg_ref, &i.items);
+    context.add_mod(&pkg_ref, None, None, &loaded_mod, 0)?;
+    Ok(context.out)
+}
+
+/// Loads the items of an IR file written by `--emit-ir`.
+pub(crate) fn parse_ir_file(path: &FilePath) -> ParseResult {
+    let ir_error = |error| Error::IrFile(path.to_string_lossy().into(), error);
+    let file = File::open(path).map_err(|x| ir_error(serde_json::Error::io(x)))?;
+    let ir: IrFile = serde_json::from_reader(BufReader::new(file)).map_err(ir_error)?;
+    if ir.version != VERSION {
+        warn!(
+            "{} was written by cbindgen {}, not {}.",
+            path.display(),
+            ir.version,
+            VERSION
+        );
+    }
+
+    let mut out = Parse::new();
+    for constant in ir.constants {
+        out.constants.try_insert(constant);
+    }
+    for global in ir.globals {
+        out.globals.try_insert(global);
+    }
+    for item in ir.items {
+        match item {
+            ItemContainer::Constant(x) => out.constants.try_insert(x),
+            ItemContainer::Static(x) => out.globals.try_insert(x),
+            ItemContainer::OpaqueItem(x) => out.opaque_items.try_insert(x),
+            ItemContainer::Struct(x) => out.structs.try_insert(x),
+            ItemContainer::Union(x) => out.unions.try_insert(x),
+            ItemContainer::Enum(x) => out.enums.try_insert(x),
+            ItemContainer::Typedef(x) => out.typedefs.try_insert(x),
+            ItemContainer::GObject(x) => out.gobjects.try_insert(x),
+        };
+    }
+    for gobject in ir.gobjects {
+        out.gobjects.try_insert(gobject);
+    }
+    out.functions = ir.functions;
+    out.source_files.push(path.to_owned());
+    Ok(out)
+}
+
 /// Recursively parses a rust library starting at the root crate's directory.
 ///
 /// Inside a crate, `mod` and `extern crate` declarations are followed
 /// and parsed. To find an external crate, the parser uses the `cargo metadata`
 /// command to find the location of dependencies.
 pub(crate) fn parse_lib(lib: Cargo, config: &Config) -> ParseResult {
-    let mut context = Parser {
-        binding_crate_name: lib.binding_crate_name().to_owned(),
-        config,
-        lib: Some(lib),
-        parsed_crates: HashSet::new(),
-        cache_src: HashMap::new(),
-        cache_expanded_crate: HashMap::new(),
-        cfg_stack: Vec::new(),
-        out: Parse::new(),
+    // The macros seen in a module change how the next ones are loaded.
+    let cache = if config.parse.cache && !config.parse.expand_local_macros {
+        let path = lib.target_dir().join(parse_cache::CACHE_FILE_NAME);
+        Some(Arc::new(Mutex::new(ParseCache::load(&path, config))))
+    } else {
+        None
     };
 
-    let binding_crate = context.lib.as_ref().unwrap().binding_crate_ref();
+    let lib = Arc::new(lib);
+    let binding_crate_name = lib.binding_crate_name().to_owned();
+    let mut context = Parser::new(binding_crate_name, config, Some(&lib), cache);
+
+    let binding_crate = lib.binding_crate_ref();
+    if config.parse.jobs > 1 && !config.parse.expand_local_macros {
+        let mut tasks = Vec::new();
+        context.plan_crate(&binding_crate, &mut tasks);
+        context.parsed_crates.clear();
+        context.prefetched = prefetch(
+            config.parse.jobs,
+            tasks,
+            Arc::new(config.clone()),
+            Arc::clone(&lib),
+            context.cache.clone(),
+        );
+    }
     context.parse_crate(&binding_crate)?;
+
+    if let Some(cache) = context.cache.take() {
+        // The threads loading modules ahead of time are done with it.
+        let cache = Arc::try_unwrap(cache).ok().unwrap().into_inner().unwrap();
+        if let Err(err) = cache.save() {
+            warn!("Couldn't write the parse cache: {}", err);
+        }
+    }
     Ok(context.out)
 }
 
-#[derive(Debug, Clone)]
+/// The crate, path to a module file, and cfg of the modules loaded ahead of
+/// time.
+type ModKey = (String, FilePathBuf, String);
+
+fn mod_key(pkg: &PackageRef, mod_path: &FilePath, cfg_stack: &[Cfg]) -> ModKey {
+    (
+        pkg.name.clone(),
+        mod_path.to_path_buf(),
+        format!("{:?}", Cfg::join(cfg_stack)),
+    )
+}
+
+/// Something to load ahead of the serial pass of `parse_lib`.
+enum Prefetch {
+    /// A crate, expanded or not.
+    Crate {
+        pkg: PackageRef,
+        cfg_stack: Vec<Cfg>,
+    },
+    /// A module file of a crate.
+    Mod {
+        pkg: PackageRef,
+        mod_path: FilePathBuf,
+        cfg_stack: Vec<Cfg>,
+        depth: usize,
+    },
+}
+
+/// The modules loaded ahead of time, with their source.
+#[derive(Debug, Default)]
+struct Prefetched {
+    mods: HashMap<ModKey, Result<(String, LoadedMod), Error>>,
+    expanded_crates: HashMap<(String, String), Result<LoadedMod, Error>>,
+}
+
+/// Loads the crates, and the module files they're made of, on `jobs`
+/// threads. `parse_lib` then goes through them in order, as if it had
+/// loaded them itself, so that the result doesn't depend on which thread
+/// finished first.
+fn prefetch(
+    jobs: usize,
+    tasks: Vec<Prefetch>,
+    config: Arc<Config>,
+    lib: Arc<Cargo>,
+    cache: Option<Arc<Mutex<ParseCache>>>,
+) -> Prefetched {
+    let prefetched = Arc::new(Mutex::new(Prefetched::default()));
+    let queued_mods = Arc::new(Mutex::new(HashSet::new()));
+
+    let results = Arc::clone(&prefetched);
+    work_queue::run(jobs, tasks, move |task, new_tasks| {
+        let binding_crate_name = lib.binding_crate_name().to_owned();
+        let mut context = Parser::new(binding_crate_name, &config, Some(&lib), cache.clone());
+        match task {
+            Prefetch::Crate { pkg, cfg_stack } => {
+                if config.parse.expand.should_expand(&pkg.name) {
+                    if std::env::var("_CBINDGEN_IS_RUNNING").is_ok() {
+                        return;
+                    }
+                    context.cfg_stack = cfg_stack;
+                    let result = context
+                        .expanded_crate_items(&pkg)
+                        .map(|items| context.load_mod(&pkg, &items));
+                    let key = (pkg.name, format!("{:?}", Cfg::join(&context.cfg_stack)));
+                    results.lock().unwrap().expanded_crates.insert(key, result);
+                } else if let Some(mod_path) = lib.find_crate_src(&pkg) {
+                    new_tasks.push(Prefetch::Mod {
+                        pkg,
+                        mod_path,
+                        cfg_stack,
+                        depth: 0,
+                    });
+                }
+            }
+            Prefetch::Mod {
+                pkg,
+                mod_path,
+                cfg_stack,
+                depth,
+            } => {
+                let key = mod_key(&pkg, &mod_path, &cfg_stack);
+                if !queued_mods.lock().unwrap().insert(key.clone()) {
+                    return;
+                }
+
+                context.cfg_stack = cfg_stack;
+                let result = context.read_mod(&pkg, &mod_path).and_then(|src| {
+                    let loaded_mod = context.load_file(&pkg, &mod_path, &src)?;
+                    Ok((src, loaded_mod))
+                });
+                if let Ok((_, ref loaded_mod)) = result {
+                    let (mod_dir, submod_dir) = mod_dirs(&mod_path, depth);
+                    context.prefetch_nested_mods(
+                        &pkg,
+                        &mod_dir,
+                        &submod_dir,
+                        loaded_mod,
+                        depth,
+                        new_tasks,
+                    );
+                }
+                results.lock().unwrap().mods.insert(key, result);
+            }
+        }
+    });
+
+    Arc::try_unwrap(prefetched)
+        .ok()
+        .unwrap()
+        .into_inner()
+        .unwrap()
+}
+
+/// The directory of a module file, and the one its nested modules are in,
+/// according to Rust 2018 rules.
+fn mod_dirs(mod_path: &FilePath, depth: usize) -> (FilePathBuf, FilePathBuf) {
+    let mod_dir = mod_path.parent().unwrap().to_path_buf();
+    let submod_dir = if depth == 0 || mod_path.ends_with("mod.rs") {
+        mod_dir.clone()
+    } else {
+        mod_dir.join(mod_path.file_stem().unwrap())
+    };
+    (mod_dir, submod_dir)
+}
+
+/// Finds the file of a module declared without its content.
+fn find_mod_path(
+    mod_dir: &FilePath,
+    submod_dir: &FilePath,
+    nested_mod: &NestedMod,
+) -> Option<FilePathBuf> {
+    let next_mod_path1 = submod_dir.join(nested_mod.name.clone() + ".rs");
+    let next_mod_path2 = submod_dir.join(&nested_mod.name).join("mod.rs");
+
+    if next_mod_path1.exists() {
+        Some(next_mod_path1)
+    } else if next_mod_path2.exists() {
+        Some(next_mod_path2)
+    } else {
+        // Last chance to find a module path
+        nested_mod.path.as_ref().map(|path| mod_dir.join(path))
+    }
+}
+
+#[derive(Debug)]
 struct Parser<'a> {
     binding_crate_name: String,
-    lib: Option<Cargo>,
+    lib: Option<&'a Cargo>,
     config: &'a Config,
 
     parsed_crates: HashSet<String>,
-    cache_src: HashMap<FilePathBuf, Vec<syn::Item>>,
+    cache_src: HashMap<FilePathBuf, String>,
     cache_expanded_crate: HashMap<String, Vec<syn::Item>>,
 
     cfg_stack: Vec<Cfg>,
+    /// The modules of the crate that `add_mod` is in.
+    mod_stack: Vec<String>,
+
+    /// The `macro_rules!` seen so far, by crate and name.
+    local_macros: HashMap<(String, String), MacroRules>,
+
+    /// The modules loaded by previous runs.
+    cache: Option<Arc<Mutex<ParseCache>>>,
+    prefetched: Prefetched,
 
     out: Parse,
 }
 
+/// The items loaded from a module, and its nested modules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct LoadedMod {
+    items: Parse,
+    nested_modules: Vec<NestedMod>,
+    /// The diagnostics logged while loading the file of the module.
+    #[serde(default)]
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// A `mod` declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NestedMod {
+    name: String,
+    cfg: Option<Cfg>,
+    /// The value of its `#[path]` attribute.
+    path: Option<String>,
+    /// The items of an inline module.
+    content: Option<LoadedMod>,
+}
+
 impl<'a> Parser<'a> {
+    fn new(
+        binding_crate_name: String,
+        config: &'a Config,
+        lib: Option<&'a Cargo>,
+        cache: Option<Arc<Mutex<ParseCache>>>,
+    ) -> Parser<'a> {
+        Parser {
+            binding_crate_name,
+            config,
+            lib,
+            parsed_crates: HashSet::new(),
+            cache_src: HashMap::new(),
+            cache_expanded_crate: HashMap::new(),
+            cfg_stack: Vec::new(),
+            mod_stack: Vec::new(),
+            local_macros: HashMap::new(),
+            cache,
+            prefetched: Prefetched::default(),
+            out: Parse::new(),
+        }
+    }
+
     fn should_parse_dependency(&self, pkg_name: &str) -> bool {
         if self.parsed_crates.contains(pkg_name) {
             return false;
@@ -106,14 +422,7 @@ impl<'a> Parser<'a> {
         }
 
         // Skip any whitelist or blacklist for expand
-        if self
-            .config
-            .parse
-            .expand
-            .crates
-            .iter()
-            .any(|name| name == pkg_name)
-        {
+        if self.config.parse.expand.should_expand(pkg_name) {
             return true;
         }
 
@@ -141,13 +450,13 @@ impl<'a> Parser<'a> {
         self.parsed_crates.insert(pkg.name.clone());
 
         // Check if we should use cargo expand for this crate
-        if self.config.parse.expand.crates.contains(&pkg.name) {
+        if self.config.parse.expand.should_expand(&pkg.name) {
             self.parse_expand_crate(pkg)?;
         } else {
             // Parse the crate before the dependencies otherwise the same-named idents we
             // want to generate bindings for would be replaced by the ones provided
             // by the first dependency containing it.
-            let crate_src = self.lib.as_ref().unwrap().find_crate_src(pkg);
+            let crate_src = self.lib.unwrap().find_crate_src(pkg);
 
             match crate_src {
                 Some(crate_src) => self.parse_mod(pkg, crate_src.as_path(), 0)?,
@@ -161,7 +470,7 @@ impl<'a> Parser<'a> {
             }
         }
 
-        for (dep_pkg, cfg) in self.lib.as_ref().unwrap().dependencies(&pkg) {
+        for (dep_pkg, cfg) in self.lib.unwrap().dependencies(pkg) {
             if !self.should_parse_dependency(&dep_pkg.name) {
                 continue;
             }
@@ -180,6 +489,31 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
+    /// Lists the crates `parse_crate` goes through, in order.
+    fn plan_crate(&mut self, pkg: &PackageRef, tasks: &mut Vec<Prefetch>) {
+        self.parsed_crates.insert(pkg.name.clone());
+        tasks.push(Prefetch::Crate {
+            pkg: pkg.clone(),
+            cfg_stack: self.cfg_stack.clone(),
+        });
+
+        for (dep_pkg, cfg) in self.lib.unwrap().dependencies(&pkg) {
+            if !self.should_parse_dependency(&dep_pkg.name) {
+                continue;
+            }
+
+            if let Some(ref cfg) = cfg {
+                self.cfg_stack.push(cfg.clone());
+            }
+
+            self.plan_crate(&dep_pkg, tasks);
+
+            if cfg.is_some() {
+                self.cfg_stack.pop();
+            }
+        }
+    }
+
     fn parse_expand_crate(&mut self, pkg: &PackageRef) -> Result<(), Error> {
         assert!(self.lib.is_some());
 
@@ -190,18 +524,50 @@ impl<'a> Parser<'a> {
             return Ok(());
         }
 
+        let key = (
+            pkg.name.clone(),
+            format!("{:?}", Cfg::join(&self.cfg_stack)),
+        );
+        let loaded_mod = match self.prefetched.expanded_crates.remove(&key) {
+            Some(loaded_mod) => loaded_mod?,
+            None => {
+                let mod_items = self.expanded_crate_items(pkg)?;
+                self.load_mod(pkg, &mod_items)
+            }
+        };
+        self.add_expanded_source_files(pkg);
+        self.add_mod(pkg, None, None, &loaded_mod, 0)
+    }
+
+    /// Records the source files of an expanded crate, which its expansion
+    /// doesn't tell, as the Rust files of the directory of its root module.
+    fn add_expanded_source_files(&mut self, pkg: &PackageRef) {
+        let src_dir = match self.lib.unwrap().find_crate_src(pkg) {
+            Some(src) => src.parent().unwrap().to_owned(),
+            None => return,
+        };
+        let mut files = Vec::new();
+        rust_files(&src_dir, &mut files);
+        files.sort();
+        for file in files {
+            if !self.out.source_files.contains(&file) {
+                self.out.source_files.push(file);
+            }
+        }
+    }
+
+    fn expanded_crate_items(&mut self, pkg: &PackageRef) -> Result<Vec<syn::Item>, Error> {
         let mod_items = {
             if !self.cache_expanded_crate.contains_key(&pkg.name) {
                 let s = self
                     .lib
-                    .as_ref()
                     .unwrap()
                     .expand_crate(
                         pkg,
-                        self.config.parse.expand.all_features,
-                        self.config.parse.expand.default_features,
-                        &self.config.parse.expand.features,
-                        self.config.parse.expand.profile,
+                        self.config.parse.expand.all_features(&pkg.name),
+                        self.config.parse.expand.default_features(&pkg.name),
+                        self.config.parse.expand.features(&pkg.name),
+                        self.config.parse.expand.profile(&pkg.name),
                     )
                     .map_err(|x| Error::CargoExpand(pkg.name.clone(), x))?;
                 let i = syn::parse_file(&s).map_err(|x| Error::ParseSyntaxError {
@@ -215,7 +581,7 @@ impl<'a> Parser<'a> {
             self.cache_expanded_crate.get(&pkg.name).unwrap().clone()
         };
 
-        self.process_mod(pkg, None, None, &mod_items, 0)
+        Ok(mod_items)
     }
 
     fn parse_mod(
@@ -224,7 +590,28 @@ impl<'a> Parser<'a> {
         mod_path: &FilePath,
         depth: usize,
     ) -> Result<(), Error> {
-        let mod_items = match self.cache_src.entry(mod_path.to_path_buf()) {
+        let key = mod_key(pkg, mod_path, &self.cfg_stack);
+        let loaded_mod = match self.prefetched.mods.remove(&key) {
+            Some(result) => {
+                let (src, loaded_mod) = result?;
+                if let Entry::Vacant(vacant_entry) = self.cache_src.entry(mod_path.to_path_buf()) {
+                    self.out.source_files.push(mod_path.to_path_buf());
+                    vacant_entry.insert(src);
+                }
+                loaded_mod
+            }
+            None => {
+                let src = self.read_mod(pkg, mod_path)?;
+                self.load_file(pkg, mod_path, &src)?
+            }
+        };
+
+        let (mod_dir, submod_dir) = mod_dirs(mod_path, depth);
+        self.add_mod(pkg, Some(&mod_dir), Some(&submod_dir), &loaded_mod, depth)
+    }
+
+    fn read_mod(&mut self, pkg: &PackageRef, mod_path: &FilePath) -> Result<String, Error> {
+        let src = match self.cache_src.entry(mod_path.to_path_buf()) {
             Entry::Vacant(vacant_entry) => {
                 let mut s = String::new();
                 let mut f = File::open(mod_path).map_err(|_| Error::ParseCannotOpenFile {
@@ -238,33 +625,140 @@ impl<'a> Parser<'a> {
                         src_path: mod_path.to_str().unwrap().to_owned(),
                     })?;
 
-                let i = syn::parse_file(&s).map_err(|x| Error::ParseSyntaxError {
-                    crate_name: pkg.name.clone(),
-                    src_path: mod_path.to_string_lossy().into(),
-                    error: x,
-                })?;
-
-                vacant_entry.insert(i.items).clone()
+                self.out.source_files.push(mod_path.to_path_buf());
+                vacant_entry.insert(s).clone()
             }
             Entry::Occupied(occupied_entry) => occupied_entry.get().clone(),
         };
+        Ok(src)
+    }
+
+    /// Loads the module file at `mod_path`, whose source is `src`, unless it
+    /// is in the cache.
+    fn load_file(
+        &mut self,
+        pkg: &PackageRef,
+        mod_path: &FilePath,
+        src: &str,
+    ) -> Result<LoadedMod, Error> {
+        let cache_key = if self.cache.is_some() {
+            Some(ParseCache::key(
+                src,
+                &pkg.name,
+                &self.binding_crate_name,
+                Cfg::join(&self.cfg_stack).as_ref(),
+            ))
+        } else {
+            None
+        };
+        let cached_mod = match (self.cache.as_ref(), cache_key.as_ref()) {
+            (Some(cache), Some(key)) => cache.lock().unwrap().get(key),
+            _ => None,
+        };
+
+        if let Some(loaded_mod) = cached_mod {
+            debug!("Using the cached items of {}", mod_path.display());
+            diagnostic::with_source_file(mod_path, || diagnostic::replay(&loaded_mod.diagnostics));
+            return Ok(loaded_mod);
+        }
+
+        let i = syn::parse_file(src).map_err(|x| Error::ParseSyntaxError {
+            crate_name: pkg.name.clone(),
+            src_path: mod_path.to_string_lossy().into(),
+            error: x,
+        })?;
+        let (mut loaded_mod, diagnostics) = diagnostic::with_source_file(mod_path, || {
+            diagnostic::record(|| self.load_mod(pkg, &i.items))
+        });
+        loaded_mod.diagnostics = diagnostics;
+        if let (Some(cache), Some(key)) = (self.cache.as_ref(), cache_key) {
+            cache.lock().unwrap().insert(key, loaded_mod.clone());
+        }
+        Ok(loaded_mod)
+    }
 
-        // Compute module directory according to Rust 2018 rules
-        let submod_dir_2018;
+    /// Lists the module files declared by a module, to load them ahead of
+    /// time.
+    fn prefetch_nested_mods(
+        &mut self,
+        pkg: &PackageRef,
+        mod_dir: &FilePath,
+        submod_dir: &FilePath,
+        loaded_mod: &LoadedMod,
+        depth: usize,
+        tasks: &mut Vec<Prefetch>,
+    ) {
+        for nested_mod in &loaded_mod.nested_modules {
+            if let Some(ref cfg) = nested_mod.cfg {
+                self.cfg_stack.push(cfg.clone());
+            }
 
-        let mod_dir = mod_path.parent().unwrap();
+            if let Some(ref content) = nested_mod.content {
+                let next_submod_dir = submod_dir.join(&nested_mod.name);
+                self.prefetch_nested_mods(pkg, mod_dir, &next_submod_dir, content, depth, tasks);
+            } else if let Some(mod_path) = find_mod_path(mod_dir, submod_dir, nested_mod) {
+                tasks.push(Prefetch::Mod {
+                    pkg: pkg.clone(),
+                    mod_path,
+                    cfg_stack: self.cfg_stack.clone(),
+                    depth: depth + 1,
+                });
+            }
 
-        let submod_dir = if depth == 0 || mod_path.ends_with("mod.rs") {
-            mod_dir
+            if nested_mod.cfg.is_some() {
+                self.cfg_stack.pop();
+            }
+        }
+    }
+
+    /// Loads the items of a module and of its inline modules.
+    fn load_mod(&mut self, pkg: &PackageRef, items: &[syn::Item]) -> LoadedMod {
+        let expanded_items;
+        let items = if self.config.parse.expand_local_macros {
+            expanded_items = self.expand_local_macros(pkg, items, 0);
+            &expanded_items
         } else {
-            submod_dir_2018 = mod_path
-                .parent()
-                .unwrap()
-                .join(mod_path.file_stem().unwrap());
-            &submod_dir_2018
+            items
         };
 
-        self.process_mod(pkg, Some(mod_dir), Some(submod_dir), &mod_items, depth)
+        let mut loaded_items = Parse::new();
+        let nested_modules = loaded_items.load_syn_crate_mod(
+            &self.config,
+            &self.binding_crate_name,
+            &pkg.name,
+            Cfg::join(&self.cfg_stack).as_ref(),
+            items,
+        );
+
+        let mut loaded_nested_modules = Vec::with_capacity(nested_modules.len());
+        for item in nested_modules {
+            let cfg = Cfg::load(&item.attrs);
+            let content = match item.content {
+                Some((_, ref inline_items)) => {
+                    if let Some(ref cfg) = cfg {
+                        self.cfg_stack.push(cfg.clone());
+                    }
+                    let content = self.load_mod(pkg, inline_items);
+                    if cfg.is_some() {
+                        self.cfg_stack.pop();
+                    }
+                    Some(content)
+                }
+                None => None,
+            };
+            loaded_nested_modules.push(NestedMod {
+                name: item.ident.to_string(),
+                cfg,
+                path: item.attrs.attr_name_value_lookup("path"),
+                content,
+            });
+        }
+
+        LoadedMod {
+            items: loaded_items,
+            nested_modules: loaded_nested_modules,
+            diagnostics: Vec::new(),
+        }
     }
 
     /// `mod_dir` is the path to the current directory of the module. It may be
@@ -272,74 +766,54 @@ impl<'a> Parser<'a> {
     ///
     /// `submod_dir` is the path to search submodules in by default, which might
     /// be different for rust 2018 for example.
-    fn process_mod(
+    fn add_mod(
         &mut self,
         pkg: &PackageRef,
         mod_dir: Option<&FilePath>,
         submod_dir: Option<&FilePath>,
-        items: &[syn::Item],
+        loaded_mod: &LoadedMod,
         depth: usize,
     ) -> Result<(), Error> {
         debug_assert_eq!(mod_dir.is_some(), submod_dir.is_some());
-        // We process the items first then the nested modules.
-        let nested_modules = self.out.load_syn_crate_mod(
-            &self.config,
-            &self.binding_crate_name,
-            &pkg.name,
-            Cfg::join(&self.cfg_stack).as_ref(),
-            items,
-        );
 
-        for item in nested_modules {
-            let next_mod_name = item.ident.to_string();
-            let cfg = Cfg::load(&item.attrs);
-            if let Some(ref cfg) = cfg {
+        // We add the items first then the nested modules.
+        let mut module = pkg.name.replace('-', "_");
+        for name in &self.mod_stack {
+            module.push_str("::");
+            module.push_str(name);
+        }
+        let group = self.split_group(pkg);
+        let namespace = self.module_namespace(pkg);
+        if group.is_none() && namespace.is_none() {
+            self.out.extend_with_mod(&loaded_mod.items, &module);
+        } else {
+            let mut items = loaded_mod.items.clone();
+            if let Some(group) = group {
+                items.add_default_annotation("header", AnnotationValue::Atom(Some(group)));
+            }
+            if let Some(namespace) = namespace {
+                items.add_default_annotation("namespace", AnnotationValue::Atom(Some(namespace)));
+            }
+            self.out.extend_with_mod(&items, &module);
+        }
+
+        for nested_mod in &loaded_mod.nested_modules {
+            let next_mod_name = &nested_mod.name;
+            if let Some(ref cfg) = nested_mod.cfg {
                 self.cfg_stack.push(cfg.clone());
             }
 
-            if let Some((_, ref inline_items)) = item.content {
-                let next_submod_dir = submod_dir.map(|dir| dir.join(&next_mod_name));
-                self.process_mod(
-                    pkg,
-                    mod_dir,
-                    next_submod_dir.as_deref(),
-                    inline_items,
-                    depth,
-                )?;
-            } else if let Some(mod_dir) = mod_dir {
-                let submod_dir = submod_dir.unwrap();
-                let next_mod_path1 = submod_dir.join(next_mod_name.clone() + ".rs");
-                let next_mod_path2 = submod_dir.join(next_mod_name.clone()).join("mod.rs");
-
-                if next_mod_path1.exists() {
-                    self.parse_mod(pkg, next_mod_path1.as_path(), depth + 1)?;
-                } else if next_mod_path2.exists() {
-                    self.parse_mod(pkg, next_mod_path2.as_path(), depth + 1)?;
-                } else {
-                    // Last chance to find a module path
-                    let mut path_attr_found = false;
-                    for attr in &item.attrs {
-                        if let Ok(syn::Meta::NameValue(syn::MetaNameValue { path, lit, .. })) =
-                            attr.parse_meta()
-                        {
-                            match lit {
-                                syn::Lit::Str(ref path_lit) if path.is_ident("path") => {
-                                    path_attr_found = true;
-                                    self.parse_mod(
-                                        pkg,
-                                        &mod_dir.join(path_lit.value()),
-                                        depth + 1,
-                                    )?;
-                                    break;
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
+            self.mod_stack.push(next_mod_name.clone());
 
-                    // This should be an error, but it's common enough to
-                    // just elicit a warning
-                    if !path_attr_found {
+            if let Some(ref content) = nested_mod.content {
+                let next_submod_dir = submod_dir.map(|dir| dir.join(next_mod_name));
+                self.add_mod(pkg, mod_dir, next_submod_dir.as_deref(), content, depth)?;
+            } else if let Some(mod_dir) = mod_dir {
+                match find_mod_path(mod_dir, submod_dir.unwrap(), nested_mod) {
+                    Some(mod_path) => self.parse_mod(pkg, &mod_path, depth + 1)?,
+                    None => {
+                        // This should be an error, but it's common enough to
+                        // just elicit a warning
                         warn!(
                             "Parsing crate `{}`: can't find mod {}`.",
                             pkg.name, next_mod_name
@@ -353,16 +827,139 @@ impl<'a> Parser<'a> {
                 );
             }
 
-            if cfg.is_some() {
+            self.mod_stack.pop();
+            if nested_mod.cfg.is_some() {
                 self.cfg_stack.pop();
             }
         }
 
         Ok(())
     }
+
+    /// The header the items of the current module go in when splitting the
+    /// bindings, if not the default one.
+    fn split_group(&self, pkg: &PackageRef) -> Option<String> {
+        if !self.config.output.split.enabled {
+            None
+        } else if pkg.name != self.binding_crate_name {
+            Some(pkg.name.clone())
+        } else {
+            self.mod_stack.first().cloned()
+        }
+    }
+
+    /// The C++ namespace of the items of the current module, with
+    /// `namespaces_from_modules`, unless it's the root of the crate.
+    fn module_namespace(&self, pkg: &PackageRef) -> Option<String> {
+        if !self.config.namespaces_from_modules
+            || pkg.name != self.binding_crate_name
+            || self.mod_stack.is_empty()
+        {
+            None
+        } else {
+            Some(self.mod_stack.join("::"))
+        }
+    }
+
+    /// Records the `macro_rules!` definitions among `items`, and replaces the
+    /// invocations of the ones already seen with the items they expand to.
+    fn expand_local_macros(
+        &mut self,
+        pkg: &PackageRef,
+        items: &[syn::Item],
+        depth: usize,
+    ) -> Vec<syn::Item> {
+        let mut expanded = Vec::with_capacity(items.len());
+        for item in items {
+            let item_macro = match *item {
+                syn::Item::Macro(ref item_macro) => item_macro,
+                _ => {
+                    expanded.push(item.clone());
+                    continue;
+                }
+            };
+            let name = match item_macro.mac.path.get_ident() {
+                Some(name) => name.to_string(),
+                None => {
+                    expanded.push(item.clone());
+                    continue;
+                }
+            };
+
+            if name == "macro_rules" {
+                if let Some(ref ident) = item_macro.ident {
+                    match local_macros::parse(item_macro.mac.tokens.clone()) {
+                        Ok(rules) => {
+                            self.local_macros
+                                .insert((pkg.name.clone(), ident.to_string()), rules);
+                        }
+                        Err(msg) => warn_at!(item_macro, "Cannot expand macro {}!: {}", ident, msg),
+                    }
+                }
+                continue;
+            }
+
+            let key = (pkg.name.clone(), name);
+            let tokens = match self.local_macros.get(&key) {
+                Some(rules) => rules.expand(item_macro.mac.tokens.clone()),
+                None => {
+                    expanded.push(item.clone());
+                    continue;
+                }
+            };
+            if depth >= MAX_MACRO_EXPANSION_DEPTH {
+                warn_at!(
+                    item_macro,
+                    "Cannot expand {}!: recursion limit reached.",
+                    key.1
+                );
+                continue;
+            }
+            match tokens
+                .and_then(|tokens| syn::parse2::<syn::File>(tokens).map_err(|err| err.to_string()))
+            {
+                Ok(file) => {
+                    let items = self.expand_local_macros(pkg, &file.items, depth + 1);
+                    expanded.extend(items);
+                }
+                Err(msg) => warn_at!(item_macro, "Cannot expand {}!: {}", key.1, msg),
+            }
+        }
+        expanded
+    }
 }
 
-#[derive(Debug, Clone)]
+/// An item of another module than the item with the same name that was
+/// loaded first, and which is written in its place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowedItem {
+    pub path: Path,
+    pub annotations: AnnotationSet,
+    /// Whether its definition differs from the one of the item written in its
+    /// place, which wouldn't compile.
+    pub differs: bool,
+}
+
+/// The definition of `item` without its annotations and documentation, to
+/// compare it to the one of an item of another module with the same name.
+fn definition<T: Serialize>(item: &T) -> serde_json::Value {
+    fn strip(value: &mut serde_json::Value) {
+        match *value {
+            serde_json::Value::Object(ref mut map) => {
+                map.remove("annotations");
+                map.remove("documentation");
+                map.values_mut().for_each(strip);
+            }
+            serde_json::Value::Array(ref mut values) => values.iter_mut().for_each(strip),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(item).unwrap_or(serde_json::Value::Null);
+    strip(&mut value);
+    value
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Parse {
     pub constants: ItemMap<Constant>,
     pub globals: ItemMap<Static>,
@@ -373,6 +970,14 @@ pub struct Parse {
     pub typedefs: ItemMap<Typedef>,
     pub functions: Vec<Function>,
     pub gobjects: ItemMap<GObject>,
+    /// The source files that were read, in order.
+    pub source_files: Vec<FilePathBuf>,
+    /// The items of other modules dropped because an item with the same name
+    /// was loaded first.
+    pub shadowed: Vec<ShadowedItem>,
+    /// The exported items which couldn't be loaded, like a `no_mangle`
+    /// function using a type that C can't declare.
+    pub skipped: Vec<String>,
 }
 
 impl Parse {
@@ -387,6 +992,9 @@ impl Parse {
             typedefs: ItemMap::default(),
             functions: Vec::new(),
             gobjects: ItemMap::default(),
+            source_files: Vec::new(),
+            shadowed: Vec::new(),
+            skipped: Vec::new(),
         }
     }
 
@@ -396,7 +1004,7 @@ impl Parse {
             let generic_params: Vec<_> = generic_params.into_iter().map(Path::new).collect();
             self.opaque_items.try_insert(OpaqueItem::new(
                 path,
-                GenericParams(generic_params),
+                GenericParams::from_paths(generic_params),
                 None,
                 AnnotationSet::new(),
                 Documentation::none(),
@@ -421,7 +1029,7 @@ impl Parse {
         add_opaque("ManuallyDrop", vec!["T"]);
         add_opaque("MaybeUninit", vec!["T"]);
 
-        // if config.gobject {
+        // if config.gobject.enabled {
         //     add_opaque("GType", vec![]);
         //     add_opaque("GObject", vec![""]);
         //     add_opaque("GObjectClass", vec![""]);
@@ -438,6 +1046,103 @@ impl Parse {
         self.typedefs.extend_with(&other.typedefs);
         self.gobjects.extend_with(&other.gobjects);
         self.functions.extend_from_slice(&other.functions);
+        self.source_files.extend_from_slice(&other.source_files);
+        self.shadowed.extend_from_slice(&other.shadowed);
+        self.skipped.extend_from_slice(&other.skipped);
+    }
+
+    fn add_default_annotation(&mut self, name: &str, value: AnnotationValue) {
+        fn add<T: Item + Clone>(items: &mut ItemMap<T>, name: &str, value: &AnnotationValue) {
+            items.for_all_items_mut(|x| x.annotations_mut().add_default(name, value.clone()));
+        }
+        add(&mut self.constants, name, &value);
+        add(&mut self.globals, name, &value);
+        add(&mut self.enums, name, &value);
+        add(&mut self.structs, name, &value);
+        add(&mut self.unions, name, &value);
+        add(&mut self.opaque_items, name, &value);
+        add(&mut self.typedefs, name, &value);
+        add(&mut self.gobjects, name, &value);
+        for function in &mut self.functions {
+            function.annotations.add_default(name, value.clone());
+        }
+    }
+
+    /// Adds the items loaded from `module`. The associated constants of
+    /// structs loaded from other modules are only attached to them here.
+    fn extend_with_mod(&mut self, other: &Parse, module: &str) {
+        fn insert<T: Item + Clone + Serialize>(
+            items: &mut ItemMap<T>,
+            mut item: T,
+            module: &str,
+            shadowed: &mut Vec<ShadowedItem>,
+        ) {
+            item.annotations_mut().rust_path = Some(format!("{}::{}", module, item.name()));
+            // The items declared for several cfgs share their path, but the
+            // ones of other modules are different items, unless they're
+            // declared for several cfgs too.
+            let mut other_module = false;
+            let mut differs = false;
+            items.for_items(item.path(), |x| {
+                if x.annotations().rust_path != item.annotations().rust_path
+                    && (x.cfg().is_none() || item.cfg().is_none())
+                {
+                    other_module = true;
+                    differs |= definition(x) != definition(&item);
+                }
+            });
+            if other_module {
+                shadowed.push(ShadowedItem {
+                    path: item.path().clone(),
+                    annotations: item.annotations().clone(),
+                    differs,
+                });
+            } else {
+                items.try_insert(item);
+            }
+        }
+        fn extend<T: Item + Clone + Serialize>(
+            items: &mut ItemMap<T>,
+            other: &ItemMap<T>,
+            module: &str,
+            shadowed: &mut Vec<ShadowedItem>,
+        ) {
+            other.for_all_items(|x| insert(items, x.clone(), module, shadowed));
+        }
+
+        let structs = &mut self.structs;
+        let constants = &mut self.constants;
+        let shadowed = &mut self.shadowed;
+        other.constants.for_all_items(|constant| {
+            let mut any = false;
+            if let Some(ref associated_to) = constant.associated_to {
+                structs.for_items_mut(associated_to, |item| {
+                    any = true;
+                    item.add_associated_constant(constant.clone());
+                });
+            }
+            if !any {
+                insert(constants, constant.clone(), module, shadowed);
+            }
+        });
+        extend(&mut self.globals, &other.globals, module, shadowed);
+        extend(&mut self.enums, &other.enums, module, shadowed);
+        extend(&mut self.structs, &other.structs, module, shadowed);
+        extend(&mut self.unions, &other.unions, module, shadowed);
+        extend(
+            &mut self.opaque_items,
+            &other.opaque_items,
+            module,
+            shadowed,
+        );
+        extend(&mut self.typedefs, &other.typedefs, module, shadowed);
+        extend(&mut self.gobjects, &other.gobjects, module, shadowed);
+        for function in &other.functions {
+            let mut function = function.clone();
+            function.annotations.rust_path = Some(format!("{}::{}", module, function.path.name()));
+            self.functions.push(function);
+        }
+        self.skipped.extend_from_slice(&other.skipped);
     }
 
     fn load_syn_crate_mod<'a>(
@@ -475,11 +1180,18 @@ impl Parse {
                     self.load_syn_static(config, binding_crate_name, crate_name, mod_cfg, item);
                 }
                 syn::Item::Struct(ref item) => {
-                    self.load_syn_struct(config, crate_name, mod_cfg, item);
+                    if config.gobject.enabled && item.has_attr_list("gobject", &["class"]) {
+                        self.load_syn_gobject_final(config, mod_cfg, item);
+                    } else {
+                        self.load_syn_struct(config, crate_name, mod_cfg, item);
+                    }
                 }
                 syn::Item::Union(ref item) => {
                     self.load_syn_union(config, crate_name, mod_cfg, item);
                 }
+                syn::Item::Trait(ref item) if config.gobject.enabled && is_ginterface(item) => {
+                    self.load_syn_gobject_trait_interface(config, mod_cfg, item);
+                }
                 syn::Item::Enum(ref item) => {
                     self.load_syn_enum(config, crate_name, mod_cfg, item);
                 }
@@ -509,7 +1221,7 @@ impl Parse {
                                     method,
                                 )
                             }
-                            if config.gobject {
+                            if config.gobject.enabled {
                                 if let Some((_, trait_path, _)) = &item_impl.trait_ {
                                     self.load_syn_gobject(
                                         config,
@@ -568,10 +1280,13 @@ impl Parse {
         mod_cfg: Option<&Cfg>,
         item: &syn::ItemForeignMod,
     ) {
-        if !item.abi.is_c() {
-            info!("Skip {} - (extern block must be extern C).", crate_name);
-            return;
-        }
+        let calling_convention = match item.abi.calling_convention() {
+            Some(calling_convention) => calling_convention,
+            None => {
+                info!("Skip {} - (extern block must be extern C).", crate_name);
+                return;
+            }
+        };
 
         for foreign_item in &item.items {
             if let syn::ForeignItem::Fn(ref function) = *foreign_item {
@@ -587,16 +1302,25 @@ impl Parse {
                 }
                 let path = Path::new(function.sig.ident.to_string());
                 match Function::load(path, None, &function.sig, true, &function.attrs, mod_cfg) {
-                    Ok(func) => {
+                    Ok(mut func) => {
                         info!("Take {}::{}.", crate_name, &function.sig.ident);
+                        func.annotations.location = diagnostic::location(function);
 
+                        func.calling_convention = calling_convention;
+                        func.import_module =
+                            item.attr_list_name_value_lookup("link", "wasm_import_module");
                         self.functions.push(func);
                     }
                     Err(msg) => {
-                        error!(
+                        error_at!(
+                            function.sig,
                             "Cannot use fn {}::{} ({}).",
-                            crate_name, &function.sig.ident, msg
+                            crate_name,
+                            &function.sig.ident,
+                            msg
                         );
+                        self.skipped
+                            .push(format!("{}::{}", crate_name, &function.sig.ident));
                     }
                 }
             }
@@ -682,50 +1406,85 @@ impl Parse {
             items.join("::")
         };
 
-        let is_extern_c = sig.abi.is_omitted() || sig.abi.is_c();
+        let is_extern_c = sig.abi.calling_convention().is_some();
         let exported_name = named_symbol.exported_name();
 
         if let syn::Visibility::Public(_) = vis {
             match (is_extern_c, exported_name) {
                 (true, Some(exported_name)) => {
                     let path = Path::new(exported_name);
-                    match Function::load(path, self_type, &sig, false, &attrs, mod_cfg) {
-                        Ok(func) => {
+                    let loaded = Function::load(path, self_type, &sig, false, &attrs, mod_cfg)
+                        .and_then(|mut func| {
+                            let result = func.lower_result(sig, config.export.result_convention)?;
+                            Ok((func, result))
+                        });
+                    match loaded {
+                        Ok((mut func, result)) => {
                             info!("Take {}.", loggable_item_name());
+                            func.annotations.location = diagnostic::location(sig);
                             self.functions.push(func);
+                            if let Some(result) = result {
+                                self.load_syn_enum(config, crate_name, mod_cfg, &result);
+                            }
                         }
                         Err(msg) => {
-                            error!("Cannot use fn {} ({}).", loggable_item_name(), msg);
+                            error_at!(sig, "Cannot use fn {} ({}).", loggable_item_name(), msg);
+                            self.skipped.push(loggable_item_name());
                         }
                     }
                 }
                 (true, None) => {
-                    warn!(
+                    warn_at!(
+                        sig,
                         "Skipping {} - (not `no_mangle`, and has no `export_name` attribute)",
                         loggable_item_name()
                     );
                 }
                 (false, Some(_exported_name)) => {
-                    warn!("Skipping {} - (not `extern \"C\"`", loggable_item_name());
+                    warn_at!(
+                        sig,
+                        "Skipping {} - (not `extern \"C\"`",
+                        loggable_item_name()
+                    );
+                }
+                // The runtime glue of `cbindgen:gasync` functions exports
+                // them as GIO-style functions, see `Function::gasync_pair`.
+                (false, None) if sig.asyncness.is_some() && config.gobject.enabled => {
+                    let path = Path::new(sig.ident.to_string());
+                    match Function::load_gasync(path, self_type, sig, attrs, mod_cfg) {
+                        Ok(Some(mut func)) => {
+                            info!("Take {}.", loggable_item_name());
+                            func.annotations.location = diagnostic::location(sig);
+                            self.functions.push(func);
+                        }
+                        Ok(None) => {}
+                        Err(msg) => {
+                            error_at!(sig, "Cannot use fn {} ({}).", loggable_item_name(), msg);
+                            self.skipped.push(loggable_item_name());
+                        }
+                    }
                 }
                 (false, None) => {}
             }
         } else {
             match (is_extern_c, exported_name) {
                 (true, Some(..)) => {
-                    warn!(
+                    warn_at!(
+                        sig,
                         "Skipping {} - (not `pub` but is `extern \"C\"` and `no_mangle`)",
                         loggable_item_name()
                     );
                 }
                 (true, None) => {
-                    warn!(
+                    warn_at!(
+                        sig,
                         "Skipping {} - (not `pub` but is `extern \"C\"`)",
                         loggable_item_name()
                     );
                 }
                 (false, Some(..)) => {
-                    warn!(
+                    warn_at!(
+                        sig,
                         "Skipping {} - (not `pub` but is `no_mangle`)",
                         loggable_item_name()
                     );
@@ -748,7 +1507,12 @@ impl Parse {
         let ty = match Type::load(impl_ty) {
             Ok(ty) => ty,
             Err(e) => {
-                warn!("Skipping associated constants for {:?}: {:?}", impl_ty, e);
+                warn_at!(
+                    impl_ty,
+                    "Skipping associated constants for {:?}: {:?}",
+                    impl_ty,
+                    e
+                );
                 return;
             }
         };
@@ -761,7 +1525,8 @@ impl Parse {
         let impl_path = match ty.get_root_path() {
             Some(p) => p,
             None => {
-                warn!(
+                warn_at!(
+                    impl_ty,
                     "Couldn't find path for {:?}, skipping associated constants",
                     ty
                 );
@@ -772,7 +1537,7 @@ impl Parse {
         for item in items.into_iter() {
             if let syn::Visibility::Public(_) = item.vis {
             } else {
-                warn!("Skip {}::{} - (not `pub`).", crate_name, &item.ident);
+                warn_at!(item, "Skip {}::{} - (not `pub`).", crate_name, &item.ident);
                 return;
             }
 
@@ -785,8 +1550,9 @@ impl Parse {
                 &item.attrs,
                 Some(impl_path.clone()),
             ) {
-                Ok(constant) => {
+                Ok(mut constant) => {
                     info!("Take {}::{}::{}.", crate_name, impl_path, &item.ident);
+                    constant.annotations.location = diagnostic::location(item);
                     let mut any = false;
                     self.structs.for_items_mut(&impl_path, |item| {
                         any = true;
@@ -795,14 +1561,17 @@ impl Parse {
                     // Handle associated constants to other item types that are
                     // not structs like enums or such as regular constants.
                     if !any && !self.constants.try_insert(constant) {
-                        error!(
+                        error_at!(
+                            item,
                             "Conflicting name for constant {}::{}::{}.",
-                            crate_name, impl_path, &item.ident,
+                            crate_name,
+                            impl_path,
+                            &item.ident,
                         );
                     }
                 }
                 Err(msg) => {
-                    warn!("Skip {}::{} - ({})", crate_name, &item.ident, msg);
+                    warn_at!(item, "Skip {}::{} - ({})", crate_name, &item.ident, msg);
                 }
             }
         }
@@ -830,22 +1599,23 @@ impl Parse {
 
         if let syn::Visibility::Public(_) = item.vis {
         } else {
-            warn!("Skip {}::{} - (not `pub`).", crate_name, &item.ident);
+            warn_at!(item, "Skip {}::{} - (not `pub`).", crate_name, &item.ident);
             return;
         }
 
         let path = Path::new(item.ident.to_string());
         match Constant::load(path, mod_cfg, &item.ty, &item.expr, &item.attrs, None) {
-            Ok(constant) => {
+            Ok(mut constant) => {
                 info!("Take {}::{}.", crate_name, &item.ident);
+                constant.annotations.location = diagnostic::location(item);
 
                 let full_name = constant.path.clone();
                 if !self.constants.try_insert(constant) {
-                    error!("Conflicting name for constant {}", full_name);
+                    error_at!(item, "Conflicting name for constant {}", full_name);
                 }
             }
             Err(msg) => {
-                warn!("Skip {}::{} - ({})", crate_name, &item.ident, msg);
+                warn_at!(item, "Skip {}::{} - ({})", crate_name, &item.ident, msg);
             }
         }
     }
@@ -873,13 +1643,16 @@ impl Parse {
         if let syn::Visibility::Public(_) = item.vis {
             if item.is_no_mangle() {
                 match Static::load(item, mod_cfg) {
-                    Ok(constant) => {
+                    Ok(mut constant) => {
                         info!("Take {}::{}.", crate_name, &item.ident);
+                        constant.annotations.location = diagnostic::location(item);
 
                         self.globals.try_insert(constant);
                     }
                     Err(msg) => {
-                        warn!("Skip {}::{} - ({})", crate_name, &item.ident, msg);
+                        warn_at!(item, "Skip {}::{} - ({})", crate_name, &item.ident, msg);
+                        self.skipped
+                            .push(format!("{}::{}", crate_name, &item.ident));
                     }
                 }
             }
@@ -888,10 +1661,15 @@ impl Parse {
         // TODO
         if let syn::Visibility::Public(_) = item.vis {
         } else {
-            warn!("Skip {}::{} - (not `pub`).", crate_name, &item.ident);
+            warn_at!(item, "Skip {}::{} - (not `pub`).", crate_name, &item.ident);
         }
         if !item.is_no_mangle() {
-            warn!("Skip {}::{} - (not `no_mangle`).", crate_name, &item.ident);
+            warn_at!(
+                item,
+                "Skip {}::{} - (not `no_mangle`).",
+                crate_name,
+                &item.ident
+            );
         }
     }
 
@@ -904,16 +1682,18 @@ impl Parse {
         item: &syn::ItemStruct,
     ) {
         match Struct::load(&config.layout, item, mod_cfg) {
-            Ok(st) => {
+            Ok(mut st) => {
                 info!("Take {}::{}.", crate_name, &item.ident);
+                st.annotations.location = diagnostic::location(item);
                 self.structs.try_insert(st);
             }
             Err(msg) => {
                 info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
                 let path = Path::new(item.ident.to_string());
-                self.opaque_items.try_insert(
-                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
-                );
+                let mut opaque =
+                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap();
+                opaque.annotations.location = diagnostic::location(item);
+                self.opaque_items.try_insert(opaque);
             }
         }
     }
@@ -927,17 +1707,19 @@ impl Parse {
         item: &syn::ItemUnion,
     ) {
         match Union::load(&config.layout, item, mod_cfg) {
-            Ok(st) => {
+            Ok(mut st) => {
                 info!("Take {}::{}.", crate_name, &item.ident);
+                st.annotations.location = diagnostic::location(item);
 
                 self.unions.try_insert(st);
             }
             Err(msg) => {
                 info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
                 let path = Path::new(item.ident.to_string());
-                self.opaque_items.try_insert(
-                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
-                );
+                let mut opaque =
+                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap();
+                opaque.annotations.location = diagnostic::location(item);
+                self.opaque_items.try_insert(opaque);
             }
         }
     }
@@ -951,16 +1733,18 @@ impl Parse {
         item: &syn::ItemEnum,
     ) {
         match Enum::load(item, mod_cfg, config) {
-            Ok(en) => {
+            Ok(mut en) => {
                 info!("Take {}::{}.", crate_name, &item.ident);
+                en.annotations.location = diagnostic::location(item);
                 self.enums.try_insert(en);
             }
             Err(msg) => {
                 info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
                 let path = Path::new(item.ident.to_string());
-                self.opaque_items.try_insert(
-                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
-                );
+                let mut opaque =
+                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap();
+                opaque.annotations.location = diagnostic::location(item);
+                self.opaque_items.try_insert(opaque);
             }
         }
     }
@@ -968,17 +1752,19 @@ impl Parse {
     /// Loads a `type` declaration
     fn load_syn_ty(&mut self, crate_name: &str, mod_cfg: Option<&Cfg>, item: &syn::ItemType) {
         match Typedef::load(item, mod_cfg) {
-            Ok(st) => {
+            Ok(mut st) => {
                 info!("Take {}::{}.", crate_name, &item.ident);
+                st.annotations.location = diagnostic::location(item);
 
                 self.typedefs.try_insert(st);
             }
             Err(msg) => {
                 info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
                 let path = Path::new(item.ident.to_string());
-                self.opaque_items.try_insert(
-                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
-                );
+                let mut opaque =
+                    OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap();
+                opaque.annotations.location = diagnostic::location(item);
+                self.opaque_items.try_insert(opaque);
             }
         }
     }
@@ -1011,7 +1797,50 @@ impl Parse {
                 self.gobjects.try_insert(gi);
             }
             Err(msg) => {
-                error!("Cannot use GInterface {} ({}).", self_type, msg);
+                error_at!(input, "Cannot use GInterface {} ({}).", self_type, msg);
+                self.skipped.push(self_type.to_string());
+            }
+        }
+    }
+
+    fn load_syn_gobject_final(
+        &mut self,
+        config: &Config,
+        mod_cfg: Option<&Cfg>,
+        item: &syn::ItemStruct,
+    ) {
+        let mut name = item.ident.to_string();
+        config.export.rename(&mut name);
+
+        match GObject::load_final(item, name, mod_cfg) {
+            Ok(gobject) => {
+                info!("Take {}.", item.ident);
+                self.gobjects.try_insert(gobject);
+            }
+            Err(msg) => {
+                error_at!(item, "Cannot use GObject {} ({}).", item.ident, msg);
+                self.skipped.push(item.ident.to_string());
+            }
+        }
+    }
+
+    fn load_syn_gobject_trait_interface(
+        &mut self,
+        config: &Config,
+        mod_cfg: Option<&Cfg>,
+        item: &syn::ItemTrait,
+    ) {
+        let mut name = item.ident.to_string();
+        config.export.rename(&mut name);
+
+        match GObject::load_trait_interface(item, name, mod_cfg) {
+            Ok(gobject) => {
+                info!("Take {}.", item.ident);
+                self.gobjects.try_insert(gobject);
+            }
+            Err(msg) => {
+                error_at!(item, "Cannot use GInterface {} ({}).", item.ident, msg);
+                self.skipped.push(item.ident.to_string());
             }
         }
     }
@@ -1096,7 +1925,8 @@ impl Parse {
                 self.gobjects.try_insert(gobject);
             }
             Err(msg) => {
-                error!("Cannot use GObject {} ({}).", self_type, msg);
+                error_at!(input, "Cannot use GObject {} ({}).", self_type, msg);
+                self.skipped.push(self_type.to_string());
             }
         }
     }
@@ -1120,11 +1950,16 @@ impl Parse {
         let bitflags = match bitflags::parse(item.mac.tokens.clone()) {
             Ok(b) => b,
             Err(e) => {
-                warn!("Failed to parse bitflags invocation: {:?}", e);
+                warn_at!(item, "Failed to parse bitflags invocation: {:?}", e);
                 return;
             }
         };
 
+        if config.macro_expansion.bitflags_as_enum {
+            self.load_syn_enum(config, crate_name, mod_cfg, &bitflags.expand_enum());
+            return;
+        }
+
         let (struct_, impl_) = bitflags.expand();
         self.load_syn_struct(config, crate_name, mod_cfg, &struct_);
         // We know that the expansion will only reference `struct_`, so it's
@@ -1133,3 +1968,15 @@ impl Parse {
         self.load_syn_assoc_consts_from_impl(crate_name, mod_cfg, &impl_);
     }
 }
+
+/// Whether the trait is annotated `cbindgen:ginterface`, with or without a
+/// prerequisite.
+fn is_ginterface(item: &syn::ItemTrait) -> bool {
+    match AnnotationSet::load(&item.attrs) {
+        Ok(annotations) => {
+            annotations.bool("ginterface").unwrap_or(false)
+                || annotations.atom("ginterface").is_some()
+        }
+        Err(_) => false,
+    }
+}
@@ -2,13 +2,24 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::borrow::Cow;
+
+use crate::bindgen::config::Language;
+use crate::bindgen::mangle;
+
 /// Taken from `https://en.cppreference.com/w/cpp/keyword`
 /// Some experimental keywords were filtered out and the resulting list was
-/// sorted using a rust program.
+/// sorted using a rust program. The alternative tokens, like `and`, are kept
+/// since they can't be used as names either.
 const RESERVED_KEYWORDS: &[&str] = &[
     "alignas",
     "alignof",
+    "and",
+    "and_eq",
+    "asm",
     "auto",
+    "bitand",
+    "bitor",
     "bool",
     "break",
     "case",
@@ -18,6 +29,11 @@ const RESERVED_KEYWORDS: &[&str] = &[
     "char32_t",
     "char8_t",
     "class",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "compl",
+    "concept",
     "const",
     "const_cast",
     "consteval",
@@ -47,13 +63,18 @@ const RESERVED_KEYWORDS: &[&str] = &[
     "namespace",
     "new",
     "noexcept",
+    "not",
+    "not_eq",
     "nullptr",
     "operator",
+    "or",
+    "or_eq",
     "private",
     "protected",
     "public",
     "register",
     "reinterpret_cast",
+    "requires",
     "return",
     "short",
     "signed",
@@ -79,13 +100,78 @@ const RESERVED_KEYWORDS: &[&str] = &[
     "volatile",
     "wchar_t",
     "while",
+    "xor",
+    "xor_eq",
 ];
 
+/// The keywords of C which aren't keywords of C++, from
+/// `https://en.cppreference.com/w/c/keyword`, sorted.
+const C_KEYWORDS: &[&str] = &[
+    "_Alignas",
+    "_Alignof",
+    "_Atomic",
+    "_Bool",
+    "_Complex",
+    "_Generic",
+    "_Imaginary",
+    "_Noreturn",
+    "_Static_assert",
+    "_Thread_local",
+    "restrict",
+];
+
+/// The keywords of Python and the ones Cython adds, which can't be used as
+/// names in Cython declarations, sorted.
+const CYTHON_KEYWORDS: &[&str] = &[
+    "DEF", "ELIF", "ELSE", "False", "IF", "None", "True", "as", "assert", "async", "await", "cdef",
+    "cimport", "class", "cpdef", "ctypedef", "def", "del", "elif", "except", "finally", "from",
+    "global", "import", "in", "include", "is", "lambda", "nogil", "nonlocal", "pass", "raise",
+    "with", "yield",
+];
+
+/// Escapes the names which are keywords in C or C++ by appending an
+/// underscore. C headers are often included in C++, so both are escaped
+/// whatever the language, and the other languages binding the C header use
+/// the same names.
 pub fn escape(rust_identifier: &mut String) {
-    if RESERVED_KEYWORDS
-        .binary_search(&rust_identifier.as_ref())
-        .is_ok()
-    {
+    if let Some(ident) = mangle::mangle_ident(rust_identifier) {
+        *rust_identifier = ident;
+    }
+    let name: &str = rust_identifier;
+    if is_keyword(name) {
+        warn!(
+            "{} is a keyword in C or C++, renaming it to {}_.",
+            name, name
+        );
         rust_identifier.push('_');
     }
 }
+
+/// Whether `name` is a keyword in C or C++.
+pub fn is_keyword(name: &str) -> bool {
+    RESERVED_KEYWORDS.binary_search(&name).is_ok() || C_KEYWORDS.binary_search(&name).is_ok()
+}
+
+/// Escapes an argument name, which only has to be valid in the language of
+/// the bindings: Cython keywords are escaped too.
+pub fn escape_arg(name: &mut String, language: Language) {
+    escape(name);
+    if language == Language::Cython && is_cython_keyword(name) {
+        warn!("{} is a keyword in Cython, renaming it to {}_.", name, name);
+        name.push('_');
+    }
+}
+
+pub fn is_cython_keyword(name: &str) -> bool {
+    CYTHON_KEYWORDS.binary_search(&name).is_ok()
+}
+
+/// The name of a field or enum variant in Cython, which has to be the one in
+/// the C header: a Cython keyword is escaped, followed by the C name.
+pub fn cython_name(name: &str) -> Cow<'_, str> {
+    if is_cython_keyword(name) {
+        Cow::Owned(format!("{}_ \"{}\"", name, name))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
@@ -0,0 +1,280 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashSet;
+use std::env;
+use std::path;
+use std::process::Command;
+
+use crate::bindgen::bindings::{write_if_changed, Bindings};
+use crate::bindgen::config::{Language, VERSION};
+use crate::bindgen::error::Error;
+use crate::bindgen::ir::{Cfg, ItemMap, ToCondition};
+use crate::bindgen::writer::SourceWriter;
+
+/// The boolean cfgs which are only set for some targets, so are unset for
+/// the others.
+const TARGET_NAMES: &[&str] = &["unix", "windows"];
+
+/// The cfgs of a target, as printed by `rustc --print cfg`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TargetCfgs {
+    names: HashSet<String>,
+    values: HashSet<(String, String)>,
+}
+
+impl TargetCfgs {
+    pub(crate) fn load(target: &str) -> Result<TargetCfgs, Error> {
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+        let output = Command::new(rustc)
+            .args(&["--print", "cfg", "--target", target])
+            .output()
+            .map_err(|x| Error::TargetCfgs(target.to_owned(), x.to_string()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Error::TargetCfgs(
+                target.to_owned(),
+                stderr.trim().to_owned(),
+            ));
+        }
+        Ok(TargetCfgs::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn parse(cfgs: &str) -> TargetCfgs {
+        let mut target = TargetCfgs::default();
+        for line in cfgs.lines().map(str::trim).filter(|x| !x.is_empty()) {
+            match line.find('=') {
+                Some(i) => {
+                    let value = line[i + 1..].trim_matches('"');
+                    target
+                        .values
+                        .insert((line[..i].to_owned(), value.to_owned()));
+                }
+                None => {
+                    target.names.insert(line.to_owned());
+                }
+            }
+        }
+        target
+    }
+
+    fn value(&self, key: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(x, _)| x == key)
+            .map(|(_, value)| &**value)
+    }
+
+    /// Evaluates `cfg` for the target. Returns `None` if it depends on cfgs
+    /// which don't describe the target, like features.
+    pub(crate) fn eval(&self, cfg: &Cfg) -> Option<bool> {
+        match *cfg {
+            Cfg::Boolean(ref name) => {
+                if self.names.contains(name) {
+                    Some(true)
+                } else if TARGET_NAMES.contains(&&**name) {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            Cfg::Named(ref key, ref value) => {
+                if self.value(key).is_some() || key.starts_with("target_") {
+                    Some(self.values.contains(&(key.clone(), value.clone())))
+                } else {
+                    None
+                }
+            }
+            Cfg::Any(ref cfgs) => {
+                let values: Vec<_> = cfgs.iter().map(|x| self.eval(x)).collect();
+                if values.contains(&Some(true)) {
+                    Some(true)
+                } else if values.contains(&None) {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            Cfg::All(ref cfgs) => {
+                let values: Vec<_> = cfgs.iter().map(|x| self.eval(x)).collect();
+                if values.contains(&Some(false)) {
+                    Some(false)
+                } else if values.contains(&None) {
+                    None
+                } else {
+                    Some(true)
+                }
+            }
+            Cfg::Not(ref cfg) => self.eval(cfg).map(|x| !x),
+        }
+    }
+
+    /// Whether `cfg` is unset for the target.
+    pub(crate) fn excludes(&self, cfg: &Option<Cfg>) -> bool {
+        cfg.as_ref().and_then(|x| self.eval(x)) == Some(false)
+    }
+
+    /// Removes `cfg` if it's set for the target.
+    pub(crate) fn resolve(&self, cfg: &mut Option<Cfg>) {
+        if cfg.as_ref().and_then(|x| self.eval(x)) == Some(true) {
+            *cfg = None;
+        }
+    }
+
+    /// The cfg telling a header is compiled for the target, from its
+    /// architecture, OS and environment.
+    fn cfg(&self) -> Cfg {
+        let cfgs = ["target_arch", "target_os", "target_env"]
+            .iter()
+            .filter_map(|key| Some((key, self.value(key)?)))
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| Cfg::Named((*key).to_owned(), value.to_owned()))
+            .collect();
+        Cfg::All(cfgs)
+    }
+}
+
+/// The bindings of several targets, generated by
+/// `Builder::generate_for_targets`.
+pub struct TargetBindings {
+    targets: Vec<(String, TargetCfgs, Bindings)>,
+}
+
+impl TargetBindings {
+    pub(crate) fn new(targets: Vec<(String, TargetCfgs, Bindings)>) -> TargetBindings {
+        TargetBindings { targets }
+    }
+
+    /// The bindings of each target.
+    #[allow(unused)]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Bindings)> {
+        self.targets
+            .iter()
+            .map(|(target, _, bindings)| (&**target, bindings))
+    }
+
+    /// The name of the header of `target`, written next to `path`.
+    fn target_path(path: &path::Path, target: &str) -> path::PathBuf {
+        let mut name = path.file_stem().unwrap().to_owned();
+        name.push("-");
+        name.push(target);
+        if let Some(extension) = path.extension() {
+            name.push(".");
+            name.push(extension);
+        }
+        path.with_file_name(name)
+    }
+
+    /// Writes the bindings of each target next to `path`, suffixed with the
+    /// target. If `merged` is set, a header is also written at `path`, which
+    /// includes the one of the target it's compiled for.
+    #[allow(unused)]
+    pub fn write_to_files<P: AsRef<path::Path>>(&self, path: P, merged: bool) -> bool {
+        let path = path.as_ref();
+        let mut changed = false;
+        for (target, _, bindings) in &self.targets {
+            changed |= bindings.write_to_file(Self::target_path(path, target));
+        }
+        if merged {
+            changed |= self.write_merged(path);
+        }
+        changed
+    }
+
+    /// Writes a header including the bindings of the target it's compiled
+    /// for, using the built-in `[platform]` conditions of the targets.
+    fn write_merged(&self, path: &path::Path) -> bool {
+        let (_, _, first) = match self.targets.first() {
+            Some(target) => target,
+            None => return false,
+        };
+        let mut config = first.config.clone();
+        config.platform.builtin = true;
+        let name = path.file_name().unwrap().to_string_lossy();
+        config.resolve_include_guard("{file}", &name);
+        if config.language != Language::C && config.language != Language::Cxx {
+            warn!("Only C and C++ bindings can be merged.");
+            return false;
+        }
+        let bindings = Bindings::new(
+            config.clone(),
+            ItemMap::default(),
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+
+        let mut contents = Vec::new();
+        {
+            let mut out = SourceWriter::new(&mut contents, &bindings);
+            if let Some(ref f) = config.header {
+                write!(out, "{}", f);
+                out.new_line();
+            }
+            if let Some(f) = config.include_guard() {
+                out.new_line_if_not_start();
+                write!(out, "#ifndef {}", f);
+                out.new_line();
+                write!(out, "#define {}", f);
+                out.new_line();
+            }
+            if config.pragma_once {
+                out.new_line_if_not_start();
+                out.write("#pragma once");
+                out.new_line();
+            }
+            if config.include_version {
+                out.new_line_if_not_start();
+                write!(out, "/* Generated with cbindgen:{} */", VERSION);
+                out.new_line();
+            }
+            if let Some(ref f) = config.autogen_warning {
+                out.new_line_if_not_start();
+                write!(out, "{}", f);
+                out.new_line();
+            }
+
+            out.new_line_if_not_start();
+            let mut first = true;
+            for (target, cfgs, _) in &self.targets {
+                let condition = match cfgs.cfg().to_condition(&config) {
+                    Some(condition) => condition,
+                    None => {
+                        warn!("Can't tell when a header is compiled for {}.", target);
+                        continue;
+                    }
+                };
+                out.write(if first { "#if " } else { "#elif " });
+                condition.write(&config, &mut out);
+                out.new_line();
+                let name = Self::target_path(path, target);
+                let name = name.file_name().unwrap().to_string_lossy();
+                write!(out, "#include \"{}\"", name);
+                out.new_line();
+                first = false;
+            }
+            if !first {
+                out.write("#else");
+                out.new_line();
+                out.write("#error \"No bindings for this target.\"");
+                out.new_line();
+                out.write("#endif");
+                out.new_line();
+            }
+
+            if let Some(f) = config.include_guard() {
+                out.new_line();
+                if config.language == Language::C {
+                    write!(out, "#endif /* {} */", f);
+                } else {
+                    write!(out, "#endif // {}", f);
+                }
+                out.new_line();
+            }
+        }
+        write_if_changed(path, &contents)
+    }
+}
@@ -4,6 +4,8 @@
 
 #![allow(clippy::redundant_closure_call)]
 
+use crate::bindgen::ir::CallingConvention;
+
 pub trait IterHelpers: Iterator {
     fn try_skip_map<F, T, E>(&mut self, f: F) -> Result<Vec<T>, E>
     where
@@ -114,6 +116,65 @@ pub trait SynAttributeHelpers {
             })
     }
 
+    /// Searches for attributes like `#[gobject(class)]`.
+    /// Example:
+    /// - `item.has_attr_list("gobject", &["class"])` => `#[gobject(class)]`
+    fn has_attr_list(&self, name: &str, args: &[&str]) -> bool {
+        self.attrs()
+            .iter()
+            .filter_map(|x| x.parse_meta().ok())
+            .any(|attr| {
+                if let syn::Meta::List(syn::MetaList { path, nested, .. }) = attr {
+                    if !path.is_ident(name) {
+                        return false;
+                    }
+                    args.iter().all(|arg| {
+                        nested.iter().any(|nested_meta| {
+                            if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = nested_meta {
+                                path.is_ident(arg)
+                            } else {
+                                false
+                            }
+                        })
+                    })
+                } else {
+                    false
+                }
+            })
+    }
+
+    /// Searches for `#[deprecated]`, returning its note, which is empty if it
+    /// has none.
+    fn deprecated_note(&self) -> Option<String> {
+        let is_note = |meta: &syn::NestedMeta| match *meta {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                ref path,
+                lit: syn::Lit::Str(ref lit),
+                ..
+            })) if path.is_ident("note") => Some(lit.value()),
+            _ => None,
+        };
+        self.attrs()
+            .iter()
+            .filter_map(|x| x.parse_meta().ok())
+            .find_map(|attr| match attr {
+                syn::Meta::Path(ref path) if path.is_ident("deprecated") => Some(String::new()),
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    lit: syn::Lit::Str(ref lit),
+                    ..
+                }) if path.is_ident("deprecated") => Some(lit.value()),
+                syn::Meta::List(syn::MetaList {
+                    ref path,
+                    ref nested,
+                    ..
+                }) if path.is_ident("deprecated") => {
+                    Some(nested.iter().find_map(is_note).unwrap_or_default())
+                }
+                _ => None,
+            })
+    }
+
     fn is_no_mangle(&self) -> bool {
         self.has_attr_word("no_mangle")
     }
@@ -153,6 +214,28 @@ pub trait SynAttributeHelpers {
             .next()
     }
 
+    /// Searches for attributes like `#[link(wasm_import_module = "env")]`.
+    /// Example:
+    /// - `item.attr_list_name_value_lookup("link", "wasm_import_module")`
+    fn attr_list_name_value_lookup(&self, list: &str, name: &str) -> Option<String> {
+        self.attrs()
+            .iter()
+            .filter_map(|x| x.parse_meta().ok())
+            .find_map(|attr| match attr {
+                syn::Meta::List(syn::MetaList { path, nested, .. }) if path.is_ident(list) => {
+                    nested.iter().find_map(|nested_meta| match *nested_meta {
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                            ref path,
+                            lit: syn::Lit::Str(ref lit),
+                            ..
+                        })) if path.is_ident(name) => Some(lit.value()),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            })
+    }
+
     fn get_comment_lines(&self) -> Vec<String> {
         let mut comment = Vec::new();
 
@@ -239,39 +322,24 @@ impl_syn_item_helper!(syn::ItemTraitAlias);
 
 /// Helper function for accessing Abi information
 pub trait SynAbiHelpers {
-    fn is_c(&self) -> bool;
-    fn is_omitted(&self) -> bool;
+    /// The calling convention of the ABI, `None` if there's no ABI or it's
+    /// not supported.
+    fn calling_convention(&self) -> Option<CallingConvention>;
 }
 
 impl SynAbiHelpers for Option<syn::Abi> {
-    fn is_c(&self) -> bool {
-        if let Some(ref abi) = *self {
-            if let Some(ref lit_string) = abi.name {
-                return lit_string.value() == "C";
-            }
-        }
-        false
-    }
-    fn is_omitted(&self) -> bool {
-        if let Some(ref abi) = *self {
-            abi.name.is_none()
-        } else {
-            false
-        }
+    fn calling_convention(&self) -> Option<CallingConvention> {
+        self.as_ref().and_then(|abi| abi.calling_convention())
     }
 }
 
 impl SynAbiHelpers for syn::Abi {
-    fn is_c(&self) -> bool {
-        if let Some(ref lit_string) = self.name {
-            lit_string.value() == "C"
-        } else {
-            false
+    fn calling_convention(&self) -> Option<CallingConvention> {
+        match self.name {
+            Some(ref lit_string) => CallingConvention::from_abi(&lit_string.value()),
+            None => Some(CallingConvention::C),
         }
     }
-    fn is_omitted(&self) -> bool {
-        self.name.is_none()
-    }
 }
 
 impl SynAttributeHelpers for [syn::Attribute] {
@@ -291,3 +359,34 @@ fn split_doc_attr(input: &str) -> Vec<String> {
         .map(|s| s.trim_end().to_string())
         .collect()
 }
+
+/// The number of single character insertions, deletions and substitutions
+/// turning `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + if a == *b { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The candidate closest to a misspelled `name`, if one is close enough to
+/// be suggested instead of it.
+pub fn closest_match<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
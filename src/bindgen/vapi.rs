@@ -0,0 +1,677 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use heck::{ShoutySnakeCase, SnakeCase};
+
+use crate::bindgen::config::Config;
+use crate::bindgen::cpp_wrappers::points_to;
+use crate::bindgen::ir::{
+    Constant, Documentation, Enum, Field, Function, GObject, GType, Item, ItemContainer, Literal,
+    PrimitiveType, Transfer, Type,
+};
+use crate::bindgen::writer::SourceWriter;
+
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "async",
+    "base",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "construct",
+    "continue",
+    "default",
+    "delegate",
+    "delete",
+    "do",
+    "dynamic",
+    "else",
+    "ensures",
+    "enum",
+    "errordomain",
+    "extern",
+    "false",
+    "finally",
+    "for",
+    "foreach",
+    "get",
+    "if",
+    "in",
+    "inline",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "namespace",
+    "new",
+    "null",
+    "out",
+    "override",
+    "owned",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "ref",
+    "requires",
+    "return",
+    "set",
+    "signal",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typeof",
+    "unowned",
+    "using",
+    "var",
+    "virtual",
+    "void",
+    "volatile",
+    "weak",
+    "while",
+    "yield",
+];
+
+/// Escapes Vala keywords used as identifiers.
+fn identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("@{}", name)
+    } else {
+        name.to_owned()
+    }
+}
+
+/// The Vala type of a primitive, the GLib type without its `g` prefix.
+fn primitive_name(primitive: &PrimitiveType, config: &Config) -> &'static str {
+    match *primitive {
+        PrimitiveType::Void => "void",
+        PrimitiveType::Bool => "bool",
+        PrimitiveType::VaList => "va_list",
+        _ => match primitive.to_repr_glib(config).unwrap() {
+            "gsize" => "size_t",
+            "gssize" => "ssize_t",
+            glib => &glib[1..],
+        },
+    }
+}
+
+/// Whether `ty` is a `char*`, which is a string in Vala.
+fn is_c_string(ty: &Type) -> bool {
+    match *ty {
+        Type::Ptr { ref ty, .. } => **ty == Type::Primitive(PrimitiveType::Char),
+        _ => false,
+    }
+}
+
+/// The functions managing the memory of a compact class.
+struct Memory {
+    free_function: Option<String>,
+    ref_function: Option<String>,
+    unref_function: Option<String>,
+}
+
+/// Writes a Vala API file describing the bindings.
+pub struct VapiWriter<'a> {
+    config: &'a Config,
+    namespace: String,
+    symbol_prefix: String,
+    /// The C headers declaring the bindings.
+    headers: Vec<String>,
+    /// The aliased types, Vala not having typedefs.
+    typedefs: HashMap<String, Type>,
+    /// The opaque items and GObject types, which are classes handled through
+    /// pointers.
+    classes: HashSet<String>,
+}
+
+impl<'a> VapiWriter<'a> {
+    pub fn new(
+        config: &'a Config,
+        items: &[ItemContainer],
+        gobjects: &[GObject],
+        header: Option<&str>,
+    ) -> Self {
+        let namespace = match config.gir.namespace {
+            Some(ref namespace) => namespace.clone(),
+            None => config.export.prefix.clone().unwrap_or_else(|| {
+                warn!("No Vala namespace, set `gir.namespace` or `export.prefix`.");
+                String::new()
+            }),
+        };
+        let symbol_prefix = namespace.to_snake_case();
+
+        let headers = if !config.vapi.headers.is_empty() {
+            config.vapi.headers.clone()
+        } else if !config.gir.c_includes.is_empty() {
+            config.gir.c_includes.clone()
+        } else {
+            header.into_iter().map(str::to_owned).collect()
+        };
+        if headers.is_empty() {
+            warn!("No C header to declare the bindings in, set `vapi.headers`.");
+        }
+
+        let mut typedefs = HashMap::new();
+        let mut classes = HashSet::new();
+        for item in items {
+            match *item {
+                ItemContainer::Typedef(ref t) => {
+                    typedefs.insert(t.export_name().to_owned(), t.aliased.clone());
+                }
+                ItemContainer::OpaqueItem(ref o) => {
+                    classes.insert(o.export_name().to_owned());
+                }
+                _ => {}
+            }
+        }
+        for gobject in gobjects {
+            classes.insert(gobject.name.clone());
+        }
+
+        VapiWriter {
+            config,
+            namespace,
+            symbol_prefix,
+            headers,
+            typedefs,
+            classes,
+        }
+    }
+
+    /// The Vala name of a type, without its namespace prefix.
+    fn local_name<'n>(&self, name: &'n str) -> &'n str {
+        if name.len() > self.namespace.len() && name.starts_with(&self.namespace) {
+            &name[self.namespace.len()..]
+        } else {
+            name
+        }
+    }
+
+    /// The Vala name of a function, without its symbol prefix.
+    fn local_symbol<'n>(&self, name: &'n str) -> &'n str {
+        if self.symbol_prefix.is_empty() {
+            return name;
+        }
+        let prefix = format!("{}_", self.symbol_prefix);
+        if name.len() > prefix.len() && name.starts_with(&prefix) {
+            &name[prefix.len()..]
+        } else {
+            name
+        }
+    }
+
+    /// The Vala name of a constant, without its namespace prefix, which may
+    /// be in shouty snake case.
+    fn local_constant<'n>(&self, name: &'n str) -> &'n str {
+        if self.namespace.is_empty() {
+            return name;
+        }
+        let prefix = format!("{}_", self.namespace.to_shouty_snake_case());
+        if name.len() > prefix.len() && name.starts_with(&prefix) {
+            &name[prefix.len()..]
+        } else {
+            self.local_name(name)
+        }
+    }
+
+    /// The Vala name of a parent class, which may come from GObject.
+    fn parent_name(&self, parent: &str) -> String {
+        match parent {
+            "Object" | "GObject" => "GLib.Object".to_owned(),
+            "InitiallyUnowned" | "GInitiallyUnowned" => "GLib.InitiallyUnowned".to_owned(),
+            parent => identifier(self.local_name(parent)),
+        }
+    }
+
+    /// Replaces typedefs by the type they alias.
+    fn resolve<'t>(&'t self, ty: &'t Type) -> &'t Type {
+        let mut ty = ty;
+        while let Type::Path(ref generic) = *ty {
+            match self.typedefs.get(generic.export_name()) {
+                Some(aliased) if aliased != ty => ty = aliased,
+                _ => break,
+            }
+        }
+        ty
+    }
+
+    /// The class a pointer type points to.
+    fn class<'t>(&self, ty: &'t Type) -> Option<&'t str> {
+        match *ty {
+            Type::Ptr { ref ty, .. } => match **ty {
+                Type::Path(ref generic) if self.classes.contains(generic.export_name()) => {
+                    Some(generic.export_name())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn type_name(&self, ty: &Type) -> String {
+        match *self.resolve(ty) {
+            Type::Primitive(ref primitive) => primitive_name(primitive, self.config).to_owned(),
+            Type::Path(ref generic) => identifier(self.local_name(generic.export_name())),
+            Type::Ptr { ref ty, .. } => match *self.resolve(ty) {
+                Type::Path(ref generic) if self.classes.contains(generic.export_name()) => {
+                    "void*".to_owned()
+                }
+                Type::FuncPtr { .. } => "void*".to_owned(),
+                ref ty => format!("{}*", self.type_name(ty)),
+            },
+            Type::Array(ref element, _) => format!("{}*", self.type_name(element)),
+            Type::FuncPtr { .. } => "void*".to_owned(),
+        }
+    }
+
+    /// The type of a parameter, a return value or a field, for which strings
+    /// and classes are references, `owned` telling whether the reference is
+    /// transferred.
+    fn reference_type_name(&self, ty: &Type, owned: bool) -> Option<String> {
+        let ty = self.resolve(ty);
+        let name = if is_c_string(ty) {
+            "string".to_owned()
+        } else {
+            identifier(self.local_name(self.class(ty)?))
+        };
+        Some(if owned {
+            name
+        } else {
+            format!("unowned {}", name)
+        })
+    }
+
+    fn write_doc<F: Write>(&self, out: &mut SourceWriter<F>, documentation: &Documentation) {
+        if documentation.doc_comment.is_empty() || !self.config.documentation {
+            return;
+        }
+
+        out.write("/**");
+        out.new_line();
+        for line in &documentation.doc_comment {
+            let line = line.trim();
+            if line.is_empty() {
+                out.write(" *");
+            } else {
+                write!(out, " * {}", line.replace("*/", "* /"));
+            }
+            out.new_line();
+        }
+        out.write(" */");
+        out.new_line();
+    }
+
+    fn write_fields<F: Write>(&self, out: &mut SourceWriter<F>, fields: &[Field]) {
+        for field in fields {
+            out.new_line();
+            self.write_doc(out, &field.documentation);
+            match *self.resolve(&field.ty) {
+                Type::Array(ref element, ref length) => write!(
+                    out,
+                    "public {} {}[{}];",
+                    self.type_name(element),
+                    identifier(&field.name),
+                    length
+                ),
+                ref ty => write!(
+                    out,
+                    "public {} {};",
+                    self.reference_type_name(ty, false)
+                        .unwrap_or_else(|| self.type_name(ty)),
+                    identifier(&field.name)
+                ),
+            }
+        }
+    }
+
+    fn write_enum<F: Write>(&self, out: &mut SourceWriter<F>, e: &Enum) {
+        if e.tag.is_some() {
+            warn!("Skipping {}: Vala enums can't carry data.", e.export_name());
+            return;
+        }
+
+        self.write_doc(out, &e.documentation);
+        write!(
+            out,
+            "[CCode (cname = \"{}\", cprefix = \"\", ",
+            e.export_name()
+        );
+        if self.config.gobject.enabled && e.has_gtype() {
+            write!(out, "type_id = \"{} ()\")]", e.get_type_function());
+        } else {
+            out.write("has_type_id = false)]");
+        }
+        out.new_line();
+        write!(
+            out,
+            "public enum {} {{",
+            identifier(self.local_name(e.export_name()))
+        );
+        out.push_tab();
+        for (i, variant) in e.variants.iter().enumerate() {
+            out.new_line();
+            self.write_doc(out, &variant.documentation);
+            write!(out, "{}", identifier(&variant.export_name));
+            if i + 1 != e.variants.len() {
+                out.write(",");
+            }
+        }
+        out.pop_tab();
+        out.new_line();
+        out.write("}");
+        out.new_line();
+    }
+
+    /// Writes a struct or a union, Vala only accessing their fields by name.
+    fn write_struct<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        name: &str,
+        fields: &[Field],
+        documentation: &Documentation,
+    ) {
+        self.write_doc(out, documentation);
+        write!(out, "[CCode (cname = \"{}\", has_type_id = false)]", name);
+        out.new_line();
+        write!(
+            out,
+            "public struct {} {{",
+            identifier(self.local_name(name))
+        );
+        out.push_tab();
+        self.write_fields(out, fields);
+        out.pop_tab();
+        out.new_line();
+        out.write("}");
+        out.new_line();
+    }
+
+    /// The functions managing the memory of the opaque item `name`, the
+    /// `name_ref` and `name_unref` functions being preferred to `name_free`.
+    fn memory(&self, name: &str, functions: &[Function]) -> Memory {
+        let snake = name.to_snake_case();
+        let exported = |suffix: &str| {
+            let exported = format!("{}_{}", snake, suffix);
+            functions
+                .iter()
+                .find(|f| {
+                    f.path().name() == exported
+                        && f.args.len() == 1
+                        && points_to(&f.args[0].ty, name)
+                })
+                .map(|f| f.path().name().to_owned())
+        };
+
+        match (exported("ref"), exported("unref")) {
+            (Some(ref_function), Some(unref_function)) => Memory {
+                free_function: None,
+                ref_function: Some(ref_function),
+                unref_function: Some(unref_function),
+            },
+            _ => Memory {
+                free_function: exported("free"),
+                ref_function: None,
+                unref_function: None,
+            },
+        }
+    }
+
+    fn write_compact_class<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        name: &str,
+        documentation: &Documentation,
+        gobjects: &[GObject],
+        functions: &[Function],
+    ) {
+        self.write_doc(out, documentation);
+        write!(out, "[CCode (cname = \"{}\"", name);
+        let memory = self.memory(name, functions);
+        if let Some(ref free_function) = memory.free_function {
+            write!(out, ", free_function = \"{}\"", free_function);
+        }
+        if let (Some(ref ref_function), Some(ref unref_function)) =
+            (memory.ref_function, memory.unref_function)
+        {
+            write!(
+                out,
+                ", ref_function = \"{}\", unref_function = \"{}\"",
+                ref_function, unref_function
+            );
+        }
+        match gobjects
+            .iter()
+            .find(|gobject| gobject.is_boxed() && gobject.name == name)
+        {
+            Some(boxed) => write!(out, ", type_id = \"{} ()\")]", boxed.get_type_function()),
+            None => out.write(", has_type_id = false)]"),
+        }
+        out.new_line();
+        out.write("[Compact]");
+        out.new_line();
+        write!(out, "public class {} {{", identifier(self.local_name(name)));
+        out.new_line();
+        out.write("}");
+        out.new_line();
+    }
+
+    fn write_gobject<F: Write>(&self, out: &mut SourceWriter<F>, gobject: &GObject) {
+        let (kind, parent) = match gobject.gtype {
+            GType::Object {
+                ref parent_type, ..
+            } => (
+                "class",
+                parent_type
+                    .get_root_path()
+                    .map(|parent| self.parent_name(parent.name())),
+            ),
+            GType::Final { ref parent } => ("class", Some(self.parent_name(parent))),
+            GType::Interface { .. } | GType::TraitInterface { .. } => {
+                ("interface", Some("GLib.Object".to_owned()))
+            }
+            // Boxed types are declared as compact classes.
+            GType::Boxed { .. } => return,
+        };
+
+        self.write_doc(out, &gobject.documentation);
+        write!(
+            out,
+            "[CCode (cname = \"{}\", type_id = \"{} ()\")]",
+            gobject.name,
+            gobject.get_type_function()
+        );
+        out.new_line();
+        write!(
+            out,
+            "public {} {}",
+            kind,
+            identifier(self.local_name(&gobject.name))
+        );
+        if let Some(parent) = parent {
+            write!(out, " : {}", parent);
+        }
+        out.write(" {");
+        out.new_line();
+        out.write("}");
+        out.new_line();
+    }
+
+    fn write_constant<F: Write>(&self, out: &mut SourceWriter<F>, constant: &Constant) {
+        if let Literal::Struct { .. } = constant.value {
+            return;
+        }
+
+        let ty = match *self.resolve(&constant.ty) {
+            ref ty if is_c_string(ty) => "string".to_owned(),
+            Type::Primitive(PrimitiveType::Void) | Type::Primitive(PrimitiveType::VaList) => return,
+            Type::Primitive(ref primitive) => primitive_name(primitive, self.config).to_owned(),
+            _ => return,
+        };
+
+        self.write_doc(out, &constant.documentation);
+        write!(out, "[CCode (cname = \"{}\")]", constant.export_name());
+        out.new_line();
+        write!(
+            out,
+            "public const {} {};",
+            ty,
+            identifier(self.local_constant(constant.export_name()))
+        );
+        out.new_line();
+    }
+
+    fn write_function<F: Write>(&self, out: &mut SourceWriter<F>, function: &Function) {
+        self.write_doc(out, &function.documentation);
+        write!(out, "[CCode (cname = \"{}\")]", function.path().name());
+        out.new_line();
+
+        let owned = function.ret_transfer() == Some(Transfer::Full);
+        let ret = self
+            .reference_type_name(&function.ret, owned)
+            .unwrap_or_else(|| self.type_name(&function.ret));
+        write!(
+            out,
+            "public static {} {} (",
+            ret,
+            identifier(self.local_symbol(function.path().name()))
+        );
+
+        // The error isn't a parameter of the throwing functions.
+        let args = if function.throws() {
+            &function.args[..function.args.len() - 1]
+        } else {
+            &function.args[..]
+        };
+        for (i, arg) in args.iter().enumerate() {
+            if i != 0 {
+                out.write(", ");
+            }
+            let name = match arg.name {
+                Some(ref name) => identifier(name),
+                None => format!("arg{}", i),
+            };
+            let ty = match self.reference_type_name(&arg.ty, true) {
+                Some(ty) if function.arg_transfer(arg) == Some(Transfer::Full) => {
+                    format!("owned {}", ty)
+                }
+                Some(ty) => ty,
+                None => self.type_name(&arg.ty),
+            };
+            write!(out, "{} {}", ty, name);
+        }
+        out.write(")");
+        if function.throws() {
+            out.write(" throws GLib.Error");
+        }
+        out.write(";");
+        out.new_line();
+    }
+
+    pub fn write<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        items: &[ItemContainer],
+        constants: &[Constant],
+        functions: &[Function],
+        gobjects: &[GObject],
+    ) {
+        if let Some(ref header) = self.config.header {
+            write!(out, "{}", header);
+            out.new_line();
+            out.new_line();
+        }
+        if let Some(ref warning) = self.config.autogen_warning {
+            write!(out, "{}", warning);
+            out.new_line();
+            out.new_line();
+        }
+
+        write!(
+            out,
+            "[CCode (cprefix = \"{}\", lower_case_cprefix = \"{}_\", cheader_filename = \"{}\")]",
+            self.namespace,
+            self.symbol_prefix,
+            self.headers.join(",")
+        );
+        out.new_line();
+        write!(out, "namespace {} {{", self.namespace);
+        out.push_tab();
+
+        for item in items {
+            if item
+                .deref()
+                .annotations()
+                .bool("no-export")
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            // GObject types are declared as classes and interfaces, and
+            // typedefs are resolved.
+            let name = item.deref().export_name();
+            if gobjects.iter().any(|x| !x.is_boxed() && x.name == name) {
+                continue;
+            }
+            match *item {
+                ItemContainer::Enum(ref e) => {
+                    out.new_line();
+                    self.write_enum(out, e);
+                }
+                ItemContainer::Struct(ref s) => {
+                    out.new_line();
+                    self.write_struct(out, name, &s.fields, &s.documentation);
+                }
+                ItemContainer::Union(ref u) => {
+                    out.new_line();
+                    self.write_struct(out, name, &u.fields, &u.documentation);
+                }
+                ItemContainer::OpaqueItem(ref o) => {
+                    out.new_line();
+                    self.write_compact_class(out, name, &o.documentation, gobjects, functions);
+                }
+                ItemContainer::Typedef(..) => {}
+                ItemContainer::Constant(..)
+                | ItemContainer::Static(..)
+                | ItemContainer::GObject(..) => unreachable!(),
+            }
+        }
+
+        for gobject in gobjects {
+            if !gobject.is_boxed() {
+                out.new_line();
+                self.write_gobject(out, gobject);
+            }
+        }
+
+        let associated_constants = items.iter().filter_map(|item| match *item {
+            ItemContainer::Struct(ref s) => Some(&s.associated_constants),
+            _ => None,
+        });
+        for constant in constants.iter().chain(associated_constants.flatten()) {
+            out.new_line();
+            self.write_constant(out, constant);
+        }
+
+        for function in functions {
+            out.new_line();
+            self.write_function(out, function);
+        }
+
+        out.pop_tab();
+        out.write("}");
+        out.new_line();
+    }
+}
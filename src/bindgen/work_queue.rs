@@ -0,0 +1,179 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::VecDeque;
+use std::panic;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+struct State<T> {
+    tasks: VecDeque<T>,
+    /// The number of tasks being run, which may add new ones.
+    running: usize,
+}
+
+struct Queue<T> {
+    state: Mutex<State<T>>,
+    changed: Condvar,
+}
+
+impl<T> Queue<T> {
+    /// Waits for a task, returning `None` once there are none left and none
+    /// being run.
+    fn next(&self) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(task) = state.tasks.pop_front() {
+                state.running += 1;
+                return Some(task);
+            }
+            if state.running == 0 {
+                return None;
+            }
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+
+    fn done(&self, new_tasks: Vec<T>) {
+        let mut state = self.state.lock().unwrap();
+        state.running -= 1;
+        state.tasks.extend(new_tasks);
+        self.changed.notify_all();
+    }
+}
+
+/// Runs `run` on `tasks`, and on the tasks it adds to its second argument,
+/// on `jobs` threads. With a single job, everything runs on the calling
+/// thread.
+pub(crate) fn run<T, F>(jobs: usize, tasks: Vec<T>, run: F)
+where
+    T: Send + 'static,
+    F: Fn(T, &mut Vec<T>) + Send + Sync + 'static,
+{
+    if jobs <= 1 {
+        let mut tasks = VecDeque::from(tasks);
+        while let Some(task) = tasks.pop_front() {
+            let mut new_tasks = Vec::new();
+            run(task, &mut new_tasks);
+            tasks.extend(new_tasks);
+        }
+        return;
+    }
+
+    let queue = Arc::new(Queue {
+        state: Mutex::new(State {
+            tasks: VecDeque::from(tasks),
+            running: 0,
+        }),
+        changed: Condvar::new(),
+    });
+    let run = Arc::new(run);
+
+    let workers: Vec<_> = (0..jobs)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let run = Arc::clone(&run);
+            thread::spawn(move || {
+                while let Some(task) = queue.next() {
+                    let mut new_tasks = Vec::new();
+                    let result =
+                        panic::catch_unwind(panic::AssertUnwindSafe(|| run(task, &mut new_tasks)));
+                    // Don't leave the other workers waiting for this task.
+                    queue.done(new_tasks);
+                    if let Err(err) = result {
+                        panic::resume_unwind(err);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    // Wait for all the workers before passing the panic of a task on, so
+    // that none of them is left running.
+    let mut panicked = None;
+    for worker in workers {
+        if let Err(err) = worker.join() {
+            panicked.get_or_insert(err);
+        }
+    }
+    if let Some(err) = panicked {
+        panic::resume_unwind(err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs tasks numbered from 1 to 3, each task `n` below 100 adding the
+    /// tasks `n * 10 + 1` to `n * 10 + 3`, and returns the tasks in the order
+    /// they ran.
+    fn run_tree(jobs: usize) -> Vec<u32> {
+        let ran = Arc::new(Mutex::new(Vec::new()));
+        let results = Arc::clone(&ran);
+        run(jobs, vec![1, 2, 3], move |task, new_tasks| {
+            if task < 100 {
+                new_tasks.extend((1..=3).map(|i| task * 10 + i));
+            }
+            results.lock().unwrap().push(task);
+        });
+        Arc::try_unwrap(ran).unwrap().into_inner().unwrap()
+    }
+
+    #[test]
+    fn completion() {
+        for &jobs in &[0, 1, 2, 8] {
+            let mut ran = run_tree(jobs);
+            // 3 tasks, 9 added by them, and 27 added by those.
+            assert_eq!(ran.len(), 39);
+            ran.sort();
+            ran.dedup();
+            assert_eq!(ran.len(), 39);
+        }
+    }
+
+    #[test]
+    fn ordering() {
+        // A single job runs the tasks in order, then the ones they added.
+        let ran = run_tree(1);
+        assert_eq!(&ran[..6], &[1, 2, 3, 11, 12, 13]);
+        assert_eq!(&ran[36..], &[331, 332, 333]);
+
+        // Several jobs run the same tasks, in any order.
+        let mut expected = ran;
+        expected.sort();
+        for _ in 0..10 {
+            let mut ran = run_tree(4);
+            ran.sort();
+            assert_eq!(ran, expected);
+        }
+    }
+
+    #[test]
+    fn panicking_task() {
+        for &jobs in &[1, 4] {
+            let ran = Arc::new(Mutex::new(Vec::new()));
+            let results = Arc::clone(&ran);
+            let result = panic::catch_unwind(move || {
+                run(jobs, (0..20).collect(), move |task: u32, _| {
+                    if task == 5 {
+                        panic!("task {} failed", task);
+                    }
+                    results.lock().unwrap().push(task);
+                })
+            });
+
+            let err = result.unwrap_err();
+            assert_eq!(err.downcast_ref::<String>().unwrap(), "task 5 failed");
+            // The other workers still finish the queue instead of waiting for
+            // the failed task forever.
+            if jobs > 1 {
+                let mut ran = ran.lock().unwrap().clone();
+                ran.sort();
+                let expected: Vec<u32> = (0..20).filter(|&task| task != 5).collect();
+                assert_eq!(ran, expected);
+            }
+        }
+    }
+}
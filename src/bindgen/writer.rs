@@ -81,8 +81,8 @@ impl<'a, F: Write> SourceWriter<'a, F> {
         }
     }
 
-    pub fn bindings(&self) -> &Bindings {
-        &self.bindings
+    pub fn bindings(&self) -> &'a Bindings {
+        self.bindings
     }
 
     /// Takes a function that writes source and returns the maximum line length
@@ -106,6 +106,20 @@ impl<'a, F: Write> SourceWriter<'a, F> {
         measurer.max_line_length
     }
 
+    /// A writer in the same state as this one, writing to a buffer, whose
+    /// text can be written to this one with `write_raw_block`.
+    pub fn buffer(&self) -> SourceWriter<'a, Vec<u8>> {
+        SourceWriter {
+            out: Vec::new(),
+            bindings: self.bindings,
+            spaces: self.spaces.clone(),
+            line_started: self.line_started,
+            line_length: self.line_length,
+            line_number: self.line_number,
+            max_line_length: self.line_length,
+        }
+    }
+
     fn spaces(&self) -> usize {
         *self.spaces.last().unwrap()
     }
@@ -172,6 +186,11 @@ impl<'a, F: Write> SourceWriter<'a, F> {
                 self.new_line();
                 self.push_tab();
             }
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
     }
 
@@ -187,6 +206,11 @@ impl<'a, F: Write> SourceWriter<'a, F> {
                 }
             }
             Language::Cython => {}
+            Language::Gir
+            | Language::CSharp
+            | Language::Vapi
+            | Language::Napi
+            | Language::Ctypes => unreachable!(),
         }
     }
 
@@ -253,6 +277,13 @@ impl<'a, F: Write> SourceWriter<'a, F> {
     }
 }
 
+impl<'a> SourceWriter<'a, Vec<u8>> {
+    /// The text written to a buffer.
+    pub fn into_text(self) -> String {
+        String::from_utf8(self.out).unwrap()
+    }
+}
+
 pub trait Source {
     fn write<F: Write>(&self, config: &Config, _: &mut SourceWriter<F>);
 }
@@ -2,11 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
 use std::env;
+use std::fs::{self, File};
 use std::io;
 use std::path::{Path, PathBuf};
 
 extern crate clap;
+extern crate heck;
 #[macro_use]
 extern crate log;
 extern crate proc_macro2;
@@ -20,13 +23,205 @@ extern crate syn;
 extern crate toml;
 
 use clap::{App, Arg, ArgMatches};
+use heck::{CamelCase, ShoutySnakeCase, SnakeCase};
 
 mod bindgen;
 mod logging;
 
-use crate::bindgen::{Bindings, Builder, Cargo, Config, Error, Language};
+use crate::bindgen::{Bindings, Builder, Cargo, Error, Language};
 
-fn load_bindings<'a>(input: &Path, matches: &ArgMatches<'a>) -> Result<Bindings, Error> {
+/// Items to restrict or rename in the GObject header.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+struct ExportConfig {
+    /// Additional items not used by exported functions to include.
+    include: Vec<String>,
+    /// Items to leave out.
+    exclude: Vec<String>,
+    /// A prefix to add before the name of every item, the namespace if unset.
+    prefix: Option<String>,
+    /// The GLib types to write for the primitive Rust types.
+    type_map: HashMap<String, String>,
+}
+
+/// The per-crate configuration, read from `gbindgen.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+struct Config {
+    /// The C namespace of the library, e.g. `Gbd`, derived from the crate
+    /// name if unset.
+    namespace: Option<String>,
+    /// Whether to define the `NAMESPACE_CHECK_VERSION` macros from the
+    /// crate version.
+    version_macros: bool,
+    /// Additional system headers to include, besides glib-object.h.
+    sys_includes: Vec<String>,
+    export: ExportConfig,
+    /// A complete cbindgen configuration, merged with the gobject defaults.
+    cbindgen: Option<toml::value::Table>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            namespace: None,
+            version_macros: true,
+            sys_includes: Vec::new(),
+            export: ExportConfig::default(),
+            cbindgen: None,
+        }
+    }
+}
+
+/// The version macros of a crate, following the GLib conventions.
+fn version_macros(namespace: &str, version: &str) -> Option<String> {
+    let mut numbers = version
+        .split(|c| c == '.' || c == '-' || c == '+')
+        .map(|number| number.parse::<u32>());
+    let (major, minor, micro) = match (numbers.next(), numbers.next(), numbers.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(micro))) => (major, minor, micro),
+        _ => return None,
+    };
+
+    let ns = namespace.to_shouty_snake_case();
+    Some(format!(
+        "\n#define {ns}_MAJOR_VERSION {}
+#define {ns}_MINOR_VERSION {}
+#define {ns}_MICRO_VERSION {}
+
+#define {ns}_CHECK_VERSION(major, minor, micro) \\
+  ({ns}_MAJOR_VERSION > (major) || \\
+   ({ns}_MAJOR_VERSION == (major) && {ns}_MINOR_VERSION > (minor)) || \\
+   ({ns}_MAJOR_VERSION == (major) && {ns}_MINOR_VERSION == (minor) && \\
+    {ns}_MICRO_VERSION >= (micro)))",
+        major,
+        minor,
+        micro,
+        ns = ns
+    ))
+}
+
+/// The prototypes and the definitions of the version functions of a crate,
+/// returning its version macros at runtime.
+fn version_functions(namespace: &str) -> (String, String) {
+    let ns = namespace.to_shouty_snake_case();
+    let snake = namespace.to_snake_case();
+    let mut prototypes = String::new();
+    let mut definitions = String::new();
+    for part in &["major", "minor", "micro"] {
+        let function = format!("unsigned int {}_get_{}_version(void)", snake, part);
+        prototypes.push_str(&format!("\n{};", function));
+        definitions.push_str(&format!(
+            "\n{} {{\n  return {}_{}_VERSION;\n}}\n",
+            function,
+            ns,
+            part.to_uppercase()
+        ));
+    }
+    (prototypes, definitions)
+}
+
+impl Config {
+    fn from_file(file_name: &Path) -> Result<Config, String> {
+        let config_text = fs::read_to_string(file_name)
+            .map_err(|_| format!("Couldn't open config file: {}.", file_name.display()))?;
+
+        match toml::from_str::<Config>(&config_text) {
+            Ok(x) => Ok(x),
+            Err(e) => Err(format!("Couldn't parse config file: {}.", e)),
+        }
+    }
+
+    fn from_root_or_default(root: &Path) -> Result<Config, String> {
+        let c = root.join("gbindgen.toml");
+
+        if c.exists() {
+            Config::from_file(&c)
+        } else {
+            Ok(Config::default())
+        }
+    }
+
+    /// The configuration of the bindings generator, the `[cbindgen]` table
+    /// taking precedence over the gobject defaults, along with the C source
+    /// of the version functions when `c_source` is set.
+    fn bindgen_config(
+        &self,
+        lib: &Cargo,
+        c_source: bool,
+    ) -> Result<(bindgen::Config, Option<String>), String> {
+        let mut table = self.cbindgen.clone().unwrap_or_default();
+        table
+            .entry("cpp_compat".to_owned())
+            .or_insert(toml::Value::Boolean(true));
+        let mut config: bindgen::Config = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("Couldn't parse the [cbindgen] table: {}.", e))?;
+
+        config
+            .sys_includes
+            .extend(self.sys_includes.iter().cloned());
+        config
+            .export
+            .include
+            .extend(self.export.include.iter().cloned());
+        config
+            .export
+            .exclude
+            .extend(self.export.exclude.iter().cloned());
+        if let Some(ref prefix) = self.export.prefix {
+            config.export.prefix = Some(prefix.clone());
+        }
+        config.export.type_map.extend(self.export.type_map.clone());
+
+        let namespace = match self.namespace {
+            Some(ref namespace) => namespace.clone(),
+            None => lib.binding_crate_name().to_camel_case(),
+        };
+        if config.export.prefix.is_none() {
+            config.export.prefix = Some(namespace.clone());
+        }
+        if config.gir.namespace.is_none() {
+            config.gir.namespace = Some(namespace.clone());
+        }
+
+        let mut source = None;
+        if self.version_macros {
+            let package = lib.binding_crate_ref();
+            let version = package.version.as_deref().unwrap_or("");
+            match version_macros(&namespace, version) {
+                Some(mut macros) => {
+                    if c_source {
+                        let (prototypes, definitions) = version_functions(&namespace);
+                        macros.push_str(&format!("\n{}", prototypes));
+                        source = Some(definitions);
+                    }
+                    config.after_includes = Some(match config.after_includes {
+                        Some(ref after_includes) => format!("{}\n{}", after_includes, macros),
+                        None => macros,
+                    });
+                }
+                None => warn!(
+                    "Skipping the version macros, {} isn't a major.minor.micro version.",
+                    version
+                ),
+            }
+        }
+
+        Ok((config, source))
+    }
+}
+
+/// Loads the bindings of the crate at `input`, along with the C source of
+/// its version functions when `--c-out` is given.
+fn load_bindings<'a>(
+    input: &Path,
+    matches: &ArgMatches<'a>,
+) -> Result<(Bindings, Option<String>), Error> {
     // We have to load a whole crate, so we use cargo to gather metadata
     let lib = Cargo::load(
         input,
@@ -37,19 +232,116 @@ fn load_bindings<'a>(input: &Path, matches: &ArgMatches<'a>) -> Result<Bindings,
         matches.value_of("metadata").map(Path::new),
     )?;
 
-    let mut config = Config::default();
-    config.cpp_compat = true;
+    // Search the configuration in the binding crate directory
+    let config = match lib.find_crate_dir(&lib.binding_crate_ref()) {
+        Some(binding_crate_dir) => Config::from_root_or_default(&binding_crate_dir),
+        None => Config::from_root_or_default(input),
+    }
+    .map_err(Error::Config)?;
+
+    generate(lib, &config, matches.is_present("c-out"))
+}
+
+fn generate(
+    lib: Cargo,
+    config: &Config,
+    c_source: bool,
+) -> Result<(Bindings, Option<String>), Error> {
+    let (config, source) = config
+        .bindgen_config(&lib, c_source)
+        .map_err(Error::Config)?;
 
-    Builder::new()
+    let bindings = Builder::new()
         .with_config(config)
         .with_language(Language::C)
         .with_gobject(true)
         .with_cargo(lib)
-        .generate()
+        .generate()?;
+    Ok((bindings, source))
 }
 
-fn main() {
-    let matches = App::new("gbindgen")
+/// Writes the GObject header of each member of the workspace at `input`
+/// which has a `gbindgen.toml`, named after the crate, next to its
+/// `Cargo.toml` or in `--out-dir`. Returns the exit code.
+fn generate_workspace<'a>(input: &Path, matches: &ArgMatches<'a>) -> i32 {
+    let workspace = match Cargo::load_workspace(
+        input,
+        matches.value_of("lockfile"),
+        true,
+        matches.is_present("clean"),
+        matches.value_of("metadata").map(Path::new),
+    ) {
+        Ok(workspace) => workspace,
+        Err(msg) => {
+            error!("{}", msg);
+            error!("Couldn't load the workspace {}.", input.display());
+            return 1;
+        }
+    };
+
+    let mut code = 0;
+    let mut found = false;
+    for member in workspace.workspace_members() {
+        let crate_dir = match workspace.find_crate_dir(&member) {
+            Some(crate_dir) => crate_dir,
+            None => continue,
+        };
+        let config_path = crate_dir.join("gbindgen.toml");
+        if !config_path.exists() {
+            continue;
+        }
+        found = true;
+
+        let config = match Config::from_file(&config_path) {
+            Ok(config) => config,
+            Err(msg) => {
+                error!("{}", msg);
+                code = 1;
+                continue;
+            }
+        };
+        let file = match matches.value_of("out-dir") {
+            Some(out_dir) => PathBuf::from(out_dir),
+            None => crate_dir,
+        }
+        .join(format!("{}.h", member.name));
+
+        let bindings = match generate(workspace.for_member(&member), &config, false) {
+            Ok((bindings, _)) => bindings,
+            Err(msg) => {
+                error!("{}", msg);
+                error!("Couldn't generate bindings for {}.", member.name);
+                code = 1;
+                continue;
+            }
+        };
+
+        let verify = matches.is_present("verify");
+        if verify && !matches.is_present("quiet") {
+            if let Some(diff) = bindings.diff_against_file(&file) {
+                eprint!("{}", diff);
+            }
+        }
+        if bindings.write_to_file(&file) && verify {
+            error!("Bindings changed: {}", file.display());
+            if code == 0 {
+                code = 2;
+            }
+        }
+    }
+
+    if !found {
+        warn!(
+            "No member of the workspace {} has a gbindgen.toml.",
+            input.display()
+        );
+    }
+    code
+}
+
+/// The command line of gbindgen.
+fn app() -> App<'static, 'static> {
+    App::new("gbindgen")
         .version(bindgen::VERSION)
         .about("Generate GObject C bindings for a glib/gtk-rs library")
         .arg(
@@ -80,6 +372,83 @@ fn main() {
                 .help("The file to output the bindings to")
                 .required(false),
         )
+        .arg(
+            Arg::with_name("depfile")
+                .long("depfile")
+                .value_name("PATH")
+                .help("Write a Makefile-style dependency file listing the parsed Rust sources")
+                .requires("out")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("symbols-out")
+                .long("symbols-out")
+                .value_name("PATH")
+                .help(
+                    "Also write the exported symbols as a linker version script, \
+                     or as a module-definition file if PATH ends with .def",
+                )
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("workspace")
+                .long("workspace")
+                .help(
+                    "Write the header of each member of the workspace which has a \
+                     gbindgen.toml next to its Cargo.toml, named after the crate")
+                .conflicts_with_all(&["out", "depfile", "symbols-out", "emit-ir", "gir-out", "vapi-out", "c-out"]),
+        )
+        .arg(
+            Arg::with_name("out-dir")
+                .long("out-dir")
+                .value_name("PATH")
+                .help("The directory to write the headers of the workspace members to")
+                .requires("workspace")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("emit-ir")
+                .long("emit-ir")
+                .value_name("PATH")
+                .help("Also write the items of the bindings as JSON, for other tools to use")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("gir-out")
+                .long("gir-out")
+                .value_name("PATH")
+                .help("Also write a GObject introspection (.gir) file describing the bindings")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("vapi-out")
+                .long("vapi-out")
+                .value_name("PATH")
+                .help("Also write a Vala API (.vapi) file describing the bindings")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("c-out")
+                .long("c-out")
+                .value_name("PATH")
+                .help(
+                    "Also write a C source defining the GTypes of the enums and of the \
+                     boxed types, and the version functions")
+                .requires("out")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("message-format")
+                .long("message-format")
+                .value_name("FORMAT")
+                .help(
+                    "How to report warnings and errors: `json` writes each of them as a JSON \
+                     object on its own line of stderr, with the file and line of the item \
+                     which caused it when there is one",
+                )
+                .possible_values(&["human", "json"])
+                .default_value("human"),
+        )
         .arg(
             Arg::with_name("quiet")
                 .short("q")
@@ -87,10 +456,24 @@ fn main() {
                 .help("Report errors only (overrides verbosity options).")
                 .required(false),
         )
-        .get_matches();
+}
+
+fn main() {
+    let matches = app().get_matches();
 
     // Initialize logging
-    if matches.is_present("quiet") {
+    if matches.value_of("message-format") == Some("json") {
+        let level = if matches.is_present("quiet") {
+            log::LevelFilter::Error
+        } else {
+            match matches.occurrences_of("v") {
+                0 => log::LevelFilter::Warn,
+                1 => log::LevelFilter::Info,
+                _ => log::LevelFilter::Trace,
+            }
+        };
+        logging::JsonLogger::init(level).unwrap();
+    } else if matches.is_present("quiet") {
         logging::ErrorLogger::init().unwrap();
     } else {
         match matches.occurrences_of("v") {
@@ -106,7 +489,11 @@ fn main() {
         None => env::current_dir().unwrap(),
     };
 
-    let bindings = match load_bindings(&input, &matches) {
+    if matches.is_present("workspace") {
+        std::process::exit(generate_workspace(&input, &matches));
+    }
+
+    let (bindings, version_source) = match load_bindings(&input, &matches) {
         Ok(bindings) => bindings,
         Err(msg) => {
             error!("{}", msg);
@@ -115,11 +502,26 @@ fn main() {
         }
     };
 
+    if let Some(ir_file) = matches.value_of("emit-ir") {
+        bindings.write_ir_to_file(ir_file);
+    }
+
+    if let Some(symbols_file) = matches.value_of("symbols-out") {
+        if bindings.write_symbols_to_file(symbols_file) && matches.is_present("verify") {
+            error!("Symbols changed: {}", symbols_file);
+            std::process::exit(2);
+        }
+    }
+
     // Write the bindings file
     match matches.value_of("out") {
         Some(file) => {
             let changed = bindings.write_to_file(file);
 
+            if let Some(depfile) = matches.value_of("depfile") {
+                bindings.write_depfile(file, depfile);
+            }
+
             if matches.is_present("verify") && changed {
                 error!("Bindings changed: {}", file);
                 std::process::exit(2);
@@ -129,4 +531,304 @@ fn main() {
             bindings.write(io::stdout());
         }
     }
+
+    if let Some(path) = matches.value_of("gir-out") {
+        match File::create(path) {
+            Ok(file) => bindings.write_gir(file),
+            Err(err) => {
+                error!("Unable to create {}: {}", path, err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(path) = matches.value_of("vapi-out") {
+        let header = matches
+            .value_of("out")
+            .and_then(|out| Path::new(out).file_name())
+            .map(|header| header.to_string_lossy());
+        match File::create(path) {
+            Ok(file) => bindings.write_vapi(file, header.as_deref()),
+            Err(err) => {
+                error!("Unable to create {}: {}", path, err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(path) = matches.value_of("c-out") {
+        let header = Path::new(matches.value_of("out").unwrap());
+        let header = header.file_name().unwrap().to_string_lossy();
+        let mut source = Vec::new();
+        bindings.write_c_source(&mut source, &header);
+        if let Some(version_source) = version_source {
+            source.extend_from_slice(version_source.as_bytes());
+        }
+        match fs::write(path, source) {
+            Ok(()) => {}
+            Err(err) => {
+                error!("Unable to create {}: {}", path, err);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(crate_dir: &str) -> Cargo {
+        Cargo::load(Path::new(crate_dir), None, None, true, false, None).unwrap()
+    }
+
+    fn config(text: &str) -> Config {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn version_macros_header() {
+        assert_eq!(
+            version_macros("GbdFoo", "1.20.3").unwrap(),
+            "
+#define GBD_FOO_MAJOR_VERSION 1
+#define GBD_FOO_MINOR_VERSION 20
+#define GBD_FOO_MICRO_VERSION 3
+
+#define GBD_FOO_CHECK_VERSION(major, minor, micro) \\
+  (GBD_FOO_MAJOR_VERSION > (major) || \\
+   (GBD_FOO_MAJOR_VERSION == (major) && GBD_FOO_MINOR_VERSION > (minor)) || \\
+   (GBD_FOO_MAJOR_VERSION == (major) && GBD_FOO_MINOR_VERSION == (minor) && \\
+    GBD_FOO_MICRO_VERSION >= (micro)))"
+        );
+
+        // Pre-release and build metadata don't change the version numbers.
+        assert_eq!(
+            version_macros("Gbd", "1.2.3-beta.1"),
+            version_macros("Gbd", "1.2.3")
+        );
+        assert_eq!(
+            version_macros("Gbd", "1.2.3+abc"),
+            version_macros("Gbd", "1.2.3")
+        );
+    }
+
+    #[test]
+    fn version_macros_none() {
+        assert_eq!(version_macros("Gbd", ""), None);
+        assert_eq!(version_macros("Gbd", "1.2"), None);
+        assert_eq!(version_macros("Gbd", "1.x.3"), None);
+        assert_eq!(version_macros("Gbd", "-1.2.3"), None);
+    }
+
+    #[test]
+    fn version_macros_bindings() {
+        let (bindings, source) =
+            generate(load("tests/rust/mod_2018"), &Config::default(), true).unwrap();
+        let mut header = Vec::new();
+        bindings.write(&mut header);
+        let header = String::from_utf8(header).unwrap();
+        assert!(header.contains("#define MOD2018_MAJOR_VERSION 0\n"));
+        assert!(header.contains("#define MOD2018_MINOR_VERSION 1\n"));
+        assert!(header.contains("#define MOD2018_CHECK_VERSION(major, minor, micro)"));
+        assert!(header.contains("unsigned int mod2018_get_major_version(void);"));
+        assert!(source.unwrap().contains(
+            "unsigned int mod2018_get_micro_version(void) {\n  return MOD2018_MICRO_VERSION;\n}"
+        ));
+
+        // Without --c-out, only the macros are written.
+        let (bindings, source) =
+            generate(load("tests/rust/mod_2018"), &Config::default(), false).unwrap();
+        let mut header = Vec::new();
+        bindings.write(&mut header);
+        let header = String::from_utf8(header).unwrap();
+        assert!(header.contains("#define MOD2018_CHECK_VERSION(major, minor, micro)"));
+        assert!(!header.contains("mod2018_get_major_version"));
+        assert_eq!(source, None);
+    }
+
+    #[test]
+    fn version_macros_disabled() {
+        let config = config("version_macros = false");
+        let (config, source) = config
+            .bindgen_config(&load("tests/rust/mod_2018"), true)
+            .unwrap();
+        assert_eq!(config.after_includes, None);
+        assert_eq!(source, None);
+
+        // The macros go after the ones of the [cbindgen] table.
+        let config = self::config(
+            r##"
+            [cbindgen]
+            after_includes = "#define MOD2018_EXTRA 1"
+            "##,
+        );
+        let (config, _) = config
+            .bindgen_config(&load("tests/rust/mod_2018"), false)
+            .unwrap();
+        assert!(config
+            .after_includes
+            .unwrap()
+            .starts_with("#define MOD2018_EXTRA 1\n\n#define MOD2018_MAJOR_VERSION 0"));
+    }
+
+    #[test]
+    fn bindgen_config_defaults() {
+        let (config, _) = Config::default()
+            .bindgen_config(&load("tests/rust/mod_2018"), false)
+            .unwrap();
+        assert!(config.cpp_compat);
+        assert_eq!(config.export.prefix.as_deref(), Some("Mod2018"));
+        assert_eq!(config.gir.namespace.as_deref(), Some("Mod2018"));
+        assert!(config.sys_includes.is_empty());
+    }
+
+    #[test]
+    fn bindgen_config_merge() {
+        let config = config(
+            r#"
+            namespace = "Gbd"
+            sys_includes = ["gio/gio.h"]
+
+            [export]
+            include = ["Extra"]
+            exclude = ["Hidden"]
+            type_map = { u32 = "guint32" }
+
+            [cbindgen]
+            sys_includes = ["stdio.h"]
+            cpp_compat = false
+
+            [cbindgen.export]
+            include = ["Other"]
+            exclude = ["Internal"]
+            "#,
+        );
+        let (config, _) = config
+            .bindgen_config(&load("tests/rust/mod_2018"), false)
+            .unwrap();
+
+        // The [cbindgen] table overrides the gobject defaults.
+        assert!(!config.cpp_compat);
+        // The gbindgen lists extend the ones of the [cbindgen] table.
+        assert_eq!(config.sys_includes, ["stdio.h", "gio/gio.h"]);
+        assert_eq!(config.export.include, ["Other", "Extra"]);
+        assert_eq!(config.export.exclude, ["Internal", "Hidden"]);
+        assert_eq!(config.export.type_map["u32"], "guint32");
+        // The namespace replaces the crate name.
+        assert_eq!(config.export.prefix.as_deref(), Some("Gbd"));
+        assert_eq!(config.gir.namespace.as_deref(), Some("Gbd"));
+    }
+
+    #[test]
+    fn bindgen_config_overrides() {
+        // The export prefix of gbindgen.toml takes precedence over the one of
+        // the [cbindgen] table, which takes precedence over the namespace.
+        let config = config(
+            r#"
+            namespace = "Gbd"
+
+            [export]
+            prefix = "GbdPrefix"
+
+            [cbindgen.export]
+            prefix = "Ignored"
+
+            [cbindgen.gir]
+            namespace = "GbdGir"
+            "#,
+        );
+        let (config, _) = config
+            .bindgen_config(&load("tests/rust/mod_2018"), false)
+            .unwrap();
+        assert_eq!(config.export.prefix.as_deref(), Some("GbdPrefix"));
+        assert_eq!(config.gir.namespace.as_deref(), Some("GbdGir"));
+
+        let config = self::config(
+            r#"
+            namespace = "Gbd"
+
+            [cbindgen.export]
+            prefix = "GbdCbindgen"
+            "#,
+        );
+        let (config, _) = config
+            .bindgen_config(&load("tests/rust/mod_2018"), false)
+            .unwrap();
+        assert_eq!(config.export.prefix.as_deref(), Some("GbdCbindgen"));
+        assert_eq!(config.gir.namespace.as_deref(), Some("Gbd"));
+    }
+
+    #[test]
+    fn bindgen_config_invalid_table() {
+        let config = config(
+            r#"
+            [cbindgen]
+            language = "Klingon"
+            "#,
+        );
+        let err = config
+            .bindgen_config(&load("tests/rust/mod_2018"), false)
+            .unwrap_err();
+        assert!(
+            err.starts_with("Couldn't parse the [cbindgen] table:"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn workspace_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let out_dir = dir.path().to_str().unwrap();
+        let matches = app().get_matches_from(vec![
+            "gbindgen",
+            "--workspace",
+            "--out-dir",
+            out_dir,
+            "tests/rust/gobject_workspace",
+        ]);
+        assert_eq!(
+            generate_workspace(Path::new("tests/rust/gobject_workspace"), &matches),
+            0
+        );
+
+        // Each member with a gbindgen.toml gets its own header, named after
+        // the crate and generated with its own configuration.
+        let shape = fs::read_to_string(dir.path().join("gbd-shape.h")).unwrap();
+        assert!(shape.contains("} GbdShapePoint;"));
+        assert!(shape.contains("GbdShapePoint gbd_shape_origin(void);"));
+        assert!(shape.contains("#define GBD_SHAPE_CHECK_VERSION"));
+        assert!(!shape.contains("Color"));
+
+        let color = fs::read_to_string(dir.path().join("gbd-color.h")).unwrap();
+        assert!(color.contains("} GbdRgbColor;"));
+        assert!(color.contains("GbdRgbColor gbd_rgb_black(void);"));
+        assert!(!color.contains("CHECK_VERSION"));
+        assert!(!color.contains("Point"));
+
+        // The members without one, and the root package, are left alone.
+        assert!(!dir.path().join("plain.h").exists());
+        assert!(!dir.path().join("gobject_workspace.h").exists());
+
+        let matches = app().get_matches_from(vec![
+            "gbindgen",
+            "--workspace",
+            "--verify",
+            "--out-dir",
+            out_dir,
+            "tests/rust/gobject_workspace",
+        ]);
+        assert_eq!(
+            generate_workspace(Path::new("tests/rust/gobject_workspace"), &matches),
+            0
+        );
+
+        // A member whose header changed fails the verification.
+        fs::write(dir.path().join("gbd-color.h"), "").unwrap();
+        assert_eq!(
+            generate_workspace(Path::new("tests/rust/gobject_workspace"), &matches),
+            2
+        );
+    }
 }
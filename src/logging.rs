@@ -7,10 +7,43 @@ use std::io::Write;
 
 use log::*;
 
+use crate::bindgen::diagnostic;
+
 pub struct TraceLogger;
 pub struct WarnLogger;
 pub struct InfoLogger;
 pub struct ErrorLogger;
+pub struct JsonLogger;
+
+/// The location of a diagnostic in the parsed sources, if the record has one.
+fn location<'a>(record: &Record<'a>) -> Option<(&'a str, u32)> {
+    if record.target() != diagnostic::TARGET {
+        return None;
+    }
+    match (record.file(), record.line()) {
+        (Some(file), Some(line)) => Some((file, line)),
+        _ => None,
+    }
+}
+
+/// The JSON object of `--message-format json` for the record, with the
+/// location of the diagnostic or `null` for the messages which don't have one.
+fn json(record: &Record) -> serde_json::Value {
+    let location = location(record);
+    serde_json::json!({
+        "level": record.level().to_string().to_lowercase(),
+        "message": record.args().to_string(),
+        "file": location.map(|(file, _)| file),
+        "line": location.map(|(_, line)| line),
+    })
+}
+
+fn write_record(record: &Record) {
+    match location(record) {
+        Some((file, line)) => eprintln!("{}: {}:{}: {}", record.level(), file, line, record.args()),
+        None => eprintln!("{}: {}", record.level(), record.args()),
+    }
+}
 
 impl TraceLogger {
     pub fn init() -> Result<(), SetLoggerError> {
@@ -26,7 +59,7 @@ impl log::Log for TraceLogger {
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            eprintln!("{}: {}", record.level(), record.args());
+            write_record(record);
         }
     }
 
@@ -49,7 +82,7 @@ impl log::Log for WarnLogger {
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            eprintln!("{}: {}", record.level(), record.args());
+            write_record(record);
         }
     }
 
@@ -72,7 +105,7 @@ impl log::Log for ErrorLogger {
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            eprintln!("{}: {}", record.level(), record.args());
+            write_record(record);
         }
     }
 
@@ -95,7 +128,7 @@ impl log::Log for InfoLogger {
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            eprintln!("{}: {}", record.level(), record.args());
+            write_record(record);
         }
     }
 
@@ -103,3 +136,82 @@ impl log::Log for InfoLogger {
         io::stderr().flush().unwrap();
     }
 }
+
+impl JsonLogger {
+    pub fn init(level: LevelFilter) -> Result<(), SetLoggerError> {
+        log::set_logger(&JsonLogger)?;
+        log::set_max_level(level);
+        Ok(())
+    }
+}
+impl log::Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    /// Writes the record as a JSON object on its own line.
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}", json(record));
+        }
+    }
+
+    fn flush(&self) {
+        io::stderr().flush().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_diagnostic() {
+        let record = Record::builder()
+            .level(Level::Warn)
+            .target(diagnostic::TARGET)
+            .file(Some("src/lib.rs"))
+            .line(Some(12))
+            .args(format_args!("Skip Foo - (not `repr(C)`)."))
+            .build();
+        assert_eq!(
+            json(&record),
+            serde_json::json!({
+                "level": "warn",
+                "message": "Skip Foo - (not `repr(C)`).",
+                "file": "src/lib.rs",
+                "line": 12,
+            })
+        );
+    }
+
+    #[test]
+    fn json_without_location() {
+        // The file and line of the other records are the ones of the logging
+        // call in cbindgen, not a location in the parsed sources.
+        let record = Record::builder()
+            .level(Level::Error)
+            .target("cbindgen::bindgen::library")
+            .file(Some("src/bindgen/library.rs"))
+            .line(Some(40))
+            .args(format_args!("Cannot find a path"))
+            .build();
+        assert_eq!(
+            json(&record),
+            serde_json::json!({
+                "level": "error",
+                "message": "Cannot find a path",
+                "file": null,
+                "line": null,
+            })
+        );
+
+        let record = Record::builder()
+            .level(Level::Info)
+            .target(diagnostic::TARGET)
+            .args(format_args!("Take Foo."))
+            .build();
+        assert_eq!(json(&record)["file"], serde_json::Value::Null);
+        assert_eq!(json(&record)["line"], serde_json::Value::Null);
+    }
+}
@@ -3,9 +3,12 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::env;
+use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 extern crate clap;
 #[macro_use]
@@ -25,7 +28,9 @@ use clap::{App, Arg, ArgMatches};
 mod bindgen;
 mod logging;
 
-use crate::bindgen::{Bindings, Builder, Cargo, Config, Error, Profile, Style};
+use crate::bindgen::{
+    Bindings, Builder, Cargo, Config, Error, Language, Profile, Style, Unresolved,
+};
 
 fn apply_config_overrides<'a>(config: &mut Config, matches: &ArgMatches<'a>) {
     // We allow specifying a language to override the config default. This is
@@ -44,6 +49,14 @@ fn apply_config_overrides<'a>(config: &mut Config, matches: &ArgMatches<'a>) {
         config.cpp_compat = true;
     }
 
+    if matches.is_present("wasm") {
+        config.wasm.enabled = true;
+    }
+
+    if matches.is_present("strict") {
+        config.parse.unresolved = Unresolved::Error;
+    }
+
     if let Some(style) = matches.value_of("style") {
         config.style = match style {
             "Both" => Style::Both,
@@ -72,23 +85,55 @@ fn apply_config_overrides<'a>(config: &mut Config, matches: &ArgMatches<'a>) {
     if matches.is_present("d") {
         config.parse.parse_deps = true;
     }
+
+    if matches.is_present("cache") {
+        config.parse.cache = true;
+    }
+    if matches.is_present("no-cache") {
+        config.parse.cache = false;
+    }
+
+    if let Some(jobs) = matches.value_of("jobs") {
+        config.parse.jobs = match jobs.parse() {
+            Ok(jobs) => jobs,
+            Err(_) => {
+                error!("Invalid number of jobs: {}.", jobs);
+                return;
+            }
+        }
+    }
 }
 
-fn load_bindings<'a>(input: &Path, matches: &ArgMatches<'a>) -> Result<Bindings, Error> {
+fn load_builder<'a>(input: &Path, matches: &ArgMatches<'a>) -> Result<Builder, Error> {
+    let (config, lib) = load_config(input, matches)?;
+    Ok(builder_for(input, config, lib))
+}
+
+fn builder_for(input: &Path, config: Config, lib: Option<Cargo>) -> Builder {
+    let builder = Builder::new().with_config(config);
+    match lib {
+        Some(lib) => builder.with_cargo(lib),
+        None => builder.with_src(input),
+    }
+}
+
+/// Loads the config, and the metadata of the crate when `input` isn't a
+/// single source file.
+fn load_config<'a>(
+    input: &Path,
+    matches: &ArgMatches<'a>,
+) -> Result<(Config, Option<Cargo>), Error> {
     // If a file is specified then we load it as a single source
     if !input.is_dir() {
         // Load any config specified or search in the input directory
         let mut config = match matches.value_of("config") {
-            Some(c) => Config::from_file(c).unwrap(),
+            Some(c) => Config::from_file(c).map_err(Error::Config)?,
             None => Config::from_root_or_default(input),
         };
 
         apply_config_overrides(&mut config, &matches);
 
-        return Builder::new()
-            .with_config(config)
-            .with_src(input)
-            .generate();
+        return Ok((config, None));
     }
 
     // We have to load a whole crate, so we use cargo to gather metadata
@@ -103,7 +148,7 @@ fn load_bindings<'a>(input: &Path, matches: &ArgMatches<'a>) -> Result<Bindings,
 
     // Load any config specified or search in the binding crate directory
     let mut config = match matches.value_of("config") {
-        Some(c) => Config::from_file(c).unwrap(),
+        Some(c) => Config::from_file(c).map_err(Error::Config)?,
         None => {
             let binding_crate_dir = lib.find_crate_dir(&lib.binding_crate_ref());
 
@@ -118,10 +163,273 @@ fn load_bindings<'a>(input: &Path, matches: &ArgMatches<'a>) -> Result<Bindings,
 
     apply_config_overrides(&mut config, &matches);
 
-    Builder::new()
-        .with_config(config)
-        .with_cargo(lib)
-        .generate()
+    Ok((config, Some(lib)))
+}
+
+/// Writes `bindings` to `file`, after printing how they differ from it with
+/// `--verify`. Returns whether `--verify` fails because they changed.
+fn write_verified<'a>(bindings: &Bindings, file: &Path, matches: &ArgMatches<'a>) -> bool {
+    let verify = matches.is_present("verify");
+    if verify && !matches.is_present("quiet") {
+        if let Some(diff) = bindings.diff_against_file(file) {
+            eprint!("{}", diff);
+        }
+    }
+    if bindings.write_to_file(file) && verify {
+        error!("Bindings changed: {}", file.display());
+        return true;
+    }
+    false
+}
+
+/// Writes the bindings of each `[[bindings]]` entry of the config to its
+/// output. Returns the exit code.
+fn generate_bindings_entries<'a>(
+    input: &Path,
+    config: &Config,
+    lib: Option<&Cargo>,
+    matches: &ArgMatches<'a>,
+) -> i32 {
+    let mut code = 0;
+    for entry in &config.bindings {
+        let builder = Builder::new().with_config(config.for_bindings(entry));
+        let builder = match (lib, entry.crate_name.as_ref()) {
+            (Some(lib), Some(name)) => {
+                let members = lib.workspace_members();
+                match members.iter().find(|member| &member.name == name) {
+                    Some(member) => builder.with_cargo(lib.for_member(member)),
+                    None => {
+                        error!("Crate {} isn't a member of the workspace.", name);
+                        code = 1;
+                        continue;
+                    }
+                }
+            }
+            (Some(lib), None) => builder.with_cargo(lib.clone()),
+            (None, None) => builder.with_src(input),
+            (None, Some(name)) => {
+                error!(
+                    "Can't generate the bindings of crate {} from a source file.",
+                    name
+                );
+                code = 1;
+                continue;
+            }
+        };
+
+        let bindings = match builder.generate() {
+            Ok(bindings) => bindings,
+            Err(msg) => {
+                error!("{}", msg);
+                error!("Couldn't generate bindings for {}.", entry.output);
+                code = 1;
+                continue;
+            }
+        };
+        if write_verified(&bindings, Path::new(&entry.output), matches) && code == 0 {
+            code = 2;
+        }
+    }
+    code
+}
+
+/// The extension of the headers written by `--workspace`.
+fn header_extension(language: Language) -> &'static str {
+    match language {
+        Language::C | Language::Cxx => "h",
+        Language::Cython => "pxd",
+        Language::Gir => "gir",
+        Language::CSharp => "cs",
+        Language::Vapi => "vapi",
+        Language::Napi => "c",
+        Language::Ctypes => "py",
+    }
+}
+
+/// Writes the bindings of each member of the workspace at `input` which has a
+/// `cbindgen.toml`, named after the crate, next to its `Cargo.toml` or in
+/// `--out-dir`. The members share the metadata of the workspace and the
+/// parse cache of its dependencies. Returns the exit code.
+fn generate_workspace<'a>(input: &Path, matches: &ArgMatches<'a>) -> i32 {
+    let workspace = match Cargo::load_workspace(
+        input,
+        matches.value_of("lockfile"),
+        true,
+        matches.is_present("clean"),
+        matches.value_of("metadata").map(Path::new),
+    ) {
+        Ok(workspace) => workspace,
+        Err(msg) => {
+            error!("{}", msg);
+            error!("Couldn't load the workspace {}.", input.display());
+            return 1;
+        }
+    };
+
+    let mut code = 0;
+    let mut found = false;
+    for member in workspace.workspace_members() {
+        let crate_dir = match workspace.find_crate_dir(&member) {
+            Some(crate_dir) => crate_dir,
+            None => continue,
+        };
+        let config_path = crate_dir.join("cbindgen.toml");
+        if !config_path.exists() {
+            continue;
+        }
+        found = true;
+
+        let mut config = match Config::from_file(&config_path) {
+            Ok(config) => config,
+            Err(msg) => {
+                error!("{}", msg);
+                code = 1;
+                continue;
+            }
+        };
+        apply_config_overrides(&mut config, matches);
+        let file = match matches.value_of("out-dir") {
+            Some(out_dir) => PathBuf::from(out_dir),
+            None => crate_dir,
+        }
+        .join(format!(
+            "{}.{}",
+            member.name,
+            header_extension(config.language)
+        ));
+
+        let bindings = match Builder::new()
+            .with_config(config)
+            .with_cargo(workspace.for_member(&member))
+            .generate()
+        {
+            Ok(bindings) => bindings,
+            Err(msg) => {
+                error!("{}", msg);
+                error!("Couldn't generate bindings for {}.", member.name);
+                code = 1;
+                continue;
+            }
+        };
+
+        if write_verified(&bindings, &file, matches) && code == 0 {
+            code = 2;
+        }
+    }
+
+    if !found {
+        warn!(
+            "No member of the workspace {} has a cbindgen.toml.",
+            input.display()
+        );
+    }
+    code
+}
+
+/// How often `--watch` checks whether the sources changed.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+fn modification_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|x| x.modified()).ok())
+        .collect()
+}
+
+/// The files `--watch` regenerates the bindings after, with the
+/// modification times they had when the bindings were last generated.
+struct Watched {
+    paths: Vec<PathBuf>,
+    times: Vec<Option<SystemTime>>,
+}
+
+impl Watched {
+    fn new(paths: Vec<PathBuf>) -> Watched {
+        let times = modification_times(&paths);
+        Watched { paths, times }
+    }
+
+    /// Whether one of the files was modified, created or removed since.
+    fn changed(&self) -> bool {
+        modification_times(&self.paths) != self.times
+    }
+}
+
+/// Adds the Rust source files in `dir` and its subdirectories to `out`.
+fn rust_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(..) => return,
+    };
+    for path in entries.filter_map(|x| x.ok()).map(|x| x.path()) {
+        if path.is_dir() {
+            rust_files(&path, out);
+        } else if path.extension().map_or(false, |x| x == "rs") {
+            out.push(path);
+        }
+    }
+}
+
+/// The Rust source files in the directory of the library of the crate at
+/// `input`, to watch until the bindings are generated once.
+fn lib_source_files(input: &Path) -> Vec<PathBuf> {
+    let mut source_files = vec![];
+    if input.is_dir() {
+        // The directory of the library target, if it's not in `src`.
+        let lib_dir = fs::read_to_string(input.join("Cargo.toml"))
+            .ok()
+            .and_then(|x| x.parse::<toml::Value>().ok())
+            .and_then(|x| {
+                let path = x.get("lib")?.get("path")?.as_str()?;
+                Some(input.join(path).parent()?.to_owned())
+            });
+        rust_files(
+            &lib_dir.unwrap_or_else(|| input.join("src")),
+            &mut source_files,
+        );
+    }
+    source_files
+}
+
+/// Writes the bindings to `file`, then again each time one of the parsed
+/// source files or the configuration changes. Errors are reported without
+/// stopping, and the sources of the last successful run stay watched. Until
+/// a run succeeds, the sources in the directory of the library of the crate
+/// are watched.
+fn watch<'a>(input: &Path, matches: &ArgMatches<'a>, file: &str) -> ! {
+    let mut config_files = vec![];
+    if let Some(config) = matches.value_of("config") {
+        config_files.push(PathBuf::from(config));
+    }
+    if input.is_dir() {
+        config_files.push(input.join("cbindgen.toml"));
+        config_files.push(input.join("Cargo.toml"));
+    } else {
+        config_files.push(input.to_owned());
+    }
+
+    let mut source_files = lib_source_files(input);
+    loop {
+        match load_builder(input, matches).and_then(Builder::generate) {
+            Ok(bindings) => {
+                if bindings.write_to_file(file) {
+                    info!("Wrote {}.", file);
+                }
+                source_files = bindings.source_files.clone();
+            }
+            Err(msg) => {
+                error!("{}", msg);
+                error!("Couldn't generate bindings for {}.", input.display());
+            }
+        }
+
+        let mut paths = config_files.clone();
+        paths.extend(source_files.iter().cloned());
+        let watched = Watched::new(paths);
+        while !watched.changed() {
+            thread::sleep(WATCH_INTERVAL);
+        }
+    }
 }
 
 fn main() {
@@ -137,7 +445,38 @@ fn main() {
         .arg(
             Arg::with_name("verify")
                 .long("verify")
-                .help("Generate bindings and compare it to the existing bindings file and error if they are different"),
+                .help("Generate bindings and compare it to the existing bindings file and error if they are different, printing a diff"),
+        )
+        .arg(
+            Arg::with_name("check-config")
+                .long("check-config")
+                .help(
+                    "Check that the entries of the config match the parsed items, and \
+                     report obsolete options, instead of generating bindings")
+                .conflicts_with_all(&["out", "verify", "workspace", "watch", "targets", "emit-ir"]),
+        )
+        .arg(
+            Arg::with_name("workspace")
+                .long("workspace")
+                .help(
+                    "Write the bindings of each member of the workspace which has a \
+                     cbindgen.toml next to its Cargo.toml, named after the crate")
+                .conflicts_with_all(&["out", "crate", "config", "depfile", "emit-ir"]),
+        )
+        .arg(
+            Arg::with_name("out-dir")
+                .long("out-dir")
+                .value_name("PATH")
+                .help("The directory to write the bindings of the workspace members to")
+                .requires("workspace")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("watch")
+                .long("watch")
+                .help("Write the bindings again whenever the parsed sources or the config change")
+                .requires("out")
+                .conflicts_with_all(&["verify", "targets", "emit-ir"]),
         )
         .arg(
             Arg::with_name("config")
@@ -152,13 +491,23 @@ fn main() {
                 .long("lang")
                 .value_name("LANGUAGE")
                 .help("Specify the language to output bindings in")
-                .possible_values(&["c++", "C++", "c", "C", "cython", "Cython"]),
+                .possible_values(&["c++", "C++", "c", "C", "cython", "Cython", "gir", "GIR", "csharp", "CSharp", "C#", "vala", "Vala", "vapi", "napi", "N-API", "ctypes", "python", "Python"]),
         )
         .arg(
             Arg::with_name("cpp-compat")
                 .long("cpp-compat")
                 .help("Whether to add C++ compatibility to generated C bindings")
         )
+        .arg(
+            Arg::with_name("strict")
+                .long("strict")
+                .help("Fail when the written items use types which couldn't be resolved, or when exported items couldn't be loaded, like `parse.unresolved = \"error\"`")
+        )
+        .arg(
+            Arg::with_name("wasm")
+                .long("wasm")
+                .help("Whether to write the bindings of the crate compiled to WebAssembly")
+        )
         .arg(
             Arg::with_name("style")
                 .short("s")
@@ -181,6 +530,30 @@ fn main() {
                     Affects performance, but might be required in certain build processes.")
                 .required(false)
         )
+        .arg(
+            Arg::with_name("cache")
+                .long("cache")
+                .help(
+                    "Keep what was loaded from the source files in the target \
+                    directory, to only parse the ones that changed next time.")
+                .required(false)
+        )
+        .arg(
+            Arg::with_name("no-cache")
+                .long("no-cache")
+                .conflicts_with("cache")
+                .help(
+                    "Parse all the source files, even if `parse.cache` is set.")
+                .required(false)
+        )
+        .arg(
+            Arg::with_name("jobs")
+                .short("j")
+                .long("jobs")
+                .value_name("N")
+                .help("The number of threads loading the crates and their source files")
+                .required(false)
+        )
         .arg(
             Arg::with_name("INPUT")
                 .help(
@@ -208,6 +581,51 @@ fn main() {
                 .help("The file to output the bindings to")
                 .required(false),
         )
+        .arg(
+            Arg::with_name("depfile")
+                .long("depfile")
+                .value_name("PATH")
+                .help("Write a Makefile-style dependency file listing the parsed Rust sources")
+                .requires("out")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("symbols-out")
+                .long("symbols-out")
+                .value_name("PATH")
+                .help(
+                    "Also write the exported symbols as a linker version script, \
+                     or as a module-definition file if PATH ends with .def",
+                )
+                .conflicts_with("targets")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("targets")
+                .long("targets")
+                .value_name("TARGETS")
+                .help(
+                    "Write the bindings of each of these comma-separated targets \
+                     next to the output file, suffixed with the target")
+                .use_delimiter(true)
+                .requires("out")
+                .conflicts_with("emit-ir")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("merge-targets")
+                .long("merge-targets")
+                .help("Also write a header at the output path including the one of the target it's compiled for")
+                .requires("targets")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("emit-ir")
+                .long("emit-ir")
+                .value_name("PATH")
+                .help("Also write the items of the bindings as JSON, for other tools to use")
+                .required(false),
+        )
         .arg(
             Arg::with_name("lockfile")
                 .long("lockfile")
@@ -244,6 +662,18 @@ fn main() {
                 )
                 .possible_values(&["Debug", "debug", "Release", "release"]),
         )
+        .arg(
+            Arg::with_name("message-format")
+                .long("message-format")
+                .value_name("FORMAT")
+                .help(
+                    "How to report warnings and errors: `json` writes each of them as a JSON \
+                     object on its own line of stderr, with the file and line of the item \
+                     which caused it when there is one",
+                )
+                .possible_values(&["human", "json"])
+                .default_value("human"),
+        )
         .arg(
             Arg::with_name("quiet")
                 .short("q")
@@ -253,15 +683,19 @@ fn main() {
         )
         .get_matches();
 
-    if !matches.is_present("out") && matches.is_present("verify") {
-        error!(
-            "Cannot verify bindings against `stdout`, please specify a file to compare against."
-        );
-        std::process::exit(2);
-    }
-
     // Initialize logging
-    if matches.is_present("quiet") {
+    if matches.value_of("message-format") == Some("json") {
+        let level = if matches.is_present("quiet") {
+            log::LevelFilter::Error
+        } else {
+            match matches.occurrences_of("v") {
+                0 => log::LevelFilter::Warn,
+                1 => log::LevelFilter::Info,
+                _ => log::LevelFilter::Trace,
+            }
+        };
+        logging::JsonLogger::init(level).unwrap();
+    } else if matches.is_present("quiet") {
         logging::ErrorLogger::init().unwrap();
     } else {
         match matches.occurrences_of("v") {
@@ -277,7 +711,99 @@ fn main() {
         None => env::current_dir().unwrap(),
     };
 
-    let bindings = match load_bindings(&input, &matches) {
+    if matches.is_present("workspace") {
+        std::process::exit(generate_workspace(&input, &matches));
+    }
+
+    if matches.is_present("watch") {
+        watch(&input, &matches, matches.value_of("out").unwrap());
+    }
+
+    if matches.is_present("check-config") {
+        if let Some(Err(msg)) = matches.value_of("config").map(Config::from_file) {
+            error!("{}", msg);
+            std::process::exit(1);
+        }
+    }
+
+    let (config, lib) = match load_config(&input, &matches) {
+        Ok(loaded) => loaded,
+        Err(msg) => {
+            error!("{}", msg);
+            error!("Couldn't generate bindings for {}.", input.display());
+            std::process::exit(1);
+        }
+    };
+
+    if !matches.is_present("out") && config.bindings.is_empty() && matches.is_present("verify") {
+        error!(
+            "Cannot verify bindings against `stdout`, please specify a file to compare against."
+        );
+        std::process::exit(2);
+    }
+
+    // The bindings of the `[[bindings]]` entries, if any, are written besides
+    // the ones at `--output`.
+    let entries = if config.bindings.is_empty() {
+        None
+    } else {
+        Some((config.clone(), lib.clone()))
+    };
+    let builder = builder_for(&input, config, lib);
+
+    if matches.is_present("check-config") {
+        match builder.validate_config() {
+            Ok(ref issues) if issues.is_empty() => return,
+            Ok(issues) => {
+                for issue in issues {
+                    error!("{}", issue);
+                }
+                std::process::exit(2);
+            }
+            Err(msg) => {
+                error!("{}", msg);
+                error!("Couldn't check the config for {}.", input.display());
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some((config, lib)) = entries {
+        let code = generate_bindings_entries(&input, &config, lib.as_ref(), &matches);
+        if code != 0 || !matches.is_present("out") {
+            std::process::exit(code);
+        }
+    }
+
+    if let Some(targets) = matches.values_of("targets") {
+        let targets: Vec<_> = targets.collect();
+        let bindings = match builder.generate_for_targets(&targets) {
+            Ok(bindings) => bindings,
+            Err(msg) => {
+                error!("{}", msg);
+                error!("Couldn't generate bindings for {}.", input.display());
+                std::process::exit(1);
+            }
+        };
+
+        let file = matches.value_of("out").unwrap();
+        let changed = bindings.write_to_files(file, matches.is_present("merge-targets"));
+
+        if let Some(depfile) = matches.value_of("depfile") {
+            // The targets share their sources.
+            if let Some((_, bindings)) = bindings.iter().next() {
+                bindings.write_depfile(file, depfile);
+            }
+        }
+
+        if matches.is_present("verify") && changed {
+            error!("Bindings changed: {}", file);
+            std::process::exit(2);
+        }
+        return;
+    }
+
+    let bindings = match builder.generate() {
         Ok(bindings) => bindings,
         Err(msg) => {
             error!("{}", msg);
@@ -286,11 +812,32 @@ fn main() {
         }
     };
 
+    if let Some(ir_file) = matches.value_of("emit-ir") {
+        bindings.write_ir_to_file(ir_file);
+    }
+
+    if let Some(symbols_file) = matches.value_of("symbols-out") {
+        if bindings.write_symbols_to_file(symbols_file) && matches.is_present("verify") {
+            error!("Symbols changed: {}", symbols_file);
+            std::process::exit(2);
+        }
+    }
+
     // Write the bindings file
     match matches.value_of("out") {
         Some(file) => {
+            if matches.is_present("verify") && !matches.is_present("quiet") {
+                if let Some(diff) = bindings.diff_against_file(file) {
+                    eprint!("{}", diff);
+                }
+            }
+
             let changed = bindings.write_to_file(file);
 
+            if let Some(depfile) = matches.value_of("depfile") {
+                bindings.write_depfile(file, depfile);
+            }
+
             if matches.is_present("verify") && changed {
                 error!("Bindings changed: {}", file);
                 std::process::exit(2);
@@ -301,3 +848,80 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `contents` to `path`, making sure that its modification time
+    /// changes even on file systems with a coarse resolution.
+    fn touch(path: &Path, contents: &str) {
+        let before = modification_times(&[path.to_owned()]);
+        loop {
+            fs::write(path, contents).unwrap();
+            if modification_times(&[path.to_owned()]) != before {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn watched_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = dir.path().join("lib.rs");
+        let module = dir.path().join("module.rs");
+        touch(&lib, "mod module;");
+
+        // The missing files are watched too.
+        let watched = Watched::new(vec![lib.clone(), module.clone()]);
+        assert!(!watched.changed());
+
+        touch(&module, "pub struct Foo;");
+        assert!(watched.changed());
+
+        let watched = Watched::new(vec![lib.clone(), module.clone()]);
+        assert!(!watched.changed());
+        touch(&lib, "mod module;\nmod other;");
+        assert!(watched.changed());
+
+        let watched = Watched::new(vec![lib, module.clone()]);
+        fs::remove_file(&module).unwrap();
+        assert!(watched.changed());
+    }
+
+    #[test]
+    fn lib_source_files_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        touch(&dir.path().join("Cargo.toml"), "[package]\nname = \"foo\"");
+        touch(&dir.path().join("src/lib.rs"), "mod nested;");
+        touch(&dir.path().join("src/nested/mod.rs"), "");
+        touch(&dir.path().join("src/notes.txt"), "");
+
+        let mut files = lib_source_files(dir.path());
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("src/lib.rs"),
+                dir.path().join("src/nested/mod.rs"),
+            ]
+        );
+
+        // A library elsewhere is watched instead of src.
+        fs::create_dir_all(dir.path().join("rust")).unwrap();
+        touch(&dir.path().join("rust/ffi.rs"), "");
+        touch(
+            &dir.path().join("Cargo.toml"),
+            "[package]\nname = \"foo\"\n\n[lib]\npath = \"rust/ffi.rs\"",
+        );
+        assert_eq!(
+            lib_source_files(dir.path()),
+            vec![dir.path().join("rust/ffi.rs")]
+        );
+
+        // A single source file has no library directory.
+        assert!(lib_source_files(&dir.path().join("src/lib.rs")).is_empty());
+    }
+}
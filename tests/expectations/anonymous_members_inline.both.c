@@ -0,0 +1,17 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Event {
+  uint32_t kind;
+  union {
+    uint32_t key;
+    struct {
+      int32_t x;
+      int32_t y;
+    };
+  };
+} Event;
+
+void handle(const struct Event *e);
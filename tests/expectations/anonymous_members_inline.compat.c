@@ -0,0 +1,25 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  uint32_t kind;
+  union {
+    uint32_t key;
+    struct {
+      int32_t x;
+      int32_t y;
+    };
+  };
+} Event;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void handle(const Event *e);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,22 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Event {
+  uint32_t kind;
+  union {
+    uint32_t key;
+    struct {
+      int32_t x;
+      int32_t y;
+    };
+  };
+};
+
+extern "C" {
+
+void handle(const Event *e);
+
+} // extern "C"
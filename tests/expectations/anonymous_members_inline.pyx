@@ -0,0 +1,21 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Event__bindgen_ty_1__bindgen_ty_1:
+    int32_t x;
+    int32_t y;
+
+  ctypedef union Event__bindgen_ty_1:
+    uint32_t key;
+    Event__bindgen_ty_1__bindgen_ty_1 __bindgen_anon_1;
+
+  ctypedef struct Event:
+    uint32_t kind;
+    Event__bindgen_ty_1 __bindgen_anon_1;
+
+  void handle(const Event *e);
@@ -0,0 +1,29 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Event_Anon1_Anon1 {
+  int32_t x;
+  int32_t y;
+} Event_Anon1_Anon1;
+
+typedef union Event_Anon1 {
+  uint32_t key;
+  struct Event_Anon1_Anon1 anon1;
+} Event_Anon1;
+
+typedef struct Event {
+  uint32_t kind;
+  union Event_Anon1 anon1;
+} Event;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void handle(const struct Event *e);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
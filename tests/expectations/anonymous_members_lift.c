@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+} Event_Anon1_Anon1;
+
+typedef union {
+  uint32_t key;
+  Event_Anon1_Anon1 anon1;
+} Event_Anon1;
+
+typedef struct {
+  uint32_t kind;
+  Event_Anon1 anon1;
+} Event;
+
+void handle(const Event *e);
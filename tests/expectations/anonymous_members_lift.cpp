@@ -0,0 +1,26 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Event_Anon1_Anon1 {
+  int32_t x;
+  int32_t y;
+};
+
+union Event_Anon1 {
+  uint32_t key;
+  Event_Anon1_Anon1 anon1;
+};
+
+struct Event {
+  uint32_t kind;
+  Event_Anon1 anon1;
+};
+
+extern "C" {
+
+void handle(const Event *e);
+
+} // extern "C"
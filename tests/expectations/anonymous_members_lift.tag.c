@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Event_Anon1_Anon1 {
+  int32_t x;
+  int32_t y;
+};
+
+union Event_Anon1 {
+  uint32_t key;
+  struct Event_Anon1_Anon1 anon1;
+};
+
+struct Event {
+  uint32_t kind;
+  union Event_Anon1 anon1;
+};
+
+void handle(const struct Event *e);
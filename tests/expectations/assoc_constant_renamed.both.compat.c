@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct MyFoo {
+
+} MyFoo;
+#define MY_FOO_GA 10
+#define MY_FOO_MAX_LEN 7
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct MyFoo x);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
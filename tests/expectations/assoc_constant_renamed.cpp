@@ -0,0 +1,17 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct MyFoo {
+
+};
+static const int32_t MY_FOO_GA = 10;
+static const uint32_t MY_FOO_MAX_LEN = 7;
+
+extern "C" {
+
+void root(MyFoo x);
+
+} // extern "C"
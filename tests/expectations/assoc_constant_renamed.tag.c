@@ -0,0 +1,12 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct MyFoo {
+
+};
+#define MY_FOO_GA 10
+#define MY_FOO_MAX_LEN 7
+
+void root(struct MyFoo x);
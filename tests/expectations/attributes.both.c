@@ -0,0 +1,41 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable: 4996)
+#endif
+
+typedef enum Kind {
+  A,
+  B,
+} Kind __attribute__((unused));
+
+typedef struct CacheLine {
+  uint8_t data[64];
+} CacheLine __attribute__((aligned(64)));
+
+typedef union Value {
+  int64_t integer;
+  double real;
+} Value __attribute__((aligned(8)));
+
+typedef uint64_t Counter __attribute__((aligned(16)));
+
+__attribute__((visibility("hidden"))) extern const Counter COUNTER;
+
+__attribute__((deprecated)) __attribute__((hot))
+void process(struct CacheLine *line,
+             union Value value,
+             enum Kind kind);
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
@@ -0,0 +1,43 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable: 4996)
+#endif
+
+enum class __attribute__((unused)) Kind {
+  A,
+  B,
+};
+
+struct __attribute__((aligned(64))) CacheLine {
+  uint8_t data[64];
+};
+
+union __attribute__((aligned(8))) Value {
+  int64_t integer;
+  double real;
+};
+
+using Counter __attribute__((aligned(16))) = uint64_t;
+
+extern "C" {
+
+__attribute__((visibility("hidden"))) extern const Counter COUNTER;
+
+[[deprecated]] __attribute__((hot)) void process(CacheLine *line, Value value, Kind kind);
+
+} // extern "C"
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
@@ -0,0 +1,24 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum Kind:
+    A,
+    B,
+
+  cdef struct CacheLine:
+    uint8_t data[64];
+
+  cdef union Value:
+    int64_t integer;
+    double real;
+
+  ctypedef uint64_t Counter;
+
+  extern const Counter COUNTER;
+
+  void process(CacheLine *line, Value value, Kind kind);
@@ -0,0 +1,35 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A read-only mirror of a status register.
+ */
+typedef struct Status {
+  uint32_t ready: 1;
+  uint32_t mode: 3;
+  uint32_t count: 28;
+  bool enabled: 1;
+  uint8_t level: 4;
+  uint8_t channel: 4;
+} Status;
+
+typedef struct Invalid {
+  float ratio;
+  uint8_t wide;
+  uint16_t empty;
+  uint8_t low: 4;
+  uint8_t straddling: 6;
+  uint16_t mixed: 2;
+} Invalid;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Status status, struct Invalid invalid);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,26 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # A read-only mirror of a status register.
+  ctypedef struct Status:
+    uint32_t ready;
+    uint32_t mode;
+    uint32_t count;
+    bool enabled;
+    uint8_t level;
+    uint8_t channel;
+
+  ctypedef struct Invalid:
+    float ratio;
+    uint8_t wide;
+    uint16_t empty;
+    uint8_t low;
+    uint8_t straddling;
+    uint16_t mixed;
+
+  void root(Status status, Invalid invalid);
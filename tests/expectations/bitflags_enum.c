@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Constants shared by multiple CSS Box Alignment properties
+ */
+enum AlignFlags {
+  ALIGN_FLAGS_NONE = 0,
+  /**
+   * 'normal'
+   */
+  ALIGN_FLAGS_NORMAL = 1,
+  /**
+   * 'start'
+   */
+  ALIGN_FLAGS_START = (1 << 1),
+  /**
+   * 'end'
+   */
+  ALIGN_FLAGS_END = (1 << 2),
+};
+typedef uint8_t AlignFlags;
+
+enum DebugFlags {
+  DEBUG_FLAGS_NONE = 0,
+  DEBUG_FLAGS_VERBOSE = 1,
+  DEBUG_FLAGS_TRACE = (1 << 1),
+};
+typedef uint32_t DebugFlags;
+
+void root(AlignFlags flags, DebugFlags debug);
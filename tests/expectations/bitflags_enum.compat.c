@@ -0,0 +1,53 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Constants shared by multiple CSS Box Alignment properties
+ */
+enum AlignFlags
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  ALIGN_FLAGS_NONE = 0,
+  /**
+   * 'normal'
+   */
+  ALIGN_FLAGS_NORMAL = 1,
+  /**
+   * 'start'
+   */
+  ALIGN_FLAGS_START = (1 << 1),
+  /**
+   * 'end'
+   */
+  ALIGN_FLAGS_END = (1 << 2),
+};
+#ifndef __cplusplus
+typedef uint8_t AlignFlags;
+#endif // __cplusplus
+
+enum DebugFlags
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  DEBUG_FLAGS_NONE = 0,
+  DEBUG_FLAGS_VERBOSE = 1,
+  DEBUG_FLAGS_TRACE = (1 << 1),
+};
+#ifndef __cplusplus
+typedef uint32_t DebugFlags;
+#endif // __cplusplus
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(AlignFlags flags, DebugFlags debug);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
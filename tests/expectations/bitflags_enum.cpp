@@ -0,0 +1,28 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+/// Constants shared by multiple CSS Box Alignment properties
+enum AlignFlags : uint8_t {
+  ALIGN_FLAGS_NONE = 0,
+  /// 'normal'
+  ALIGN_FLAGS_NORMAL = 1,
+  /// 'start'
+  ALIGN_FLAGS_START = (1 << 1),
+  /// 'end'
+  ALIGN_FLAGS_END = (1 << 2),
+};
+
+enum DebugFlags : uint32_t {
+  DEBUG_FLAGS_NONE = 0,
+  DEBUG_FLAGS_VERBOSE = 1,
+  DEBUG_FLAGS_TRACE = (1 << 1),
+};
+
+extern "C" {
+
+void root(AlignFlags flags, DebugFlags debug);
+
+} // extern "C"
@@ -0,0 +1,26 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # Constants shared by multiple CSS Box Alignment properties
+  cdef enum:
+    ALIGN_FLAGS_NONE # = 0,
+    # 'normal'
+    ALIGN_FLAGS_NORMAL # = 1,
+    # 'start'
+    ALIGN_FLAGS_START # = (1 << 1),
+    # 'end'
+    ALIGN_FLAGS_END # = (1 << 2),
+  ctypedef uint8_t AlignFlags;
+
+  cdef enum:
+    DEBUG_FLAGS_NONE # = 0,
+    DEBUG_FLAGS_VERBOSE # = 1,
+    DEBUG_FLAGS_TRACE # = (1 << 1),
+  ctypedef uint32_t DebugFlags;
+
+  void root(AlignFlags flags, DebugFlags debug);
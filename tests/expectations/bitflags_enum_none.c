@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum OpenFlags {
+  OpenFlags_NONE = 0,
+  READ = 1,
+  WRITE = (1 << 1),
+};
+typedef uint32_t OpenFlags;
+
+enum WatchFlags {
+  WatchFlags_NONE = 0,
+  CREATE = 1,
+  DELETE = (1 << 1),
+};
+typedef uint8_t WatchFlags;
+
+void watch(OpenFlags open, WatchFlags watch);
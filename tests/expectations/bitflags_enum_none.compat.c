@@ -0,0 +1,40 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum OpenFlags
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  OpenFlags_NONE = 0,
+  READ = 1,
+  WRITE = (1 << 1),
+};
+#ifndef __cplusplus
+typedef uint32_t OpenFlags;
+#endif // __cplusplus
+
+enum WatchFlags
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  WatchFlags_NONE = 0,
+  CREATE = 1,
+  DELETE = (1 << 1),
+};
+#ifndef __cplusplus
+typedef uint8_t WatchFlags;
+#endif // __cplusplus
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void watch(OpenFlags open, WatchFlags watch);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
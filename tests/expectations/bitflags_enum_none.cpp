@@ -0,0 +1,23 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+enum OpenFlags : uint32_t {
+  OpenFlags_NONE = 0,
+  READ = 1,
+  WRITE = (1 << 1),
+};
+
+enum WatchFlags : uint8_t {
+  WatchFlags_NONE = 0,
+  CREATE = 1,
+  DELETE = (1 << 1),
+};
+
+extern "C" {
+
+void watch(OpenFlags open, WatchFlags watch);
+
+} // extern "C"
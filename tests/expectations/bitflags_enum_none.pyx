@@ -0,0 +1,21 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum:
+    OpenFlags_NONE # = 0,
+    READ # = 1,
+    WRITE # = (1 << 1),
+  ctypedef uint32_t OpenFlags;
+
+  cdef enum:
+    WatchFlags_NONE # = 0,
+    CREATE # = 1,
+    DELETE # = (1 << 1),
+  ctypedef uint8_t WatchFlags;
+
+  void watch(OpenFlags open, WatchFlags watch);
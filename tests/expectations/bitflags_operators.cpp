@@ -0,0 +1,88 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+/// Constants shared by multiple CSS Box Alignment properties
+enum class AlignFlags : uint8_t {
+  ALIGN_FLAGS_NONE = 0,
+  /// 'normal'
+  ALIGN_FLAGS_NORMAL = 1,
+  /// 'start'
+  ALIGN_FLAGS_START = (1 << 1),
+};
+
+inline constexpr AlignFlags operator|(AlignFlags a, AlignFlags b) {
+  return static_cast<AlignFlags>(static_cast<uint8_t>(a) | static_cast<uint8_t>(b));
+}
+
+inline AlignFlags& operator|=(AlignFlags& a, AlignFlags b) {
+  return a = a | b;
+}
+
+inline constexpr AlignFlags operator&(AlignFlags a, AlignFlags b) {
+  return static_cast<AlignFlags>(static_cast<uint8_t>(a) & static_cast<uint8_t>(b));
+}
+
+inline AlignFlags& operator&=(AlignFlags& a, AlignFlags b) {
+  return a = a & b;
+}
+
+inline constexpr AlignFlags operator^(AlignFlags a, AlignFlags b) {
+  return static_cast<AlignFlags>(static_cast<uint8_t>(a) ^ static_cast<uint8_t>(b));
+}
+
+inline AlignFlags& operator^=(AlignFlags& a, AlignFlags b) {
+  return a = a ^ b;
+}
+
+inline constexpr AlignFlags operator~(AlignFlags a) {
+  return static_cast<AlignFlags>(~static_cast<uint8_t>(a));
+}
+
+enum class DebugFlags : uint32_t {
+  DEBUG_FLAGS_NONE = 0,
+  DEBUG_FLAGS_VERBOSE = 1,
+  DEBUG_FLAGS_TRACE = (1 << 1),
+};
+
+inline constexpr DebugFlags operator|(DebugFlags a, DebugFlags b) {
+  return static_cast<DebugFlags>(static_cast<uint32_t>(a) | static_cast<uint32_t>(b));
+}
+
+inline DebugFlags& operator|=(DebugFlags& a, DebugFlags b) {
+  return a = a | b;
+}
+
+inline constexpr DebugFlags operator&(DebugFlags a, DebugFlags b) {
+  return static_cast<DebugFlags>(static_cast<uint32_t>(a) & static_cast<uint32_t>(b));
+}
+
+inline DebugFlags& operator&=(DebugFlags& a, DebugFlags b) {
+  return a = a & b;
+}
+
+inline constexpr DebugFlags operator^(DebugFlags a, DebugFlags b) {
+  return static_cast<DebugFlags>(static_cast<uint32_t>(a) ^ static_cast<uint32_t>(b));
+}
+
+inline DebugFlags& operator^=(DebugFlags& a, DebugFlags b) {
+  return a = a ^ b;
+}
+
+inline constexpr DebugFlags operator~(DebugFlags a) {
+  return static_cast<DebugFlags>(~static_cast<uint32_t>(a));
+}
+
+enum LogFlags : uint16_t {
+  LOG_FLAGS_NONE = 0,
+  LOG_FLAGS_STDOUT = 1,
+  LOG_FLAGS_STDERR = (1 << 1),
+};
+
+extern "C" {
+
+void root(AlignFlags flags, DebugFlags debug, LogFlags log);
+
+} // extern "C"
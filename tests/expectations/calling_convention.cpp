@@ -0,0 +1,35 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#ifndef _WIN32
+#define __cdecl
+#define __stdcall
+#define __fastcall
+#endif
+
+using Callback = int32_t(__stdcall *)(int32_t);
+
+struct Handlers {
+  void (__fastcall *on_event)(uint32_t);
+  void (*on_error)(int32_t);
+};
+
+extern "C" {
+
+void c_function(Callback callback);
+
+void __cdecl cdecl_function(const Handlers *handlers);
+
+int32_t *__stdcall stdcall_function(int32_t value);
+
+uint32_t __fastcall fastcall_function(uint32_t first, uint32_t second);
+
+void __stdcall system_function();
+
+void __cdecl annotated_function();
+
+extern void __stdcall imported_function(int32_t value);
+
+} // extern "C"
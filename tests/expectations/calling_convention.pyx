@@ -0,0 +1,32 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+#ifndef _WIN32
+#define __cdecl
+#define __stdcall
+#define __fastcall
+#endif
+
+cdef extern from *:
+
+  ctypedef int32_t (*Callback)(int32_t);
+
+  ctypedef struct Handlers:
+    void (*on_event)(uint32_t);
+    void (*on_error)(int32_t);
+
+  void c_function(Callback callback);
+
+  void cdecl_function(const Handlers *handlers);
+
+  int32_t *stdcall_function(int32_t value);
+
+  uint32_t fastcall_function(uint32_t first, uint32_t second);
+
+  void system_function();
+
+  void annotated_function();
+
+  extern void imported_function(int32_t value);
@@ -0,0 +1,30 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#ifndef _WIN32
+#define __cdecl
+#define __stdcall
+#define __fastcall
+#endif
+
+typedef int32_t (__stdcall *Callback)(int32_t);
+
+struct Handlers {
+  void (__fastcall *on_event)(uint32_t);
+  void (*on_error)(int32_t);
+};
+
+void c_function(Callback callback);
+
+void __cdecl cdecl_function(const struct Handlers *handlers);
+
+int32_t *__stdcall stdcall_function(int32_t value);
+
+uint32_t __fastcall fastcall_function(uint32_t first, uint32_t second);
+
+void __stdcall system_function(void);
+
+void __cdecl annotated_function(void);
+
+extern void __stdcall imported_function(int32_t value);
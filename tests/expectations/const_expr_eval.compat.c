@@ -0,0 +1,38 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define LEN 4
+
+#define MASK ~LEN
+
+#define ENABLED true
+
+#define DISABLED !ENABLED
+
+#define MAX_LEN 4294967295
+
+#define MIN_OFFSET -32767
+
+#define SIZE 33
+
+#define WRAPPED 10
+
+typedef struct {
+  uint32_t len;
+} Buffer;
+#define Buffer_CAPACITY (LEN * 4)
+#define Buffer_DOUBLE_CAPACITY 32
+#define Buffer_HALF_MAX 9223372036854775807
+#define Buffer_FULL 18446744073709551615ULL
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Buffer buffer);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,35 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+static const uint32_t LEN = 4;
+
+static const uint32_t MASK = ~LEN;
+
+static const bool ENABLED = true;
+
+static const bool DISABLED = !ENABLED;
+
+static const uint32_t MAX_LEN = 4294967295;
+
+static const int16_t MIN_OFFSET = -32767;
+
+static const uint32_t SIZE = 33;
+
+static const uint8_t WRAPPED = 10;
+
+struct Buffer {
+  uint32_t len;
+};
+static const uint32_t Buffer_CAPACITY = (LEN * 4);
+static const uint32_t Buffer_DOUBLE_CAPACITY = 32;
+static const uint64_t Buffer_HALF_MAX = 9223372036854775807;
+static const uint64_t Buffer_FULL = 18446744073709551615ULL;
+
+extern "C" {
+
+void root(Buffer buffer);
+
+} // extern "C"
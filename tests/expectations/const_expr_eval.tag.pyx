@@ -0,0 +1,32 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const uint32_t LEN # = 4
+
+  const uint32_t MASK # = ~LEN
+
+  const bool ENABLED # = True
+
+  const bool DISABLED # = !ENABLED
+
+  const uint32_t MAX_LEN # = 4294967295
+
+  const int16_t MIN_OFFSET # = -32767
+
+  const uint32_t SIZE # = 33
+
+  const uint8_t WRAPPED # = 10
+
+  cdef struct Buffer:
+    uint32_t len;
+  const uint32_t Buffer_CAPACITY # = (LEN * 4)
+  const uint32_t Buffer_DOUBLE_CAPACITY # = 32
+  const uint64_t Buffer_HALF_MAX # = 9223372036854775807
+  const uint64_t Buffer_FULL # = 18446744073709551615ULL
+
+  void root(Buffer buffer);
@@ -0,0 +1,26 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buf_4 {
+  uint8_t data[4];
+  uintptr_t len;
+} Buf_4;
+
+typedef struct Buf_64 {
+  uint8_t data[64];
+  uintptr_t len;
+} Buf_64;
+
+typedef struct Buf_2 {
+  uint8_t data[2];
+  uintptr_t len;
+} Buf_2;
+
+typedef struct Pair_u32__2 {
+  uint32_t items[2];
+  struct Buf_2 buf;
+} Pair_u32__2;
+
+void root(struct Buf_4 small, struct Buf_64 big, struct Pair_u32__2 pair);
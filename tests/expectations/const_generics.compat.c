@@ -0,0 +1,34 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  uint8_t data[4];
+  uintptr_t len;
+} Buf_4;
+
+typedef struct {
+  uint8_t data[64];
+  uintptr_t len;
+} Buf_64;
+
+typedef struct {
+  uint8_t data[2];
+  uintptr_t len;
+} Buf_2;
+
+typedef struct {
+  uint32_t items[2];
+  Buf_2 buf;
+} Pair_u32__2;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Buf_4 small, Buf_64 big, Pair_u32__2 pair);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
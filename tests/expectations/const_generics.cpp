@@ -0,0 +1,23 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+template<uintptr_t N>
+struct Buf {
+  uint8_t data[N];
+  uintptr_t len;
+};
+
+template<typename T, uintptr_t N>
+struct Pair {
+  T items[N];
+  Buf<N> buf;
+};
+
+extern "C" {
+
+void root(Buf<4> small, Buf<64> big, Pair<uint32_t, 2> pair);
+
+} // extern "C"
@@ -0,0 +1,25 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Buf_4:
+    uint8_t data[4];
+    uintptr_t len;
+
+  ctypedef struct Buf_64:
+    uint8_t data[64];
+    uintptr_t len;
+
+  ctypedef struct Buf_2:
+    uint8_t data[2];
+    uintptr_t len;
+
+  ctypedef struct Pair_u32__2:
+    uint32_t items[2];
+    Buf_2 buf;
+
+  void root(Buf_4 small, Buf_64 big, Pair_u32__2 pair);
@@ -0,0 +1,34 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Buf_4 {
+  uint8_t data[4];
+  uintptr_t len;
+};
+
+struct Buf_64 {
+  uint8_t data[64];
+  uintptr_t len;
+};
+
+struct Buf_2 {
+  uint8_t data[2];
+  uintptr_t len;
+};
+
+struct Pair_u32__2 {
+  uint32_t items[2];
+  struct Buf_2 buf;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Buf_4 small, struct Buf_64 big, struct Pair_u32__2 pair);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,27 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define GREETING "hello \"world\"\n\t\\ \303\251?\?="
+
+#define EMPTY ""
+
+#define MAGIC (const uint8_t*)"\177ELF\000\377"
+
+#define TAG (const uint8_t*)"RIFF"
+
+typedef struct {
+  uint32_t version;
+} Format;
+#define Format_NAME "format"
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Format format);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,24 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+static const char* const GREETING = "hello \"world\"\n\t\\ \303\251?\?=";
+
+static const char* const EMPTY = "";
+
+static const uint8_t* const MAGIC = (const uint8_t*)"\177ELF\000\377";
+
+static const uint8_t* const TAG = (const uint8_t*)"RIFF";
+
+struct Format {
+  uint32_t version;
+};
+static const char* const Format_NAME = "format";
+
+extern "C" {
+
+void root(Format format);
+
+} // extern "C"
@@ -0,0 +1,21 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const char* GREETING # = "hello \"world\"\n\t\\ \303\251?\?="
+
+  const char* EMPTY # = ""
+
+  const uint8_t* MAGIC # = <const uint8_t*>"\177ELF\000\377"
+
+  const uint8_t* TAG # = <const uint8_t*>"RIFF"
+
+  cdef struct Format:
+    uint32_t version;
+  const char* Format_NAME # = "format"
+
+  void root(Format format);
@@ -0,0 +1,47 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define MAX_NAME_LEN 16
+
+/**
+ * The mode of a buffer.
+ */
+enum Mode {
+  Read,
+  Write = 4,
+  Append,
+};
+typedef uint8_t Mode;
+
+typedef struct Buffer Buffer;
+
+typedef uint64_t Offset;
+
+/**
+ * A point on the screen.
+ */
+typedef struct {
+  int32_t x;
+  int32_t y;
+  bool visible;
+} Point;
+
+typedef union {
+  int64_t integer;
+  double real;
+} Value;
+
+typedef void (*Callback)(Buffer *buffer, void *data);
+
+/**
+ * Creates a buffer named `name`.
+ */
+Buffer *buffer_new(const char *name, Mode mode);
+
+bool buffer_seek(Buffer *buffer, Offset offset, Point origin);
+
+double buffer_get(const Buffer *buffer, uint32_t event, Value *value);
+
+void buffer_watch(const Buffer *buffer, Callback callback);
@@ -0,0 +1,61 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define MAX_NAME_LEN 16
+
+/**
+ * The mode of a buffer.
+ */
+enum Mode
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Read,
+  Write = 4,
+  Append,
+};
+#ifndef __cplusplus
+typedef uint8_t Mode;
+#endif // __cplusplus
+
+typedef struct Buffer Buffer;
+
+typedef uint64_t Offset;
+
+/**
+ * A point on the screen.
+ */
+typedef struct {
+  int32_t x;
+  int32_t y;
+  bool visible;
+} Point;
+
+typedef union {
+  int64_t integer;
+  double real;
+} Value;
+
+typedef void (*Callback)(Buffer *buffer, void *data);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a buffer named `name`.
+ */
+Buffer *buffer_new(const char *name, Mode mode);
+
+bool buffer_seek(Buffer *buffer, Offset offset, Point origin);
+
+double buffer_get(const Buffer *buffer, uint32_t event, Value *value);
+
+void buffer_watch(const Buffer *buffer, Callback callback);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
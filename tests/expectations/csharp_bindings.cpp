@@ -0,0 +1,45 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+static const uint32_t MAX_NAME_LEN = 16;
+
+/// The mode of a buffer.
+enum class Mode : uint8_t {
+  Read,
+  Write = 4,
+  Append,
+};
+
+struct Buffer;
+
+using Offset = uint64_t;
+
+/// A point on the screen.
+struct Point {
+  int32_t x;
+  int32_t y;
+  bool visible;
+};
+
+union Value {
+  int64_t integer;
+  double real;
+};
+
+using Callback = void(*)(Buffer *buffer, void *data);
+
+extern "C" {
+
+/// Creates a buffer named `name`.
+Buffer *buffer_new(const char *name, Mode mode);
+
+bool buffer_seek(Buffer *buffer, Offset offset, Point origin);
+
+double buffer_get(const Buffer *buffer, uint32_t event, Value *value);
+
+void buffer_watch(const Buffer *buffer, Callback callback);
+
+} // extern "C"
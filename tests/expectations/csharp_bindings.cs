@@ -0,0 +1,59 @@
+using System;
+using System.Runtime.InteropServices;
+
+namespace Buffers
+{
+  /// <summary>
+  /// The mode of a buffer.
+  /// </summary>
+  public enum Mode : byte
+  {
+    Read,
+    Write = 4,
+    Append,
+  }
+
+  /// <summary>
+  /// A point on the screen.
+  /// </summary>
+  [StructLayout(LayoutKind.Sequential)]
+  public struct Point
+  {
+    public int x;
+    public int y;
+    [MarshalAs(UnmanagedType.I1)]
+    public bool visible;
+  }
+
+  [StructLayout(LayoutKind.Explicit)]
+  public struct Value
+  {
+    [FieldOffset(0)]
+    public long integer;
+    [FieldOffset(0)]
+    public double real;
+  }
+
+  public static partial class NativeMethods
+  {
+    public const string LibraryName = "buffers";
+
+    public const uint MAX_NAME_LEN = 16;
+
+    /// <summary>
+    /// Creates a buffer named `name`.
+    /// </summary>
+    [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl, EntryPoint = "buffer_new")]
+    public static extern IntPtr buffer_new([MarshalAs(UnmanagedType.LPUTF8Str)] string name, Mode mode);
+
+    [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl, EntryPoint = "buffer_seek")]
+    [return: MarshalAs(UnmanagedType.I1)]
+    public static extern bool buffer_seek(IntPtr buffer, ulong offset, Point origin);
+
+    [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl, EntryPoint = "buffer_get")]
+    public static extern double buffer_get(IntPtr buffer, uint @event, IntPtr value);
+
+    [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl, EntryPoint = "buffer_watch")]
+    public static extern void buffer_watch(IntPtr buffer, IntPtr callback);
+  }
+}
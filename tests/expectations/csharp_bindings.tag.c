@@ -0,0 +1,47 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define MAX_NAME_LEN 16
+
+/**
+ * The mode of a buffer.
+ */
+enum Mode {
+  Read,
+  Write = 4,
+  Append,
+};
+typedef uint8_t Mode;
+
+struct Buffer;
+
+typedef uint64_t Offset;
+
+/**
+ * A point on the screen.
+ */
+struct Point {
+  int32_t x;
+  int32_t y;
+  bool visible;
+};
+
+union Value {
+  int64_t integer;
+  double real;
+};
+
+typedef void (*Callback)(struct Buffer *buffer, void *data);
+
+/**
+ * Creates a buffer named `name`.
+ */
+struct Buffer *buffer_new(const char *name, Mode mode);
+
+bool buffer_seek(struct Buffer *buffer, Offset offset, struct Point origin);
+
+double buffer_get(const struct Buffer *buffer, uint32_t event, union Value *value);
+
+void buffer_watch(const struct Buffer *buffer, Callback callback);
@@ -0,0 +1,42 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const uint32_t MAX_NAME_LEN # = 16
+
+  # The mode of a buffer.
+  cdef enum:
+    Read,
+    Write # = 4,
+    Append,
+  ctypedef uint8_t Mode;
+
+  cdef struct Buffer:
+    pass
+
+  ctypedef uint64_t Offset;
+
+  # A point on the screen.
+  cdef struct Point:
+    int32_t x;
+    int32_t y;
+    bool visible;
+
+  cdef union Value:
+    int64_t integer;
+    double real;
+
+  ctypedef void (*Callback)(Buffer *buffer, void *data);
+
+  # Creates a buffer named `name`.
+  Buffer *buffer_new(const char *name, Mode mode);
+
+  bool buffer_seek(Buffer *buffer, Offset offset, Point origin);
+
+  double buffer_get(const Buffer *buffer, uint32_t event, Value *value);
+
+  void buffer_watch(const Buffer *buffer, Callback callback);
@@ -0,0 +1,9 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Imported from the library named after the crate.
+ */
+uint32_t answer(void);
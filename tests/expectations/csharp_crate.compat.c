@@ -0,0 +1,17 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Imported from the library named after the crate.
+ */
+uint32_t answer(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
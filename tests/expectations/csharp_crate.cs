@@ -0,0 +1,13 @@
+using System;
+using System.Runtime.InteropServices;
+
+public static partial class NativeMethods
+{
+  public const string LibraryName = "csharp_crate";
+
+  /// <summary>
+  /// Imported from the library named after the crate.
+  /// </summary>
+  [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl, EntryPoint = "answer")]
+  public static extern uint answer();
+}
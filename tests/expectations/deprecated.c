@@ -0,0 +1,61 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable: 4996)
+#endif
+
+enum DeprecatedEnum {
+  A,
+  B,
+};
+typedef uint8_t DeprecatedEnum __attribute__((deprecated("Use `Flags` instead")));
+
+typedef struct {
+  int32_t a;
+} DeprecatedStruct __attribute__((deprecated));
+
+typedef enum {
+  Foo,
+  Bar,
+} DeprecatedEnumWithData_Tag;
+
+typedef struct {
+  uint32_t x;
+} Bar_Body;
+
+typedef struct {
+  DeprecatedEnumWithData_Tag tag;
+  union {
+    struct {
+      int32_t foo;
+    };
+    Bar_Body bar;
+  };
+} DeprecatedEnumWithData __attribute__((deprecated("Say \"hello\" instead")));
+
+typedef uint32_t DeprecatedAlias __attribute__((deprecated));
+
+__attribute__((deprecated("Use `NEW_COUNTER` instead"))) extern uint32_t DEPRECATED_COUNTER;
+
+__attribute__((deprecated("Use `new_function` instead"))) void deprecated_function(void);
+
+__attribute__((deprecated))
+void deprecated_with_struct(DeprecatedStruct a,
+                            DeprecatedEnum b,
+                            const DeprecatedEnumWithData *c,
+                            DeprecatedAlias d);
+
+void new_function(void);
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
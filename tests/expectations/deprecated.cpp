@@ -0,0 +1,67 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable: 4996)
+#endif
+
+enum class [[deprecated("Use `Flags` instead")]] DeprecatedEnum : uint8_t {
+  A,
+  B,
+};
+
+struct [[deprecated]] DeprecatedStruct {
+  int32_t a;
+};
+
+struct [[deprecated("Say \"hello\" instead")]] DeprecatedEnumWithData {
+  enum class Tag {
+    Foo,
+    Bar,
+  };
+
+  struct Foo_Body {
+    int32_t _0;
+  };
+
+  struct Bar_Body {
+    uint32_t x;
+  };
+
+  Tag tag;
+  union {
+    Foo_Body foo;
+    Bar_Body bar;
+  };
+};
+
+using DeprecatedAlias [[deprecated]] = uint32_t;
+
+extern "C" {
+
+[[deprecated("Use `NEW_COUNTER` instead")]] extern uint32_t DEPRECATED_COUNTER;
+
+[[deprecated("Use `new_function` instead")]] void deprecated_function();
+
+[[deprecated]]
+void deprecated_with_struct(DeprecatedStruct a,
+                            DeprecatedEnum b,
+                            const DeprecatedEnumWithData *c,
+                            DeprecatedAlias d);
+
+void new_function();
+
+} // extern "C"
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
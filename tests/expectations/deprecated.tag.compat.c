@@ -0,0 +1,75 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable: 4996)
+#endif
+
+enum DeprecatedEnum
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  A,
+  B,
+};
+#ifndef __cplusplus
+typedef uint8_t DeprecatedEnum __attribute__((deprecated("Use `Flags` instead")));
+#endif // __cplusplus
+
+struct __attribute__((deprecated)) DeprecatedStruct {
+  int32_t a;
+};
+
+enum DeprecatedEnumWithData_Tag {
+  Foo,
+  Bar,
+};
+
+struct Bar_Body {
+  uint32_t x;
+};
+
+struct __attribute__((deprecated("Say \"hello\" instead"))) DeprecatedEnumWithData {
+  enum DeprecatedEnumWithData_Tag tag;
+  union {
+    struct {
+      int32_t foo;
+    };
+    struct Bar_Body bar;
+  };
+};
+
+typedef uint32_t DeprecatedAlias __attribute__((deprecated));
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+__attribute__((deprecated("Use `NEW_COUNTER` instead"))) extern uint32_t DEPRECATED_COUNTER;
+
+__attribute__((deprecated("Use `new_function` instead"))) void deprecated_function(void);
+
+__attribute__((deprecated))
+void deprecated_with_struct(struct DeprecatedStruct a,
+                            DeprecatedEnum b,
+                            const struct DeprecatedEnumWithData *c,
+                            DeprecatedAlias d);
+
+void new_function(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
@@ -0,0 +1,40 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum:
+    A,
+    B,
+  ctypedef uint8_t DeprecatedEnum;
+
+  cdef struct DeprecatedStruct:
+    int32_t a;
+
+  cdef enum DeprecatedEnumWithData_Tag:
+    Foo,
+    Bar,
+
+  cdef struct Bar_Body:
+    uint32_t x;
+
+  cdef struct DeprecatedEnumWithData:
+    DeprecatedEnumWithData_Tag tag;
+    int32_t foo;
+    Bar_Body bar;
+
+  ctypedef uint32_t DeprecatedAlias;
+
+  extern uint32_t DEPRECATED_COUNTER;
+
+  void deprecated_function();
+
+  void deprecated_with_struct(DeprecatedStruct a,
+                              DeprecatedEnum b,
+                              const DeprecatedEnumWithData *c,
+                              DeprecatedAlias d);
+
+  void new_function();
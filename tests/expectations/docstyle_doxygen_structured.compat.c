@@ -0,0 +1,31 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Copies `len` bytes from `src` to `dest`.
+ *
+ * \param dest The buffer to copy to, which must hold `len` bytes.
+ * \param src The buffer to copy from.
+ *        It may not overlap `dest`.
+ * \param len The number of bytes to copy.
+ *
+ * \return The number of bytes copied,
+ * which is always `len`.
+ *
+ * \warning Both pointers must be valid.
+ *
+ * # Examples
+ *
+ * Some prose.
+ */
+uintptr_t copy(uint8_t *dest, const uint8_t *src, uintptr_t len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,30 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # Copies `len` bytes from `src` to `dest`.
+  #
+  # # Arguments
+  #
+  # * `dest` - The buffer to copy to, which must hold `len` bytes.
+  # * `src` - The buffer to copy from.
+  #   It may not overlap `dest`.
+  # * `len`: The number of bytes to copy.
+  #
+  # # Returns
+  #
+  # The number of bytes copied,
+  # which is always `len`.
+  #
+  # # Safety
+  #
+  # Both pointers must be valid.
+  #
+  # # Examples
+  #
+  # Some prose.
+  uintptr_t copy(uint8_t *dest, const uint8_t *src, uintptr_t len);
@@ -0,0 +1,204 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Point {
+  int32_t x;
+  int32_t y;
+} Point;
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Circle,
+  Rect,
+  Label,
+  Id,
+  Empty,
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+typedef struct Circle_Body {
+  struct Point center;
+  float radius;
+} Circle_Body;
+
+typedef struct Rect_Body {
+  struct Point _0;
+  struct Point _1;
+} Rect_Body;
+
+typedef struct Shape {
+  Shape_Tag tag;
+  union {
+    Circle_Body circle;
+    Rect_Body rect;
+    struct {
+      uint8_t label[4];
+    };
+    struct {
+      uint32_t id;
+    };
+  };
+} Shape;
+
+static inline Shape Shape_Circle_new(struct Point center,
+                                     float radius) {
+  Shape result;
+  result.tag = Circle;
+  result.circle.center = center;
+  result.circle.radius = radius;
+  return result;
+}
+
+static inline bool Shape_is_Circle(const Shape *self) {
+  return self->tag == Circle;
+}
+
+static inline const Circle_Body *Shape_get_Circle(const Shape *self) {
+  return self->tag == Circle ? &self->circle : NULL;
+}
+
+static inline Shape Shape_Rect_new(struct Point _0,
+                                   struct Point _1) {
+  Shape result;
+  result.tag = Rect;
+  result.rect._0 = _0;
+  result.rect._1 = _1;
+  return result;
+}
+
+static inline bool Shape_is_Rect(const Shape *self) {
+  return self->tag == Rect;
+}
+
+static inline const Rect_Body *Shape_get_Rect(const Shape *self) {
+  return self->tag == Rect ? &self->rect : NULL;
+}
+
+static inline Shape Shape_Label_new(uint8_t label[4]) {
+  Shape result;
+  result.tag = Label;
+  for (int i = 0; i < 4; i++) {
+    result.label[i] = label[i];
+  }
+  return result;
+}
+
+static inline bool Shape_is_Label(const Shape *self) {
+  return self->tag == Label;
+}
+
+static inline const uint8_t (*Shape_get_Label(const Shape *self))[4] {
+  return self->tag == Label ? &self->label : NULL;
+}
+
+static inline Shape Shape_Id_new(uint32_t id) {
+  Shape result;
+  result.tag = Id;
+  result.id = id;
+  return result;
+}
+
+static inline bool Shape_is_Id(const Shape *self) {
+  return self->tag == Id;
+}
+
+static inline const uint32_t *Shape_get_Id(const Shape *self) {
+  return self->tag == Id ? &self->id : NULL;
+}
+
+static inline Shape Shape_Empty_new(void) {
+  Shape result;
+  result.tag = Empty;
+  return result;
+}
+
+static inline bool Shape_is_Empty(const Shape *self) {
+  return self->tag == Empty;
+}
+
+enum Event_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Key,
+  Click,
+  Quit,
+};
+#ifndef __cplusplus
+typedef uint8_t Event_Tag;
+#endif // __cplusplus
+
+typedef struct Click_Body {
+  Event_Tag tag;
+  int32_t x;
+  int32_t y;
+} Click_Body;
+
+typedef union Event {
+  Event_Tag tag;
+  struct {
+    Event_Tag key_tag;
+    uint32_t key;
+  };
+  Click_Body click;
+} Event;
+
+static inline Event Event_Key_new(uint32_t key) {
+  Event result;
+  result.tag = Key;
+  result.key = key;
+  return result;
+}
+
+static inline bool Event_is_Key(const Event *self) {
+  return self->tag == Key;
+}
+
+static inline const uint32_t *Event_get_Key(const Event *self) {
+  return self->tag == Key ? &self->key : NULL;
+}
+
+static inline Event Event_Click_new(int32_t x,
+                                    int32_t y) {
+  Event result;
+  result.tag = Click;
+  result.click.x = x;
+  result.click.y = y;
+  return result;
+}
+
+static inline bool Event_is_Click(const Event *self) {
+  return self->tag == Click;
+}
+
+static inline const Click_Body *Event_get_Click(const Event *self) {
+  return self->tag == Click ? &self->click : NULL;
+}
+
+static inline Event Event_Quit_new(void) {
+  Event result;
+  result.tag = Quit;
+  return result;
+}
+
+static inline bool Event_is_Quit(const Event *self) {
+  return self->tag == Quit;
+}
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Shape shape, union Event event);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
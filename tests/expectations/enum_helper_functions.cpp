@@ -0,0 +1,77 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Circle,
+    Rect,
+    Label,
+    Id,
+    Empty,
+  };
+
+  struct Circle_Body {
+    Point center;
+    float radius;
+  };
+
+  struct Rect_Body {
+    Point _0;
+    Point _1;
+  };
+
+  struct Label_Body {
+    uint8_t _0[4];
+  };
+
+  struct Id_Body {
+    uint32_t _0;
+  };
+
+  Tag tag;
+  union {
+    Circle_Body circle;
+    Rect_Body rect;
+    Label_Body label;
+    Id_Body id;
+  };
+};
+
+union Event {
+  enum class Tag : uint8_t {
+    Key,
+    Click,
+    Quit,
+  };
+
+  struct Key_Body {
+    Tag tag;
+    uint32_t _0;
+  };
+
+  struct Click_Body {
+    Tag tag;
+    int32_t x;
+    int32_t y;
+  };
+
+  struct {
+    Tag tag;
+  };
+  Key_Body key;
+  Click_Body click;
+};
+
+extern "C" {
+
+void root(Shape shape, Event event);
+
+} // extern "C"
@@ -0,0 +1,53 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Point:
+    int32_t x;
+    int32_t y;
+
+  cdef enum:
+    Circle,
+    Rect,
+    Label,
+    Id,
+    Empty,
+  ctypedef uint8_t Shape_Tag;
+
+  ctypedef struct Circle_Body:
+    Point center;
+    float radius;
+
+  ctypedef struct Rect_Body:
+    Point _0;
+    Point _1;
+
+  ctypedef struct Shape:
+    Shape_Tag tag;
+    Circle_Body circle;
+    Rect_Body rect;
+    uint8_t label[4];
+    uint32_t id;
+
+  cdef enum:
+    Key,
+    Click,
+    Quit,
+  ctypedef uint8_t Event_Tag;
+
+  ctypedef struct Click_Body:
+    Event_Tag tag;
+    int32_t x;
+    int32_t y;
+
+  ctypedef union Event:
+    Event_Tag tag;
+    Event_Tag key_tag;
+    uint32_t key;
+    Click_Body click;
+
+  void root(Shape shape, Event event);
@@ -0,0 +1,184 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+enum Shape_Tag {
+  Circle,
+  Rect,
+  Label,
+  Id,
+  Empty,
+};
+typedef uint8_t Shape_Tag;
+
+struct Circle_Body {
+  struct Point center;
+  float radius;
+};
+
+struct Rect_Body {
+  struct Point _0;
+  struct Point _1;
+};
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct Circle_Body circle;
+    struct Rect_Body rect;
+    struct {
+      uint8_t label[4];
+    };
+    struct {
+      uint32_t id;
+    };
+  };
+};
+
+static inline struct Shape Shape_Circle_new(struct Point center,
+                                            float radius) {
+  struct Shape result;
+  result.tag = Circle;
+  result.circle.center = center;
+  result.circle.radius = radius;
+  return result;
+}
+
+static inline bool Shape_is_Circle(const struct Shape *self) {
+  return self->tag == Circle;
+}
+
+static inline const struct Circle_Body *Shape_get_Circle(const struct Shape *self) {
+  return self->tag == Circle ? &self->circle : NULL;
+}
+
+static inline struct Shape Shape_Rect_new(struct Point _0,
+                                          struct Point _1) {
+  struct Shape result;
+  result.tag = Rect;
+  result.rect._0 = _0;
+  result.rect._1 = _1;
+  return result;
+}
+
+static inline bool Shape_is_Rect(const struct Shape *self) {
+  return self->tag == Rect;
+}
+
+static inline const struct Rect_Body *Shape_get_Rect(const struct Shape *self) {
+  return self->tag == Rect ? &self->rect : NULL;
+}
+
+static inline struct Shape Shape_Label_new(uint8_t label[4]) {
+  struct Shape result;
+  result.tag = Label;
+  for (int i = 0; i < 4; i++) {
+    result.label[i] = label[i];
+  }
+  return result;
+}
+
+static inline bool Shape_is_Label(const struct Shape *self) {
+  return self->tag == Label;
+}
+
+static inline const uint8_t (*Shape_get_Label(const struct Shape *self))[4] {
+  return self->tag == Label ? &self->label : NULL;
+}
+
+static inline struct Shape Shape_Id_new(uint32_t id) {
+  struct Shape result;
+  result.tag = Id;
+  result.id = id;
+  return result;
+}
+
+static inline bool Shape_is_Id(const struct Shape *self) {
+  return self->tag == Id;
+}
+
+static inline const uint32_t *Shape_get_Id(const struct Shape *self) {
+  return self->tag == Id ? &self->id : NULL;
+}
+
+static inline struct Shape Shape_Empty_new(void) {
+  struct Shape result;
+  result.tag = Empty;
+  return result;
+}
+
+static inline bool Shape_is_Empty(const struct Shape *self) {
+  return self->tag == Empty;
+}
+
+enum Event_Tag {
+  Key,
+  Click,
+  Quit,
+};
+typedef uint8_t Event_Tag;
+
+struct Click_Body {
+  Event_Tag tag;
+  int32_t x;
+  int32_t y;
+};
+
+union Event {
+  Event_Tag tag;
+  struct {
+    Event_Tag key_tag;
+    uint32_t key;
+  };
+  struct Click_Body click;
+};
+
+static inline union Event Event_Key_new(uint32_t key) {
+  union Event result;
+  result.tag = Key;
+  result.key = key;
+  return result;
+}
+
+static inline bool Event_is_Key(const union Event *self) {
+  return self->tag == Key;
+}
+
+static inline const uint32_t *Event_get_Key(const union Event *self) {
+  return self->tag == Key ? &self->key : NULL;
+}
+
+static inline union Event Event_Click_new(int32_t x,
+                                          int32_t y) {
+  union Event result;
+  result.tag = Click;
+  result.click.x = x;
+  result.click.y = y;
+  return result;
+}
+
+static inline bool Event_is_Click(const union Event *self) {
+  return self->tag == Click;
+}
+
+static inline const struct Click_Body *Event_get_Click(const union Event *self) {
+  return self->tag == Click ? &self->click : NULL;
+}
+
+static inline union Event Event_Quit_new(void) {
+  union Event result;
+  result.tag = Quit;
+  return result;
+}
+
+static inline bool Event_is_Quit(const union Event *self) {
+  return self->tag == Quit;
+}
+
+void root(struct Shape shape, union Event event);
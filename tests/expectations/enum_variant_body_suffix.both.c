@@ -0,0 +1,34 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Shape_Tag {
+  Circle,
+  Rect,
+  Point,
+  Empty,
+};
+typedef uint8_t Shape_Tag;
+
+typedef struct Circle_Data {
+  float radius;
+} Circle_Data;
+
+typedef struct Rect_Data {
+  float _0;
+  float _1;
+} Rect_Data;
+
+typedef struct Shape {
+  Shape_Tag tag;
+  union {
+    Circle_Data circle;
+    Rect_Data rect;
+    struct {
+      uint32_t point;
+    };
+  };
+} Shape;
+
+void root(struct Shape shape);
@@ -0,0 +1,48 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Circle,
+  Rect,
+  Point,
+  Empty,
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+typedef struct {
+  float radius;
+} Circle_Data;
+
+typedef struct {
+  float _0;
+  float _1;
+} Rect_Data;
+
+typedef struct {
+  Shape_Tag tag;
+  union {
+    Circle_Data circle;
+    Rect_Data rect;
+    struct {
+      uint32_t point;
+    };
+  };
+} Shape;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Shape shape);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
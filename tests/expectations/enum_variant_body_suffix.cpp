@@ -0,0 +1,40 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Circle,
+    Rect,
+    Point,
+    Empty,
+  };
+
+  struct Circle_Data {
+    float radius;
+  };
+
+  struct Rect_Data {
+    float _0;
+    float _1;
+  };
+
+  struct Point_Data {
+    uint32_t _0;
+  };
+
+  Tag tag;
+  union {
+    Circle_Data circle;
+    Rect_Data rect;
+    Point_Data point;
+  };
+};
+
+extern "C" {
+
+void root(Shape shape);
+
+} // extern "C"
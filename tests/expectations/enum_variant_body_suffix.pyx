@@ -0,0 +1,29 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum:
+    Circle,
+    Rect,
+    Point,
+    Empty,
+  ctypedef uint8_t Shape_Tag;
+
+  ctypedef struct Circle_Data:
+    float radius;
+
+  ctypedef struct Rect_Data:
+    float _0;
+    float _1;
+
+  ctypedef struct Shape:
+    Shape_Tag tag;
+    Circle_Data circle;
+    Rect_Data rect;
+    uint32_t point;
+
+  void root(Shape shape);
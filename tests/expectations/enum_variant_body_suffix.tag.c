@@ -0,0 +1,34 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Shape_Tag {
+  Circle,
+  Rect,
+  Point,
+  Empty,
+};
+typedef uint8_t Shape_Tag;
+
+struct Circle_Data {
+  float radius;
+};
+
+struct Rect_Data {
+  float _0;
+  float _1;
+};
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct Circle_Data circle;
+    struct Rect_Data rect;
+    struct {
+      uint32_t point;
+    };
+  };
+};
+
+void root(struct Shape shape);
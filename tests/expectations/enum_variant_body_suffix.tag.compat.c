@@ -0,0 +1,48 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Circle,
+  Rect,
+  Point,
+  Empty,
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+struct Circle_Data {
+  float radius;
+};
+
+struct Rect_Data {
+  float _0;
+  float _1;
+};
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct Circle_Data circle;
+    struct Rect_Data rect;
+    struct {
+      uint32_t point;
+    };
+  };
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Shape shape);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
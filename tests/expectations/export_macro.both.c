@@ -0,0 +1,34 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifndef MYLIB_API
+#if defined(_WIN32) || defined(__CYGWIN__)
+#ifdef MYLIB_API_EXPORTS
+#define MYLIB_API __declspec(dllexport)
+#else
+#define MYLIB_API __declspec(dllimport)
+#endif
+#elif defined(__GNUC__)
+#define MYLIB_API __attribute__((visibility("default")))
+#else
+#define MYLIB_API
+#endif
+#endif
+
+typedef struct Counter {
+  uint32_t value;
+} Counter;
+
+MYLIB_API extern struct Counter COUNTER;
+
+MYLIB_API extern const uint32_t VERSION;
+
+MYLIB_API uint32_t counter_increment(struct Counter *counter);
+
+MYLIB_API void counter_reset(struct Counter *counter,
+                             uint32_t first,
+                             uint32_t second,
+                             uint32_t third,
+                             uint32_t fourth);
@@ -0,0 +1,22 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Counter:
+    uint32_t value;
+
+  extern Counter COUNTER;
+
+  extern const uint32_t VERSION;
+
+  uint32_t counter_increment(Counter *counter);
+
+  void counter_reset(Counter *counter,
+                     uint32_t first,
+                     uint32_t second,
+                     uint32_t third,
+                     uint32_t fourth);
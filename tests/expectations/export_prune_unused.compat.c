@@ -0,0 +1,41 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define MAX_WIDTH 1920
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+  int32_t w;
+  int32_t h;
+} Rect;
+
+typedef struct {
+  uint8_t r;
+  uint8_t g;
+  uint8_t b;
+} Color;
+#define Color_BLACK (Color){ .r = 0, .g = 0, .b = 0 }
+
+typedef struct {
+  int32_t top;
+  int32_t bottom;
+} Margins;
+
+#define WHITE (Color){ .r = 255, .g = 255, .b = 255 }
+
+#define EMPTY_RECT (Rect){ .x = 0, .y = 0, .w = 0, .h = 0 }
+
+#define NO_MARGINS (Margins){ .top = 0, .bottom = 0 }
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void fill(Rect rect, Color color);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,38 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+static const int32_t MAX_WIDTH = 1920;
+
+struct Rect {
+  int32_t x;
+  int32_t y;
+  int32_t w;
+  int32_t h;
+};
+
+struct Color {
+  uint8_t r;
+  uint8_t g;
+  uint8_t b;
+};
+static const Color Color_BLACK = Color{ /* .r = */ 0, /* .g = */ 0, /* .b = */ 0 };
+
+struct Margins {
+  int32_t top;
+  int32_t bottom;
+};
+
+static const Color WHITE = Color{ /* .r = */ 255, /* .g = */ 255, /* .b = */ 255 };
+
+static const Rect EMPTY_RECT = Rect{ /* .x = */ 0, /* .y = */ 0, /* .w = */ 0, /* .h = */ 0 };
+
+static const Margins NO_MARGINS = Margins{ /* .top = */ 0, /* .bottom = */ 0 };
+
+extern "C" {
+
+void fill(Rect rect, Color color);
+
+} // extern "C"
@@ -0,0 +1,33 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const int32_t MAX_WIDTH # = 1920
+
+  ctypedef struct Rect:
+    int32_t x;
+    int32_t y;
+    int32_t w;
+    int32_t h;
+
+  ctypedef struct Color:
+    uint8_t r;
+    uint8_t g;
+    uint8_t b;
+  const Color Color_BLACK # = <Color>{ 0, 0, 0 }
+
+  ctypedef struct Margins:
+    int32_t top;
+    int32_t bottom;
+
+  const Color WHITE # = <Color>{ 255, 255, 255 }
+
+  const Rect EMPTY_RECT # = <Rect>{ 0, 0, 0, 0 }
+
+  const Margins NO_MARGINS # = <Margins>{ 0, 0 }
+
+  void fill(Rect rect, Color color);
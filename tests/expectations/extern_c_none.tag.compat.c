@@ -0,0 +1,32 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable: 4996)
+#endif
+
+struct Counter {
+  uint64_t value;
+};
+
+extern struct Counter COUNTER;
+
+extern const uint64_t LIMIT;
+
+uint64_t counter_increment(struct Counter *counter);
+
+__attribute__((deprecated("use counter_increment"))) void counter_bump(struct Counter *counter);
+
+extern void counter_changed(const struct Counter *counter);
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
@@ -0,0 +1,20 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Counter:
+    uint64_t value;
+
+  extern Counter COUNTER;
+
+  extern const uint64_t LIMIT;
+
+  uint64_t counter_increment(Counter *counter);
+
+  void counter_bump(Counter *counter);
+
+  extern void counter_changed(const Counter *counter);
@@ -0,0 +1,33 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#elif defined(_MSC_VER)
+#pragma warning(push)
+#pragma warning(disable: 4996)
+#endif
+
+struct Counter {
+  uint64_t value;
+};
+
+extern "C" Counter COUNTER;
+
+extern "C" const uint64_t LIMIT;
+
+extern "C" uint64_t counter_increment(Counter *counter);
+
+extern "C" [[deprecated("use counter_increment")]] void counter_bump(Counter *counter);
+
+extern "C" void counter_changed(const Counter *counter);
+
+#if defined(__GNUC__)
+#pragma GCC diagnostic pop
+#elif defined(_MSC_VER)
+#pragma warning(pop)
+#endif
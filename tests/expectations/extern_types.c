@@ -0,0 +1,11 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <time.h>
+
+typedef struct sqlite3 sqlite3;
+
+sqlite3 *db_open(time_t when);
+
+clock_t db_elapsed(sqlite3 *db);
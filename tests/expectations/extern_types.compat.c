@@ -0,0 +1,19 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <time.h>
+
+typedef struct sqlite3 sqlite3;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+sqlite3 *db_open(time_t when);
+
+clock_t db_elapsed(sqlite3 *db);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
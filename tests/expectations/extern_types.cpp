@@ -0,0 +1,16 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <time.h>
+
+struct sqlite3;
+
+extern "C" {
+
+sqlite3 *db_open(time_t when);
+
+clock_t db_elapsed(sqlite3 *db);
+
+} // extern "C"
@@ -0,0 +1,15 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+from libc.time cimport clock_t, time_t
+
+cdef extern from *:
+  ctypedef struct sqlite3
+
+cdef extern from *:
+
+  sqlite3 *db_open(time_t when);
+
+  clock_t db_elapsed(sqlite3 *db);
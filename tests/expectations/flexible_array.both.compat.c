@@ -0,0 +1,29 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Packet {
+  uint32_t len;
+  uint8_t data[];
+} Packet;
+
+typedef struct Names {
+  uintptr_t count;
+  const uint8_t *names[];
+} Names;
+
+typedef struct NotTrailing {
+  uint8_t data[0];
+  uint32_t len;
+} NotTrailing;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(const struct Packet *p, struct Names *n, struct NotTrailing t);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
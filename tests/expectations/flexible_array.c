@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  uint32_t len;
+  uint8_t data[];
+} Packet;
+
+typedef struct {
+  uintptr_t count;
+  const uint8_t *names[];
+} Names;
+
+typedef struct {
+  uint8_t data[0];
+  uint32_t len;
+} NotTrailing;
+
+void root(const Packet *p, Names *n, NotTrailing t);
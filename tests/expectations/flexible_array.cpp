@@ -0,0 +1,26 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Packet {
+  uint32_t len;
+  uint8_t data[];
+};
+
+struct Names {
+  uintptr_t count;
+  const uint8_t *names[];
+};
+
+struct NotTrailing {
+  uint8_t data[0];
+  uint32_t len;
+};
+
+extern "C" {
+
+void root(const Packet *p, Names *n, NotTrailing t);
+
+} // extern "C"
@@ -0,0 +1,21 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Packet:
+    uint32_t len;
+    uint8_t data[0];
+
+  cdef struct Names:
+    uintptr_t count;
+    const uint8_t *names[1];
+
+  cdef struct NotTrailing:
+    uint8_t data[0];
+    uint32_t len;
+
+  void root(const Packet *p, Names *n, NotTrailing t);
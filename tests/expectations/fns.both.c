@@ -13,4 +13,4 @@ typedef struct Fns {
 
 void root(struct Fns _fns);
 
-void no_return(void);
+_Noreturn void no_return(void);
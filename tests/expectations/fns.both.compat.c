@@ -17,6 +17,11 @@ extern "C" {
 
 void root(struct Fns _fns);
 
+#ifdef __cplusplus
+[[noreturn]]
+#else
+_Noreturn
+#endif // __cplusplus
 void no_return(void);
 
 #ifdef __cplusplus
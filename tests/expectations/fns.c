@@ -13,4 +13,4 @@ typedef struct {
 
 void root(Fns _fns);
 
-void no_return(void);
+_Noreturn void no_return(void);
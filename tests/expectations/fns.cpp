@@ -16,6 +16,6 @@ extern "C" {
 
 void root(Fns _fns);
 
-void no_return();
+[[noreturn]] void no_return();
 
 } // extern "C"
@@ -1,14 +1,3 @@
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-/* ANONYMOUS STRUCTS DO NOT SUPPORT FORWARD DECLARATIONS!
-#endif
-#if 0
-' '''
-#endif
-
-
 #include <stdarg.h>
 #include <stdbool.h>
 #include <stdint.h>
@@ -38,13 +27,3 @@ typedef struct TypeInfo {
 } TypeInfo;
 
 void root(struct TypeInfo x);
-
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-*/
-#endif
-#if 0
-' '''
-#endif
@@ -1,14 +1,3 @@
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-/* ANONYMOUS STRUCTS DO NOT SUPPORT FORWARD DECLARATIONS!
-#endif
-#if 0
-' '''
-#endif
-
-
 #include <stdarg.h>
 #include <stdbool.h>
 #include <stdint.h>
@@ -46,13 +35,3 @@ void root(struct TypeInfo x);
 #ifdef __cplusplus
 } // extern "C"
 #endif // __cplusplus
-
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-*/
-#endif
-#if 0
-' '''
-#endif
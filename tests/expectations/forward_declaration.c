@@ -1,19 +1,10 @@
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-/* ANONYMOUS STRUCTS DO NOT SUPPORT FORWARD DECLARATIONS!
-#endif
-#if 0
-' '''
-#endif
-
-
 #include <stdarg.h>
 #include <stdbool.h>
 #include <stdint.h>
 #include <stdlib.h>
 
+typedef struct TypeInfo TypeInfo;
+
 typedef struct {
   const TypeInfo *const *fields;
   uintptr_t num_fields;
@@ -33,18 +24,8 @@ typedef struct {
   };
 } TypeData;
 
-typedef struct {
+struct TypeInfo {
   TypeData data;
-} TypeInfo;
+};
 
 void root(TypeInfo x);
-
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-*/
-#endif
-#if 0
-' '''
-#endif
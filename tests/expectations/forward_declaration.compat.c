@@ -1,19 +1,10 @@
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-/* ANONYMOUS STRUCTS DO NOT SUPPORT FORWARD DECLARATIONS!
-#endif
-#if 0
-' '''
-#endif
-
-
 #include <stdarg.h>
 #include <stdbool.h>
 #include <stdint.h>
 #include <stdlib.h>
 
+typedef struct TypeInfo TypeInfo;
+
 typedef struct {
   const TypeInfo *const *fields;
   uintptr_t num_fields;
@@ -33,9 +24,9 @@ typedef struct {
   };
 } TypeData;
 
-typedef struct {
+struct TypeInfo {
   TypeData data;
-} TypeInfo;
+};
 
 #ifdef __cplusplus
 extern "C" {
@@ -46,13 +37,3 @@ void root(TypeInfo x);
 #ifdef __cplusplus
 } // extern "C"
 #endif // __cplusplus
-
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-*/
-#endif
-#if 0
-' '''
-#endif
@@ -1,20 +1,11 @@
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-/* ANONYMOUS STRUCTS DO NOT SUPPORT FORWARD DECLARATIONS!
-#endif
-#if 0
-' '''
-#endif
-
-
 #include <cstdarg>
 #include <cstdint>
 #include <cstdlib>
 #include <ostream>
 #include <new>
 
+struct TypeInfo;
+
 struct StructInfo {
   const TypeInfo *const *fields;
   uintptr_t num_fields;
@@ -45,13 +36,3 @@ extern "C" {
 void root(TypeInfo x);
 
 } // extern "C"
-
-#if 0
-''' '
-#endif
-#if defined(CBINDGEN_STYLE_TYPE)
-*/
-#endif
-#if 0
-' '''
-#endif
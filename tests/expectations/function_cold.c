@@ -0,0 +1,12 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+__attribute__((cold)) void report_error(int32_t code);
+
+_Noreturn __attribute__((cold)) void abort_with(int32_t code);
+
+_Noreturn void exit_with(int32_t code);
+
+uint8_t normal_return(void);
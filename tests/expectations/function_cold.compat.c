@@ -0,0 +1,30 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+__attribute__((cold)) void report_error(int32_t code);
+
+#ifdef __cplusplus
+[[noreturn]]
+#else
+_Noreturn
+#endif // __cplusplus
+__attribute__((cold)) void abort_with(int32_t code);
+
+#ifdef __cplusplus
+[[noreturn]]
+#else
+_Noreturn
+#endif // __cplusplus
+void exit_with(int32_t code);
+
+uint8_t normal_return(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,17 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+extern "C" {
+
+__attribute__((cold)) void report_error(int32_t code);
+
+[[noreturn]] __attribute__((cold)) void abort_with(int32_t code);
+
+[[noreturn]] void exit_with(int32_t code);
+
+uint8_t normal_return();
+
+} // extern "C"
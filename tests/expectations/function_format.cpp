@@ -0,0 +1,19 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+extern "C" {
+
+extern void log_message(int32_t level, const char *fmt, ...) __attribute__((format(printf, 2, 3)));
+
+void print_message(const char *fmt, ...) __attribute__((format(printf, 1, 2)));
+
+void print_to(int32_t stream, const char *format, ...) __attribute__((format(printf, 2, 3)));
+
+void print_list(const char *format, char *list) __attribute__((format(printf, 1, 0)));
+
+int32_t sum(int32_t count, ...);
+
+} // extern "C"
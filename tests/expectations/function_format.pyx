@@ -0,0 +1,17 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  extern void log_message(int32_t level, const char *fmt, ...);
+
+  void print_message(const char *fmt, ...);
+
+  void print_to(int32_t stream, const char *format, ...);
+
+  void print_list(const char *format, char *list);
+
+  int32_t sum(int32_t count, ...);
@@ -0,0 +1,25 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Called with the user data given along with it.
+ */
+typedef void (*Callback)(void *user_data, int32_t status);
+
+typedef bool (*NamedCallback)(void *user_data, int32_t status);
+
+typedef void (*RenamedCallback)(const uint8_t *data, uintptr_t len);
+
+typedef void (*KeywordCallback)(int32_t int_, float float_);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Callback a, NamedCallback b, RenamedCallback c, KeywordCallback d);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
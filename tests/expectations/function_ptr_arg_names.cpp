@@ -0,0 +1,20 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+/// Called with the user data given along with it.
+using Callback = void(*)(void *user_data, int32_t status);
+
+using NamedCallback = bool(*)(void *user_data, int32_t status);
+
+using RenamedCallback = void(*)(const uint8_t *data, uintptr_t len);
+
+using KeywordCallback = void(*)(int32_t int_, float float_);
+
+extern "C" {
+
+void root(Callback a, NamedCallback b, RenamedCallback c, KeywordCallback d);
+
+} // extern "C"
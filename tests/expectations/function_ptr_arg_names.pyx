@@ -0,0 +1,18 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # Called with the user data given along with it.
+  ctypedef void (*Callback)(void *user_data, int32_t status);
+
+  ctypedef bool (*NamedCallback)(void *user_data, int32_t status);
+
+  ctypedef void (*RenamedCallback)(const uint8_t *data, uintptr_t len);
+
+  ctypedef void (*KeywordCallback)(int32_t int_, float float_);
+
+  void root(Callback a, NamedCallback b, RenamedCallback c, KeywordCallback d);
@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+void print_message(int32_t level, const char *fmt, ...) __attribute__((format(printf, 2, 3)));
+
+/**
+ * Like `print_message`, with the variable arguments in a `va_list`.
+ */
+void print_message_valist(int32_t level,
+                          const char *fmt,
+                          va_list args) __attribute__((format(printf, 2, 0)));
+
+void print_line(const char *format, ...) __attribute__((format(printf, 1, 2)));
+
+/**
+ * Like `print_line`, with the variable arguments in a `va_list`.
+ */
+void print_line_valist(const char *format, va_list args) __attribute__((format(printf, 1, 0)));
+
+void push_values(int32_t count, ...);
+
+/**
+ * Like `push_values`, with the variable arguments in a `va_list`.
+ */
+void push_values_valist(int32_t count, va_list args);
+
+int32_t sum(int32_t count, ...);
+
+char *concat(const char *first, ...);
+
+char *concat_valist(const char *first, va_list args);
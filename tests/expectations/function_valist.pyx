@@ -0,0 +1,28 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  void print_message(int32_t level, const char *fmt, ...);
+
+  # Like `print_message`, with the variable arguments in a `va_list`.
+  void print_message_valist(int32_t level, const char *fmt, va_list args);
+
+  void print_line(const char *format, ...);
+
+  # Like `print_line`, with the variable arguments in a `va_list`.
+  void print_line_valist(const char *format, va_list args);
+
+  void push_values(int32_t count, ...);
+
+  # Like `push_values`, with the variable arguments in a `va_list`.
+  void push_values_valist(int32_t count, va_list args);
+
+  int32_t sum(int32_t count, ...);
+
+  char *concat(const char *first, ...);
+
+  char *concat_valist(const char *first, va_list args);
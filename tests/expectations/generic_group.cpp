@@ -0,0 +1,23 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Value {
+  uint8_t kind;
+};
+
+extern "C" {
+
+void value_set_i32(Value *value, int32_t v);
+
+void value_set_f64(Value *value, double v);
+
+void value_set_str(Value *value, const uint8_t *v);
+
+int32_t value_get_i32(const Value *value);
+
+double value_get_f64(const Value *value, double fallback);
+
+} // extern "C"
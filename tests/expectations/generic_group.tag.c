@@ -0,0 +1,25 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Value {
+  uint8_t kind;
+};
+
+void value_set_i32(struct Value *value, int32_t v);
+
+void value_set_f64(struct Value *value, double v);
+
+void value_set_str(struct Value *value, const uint8_t *v);
+
+int32_t value_get_i32(const struct Value *value);
+
+double value_get_f64(const struct Value *value, double fallback);
+
+#if defined(__STDC_VERSION__) && __STDC_VERSION__ >= 201112L
+#define value_set(value, v) _Generic((v), \
+    int32_t: value_set_i32, \
+    double: value_set_f64, \
+    const uint8_t*: value_set_str)(value, v)
+#endif
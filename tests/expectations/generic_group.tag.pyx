@@ -0,0 +1,20 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Value:
+    uint8_t kind;
+
+  void value_set_i32(Value *value, int32_t v);
+
+  void value_set_f64(Value *value, double v);
+
+  void value_set_str(Value *value, const uint8_t *v);
+
+  int32_t value_get_i32(const Value *value);
+
+  double value_get_f64(const Value *value, double fallback);
@@ -0,0 +1,42 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+typedef struct GbdBuffer GbdBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * gbd_buffer_load:
+ * @path: (nullable):
+ * @error: return location for a #GError, or %NULL
+ *
+ * Loads a buffer.
+ *
+ * Returns: (nullable): %NULL if @error is set
+ */
+GbdBuffer *gbd_buffer_load(const char *path, GError **error);
+
+/**
+ * gbd_buffer_save:
+ * @buffer: (nullable):
+ * @error: return location for a #GError, or %NULL
+ *
+ * Returns: %TRUE on success, %FALSE if @error is set
+ */
+bool gbd_buffer_save(GbdBuffer *buffer, GError **error);
+
+/**
+ * gbd_buffer_sync:
+ * @buffer: (nullable):
+ * @error: return location for a #GError, or %NULL
+ */
+void gbd_buffer_sync(GbdBuffer *buffer, GError **error);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,19 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <glib-object.h>
+
+struct GbdBuffer;
+
+extern "C" {
+
+/// Loads a buffer.
+GbdBuffer *gbd_buffer_load(const char *path, GError **error);
+
+bool gbd_buffer_save(GbdBuffer *buffer, GError **error);
+
+void gbd_buffer_sync(GbdBuffer *buffer, GError **error);
+
+} // extern "C"
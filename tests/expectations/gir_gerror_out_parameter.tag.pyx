@@ -0,0 +1,17 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct GbdBuffer:
+    pass
+
+  # Loads a buffer.
+  GbdBuffer *gbd_buffer_load(const char *path, GError **error);
+
+  bool gbd_buffer_save(GbdBuffer *buffer, GError **error);
+
+  void gbd_buffer_sync(GbdBuffer *buffer, GError **error);
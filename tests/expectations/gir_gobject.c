@@ -0,0 +1,66 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+#define GBD_TYPE_BAR                    (gbd_bar_get_type())
+G_DECLARE_FINAL_TYPE(GbdBar, gbd_bar, GBD, BAR, GObject)
+
+struct _GbdBar {
+  GObject parent_instance;
+};
+
+/**
+ * GbdBar::changed:
+ * @self: the object which received the signal
+ * @user_data: user data set when the signal handler was connected
+ *
+ * Connect with g_signal_connect (self, "changed", G_CALLBACK (handler), user_data).
+ */
+typedef void (*GbdBarChangedHandler)(GbdBar *self, gpointer user_data);
+
+#define GBD_TYPE_LABEL                    (gbd_label_get_type())
+G_DECLARE_FINAL_TYPE(GbdLabel, gbd_label, GBD, LABEL, GInitiallyUnowned)
+
+struct _GbdLabel {
+  GInitiallyUnowned parent_instance;
+};
+
+#define GBD_TYPE_READABLE (gbd_readable_get_type())
+G_DECLARE_INTERFACE(GbdReadable, gbd_readable, GBD, READABLE, GObject)
+
+struct _GbdReadableInterface {
+  GTypeInterface g_iface;
+
+  intptr_t (*read)(GbdReadable *self, uint8_t *buffer, uintptr_t len);
+};
+
+/**
+ * gbd_bar_new:
+ *
+ * Returns: (nullable):
+ */
+GbdBar *gbd_bar_new(void);
+
+/**
+ * gbd_label_set_bar:
+ * @label: (transfer none):
+ * @bar: (transfer none):
+ */
+void gbd_label_set_bar(GbdLabel *label, const GbdBar *bar);
+
+/**
+ * gbd_bar_get_title:
+ * @self: a #GbdBar
+ *
+ * Returns: (transfer none): the value of the #GbdBar:title property
+ */
+const gchar *gbd_bar_get_title(GbdBar *self);
+
+/**
+ * gbd_bar_set_title:
+ * @self: a #GbdBar
+ * @title: (transfer none): the new value of the #GbdBar:title property
+ */
+void gbd_bar_set_title(GbdBar *self, const gchar *title);
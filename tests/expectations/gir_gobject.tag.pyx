@@ -0,0 +1,20 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct GbdBar:
+    pass
+
+  cdef struct GbdLabel:
+    pass
+
+  cdef struct GbdReadable:
+    pass
+
+  GbdBar *gbd_bar_new();
+
+  void gbd_label_set_bar(GbdLabel *label, const GbdBar *bar);
@@ -0,0 +1,86 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+/**
+ * The longest name of a buffer.
+ */
+#define GbdMAX_NAME_LEN 16
+
+/**
+ * The mode of a buffer.
+ */
+typedef enum GbdMode {
+  Read,
+  Write = 4,
+  Append,
+} GbdMode;
+#define GBD_TYPE_MODE (gbd_mode_get_type())
+
+typedef struct GbdBuffer GbdBuffer;
+
+typedef struct GbdCursor GbdCursor;
+
+typedef uint64_t GbdOffset;
+
+/**
+ * A point on the screen.
+ */
+typedef struct GbdPoint {
+  int32_t x;
+  int32_t y;
+  uint8_t tag[4];
+} GbdPoint;
+
+typedef union GbdValue {
+  int64_t integer;
+  double real;
+} GbdValue;
+
+#define GBD_TYPE_BUFFER                    (gbd_buffer_get_type())
+
+/**
+ * gbd_buffer_new:
+ * @name: (nullable):
+ * @mode:
+ *
+ * Creates a buffer.
+ *
+ * Returns: (nullable) (transfer full):
+ */
+struct GbdBuffer *gbd_buffer_new(const char *name, enum GbdMode mode);
+
+/**
+ * gbd_buffer_free:
+ * @buffer: (nullable) (transfer full):
+ */
+void gbd_buffer_free(struct GbdBuffer *buffer);
+
+/**
+ * gbd_buffer_seek:
+ * @buffer: (transfer none):
+ * @offset:
+ * @origin:
+ */
+bool gbd_buffer_seek(struct GbdBuffer *buffer, GbdOffset offset, struct GbdPoint origin);
+
+/**
+ * gbd_buffer_cursor:
+ * @buffer: (transfer none):
+ * @value:
+ *
+ * Returns: (nullable):
+ */
+struct GbdCursor *gbd_buffer_cursor(const struct GbdBuffer *buffer, union GbdValue value);
+
+GType gbd_buffer_get_type(void);
+
+GbdBuffer *gbd_buffer_copy(const GbdBuffer *self);
+
+GType gbd_mode_get_type(void);
+
+#if GLIB_CHECK_VERSION(2, 44, 0)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdBuffer, gbd_buffer_free)
+#endif
@@ -0,0 +1,61 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <glib-object.h>
+
+/// The longest name of a buffer.
+static const uint32_t GbdMAX_NAME_LEN = 16;
+
+/// The mode of a buffer.
+enum class GbdMode {
+  Read,
+  Write = 4,
+  Append,
+};
+#define GBD_TYPE_MODE (gbd_mode_get_type())
+
+struct GbdBuffer;
+
+struct GbdCursor;
+
+using GbdOffset = uint64_t;
+
+/// A point on the screen.
+struct GbdPoint {
+  int32_t x;
+  int32_t y;
+  uint8_t tag[4];
+};
+
+union GbdValue {
+  int64_t integer;
+  double real;
+};
+
+#define GBD_TYPE_BUFFER                    (gbd_buffer_get_type())
+
+extern "C" {
+
+/// Creates a buffer.
+///
+GbdBuffer *gbd_buffer_new(const char *name, GbdMode mode);
+
+void gbd_buffer_free(GbdBuffer *buffer);
+
+bool gbd_buffer_seek(GbdBuffer *buffer, GbdOffset offset, GbdPoint origin);
+
+GbdCursor *gbd_buffer_cursor(const GbdBuffer *buffer, GbdValue value);
+
+GType gbd_buffer_get_type(void);
+
+GbdBuffer *gbd_buffer_copy(const GbdBuffer *self);
+
+GType gbd_mode_get_type(void);
+
+#if GLIB_CHECK_VERSION(2, 44, 0)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdBuffer, gbd_buffer_free)
+#endif
+
+} // extern "C"
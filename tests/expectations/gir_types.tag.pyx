@@ -0,0 +1,45 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # The longest name of a buffer.
+  const uint32_t GbdMAX_NAME_LEN # = 16
+
+  # The mode of a buffer.
+  cdef enum GbdMode:
+    Read,
+    Write # = 4,
+    Append,
+  #define GBD_TYPE_MODE (gbd_mode_get_type())
+
+  cdef struct GbdBuffer:
+    pass
+
+  cdef struct GbdCursor:
+    pass
+
+  ctypedef uint64_t GbdOffset;
+
+  # A point on the screen.
+  cdef struct GbdPoint:
+    int32_t x;
+    int32_t y;
+    uint8_t tag[4];
+
+  cdef union GbdValue:
+    int64_t integer;
+    double real;
+
+  # Creates a buffer.
+  #
+  GbdBuffer *gbd_buffer_new(const char *name, GbdMode mode);
+
+  void gbd_buffer_free(GbdBuffer *buffer);
+
+  bool gbd_buffer_seek(GbdBuffer *buffer, GbdOffset offset, GbdPoint origin);
+
+  GbdCursor *gbd_buffer_cursor(const GbdBuffer *buffer, GbdValue value);
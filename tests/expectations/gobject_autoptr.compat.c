@@ -0,0 +1,58 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+typedef struct GbdBuffer GbdBuffer;
+
+typedef struct GbdRange GbdRange;
+
+typedef struct GbdStream GbdStream;
+
+#define GBD_TYPE_RANGE                    (gbd_range_get_type())
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * gbd_buffer_free:
+ * @buffer: (nullable):
+ */
+void gbd_buffer_free(GbdBuffer *buffer);
+
+/**
+ * gbd_stream_free:
+ * @stream: (nullable):
+ */
+void gbd_stream_free(GbdStream *stream);
+
+/**
+ * gbd_stream_unref:
+ * @stream: (nullable):
+ */
+void gbd_stream_unref(GbdStream *stream);
+
+/**
+ * gbd_range_new:
+ *
+ * Returns: (nullable):
+ */
+GbdRange *gbd_range_new(void);
+
+GType gbd_range_get_type(void);
+
+GbdRange *gbd_range_copy(const GbdRange *self);
+
+void gbd_range_free(GbdRange *self);
+
+#if GLIB_CHECK_VERSION(2, 44, 0)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdBuffer, gbd_buffer_free)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdRange, gbd_range_free)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdStream, gbd_stream_unref)
+#endif
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,38 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <glib-object.h>
+
+struct GbdBuffer;
+
+struct GbdRange;
+
+struct GbdStream;
+
+#define GBD_TYPE_RANGE                    (gbd_range_get_type())
+
+extern "C" {
+
+void gbd_buffer_free(GbdBuffer *buffer);
+
+void gbd_stream_free(GbdStream *stream);
+
+void gbd_stream_unref(GbdStream *stream);
+
+GbdRange *gbd_range_new();
+
+GType gbd_range_get_type(void);
+
+GbdRange *gbd_range_copy(const GbdRange *self);
+
+void gbd_range_free(GbdRange *self);
+
+#if GLIB_CHECK_VERSION(2, 44, 0)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdBuffer, gbd_buffer_free)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdRange, gbd_range_free)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(GbdStream, gbd_stream_unref)
+#endif
+
+} // extern "C"
@@ -0,0 +1,24 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct GbdBuffer:
+    pass
+
+  ctypedef struct GbdRange:
+    pass
+
+  ctypedef struct GbdStream:
+    pass
+
+  void gbd_buffer_free(GbdBuffer *buffer);
+
+  void gbd_stream_free(GbdStream *stream);
+
+  void gbd_stream_unref(GbdStream *stream);
+
+  GbdRange *gbd_range_new();
@@ -0,0 +1,23 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+typedef enum {
+  Plain,
+  LineBuffered,
+} GbdBufferKind;
+#define GBD_TYPE_BUFFER_KIND (gbd_buffer_kind_get_type())
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void gbd_buffer_kind(GbdBufferKind kind);
+
+GType gbd_buffer_kind_get_type(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
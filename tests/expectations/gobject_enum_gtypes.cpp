@@ -0,0 +1,20 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <glib-object.h>
+
+enum class GbdBufferKind {
+  Plain,
+  LineBuffered,
+};
+#define GBD_TYPE_BUFFER_KIND (gbd_buffer_kind_get_type())
+
+extern "C" {
+
+void gbd_buffer_kind(GbdBufferKind kind);
+
+GType gbd_buffer_kind_get_type(void);
+
+} // extern "C"
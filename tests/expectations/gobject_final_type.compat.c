@@ -0,0 +1,42 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+#define GBD_TYPE_BAR                    (gbd_bar_get_type())
+G_DECLARE_FINAL_TYPE(GbdBar, gbd_bar, GBD, BAR, GObject)
+
+struct _GbdBar {
+  GObject parent_instance;
+};
+
+#define GBD_TYPE_LABEL                    (gbd_label_get_type())
+G_DECLARE_FINAL_TYPE(GbdLabel, gbd_label, GBD, LABEL, GInitiallyUnowned)
+
+struct _GbdLabel {
+  GInitiallyUnowned parent_instance;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * gbd_bar_new:
+ *
+ * Returns: (nullable):
+ */
+GbdBar *gbd_bar_new(void);
+
+/**
+ * gbd_label_new:
+ * @bar: (nullable):
+ *
+ * Returns: (nullable):
+ */
+GbdLabel *gbd_label_new(GbdBar *bar);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,68 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <gio/gio.h>
+
+typedef struct {
+  uintptr_t len;
+} Buffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * buffer_load_async:
+ * @buffer: (transfer none):
+ * @path: (nullable):
+ * @cancellable: (nullable):
+ * @callback: (scope async) (closure user_data):
+ * @user_data: (nullable):
+ *
+ * Loads the buffer.
+ */
+void buffer_load_async(Buffer *buffer,
+                       const uint8_t *path,
+                       GCancellable *cancellable,
+                       GAsyncReadyCallback callback,
+                       void *user_data);
+
+/**
+ * buffer_load_finish:
+ * @buffer: (transfer none):
+ * @result:
+ * @error: return location for a #GError, or %NULL
+ *
+ * Finishes the operation started with `buffer_load_async()`.
+ *
+ * Returns: %TRUE on success, %FALSE if @error is set
+ */
+bool buffer_load_finish(Buffer *buffer, GAsyncResult *result, GError **error);
+
+/**
+ * fetch_async:
+ * @url: (nullable):
+ * @cancellable: (nullable):
+ * @callback: (scope async) (closure user_data):
+ * @user_data: (nullable):
+ */
+void fetch_async(const uint8_t *url,
+                 GCancellable *cancellable,
+                 GAsyncReadyCallback callback,
+                 void *user_data);
+
+/**
+ * fetch_finish:
+ * @result:
+ * @error: return location for a #GError, or %NULL
+ *
+ * Finishes the operation started with `fetch_async()`.
+ *
+ * Returns: (nullable) (transfer full): %NULL if @error is set
+ */
+Buffer *fetch_finish(GAsyncResult *result, GError **error);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,35 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <gio/gio.h>
+
+template<typename T = void>
+struct Box;
+
+struct Buffer {
+  uintptr_t len;
+};
+
+extern "C" {
+
+/// Loads the buffer.
+void buffer_load_async(Buffer *buffer,
+                       const uint8_t *path,
+                       GCancellable *cancellable,
+                       GAsyncReadyCallback callback,
+                       void *user_data);
+
+/// Finishes the operation started with `buffer_load_async()`.
+bool buffer_load_finish(Buffer *buffer, GAsyncResult *result, GError **error);
+
+void fetch_async(const uint8_t *url,
+                 GCancellable *cancellable,
+                 GAsyncReadyCallback callback,
+                 void *user_data);
+
+/// Finishes the operation started with `fetch_async()`.
+Box<Buffer> fetch_finish(GAsyncResult *result, GError **error);
+
+} // extern "C"
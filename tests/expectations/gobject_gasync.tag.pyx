@@ -0,0 +1,28 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Buffer:
+    uintptr_t len;
+
+  # Loads the buffer.
+  void buffer_load_async(Buffer *buffer,
+                         const uint8_t *path,
+                         GCancellable *cancellable,
+                         GAsyncReadyCallback callback,
+                         void *user_data);
+
+  # Finishes the operation started with `buffer_load_async()`.
+  bool buffer_load_finish(Buffer *buffer, GAsyncResult *result, GError **error);
+
+  void fetch_async(const uint8_t *url,
+                   GCancellable *cancellable,
+                   GAsyncReadyCallback callback,
+                   void *user_data);
+
+  # Finishes the operation started with `fetch_async()`.
+  Buffer *fetch_finish(GAsyncResult *result, GError **error);
@@ -0,0 +1,39 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+typedef struct {
+  uintptr_t len;
+} GbdChunk;
+
+/**
+ * A source of chunks.
+ */
+#define GBD_TYPE_CHUNK_SOURCE (gbd_chunk_source_get_type())
+G_DECLARE_INTERFACE(GbdChunkSource, gbd_chunk_source, GBD, CHUNK_SOURCE, GObject)
+
+struct _GbdChunkSourceInterface {
+  GTypeInterface g_iface;
+
+  /**
+   * Reads the next chunk.
+   */
+  bool (*read)(GbdChunkSource *self, GbdChunk *chunk);
+  void (*close)(GbdChunkSource *self);
+};
+
+#define GBD_TYPE_SINK (gbd_sink_get_type())
+G_DECLARE_INTERFACE(GbdSink, gbd_sink, GBD, SINK, GInitiallyUnowned)
+
+struct _GbdSinkInterface {
+  GTypeInterface g_iface;
+};
+
+/**
+ * gbd_chunk_source_next:
+ * @source: (transfer none):
+ * @chunk: (transfer none):
+ */
+bool gbd_chunk_source_next(GbdChunkSource *source, GbdChunk *chunk);
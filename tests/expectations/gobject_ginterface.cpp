@@ -0,0 +1,35 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <glib-object.h>
+
+struct GbdChunk {
+  uintptr_t len;
+};
+
+/// A source of chunks.
+#define GBD_TYPE_CHUNK_SOURCE (gbd_chunk_source_get_type())
+G_DECLARE_INTERFACE(GbdChunkSource, gbd_chunk_source, GBD, CHUNK_SOURCE, GObject)
+
+struct _GbdChunkSourceInterface {
+  GTypeInterface g_iface;
+
+  /// Reads the next chunk.
+  bool (*read)(GbdChunkSource *self, GbdChunk *chunk);
+  void (*close)(GbdChunkSource *self);
+};
+
+#define GBD_TYPE_SINK (gbd_sink_get_type())
+G_DECLARE_INTERFACE(GbdSink, gbd_sink, GBD, SINK, GInitiallyUnowned)
+
+struct _GbdSinkInterface {
+  GTypeInterface g_iface;
+};
+
+extern "C" {
+
+bool gbd_chunk_source_next(GbdChunkSource *source, GbdChunk *chunk);
+
+} // extern "C"
@@ -0,0 +1,18 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct GbdChunk:
+    uintptr_t len;
+
+  cdef struct GbdChunkSource:
+    pass
+
+  cdef struct GbdSink:
+    pass
+
+  bool gbd_chunk_source_next(GbdChunkSource *source, GbdChunk *chunk);
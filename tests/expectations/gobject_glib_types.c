@@ -0,0 +1,12 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <glib.h>
+#include <stdlib.h>
+
+/**
+ * foo:
+ * @a:
+ * @b: (nullable):
+ * @c:
+ */
+bool foo(gint32 a, const gchar *b, guintptr c);
@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <glib.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * foo:
+ * @a:
+ * @b: (nullable):
+ * @c:
+ */
+bool foo(gint32 a, const gchar *b, guintptr c);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
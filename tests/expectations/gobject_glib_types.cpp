@@ -0,0 +1,11 @@
+#include <cstdarg>
+#include <glib.h>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+extern "C" {
+
+bool foo(gint32 a, const gchar *b, guintptr c);
+
+} // extern "C"
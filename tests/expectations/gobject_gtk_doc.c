@@ -0,0 +1,60 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+typedef struct Buffer Buffer;
+
+/**
+ * buffer_new:
+ * @key: (nullable) (array fixed-size=16): the bytes of the key
+ * @flags: unused
+ *
+ * Creates a buffer holding a copy of a key.
+ *
+ * The bytes are copied.
+ *
+ * Returns: (nullable) (transfer full): a new buffer, to free with
+ *   `buffer_free()`
+ */
+Buffer *buffer_new(const uint8_t key[16], uint32_t flags);
+
+/**
+ * buffer_free:
+ * @buffer: (nullable) (transfer full):
+ *
+ * Frees a buffer.
+ *
+ * # Safety
+ *
+ * The buffer must not be used afterwards.
+ */
+void buffer_free(Buffer *buffer);
+
+/**
+ * buffer_len:
+ * @buffer: (nullable) (transfer none):
+ *
+ * Returns the length of a buffer, or 0 if there's none.
+ */
+uintptr_t buffer_len(const Buffer *buffer);
+
+/**
+ * buffer_data:
+ * @buffer: (transfer none):
+ * @out: (nullable):
+ *
+ * Returns: (nullable):
+ */
+const uint8_t *buffer_data(const Buffer *buffer, uint8_t (*out)[4]);
+
+/**
+ * buffer_clear:
+ * @buffer: (transfer none):
+ */
+void buffer_clear(Buffer *buffer);
+
+#if GLIB_CHECK_VERSION(2, 44, 0)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(Buffer, buffer_free)
+#endif
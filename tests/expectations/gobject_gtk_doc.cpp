@@ -0,0 +1,47 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <glib-object.h>
+
+struct Buffer;
+
+extern "C" {
+
+/// Creates a buffer holding a copy of a key.
+///
+/// The bytes are copied.
+///
+/// # Arguments
+///
+/// * `key` - the bytes of the key
+/// * `flags` - unused
+///
+/// # Returns
+///
+/// a new buffer, to free with
+/// `buffer_free()`
+///
+Buffer *buffer_new(const uint8_t key[16], uint32_t flags);
+
+/// Frees a buffer.
+///
+/// # Safety
+///
+/// The buffer must not be used afterwards.
+///
+void buffer_free(Buffer *buffer);
+
+/// Returns the length of a buffer, or 0 if there's none.
+uintptr_t buffer_len(const Buffer *buffer);
+
+const uint8_t *buffer_data(const Buffer *buffer, uint8_t (*out)[4]);
+
+void buffer_clear(Buffer *buffer);
+
+#if GLIB_CHECK_VERSION(2, 44, 0)
+G_DEFINE_AUTOPTR_CLEANUP_FUNC(Buffer, buffer_free)
+#endif
+
+} // extern "C"
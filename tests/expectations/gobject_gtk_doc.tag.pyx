@@ -0,0 +1,41 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Buffer:
+    pass
+
+  # Creates a buffer holding a copy of a key.
+  #
+  # The bytes are copied.
+  #
+  # # Arguments
+  #
+  # * `key` - the bytes of the key
+  # * `flags` - unused
+  #
+  # # Returns
+  #
+  # a new buffer, to free with
+  # `buffer_free()`
+  #
+  Buffer *buffer_new(const uint8_t key[16], uint32_t flags);
+
+  # Frees a buffer.
+  #
+  # # Safety
+  #
+  # The buffer must not be used afterwards.
+  #
+  void buffer_free(Buffer *buffer);
+
+  # Returns the length of a buffer, or 0 if there's none.
+  uintptr_t buffer_len(const Buffer *buffer);
+
+  const uint8_t *buffer_data(const Buffer *buffer, uint8_t (*out)[4]);
+
+  void buffer_clear(Buffer *buffer);
@@ -0,0 +1,86 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+#define GBD_TYPE_BAR                    (gbd_bar_get_type())
+G_DECLARE_FINAL_TYPE(GbdBar, gbd_bar, GBD, BAR, GObject)
+
+struct _GbdBar {
+  GObject parent_instance;
+};
+
+#define GBD_TYPE_BAZ                    (gbd_baz_get_type())
+G_DECLARE_FINAL_TYPE(GbdBaz, gbd_baz, GBD, BAZ, GObject)
+
+struct _GbdBaz {
+  GObject parent_instance;
+};
+
+/**
+ * gbd_bar_new:
+ *
+ * Returns: (nullable):
+ */
+GbdBar *gbd_bar_new(void);
+
+/**
+ * gbd_bar_get_item_count:
+ * @bar: (nullable):
+ *
+ * The crate already exports this getter.
+ */
+uint32_t gbd_bar_get_item_count(GbdBar *bar);
+
+/**
+ * gbd_bar_get_title:
+ * @self: a #GbdBar
+ *
+ * Returns: (transfer none): the value of the #GbdBar:title property
+ */
+const gchar *gbd_bar_get_title(GbdBar *self);
+
+/**
+ * gbd_bar_set_title:
+ * @self: a #GbdBar
+ * @title: (transfer none): the new value of the #GbdBar:title property
+ */
+void gbd_bar_set_title(GbdBar *self, const gchar *title);
+
+/**
+ * gbd_bar_set_item_count:
+ * @self: a #GbdBar
+ * @item_count: the new value of the #GbdBar:item-count property
+ */
+void gbd_bar_set_item_count(GbdBar *self, guint item_count);
+
+/**
+ * gbd_bar_get_model:
+ * @self: a #GbdBar
+ *
+ * Returns: (transfer none): the value of the #GbdBar:model property
+ */
+GObject *gbd_bar_get_model(GbdBar *self);
+
+/**
+ * gbd_bar_set_model:
+ * @self: a #GbdBar
+ * @model: (transfer none): the new value of the #GbdBar:model property
+ */
+void gbd_bar_set_model(GbdBar *self, GObject *model);
+
+/**
+ * gbd_baz_get_label:
+ * @self: a #GbdBaz
+ *
+ * Returns: (transfer none): the value of the #GbdBaz:label property
+ */
+const gchar *gbd_baz_get_label(GbdBaz *self);
+
+/**
+ * gbd_baz_set_label:
+ * @self: a #GbdBaz
+ * @label: (transfer none): the new value of the #GbdBaz:label property
+ */
+void gbd_baz_set_label(GbdBaz *self, const gchar *label);
@@ -0,0 +1,18 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct GbdBar:
+    pass
+
+  ctypedef struct GbdBaz:
+    pass
+
+  GbdBar *gbd_bar_new();
+
+  # The crate already exports this getter.
+  uint32_t gbd_bar_get_item_count(GbdBar *bar);
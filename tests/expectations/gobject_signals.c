@@ -0,0 +1,55 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <glib-object.h>
+
+#define GBD_TYPE_BAR                    (gbd_bar_get_type())
+G_DECLARE_FINAL_TYPE(GbdBar, gbd_bar, GBD, BAR, GObject)
+
+struct _GbdBar {
+  GObject parent_instance;
+};
+
+/**
+ * GbdBar::changed:
+ * @self: the object which received the signal
+ * @user_data: user data set when the signal handler was connected
+ *
+ * Connect with g_signal_connect (self, "changed", G_CALLBACK (handler), user_data).
+ */
+typedef void (*GbdBarChangedHandler)(GbdBar *self, gpointer user_data);
+
+/**
+ * GbdBar::item-added:
+ * @self: the object which received the signal
+ * @user_data: user data set when the signal handler was connected
+ *
+ * Connect with g_signal_connect (self, "item-added", G_CALLBACK (handler), user_data).
+ */
+typedef void (*GbdBarItemAddedHandler)(GbdBar *self, gpointer user_data);
+
+#define GBD_TYPE_READABLE (gbd_readable_get_type())
+G_DECLARE_INTERFACE(GbdReadable, gbd_readable, GBD, READABLE, GObject)
+
+struct _GbdReadableInterface {
+  GTypeInterface g_iface;
+
+  intptr_t (*read)(GbdReadable *self, uint8_t *buffer, uintptr_t len);
+};
+
+/**
+ * GbdReadable::closed:
+ * @self: the object which received the signal
+ * @user_data: user data set when the signal handler was connected
+ *
+ * Connect with g_signal_connect (self, "closed", G_CALLBACK (handler), user_data).
+ */
+typedef void (*GbdReadableClosedHandler)(GbdReadable *self, gpointer user_data);
+
+/**
+ * gbd_bar_new:
+ *
+ * Returns: (nullable):
+ */
+GbdBar *gbd_bar_new(void);
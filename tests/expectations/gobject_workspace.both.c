@@ -0,0 +1,10 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Root {
+  int32_t x;
+} Root;
+
+void root(struct Root _root);
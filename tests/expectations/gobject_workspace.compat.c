@@ -0,0 +1,18 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t x;
+} Root;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Root _root);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,15 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Root {
+  int32_t x;
+};
+
+extern "C" {
+
+void root(Root _root);
+
+} // extern "C"
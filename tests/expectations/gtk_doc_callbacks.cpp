@@ -0,0 +1,40 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+using ReadyCallback = void(*)(int32_t result, void *user_data);
+
+using DestroyNotify = void(*)(void *data);
+
+extern "C" {
+
+/**
+ * load_async:
+ * @path: (transfer none):
+ * @callback: (nullable) (scope async) (closure user_data): called once the file is loaded
+ * @user_data: (nullable): the data to pass to `callback`
+ *
+ * Loads the file in the background.
+ */
+void load_async(const uint8_t *path,
+                void (*callback)(int32_t result, void *user_data),
+                void *user_data);
+
+/**
+ * watch:
+ * @func: (scope notified) (closure data) (destroy destroy):
+ * @data: (nullable):
+ * @destroy:
+ */
+uint32_t watch(ReadyCallback func, void *data, DestroyNotify destroy);
+
+/**
+ * foreach:
+ * @func: (scope call) (closure data):
+ * @data: (nullable):
+ */
+void foreach(ReadyCallback func, void *data);
+
+} // extern "C"
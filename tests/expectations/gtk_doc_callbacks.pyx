@@ -0,0 +1,26 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef void (*ReadyCallback)(int32_t result, void *user_data);
+
+  ctypedef void (*DestroyNotify)(void *data);
+
+  # Loads the file in the background.
+  #
+  # # Arguments
+  #
+  # * `callback` - called once the file is loaded
+  # * `user_data` - the data to pass to `callback`
+  #
+  void load_async(const uint8_t *path,
+                  void (*callback)(int32_t result, void *user_data),
+                  void *user_data);
+
+  uint32_t watch(ReadyCallback func, void *data, DestroyNotify destroy);
+
+  void foreach(ReadyCallback func, void *data);
@@ -0,0 +1,27 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+} Point;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+static inline int32_t point_x(const Point *point) {
+  return point->x;
+}
+
+static inline int32_t point_y(const Point *point) {
+  return point->y;
+}
+
+double point_length(const Point *point);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
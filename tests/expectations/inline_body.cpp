@@ -0,0 +1,24 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+extern "C" {
+
+inline int32_t point_x(const Point *point) {
+  return point->x;
+}
+
+inline int32_t point_y(const Point *point) {
+  return point->y;
+}
+
+double point_length(const Point *point);
+
+} // extern "C"
@@ -0,0 +1,36 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Row Row;
+
+/**
+ * A database connection.
+ */
+typedef uint64_t ConnectionHandle;
+
+typedef uint64_t StatementHandle;
+
+ConnectionHandle connection_open(const uint8_t *url);
+
+StatementHandle connection_prepare(ConnectionHandle connection, const uint8_t *sql);
+
+void statement_destroy(StatementHandle statement);
+
+const Row *statement_next(StatementHandle statement);
+
+/**
+ * Creates a Connection and returns its handle.
+ */
+ConnectionHandle connection_create(void);
+
+/**
+ * Destroys the Connection of a handle.
+ */
+void connection_destroy(ConnectionHandle handle);
+
+/**
+ * Creates a Statement and returns its handle.
+ */
+StatementHandle statement_create(void);
@@ -0,0 +1,33 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Row;
+
+/// A database connection.
+using ConnectionHandle = uint64_t;
+
+using StatementHandle = uint64_t;
+
+extern "C" {
+
+ConnectionHandle connection_open(const uint8_t *url);
+
+StatementHandle connection_prepare(ConnectionHandle connection, const uint8_t *sql);
+
+void statement_destroy(StatementHandle statement);
+
+const Row *statement_next(StatementHandle statement);
+
+/// Creates a Connection and returns its handle.
+ConnectionHandle connection_create();
+
+/// Destroys the Connection of a handle.
+void connection_destroy(ConnectionHandle handle);
+
+/// Creates a Statement and returns its handle.
+StatementHandle statement_create();
+
+} // extern "C"
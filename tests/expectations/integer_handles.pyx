@@ -0,0 +1,32 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Row:
+    pass
+
+  # A database connection.
+  ctypedef uint64_t ConnectionHandle;
+
+  ctypedef uint64_t StatementHandle;
+
+  ConnectionHandle connection_open(const uint8_t *url);
+
+  StatementHandle connection_prepare(ConnectionHandle connection, const uint8_t *sql);
+
+  void statement_destroy(StatementHandle statement);
+
+  const Row *statement_next(StatementHandle statement);
+
+  # Creates a Connection and returns its handle.
+  ConnectionHandle connection_create();
+
+  # Destroys the Connection of a handle.
+  void connection_destroy(ConnectionHandle handle);
+
+  # Creates a Statement and returns its handle.
+  StatementHandle statement_create();
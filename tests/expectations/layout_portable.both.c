@@ -0,0 +1,40 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifndef CBINDGEN_ALIGNED
+#if defined(__cplusplus)
+#define CBINDGEN_ALIGNED(n) alignas(n)
+#elif defined(_MSC_VER)
+#define CBINDGEN_ALIGNED(n) __declspec(align(n))
+#else
+#define CBINDGEN_ALIGNED(n) __attribute__((aligned(n)))
+#endif
+#endif
+
+typedef struct CBINDGEN_ALIGNED(8) Align8Struct {
+  uintptr_t arg1;
+  uint8_t *arg2;
+} Align8Struct;
+
+#pragma pack(push, 1)
+typedef struct PackedStruct {
+  uint8_t arg1;
+  uint32_t arg2;
+} PackedStruct;
+#pragma pack(pop)
+
+typedef union CBINDGEN_ALIGNED(16) Align16Union {
+  uintptr_t variant1;
+  uint8_t *variant2;
+} Align16Union;
+
+#pragma pack(push, 1)
+typedef union PackedUnion {
+  uint16_t variant1;
+  uint64_t variant2;
+} PackedUnion;
+#pragma pack(pop)
+
+void root(struct Align8Struct a, struct PackedStruct b, union Align16Union c, union PackedUnion d);
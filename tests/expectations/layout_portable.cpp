@@ -0,0 +1,35 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct alignas(8) Align8Struct {
+  uintptr_t arg1;
+  uint8_t *arg2;
+};
+
+#pragma pack(push, 1)
+struct PackedStruct {
+  uint8_t arg1;
+  uint32_t arg2;
+};
+#pragma pack(pop)
+
+union alignas(16) Align16Union {
+  uintptr_t variant1;
+  uint8_t *variant2;
+};
+
+#pragma pack(push, 1)
+union PackedUnion {
+  uint16_t variant1;
+  uint64_t variant2;
+};
+#pragma pack(pop)
+
+extern "C" {
+
+void root(Align8Struct a, PackedStruct b, Align16Union c, PackedUnion d);
+
+} // extern "C"
@@ -0,0 +1,25 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Align8Struct:
+    uintptr_t arg1;
+    uint8_t *arg2;
+
+  ctypedef packed struct PackedStruct:
+    uint8_t arg1;
+    uint32_t arg2;
+
+  ctypedef union Align16Union:
+    uintptr_t variant1;
+    uint8_t *variant2;
+
+  ctypedef union PackedUnion:
+    uint16_t variant1;
+    uint64_t variant2;
+
+  void root(Align8Struct a, PackedStruct b, Align16Union c, PackedUnion d);
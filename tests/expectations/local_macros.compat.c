@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+} PointI;
+
+typedef struct {
+  float x;
+  float y;
+} PointF;
+
+typedef struct {
+  double x;
+  double y;
+} PointD;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+int32_t get_one(void);
+
+int32_t get_two(void);
+
+void root(PointI a, PointF b, PointD c);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
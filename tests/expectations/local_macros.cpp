@@ -0,0 +1,30 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct PointI {
+  int32_t x;
+  int32_t y;
+};
+
+struct PointF {
+  float x;
+  float y;
+};
+
+struct PointD {
+  double x;
+  double y;
+};
+
+extern "C" {
+
+int32_t get_one();
+
+int32_t get_two();
+
+void root(PointI a, PointF b, PointD c);
+
+} // extern "C"
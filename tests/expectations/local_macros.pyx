@@ -0,0 +1,25 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct PointI:
+    int32_t x;
+    int32_t y;
+
+  ctypedef struct PointF:
+    float x;
+    float y;
+
+  ctypedef struct PointD:
+    double x;
+    double y;
+
+  int32_t get_one();
+
+  int32_t get_two();
+
+  void root(PointI a, PointF b, PointD c);
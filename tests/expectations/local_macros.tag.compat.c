@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct PointI {
+  int32_t x;
+  int32_t y;
+};
+
+struct PointF {
+  float x;
+  float y;
+};
+
+struct PointD {
+  double x;
+  double y;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+int32_t get_one(void);
+
+int32_t get_two(void);
+
+void root(struct PointI a, struct PointF b, struct PointD c);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,27 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t fd;
+} Stream;
+
+/**
+ * stream_write:
+ * @stream: (nullable):
+ * @data: (array length=data_len) (transfer none): the bytes to write
+ * @data_len:
+ *
+ * Writes the bytes to the stream.
+ */
+ptrdiff_t stream_write(Stream *stream, const uint8_t *data, size_t data_len);
+
+/**
+ * samples_scale:
+ * @samples: (array length=samples_len) (transfer none):
+ * @samples_len:
+ * @factor:
+ */
+void samples_scale(float *samples, size_t samples_len, float factor, const uint8_t*, size_t);
@@ -0,0 +1,20 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Stream:
+    int32_t fd;
+
+  # Writes the bytes to the stream.
+  #
+  # # Arguments
+  #
+  # * `data` - the bytes to write
+  #
+  ptrdiff_t stream_write(Stream *stream, const uint8_t *data, size_t data_len);
+
+  void samples_scale(float *samples, size_t samples_len, float factor, const uint8_t*, size_t);
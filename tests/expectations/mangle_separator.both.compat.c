@@ -0,0 +1,28 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buffer__u8 {
+  uint8_t *data;
+  uintptr_t len;
+} Buffer__u8;
+
+typedef struct Buffer__u8 Bytes;
+
+typedef struct Pair__Buffer__u8____________________i32 {
+  struct Buffer__u8 first;
+  const int32_t *second;
+} Pair__Buffer__u8____________________i32;
+
+typedef struct Pair__Buffer__u8____________________i32 Entry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Bytes bytes, Entry entry);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
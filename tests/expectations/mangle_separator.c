@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  uint8_t *data;
+  uintptr_t len;
+} Buffer__u8;
+
+typedef Buffer__u8 Bytes;
+
+typedef struct {
+  Buffer__u8 first;
+  const int32_t *second;
+} Pair__Buffer__u8____________________i32;
+
+typedef Pair__Buffer__u8____________________i32 Entry;
+
+void root(Bytes bytes, Entry entry);
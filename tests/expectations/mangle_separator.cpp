@@ -0,0 +1,27 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+template<typename T>
+struct Buffer {
+  T *data;
+  uintptr_t len;
+};
+
+using Bytes = Buffer<uint8_t>;
+
+template<typename A, typename B>
+struct Pair {
+  A first;
+  B second;
+};
+
+using Entry = Pair<Buffer<uint8_t>, const int32_t*>;
+
+extern "C" {
+
+void root(Bytes bytes, Entry entry);
+
+} // extern "C"
@@ -0,0 +1,21 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Buffer__u8:
+    uint8_t *data;
+    uintptr_t len;
+
+  ctypedef Buffer__u8 Bytes;
+
+  ctypedef struct Pair__Buffer__u8____________________i32:
+    Buffer__u8 first;
+    const int32_t *second;
+
+  ctypedef Pair__Buffer__u8____________________i32 Entry;
+
+  void root(Bytes bytes, Entry entry);
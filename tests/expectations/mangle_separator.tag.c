@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Buffer__u8 {
+  uint8_t *data;
+  uintptr_t len;
+};
+
+typedef struct Buffer__u8 Bytes;
+
+struct Pair__Buffer__u8____________________i32 {
+  struct Buffer__u8 first;
+  const int32_t *second;
+};
+
+typedef struct Pair__Buffer__u8____________________i32 Entry;
+
+void root(Bytes bytes, Entry entry);
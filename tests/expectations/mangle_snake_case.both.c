@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct buffer_u8 {
+  uint8_t *data;
+  uintptr_t len;
+} buffer_u8;
+
+typedef struct buffer_u8 Bytes;
+
+typedef struct pair_buffer_u8_i32 {
+  struct buffer_u8 first;
+  const int32_t *second;
+} pair_buffer_u8_i32;
+
+typedef struct pair_buffer_u8_i32 Entry;
+
+void root(Bytes bytes, Entry entry);
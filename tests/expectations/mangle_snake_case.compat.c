@@ -0,0 +1,28 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  uint8_t *data;
+  uintptr_t len;
+} buffer_u8;
+
+typedef buffer_u8 Bytes;
+
+typedef struct {
+  buffer_u8 first;
+  const int32_t *second;
+} pair_buffer_u8_i32;
+
+typedef pair_buffer_u8_i32 Entry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Bytes bytes, Entry entry);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,21 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct buffer_u8:
+    uint8_t *data;
+    uintptr_t len;
+
+  ctypedef buffer_u8 Bytes;
+
+  ctypedef struct pair_buffer_u8_i32:
+    buffer_u8 first;
+    const int32_t *second;
+
+  ctypedef pair_buffer_u8_i32 Entry;
+
+  void root(Bytes bytes, Entry entry);
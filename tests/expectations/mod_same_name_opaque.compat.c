@@ -0,0 +1,18 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Inner Inner;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void a_inner(Inner *inner);
+
+void b_inner(Inner *inner);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,15 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Inner;
+
+extern "C" {
+
+void a_inner(Inner *inner);
+
+void b_inner(Inner *inner);
+
+} // extern "C"
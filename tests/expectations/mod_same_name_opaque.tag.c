@@ -0,0 +1,10 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Inner;
+
+void a_inner(struct Inner *inner);
+
+void b_inner(struct Inner *inner);
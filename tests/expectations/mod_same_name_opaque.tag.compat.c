@@ -0,0 +1,18 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Inner;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void a_inner(struct Inner *inner);
+
+void b_inner(struct Inner *inner);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
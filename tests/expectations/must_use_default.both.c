@@ -0,0 +1,23 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Status {
+  int32_t code;
+} Status;
+
+typedef struct Status Outcome;
+
+typedef struct Point {
+  int32_t x;
+  int32_t y;
+} Point;
+
+__attribute__((warn_unused_result)) struct Status try_open(void);
+
+__attribute__((warn_unused_result)) Outcome try_close(void);
+
+__attribute__((warn_unused_result)) struct Point origin(void);
+
+void move_to(struct Point point);
@@ -0,0 +1,23 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t code;
+} Status;
+
+typedef Status Outcome;
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+} Point;
+
+__attribute__((warn_unused_result)) Status try_open(void);
+
+__attribute__((warn_unused_result)) Outcome try_close(void);
+
+__attribute__((warn_unused_result)) Point origin(void);
+
+void move_to(Point point);
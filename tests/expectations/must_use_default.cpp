@@ -0,0 +1,28 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Status {
+  int32_t code;
+};
+
+using Outcome = Status;
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+extern "C" {
+
+[[nodiscard]] Status try_open();
+
+[[nodiscard]] Outcome try_close();
+
+[[nodiscard]] Point origin();
+
+void move_to(Point point);
+
+} // extern "C"
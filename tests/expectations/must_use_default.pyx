@@ -0,0 +1,24 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Status:
+    int32_t code;
+
+  ctypedef Status Outcome;
+
+  ctypedef struct Point:
+    int32_t x;
+    int32_t y;
+
+  Status try_open();
+
+  Outcome try_close();
+
+  Point origin();
+
+  void move_to(Point point);
@@ -0,0 +1,23 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Status {
+  int32_t code;
+};
+
+typedef struct Status Outcome;
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+__attribute__((warn_unused_result)) struct Status try_open(void);
+
+__attribute__((warn_unused_result)) Outcome try_close(void);
+
+__attribute__((warn_unused_result)) struct Point origin(void);
+
+void move_to(struct Point point);
@@ -0,0 +1,27 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define VERSION 1
+
+#define MAX_ADDRESS_LEN 16
+
+typedef struct Address {
+  uint8_t bytes[MAX_ADDRESS_LEN];
+  uint16_t port;
+} Address;
+
+typedef struct Socket {
+  struct Address local;
+  struct Peer *peer;
+} Socket;
+
+typedef struct Peer {
+  struct Socket *socket;
+  uint32_t version;
+} Peer;
+
+void peer_connect(struct Peer *peer, struct Address address);
+
+struct Socket *socket_open(const struct Address *address);
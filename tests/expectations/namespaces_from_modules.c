@@ -0,0 +1,29 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define VERSION 1
+
+#define MAX_ADDRESS_LEN 16
+
+typedef struct Peer Peer;
+
+typedef struct {
+  uint8_t bytes[MAX_ADDRESS_LEN];
+  uint16_t port;
+} Address;
+
+typedef struct {
+  Address local;
+  Peer *peer;
+} Socket;
+
+struct Peer {
+  Socket *socket;
+  uint32_t version;
+};
+
+void peer_connect(Peer *peer, Address address);
+
+Socket *socket_open(const Address *address);
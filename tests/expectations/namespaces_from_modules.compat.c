@@ -0,0 +1,47 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+namespace mylib {
+inline namespace v1 {
+#endif // __cplusplus
+
+#define VERSION 1
+
+#define MAX_ADDRESS_LEN 16
+
+typedef struct Peer Peer;
+
+typedef struct {
+  uint8_t bytes[MAX_ADDRESS_LEN];
+  uint16_t port;
+} Address;
+
+typedef struct {
+  Address local;
+  Peer *peer;
+} Socket;
+
+struct Peer {
+  Socket *socket;
+  uint32_t version;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void peer_connect(Peer *peer, Address address);
+
+Socket *socket_open(const Address *address);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#ifdef __cplusplus
+} // namespace v1
+} // namespace mylib
+#endif // __cplusplus
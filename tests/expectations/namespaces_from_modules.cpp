@@ -0,0 +1,60 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+namespace mylib {
+inline namespace v1 {
+
+static const uint32_t VERSION = 1;
+
+namespace net {
+
+static const uintptr_t MAX_ADDRESS_LEN = 16;
+
+} // namespace net
+
+struct Peer;
+
+namespace net {
+
+struct Address {
+  uint8_t bytes[net::MAX_ADDRESS_LEN];
+  uint16_t port;
+};
+
+} // namespace net
+
+namespace net {
+namespace tcp {
+
+struct Socket {
+  net::Address local;
+  Peer *peer;
+};
+
+} // namespace tcp
+} // namespace net
+
+struct Peer {
+  net::tcp::Socket *socket;
+  uint32_t version;
+};
+
+extern "C" {
+
+void peer_connect(Peer *peer, net::Address address);
+
+namespace net {
+namespace tcp {
+
+net::tcp::Socket *socket_open(const net::Address *address);
+
+} // namespace tcp
+} // namespace net
+
+} // extern "C"
+
+} // namespace v1
+} // namespace mylib
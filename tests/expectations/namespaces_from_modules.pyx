@@ -0,0 +1,27 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const uint32_t VERSION # = 1
+
+  const uintptr_t MAX_ADDRESS_LEN # = 16
+
+  ctypedef struct Address:
+    uint8_t bytes[MAX_ADDRESS_LEN];
+    uint16_t port;
+
+  ctypedef struct Socket:
+    Address local;
+    Peer *peer;
+
+  ctypedef struct Peer:
+    Socket *socket;
+    uint32_t version;
+
+  void peer_connect(Peer *peer, Address address);
+
+  Socket *socket_open(const Address *address);
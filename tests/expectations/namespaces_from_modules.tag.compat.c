@@ -0,0 +1,45 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+namespace mylib {
+inline namespace v1 {
+#endif // __cplusplus
+
+#define VERSION 1
+
+#define MAX_ADDRESS_LEN 16
+
+struct Address {
+  uint8_t bytes[MAX_ADDRESS_LEN];
+  uint16_t port;
+};
+
+struct Socket {
+  struct Address local;
+  struct Peer *peer;
+};
+
+struct Peer {
+  struct Socket *socket;
+  uint32_t version;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void peer_connect(struct Peer *peer, struct Address address);
+
+struct Socket *socket_open(const struct Address *address);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#ifdef __cplusplus
+} // namespace v1
+} // namespace mylib
+#endif // __cplusplus
@@ -0,0 +1,23 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define LEN 3
+
+typedef struct Matrix {
+  float m[4][4];
+  int8_t cube[5][LEN][2];
+  const uint8_t *rows[LEN];
+  uint16_t padded[(LEN + 1) * 2];
+  uint32_t block[LEN];
+  uint32_t cast[LEN - 1];
+  const int32_t (*row)[4];
+} Matrix;
+
+typedef struct Grid_8 {
+  uint8_t cells[8][8];
+  uint8_t flat[8 * 8];
+} Grid_8;
+
+void root(struct Matrix m, struct Grid_8 g, uint8_t (*out)[2][3]);
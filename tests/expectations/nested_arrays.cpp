@@ -0,0 +1,29 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+static const uintptr_t LEN = 3;
+
+struct Matrix {
+  float m[4][4];
+  int8_t cube[5][LEN][2];
+  const uint8_t *rows[LEN];
+  uint16_t padded[(LEN + 1) * 2];
+  uint32_t block[LEN];
+  uint32_t cast[LEN - 1];
+  const int32_t (*row)[4];
+};
+
+template<uintptr_t N>
+struct Grid {
+  uint8_t cells[N][N];
+  uint8_t flat[N * N];
+};
+
+extern "C" {
+
+void root(Matrix m, Grid<8> g, uint8_t (*out)[2][3]);
+
+} // extern "C"
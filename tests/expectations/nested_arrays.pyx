@@ -0,0 +1,24 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const uintptr_t LEN # = 3
+
+  ctypedef struct Matrix:
+    float m[4][4];
+    int8_t cube[5][LEN][2];
+    const uint8_t *rows[LEN];
+    uint16_t padded[(LEN + 1) * 2];
+    uint32_t block[LEN];
+    uint32_t cast[LEN - 1];
+    const int32_t (*row)[4];
+
+  ctypedef struct Grid_8:
+    uint8_t cells[8][8];
+    uint8_t flat[8 * 8];
+
+  void root(Matrix m, Grid_8 g, uint8_t (*out)[2][3]);
@@ -0,0 +1,45 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Never zero.
+ */
+typedef uint32_t Handle;
+
+/**
+ * An entry of the table.
+ */
+typedef struct {
+  /**
+   * The number of references.
+   * Never zero.
+   */
+  uintptr_t refs;
+  /**
+   * Never zero.
+   */
+  int16_t id;
+  uint32_t next;
+} Entry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * `index` is never zero.
+ */
+uint32_t lookup(Handle handle, uint64_t index, const Entry *entry);
+
+/**
+ * Creates a new entry.
+ * `refs` is never zero.
+ * Never returns zero.
+ */
+uint32_t create(uintptr_t refs);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,30 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+/// Never zero.
+using Handle = uint32_t;
+
+/// An entry of the table.
+struct Entry {
+  /// The number of references.
+  /// Never zero.
+  uintptr_t refs;
+  /// Never zero.
+  int16_t id;
+  uint32_t next;
+};
+
+extern "C" {
+
+/// `index` is never zero.
+uint32_t lookup(Handle handle, uint64_t index, const Entry *entry);
+
+/// Creates a new entry.
+/// `refs` is never zero.
+/// Never returns zero.
+uint32_t create(uintptr_t refs);
+
+} // extern "C"
@@ -0,0 +1,27 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # Never zero.
+  ctypedef uint32_t Handle;
+
+  # An entry of the table.
+  cdef struct Entry:
+    # The number of references.
+    # Never zero.
+    uintptr_t refs;
+    # Never zero.
+    int16_t id;
+    uint32_t next;
+
+  # `index` is never zero.
+  uint32_t lookup(Handle handle, uint64_t index, const Entry *entry);
+
+  # Creates a new entry.
+  # `refs` is never zero.
+  # Never returns zero.
+  uint32_t create(uintptr_t refs);
@@ -0,0 +1,28 @@
+#ifndef __clang__
+#define _Nonnull
+#define _Nullable
+#endif
+
+
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Opaque;
+
+struct Callbacks {
+  void (*_Nonnull on_event)(int32_t event);
+  void (*_Nullable on_error)(int32_t code);
+};
+
+extern "C" {
+
+void opaque_get(const Opaque *_Nonnull opaque, int32_t *_Nonnull out);
+
+Opaque *_Nullable opaque_find(const uint8_t *_Nullable name, Opaque *_Nullable hint);
+
+void callbacks_set(const Callbacks *_Nullable callbacks);
+
+} // extern "C"
@@ -0,0 +1,23 @@
+#ifndef __clang__
+#define _Nonnull
+#define _Nullable
+#endif
+
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Opaque;
+
+struct Callbacks {
+  void (*_Nonnull on_event)(int32_t event);
+  void (*_Nullable on_error)(int32_t code);
+};
+
+void opaque_get(const struct Opaque *_Nonnull opaque, int32_t *_Nonnull out);
+
+struct Opaque *_Nullable opaque_find(const uint8_t *_Nullable name, struct Opaque *_Nullable hint);
+
+void callbacks_set(const struct Callbacks *_Nullable callbacks);
@@ -0,0 +1,26 @@
+#ifndef __clang__
+#define _Nonnull
+#define _Nullable
+#endif
+
+
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Opaque:
+    pass
+
+  cdef struct Callbacks:
+    void (*on_event)(int32_t event);
+    void (*on_error)(int32_t code);
+
+  void opaque_get(const Opaque *opaque, int32_t *out);
+
+  Opaque *opaque_find(const uint8_t *name, Opaque *hint);
+
+  void callbacks_set(const Callbacks *callbacks);
@@ -0,0 +1,36 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#if ((__SIZEOF_POINTER__ == 8 || defined(_WIN64)) && (defined(_MSC_VER)))
+#define WORD_SIZE 8
+#endif
+
+#if !(defined(__x86_64__) || defined(_M_X64))
+#define WORD_SIZE 4
+#endif
+
+#if defined(DEFINE_TRACING)
+#define TRACING true
+#endif
+
+#if (defined(_WIN32))
+typedef uint8_t *Handle;
+#endif
+
+#if (defined(__unix__) || defined(__APPLE__))
+typedef int32_t Handle;
+#endif
+
+#if (defined(__linux__))
+typedef struct {
+  int32_t fd;
+} Epoll;
+#endif
+
+void root(Handle handle);
+
+#if (defined(__linux__))
+void epoll(Epoll epoll);
+#endif
@@ -0,0 +1,41 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+#if ((__SIZEOF_POINTER__ == 8 || defined(_WIN64)) && (defined(_MSC_VER)))
+static const uint32_t WORD_SIZE = 8;
+#endif
+
+#if !(defined(__x86_64__) || defined(_M_X64))
+static const uint32_t WORD_SIZE = 4;
+#endif
+
+#if defined(DEFINE_TRACING)
+static const bool TRACING = true;
+#endif
+
+#if (defined(_WIN32))
+using Handle = uint8_t*;
+#endif
+
+#if (defined(__unix__) || defined(__APPLE__))
+using Handle = int32_t;
+#endif
+
+#if (defined(__linux__))
+struct Epoll {
+  int32_t fd;
+};
+#endif
+
+extern "C" {
+
+void root(Handle handle);
+
+#if (defined(__linux__))
+void epoll(Epoll epoll);
+#endif
+
+} // extern "C"
@@ -0,0 +1,25 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const uint32_t WORD_SIZE # = 8
+
+  const uint32_t WORD_SIZE # = 4
+
+  IF DEFINE_TRACING:
+    const bool TRACING # = True
+
+  ctypedef uint8_t *Handle;
+
+  ctypedef int32_t Handle;
+
+  ctypedef struct Epoll:
+    int32_t fd;
+
+  void root(Handle handle);
+
+  void epoll(Epoll epoll);
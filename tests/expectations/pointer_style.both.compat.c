@@ -0,0 +1,25 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Node
+{
+  struct Node* next;
+  const uint8_t* name;
+  struct Node** children;
+  const uint8_t* const* names;
+  struct Node* (*visit)(const struct Node* node);
+} Node;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+extern struct Node* LAST_NODE;
+
+struct Node* node_find(const struct Node* root, const uint8_t* name);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,19 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Node Node;
+
+struct Node
+{
+  Node* next;
+  const uint8_t* name;
+  Node** children;
+  const uint8_t* const* names;
+  Node* (*visit)(const Node* node);
+};
+
+extern Node* LAST_NODE;
+
+Node* node_find(const Node* root, const uint8_t* name);
@@ -0,0 +1,24 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Node;
+
+struct Node
+{
+  Node* next;
+  const uint8_t* name;
+  Node** children;
+  const uint8_t* const* names;
+  Node* (*visit)(const Node* node);
+};
+
+extern "C" {
+
+extern Node* LAST_NODE;
+
+Node* node_find(const Node* root, const uint8_t* name);
+
+} // extern "C"
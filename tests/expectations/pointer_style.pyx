@@ -0,0 +1,18 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Node:
+    Node* next;
+    const uint8_t* name;
+    Node** children;
+    const uint8_t* const* names;
+    Node* (*visit)(const Node* node);
+
+  extern Node* LAST_NODE;
+
+  Node* node_find(const Node* root, const uint8_t* name);
@@ -0,0 +1,17 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Node
+{
+  struct Node* next;
+  const uint8_t* name;
+  struct Node** children;
+  const uint8_t* const* names;
+  struct Node* (*visit)(const struct Node* node);
+};
+
+extern struct Node* LAST_NODE;
+
+struct Node* node_find(const struct Node* root, const uint8_t* name);
@@ -0,0 +1,27 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Ordre {
+  Premiere,
+  Second,
+  Cafe,
+};
+typedef uint8_t Ordre;
+
+typedef struct {
+  int32_t type;
+  uint8_t match;
+  uint32_t grosse;
+} Type;
+
+typedef float _u0394;
+
+extern const Ordre loop;
+
+Ordre ordre_match(const Type *type, _u0394 _u03C0);
+
+void naive(Ordre ref);
+
+void type(int32_t in);
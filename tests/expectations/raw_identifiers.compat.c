@@ -0,0 +1,41 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Ordre
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Premiere,
+  Second,
+  Cafe,
+};
+#ifndef __cplusplus
+typedef uint8_t Ordre;
+#endif // __cplusplus
+
+typedef struct {
+  int32_t type;
+  uint8_t match;
+  uint32_t grosse;
+} Type;
+
+typedef float _u0394;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+extern const Ordre loop;
+
+Ordre ordre_match(const Type *type, _u0394 _u03C0);
+
+void naive(Ordre ref);
+
+void type(int32_t in);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
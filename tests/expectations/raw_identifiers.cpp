@@ -0,0 +1,31 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+enum class Ordre : uint8_t {
+  Premiere,
+  Second,
+  Cafe,
+};
+
+struct Type {
+  int32_t type;
+  uint8_t match;
+  uint32_t grosse;
+};
+
+using _u0394 = float;
+
+extern "C" {
+
+extern const Ordre loop;
+
+Ordre ordre_match(const Type *type, _u0394 _u03C0);
+
+void naive(Ordre ref);
+
+void type(int32_t in);
+
+} // extern "C"
@@ -0,0 +1,28 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum:
+    Premiere,
+    Second,
+    Cafe,
+  ctypedef uint8_t Ordre;
+
+  ctypedef struct Type:
+    int32_t type;
+    uint8_t match;
+    uint32_t grosse;
+
+  ctypedef float _u0394;
+
+  extern const Ordre loop;
+
+  Ordre ordre_match(const Type *type, _u0394 _u03C0);
+
+  void naive(Ordre ref);
+
+  void type(int32_t in_);
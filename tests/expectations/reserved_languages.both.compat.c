@@ -0,0 +1,29 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum Choice {
+  None,
+  Some,
+} Choice;
+
+typedef struct Range {
+  uint32_t from;
+  uint32_t to;
+  bool restrict_;
+  uint8_t and_;
+  uint8_t lambda[4];
+} Range;
+
+typedef void (*Callback)(const struct Range *in, uint32_t pass);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void range_check(struct Range range, enum Choice choice, Callback callback, bool is, uint32_t not_);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum {
+  None,
+  Some,
+} Choice;
+
+typedef struct {
+  uint32_t from;
+  uint32_t to;
+  bool restrict_;
+  uint8_t and_;
+  uint8_t lambda[4];
+} Range;
+
+typedef void (*Callback)(const Range *in, uint32_t pass);
+
+void range_check(Range range, Choice choice, Callback callback, bool is, uint32_t not_);
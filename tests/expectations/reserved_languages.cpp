@@ -0,0 +1,26 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+enum class Choice {
+  None,
+  Some,
+};
+
+struct Range {
+  uint32_t from;
+  uint32_t to;
+  bool restrict_;
+  uint8_t and_;
+  uint8_t lambda[4];
+};
+
+using Callback = void(*)(const Range *in, uint32_t pass);
+
+extern "C" {
+
+void range_check(Range range, Choice choice, Callback callback, bool is, uint32_t not_);
+
+} // extern "C"
@@ -0,0 +1,22 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef enum Choice:
+    None_ "None",
+    Some,
+
+  ctypedef struct Range:
+    uint32_t from_ "from";
+    uint32_t to;
+    bool restrict_;
+    uint8_t and_;
+    uint8_t lambda_ "lambda"[4];
+
+  ctypedef void (*Callback)(const Range *in_, uint32_t pass_);
+
+  void range_check(Range range, Choice choice, Callback callback, bool is_, uint32_t not_);
@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Choice {
+  None,
+  Some,
+};
+
+struct Range {
+  uint32_t from;
+  uint32_t to;
+  bool restrict_;
+  uint8_t and_;
+  uint8_t lambda[4];
+};
+
+typedef void (*Callback)(const struct Range *in, uint32_t pass);
+
+void range_check(struct Range range, enum Choice choice, Callback callback, bool is, uint32_t not_);
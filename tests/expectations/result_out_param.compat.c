@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum {
+  Success,
+  Invalid,
+  NotFound,
+} Status;
+
+typedef struct {
+  bool verbose;
+} Config;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parses the configuration in `text`.
+ * Returns 0 on success, having written the value to `*out`, or the error.
+ */
+Status config_parse(const uint8_t *text, uintptr_t len, Config *out);
+
+/**
+ * Returns 0 on success, or the error.
+ */
+Status config_check(const Config *config);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,26 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+enum class Status {
+  Success,
+  Invalid,
+  NotFound,
+};
+
+struct Config {
+  bool verbose;
+};
+
+extern "C" {
+
+/// Parses the configuration in `text`.
+/// Returns 0 on success, having written the value to `*out`, or the error.
+Status config_parse(const uint8_t *text, uintptr_t len, Config *out);
+
+/// Returns 0 on success, or the error.
+Status config_check(const Config *config);
+
+} // extern "C"
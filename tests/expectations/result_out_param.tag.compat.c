@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Status {
+  Success,
+  Invalid,
+  NotFound,
+};
+
+struct Config {
+  bool verbose;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parses the configuration in `text`.
+ * Returns 0 on success, having written the value to `*out`, or the error.
+ */
+enum Status config_parse(const uint8_t *text, uintptr_t len, struct Config *out);
+
+/**
+ * Returns 0 on success, or the error.
+ */
+enum Status config_check(const struct Config *config);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,22 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum Status:
+    Success,
+    Invalid,
+    NotFound,
+
+  cdef struct Config:
+    bool verbose;
+
+  # Parses the configuration in `text`.
+  # Returns 0 on success, having written the value to `*out`, or the error.
+  Status config_parse(const uint8_t *text, uintptr_t len, Config *out);
+
+  # Returns 0 on success, or the error.
+  Status config_check(const Config *config);
@@ -0,0 +1,62 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum Status {
+  Invalid,
+  NotFound,
+} Status;
+
+typedef struct Config {
+  bool verbose;
+} Config;
+
+/**
+ * The result of `config_parse`.
+ */
+typedef enum ConfigParseResult_Tag {
+  ConfigParseResult_Ok,
+  ConfigParseResult_Err,
+} ConfigParseResult_Tag;
+
+typedef struct ConfigParseResult {
+  ConfigParseResult_Tag tag;
+  union {
+    struct {
+      struct Config ok;
+    };
+    struct {
+      enum Status err;
+    };
+  };
+} ConfigParseResult;
+
+/**
+ * The result of `config_check`.
+ */
+typedef enum ConfigCheckResult_Tag {
+  ConfigCheckResult_Ok,
+  ConfigCheckResult_Err,
+} ConfigCheckResult_Tag;
+
+typedef struct ConfigCheckResult {
+  ConfigCheckResult_Tag tag;
+  union {
+    struct {
+      enum Status err;
+    };
+  };
+} ConfigCheckResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+struct ConfigParseResult config_parse(const uint8_t *text, uintptr_t len);
+
+struct ConfigCheckResult config_check(const struct Config *config);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,62 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum {
+  Invalid,
+  NotFound,
+} Status;
+
+typedef struct {
+  bool verbose;
+} Config;
+
+/**
+ * The result of `config_parse`.
+ */
+typedef enum {
+  ConfigParseResult_Ok,
+  ConfigParseResult_Err,
+} ConfigParseResult_Tag;
+
+typedef struct {
+  ConfigParseResult_Tag tag;
+  union {
+    struct {
+      Config ok;
+    };
+    struct {
+      Status err;
+    };
+  };
+} ConfigParseResult;
+
+/**
+ * The result of `config_check`.
+ */
+typedef enum {
+  ConfigCheckResult_Ok,
+  ConfigCheckResult_Err,
+} ConfigCheckResult_Tag;
+
+typedef struct {
+  ConfigCheckResult_Tag tag;
+  union {
+    struct {
+      Status err;
+    };
+  };
+} ConfigCheckResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+ConfigParseResult config_parse(const uint8_t *text, uintptr_t len);
+
+ConfigCheckResult config_check(const Config *config);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,61 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+enum class Status {
+  Invalid,
+  NotFound,
+};
+
+struct Config {
+  bool verbose;
+};
+
+/// The result of `config_parse`.
+struct ConfigParseResult {
+  enum class Tag {
+    ConfigParseResult_Ok,
+    ConfigParseResult_Err,
+  };
+
+  struct ConfigParseResult_Ok_Body {
+    Config _0;
+  };
+
+  struct ConfigParseResult_Err_Body {
+    Status _0;
+  };
+
+  Tag tag;
+  union {
+    ConfigParseResult_Ok_Body ok;
+    ConfigParseResult_Err_Body err;
+  };
+};
+
+/// The result of `config_check`.
+struct ConfigCheckResult {
+  enum class Tag {
+    ConfigCheckResult_Ok,
+    ConfigCheckResult_Err,
+  };
+
+  struct ConfigCheckResult_Err_Body {
+    Status _0;
+  };
+
+  Tag tag;
+  union {
+    ConfigCheckResult_Err_Body err;
+  };
+};
+
+extern "C" {
+
+ConfigParseResult config_parse(const uint8_t *text, uintptr_t len);
+
+ConfigCheckResult config_check(const Config *config);
+
+} // extern "C"
@@ -0,0 +1,37 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef enum Status:
+    Invalid,
+    NotFound,
+
+  ctypedef struct Config:
+    bool verbose;
+
+  # The result of `config_parse`.
+  ctypedef enum ConfigParseResult_Tag:
+    ConfigParseResult_Ok,
+    ConfigParseResult_Err,
+
+  ctypedef struct ConfigParseResult:
+    ConfigParseResult_Tag tag;
+    Config ok;
+    Status err;
+
+  # The result of `config_check`.
+  ctypedef enum ConfigCheckResult_Tag:
+    ConfigCheckResult_Ok,
+    ConfigCheckResult_Err,
+
+  ctypedef struct ConfigCheckResult:
+    ConfigCheckResult_Tag tag;
+    Status err;
+
+  ConfigParseResult config_parse(const uint8_t *text, uintptr_t len);
+
+  ConfigCheckResult config_check(const Config *config);
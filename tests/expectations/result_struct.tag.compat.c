@@ -0,0 +1,62 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Status {
+  Invalid,
+  NotFound,
+};
+
+struct Config {
+  bool verbose;
+};
+
+/**
+ * The result of `config_parse`.
+ */
+enum ConfigParseResult_Tag {
+  ConfigParseResult_Ok,
+  ConfigParseResult_Err,
+};
+
+struct ConfigParseResult {
+  enum ConfigParseResult_Tag tag;
+  union {
+    struct {
+      struct Config ok;
+    };
+    struct {
+      enum Status err;
+    };
+  };
+};
+
+/**
+ * The result of `config_check`.
+ */
+enum ConfigCheckResult_Tag {
+  ConfigCheckResult_Ok,
+  ConfigCheckResult_Err,
+};
+
+struct ConfigCheckResult {
+  enum ConfigCheckResult_Tag tag;
+  union {
+    struct {
+      enum Status err;
+    };
+  };
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+struct ConfigParseResult config_parse(const uint8_t *text, uintptr_t len);
+
+struct ConfigCheckResult config_check(const struct Config *config);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,13 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Handle {
+  uint8_t _pad_internals[8];
+  uint8_t _pad_cache[16];
+  uint32_t id;
+  uint32_t flags;
+} Handle;
+
+uint32_t handle_flags(const struct Handle *handle);
@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  uint8_t _pad_internals[8];
+  uint8_t _pad_cache[16];
+  uint32_t id;
+  uint32_t flags;
+} Handle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+uint32_t handle_flags(const Handle *handle);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,18 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Handle {
+  uint8_t _pad_internals[8];
+  uint8_t _pad_cache[16];
+  uint32_t id;
+  uint32_t flags;
+};
+
+extern "C" {
+
+uint32_t handle_flags(const Handle *handle);
+
+} // extern "C"
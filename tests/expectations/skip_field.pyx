@@ -0,0 +1,15 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Handle:
+    uint8_t _pad_internals[8];
+    uint8_t _pad_cache[16];
+    uint32_t id;
+    uint32_t flags;
+
+  uint32_t handle_flags(const Handle *handle);
@@ -0,0 +1,40 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define CAGE_SIZE 2
+
+#define ZOO_SIZE 10
+
+typedef struct Size {
+  uint32_t width;
+  uint32_t height;
+} Size;
+
+typedef struct Animal {
+  struct Size size;
+} Animal;
+
+typedef struct Cage {
+  struct Size size;
+  struct Animal *animal;
+} Cage;
+
+typedef uint32_t KeeperId;
+
+typedef struct Keeper {
+  KeeperId id;
+  struct Cage *cage;
+} Keeper;
+
+typedef struct Zoo {
+  struct Cage cage;
+  struct Keeper keeper;
+} Zoo;
+
+struct Cage *animal_cage(const struct Animal *animal);
+
+uint32_t cage_area(struct Size size);
+
+void zoo_open(struct Zoo *zoo);
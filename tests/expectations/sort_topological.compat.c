@@ -0,0 +1,48 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define CAGE_SIZE 2
+
+#define ZOO_SIZE 10
+
+typedef struct {
+  uint32_t width;
+  uint32_t height;
+} Size;
+
+typedef struct {
+  Size size;
+} Animal;
+
+typedef struct {
+  Size size;
+  Animal *animal;
+} Cage;
+
+typedef uint32_t KeeperId;
+
+typedef struct {
+  KeeperId id;
+  Cage *cage;
+} Keeper;
+
+typedef struct {
+  Cage cage;
+  Keeper keeper;
+} Zoo;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+Cage *animal_cage(const Animal *animal);
+
+uint32_t cage_area(Size size);
+
+void zoo_open(Zoo *zoo);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,45 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+static const uint32_t CAGE_SIZE = 2;
+
+static const uint32_t ZOO_SIZE = 10;
+
+struct Size {
+  uint32_t width;
+  uint32_t height;
+};
+
+struct Animal {
+  Size size;
+};
+
+struct Cage {
+  Size size;
+  Animal *animal;
+};
+
+using KeeperId = uint32_t;
+
+struct Keeper {
+  KeeperId id;
+  Cage *cage;
+};
+
+struct Zoo {
+  Cage cage;
+  Keeper keeper;
+};
+
+extern "C" {
+
+Cage *animal_cage(const Animal *animal);
+
+uint32_t cage_area(Size size);
+
+void zoo_open(Zoo *zoo);
+
+} // extern "C"
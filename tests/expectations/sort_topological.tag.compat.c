@@ -0,0 +1,48 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define CAGE_SIZE 2
+
+#define ZOO_SIZE 10
+
+struct Size {
+  uint32_t width;
+  uint32_t height;
+};
+
+struct Animal {
+  struct Size size;
+};
+
+struct Cage {
+  struct Size size;
+  struct Animal *animal;
+};
+
+typedef uint32_t KeeperId;
+
+struct Keeper {
+  KeeperId id;
+  struct Cage *cage;
+};
+
+struct Zoo {
+  struct Cage cage;
+  struct Keeper keeper;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+struct Cage *animal_cage(const struct Animal *animal);
+
+uint32_t cage_area(struct Size size);
+
+void zoo_open(struct Zoo *zoo);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,38 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const uint32_t CAGE_SIZE # = 2
+
+  const uint32_t ZOO_SIZE # = 10
+
+  cdef struct Size:
+    uint32_t width;
+    uint32_t height;
+
+  cdef struct Animal:
+    Size size;
+
+  cdef struct Cage:
+    Size size;
+    Animal *animal;
+
+  ctypedef uint32_t KeeperId;
+
+  cdef struct Keeper:
+    KeeperId id;
+    Cage *cage;
+
+  cdef struct Zoo:
+    Cage cage;
+    Keeper keeper;
+
+  Cage *animal_cage(const Animal *animal);
+
+  uint32_t cage_area(Size size);
+
+  void zoo_open(Zoo *zoo);
@@ -0,0 +1,26 @@
+#ifndef __clang__
+#define _Nonnull
+#define _Nullable
+#define swift_name(name)
+#endif
+
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Opaque Opaque;
+
+typedef struct Point {
+  int32_t x;
+  int32_t y;
+} Point;
+
+float Point_length(const struct Point *_Nonnull self) __attribute__((swift_name("Point.length(self:)")));
+
+struct Point *_Nonnull point_new(int32_t x,
+                                 int32_t y) __attribute__((swift_name("point_new(x:y:)")));
+
+const struct Opaque *_Nullable opaque_find(const uint8_t *_Nullable name,
+                                           struct Opaque *_Nullable fallback) __attribute__((swift_name("opaque_find(name:fallback:)")));
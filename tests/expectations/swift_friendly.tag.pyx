@@ -0,0 +1,27 @@
+#ifndef __clang__
+#define _Nonnull
+#define _Nullable
+#define swift_name(name)
+#endif
+
+
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Opaque:
+    pass
+
+  cdef struct Point:
+    int32_t x;
+    int32_t y;
+
+  float Point_length(const Point *self);
+
+  Point *point_new(int32_t x, int32_t y);
+
+  const Opaque *opaque_find(const uint8_t *name, Opaque *fallback);
@@ -0,0 +1,30 @@
+#ifndef TEMPLATE_H
+#define TEMPLATE_H
+
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+#define TEMPLATE_VERSION 2
+
+/// Written in place of {{typedefs}}, before {{functions}} and {{unknown}}.
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+static const uint32_t LIMIT = 16;
+
+static const Point ORIGIN = Point{ /* .x = */ 0, /* .y = */ 0 };
+
+extern "C" {
+
+Point point_scale(Point point, int32_t factor);
+
+} // extern "C"
+
+#endif
+
+/* {{unterminated */
@@ -0,0 +1,31 @@
+#ifndef TEMPLATE_H
+#define TEMPLATE_H
+
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+#define TEMPLATE_VERSION 2
+
+cdef extern from *:
+
+  # Written in place of {{typedefs}}, before {{functions}} and {{unknown}}.
+  ctypedef struct Point:
+    int32_t x;
+    int32_t y;
+
+cdef extern from *:
+
+  const uint32_t LIMIT # = 16
+
+  const Point ORIGIN # = <Point>{ 0, 0 }
+
+cdef extern from *:
+
+  Point point_scale(Point point, int32_t factor);
+
+#endif
+
+/* {{unterminated */
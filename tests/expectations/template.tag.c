@@ -0,0 +1,27 @@
+#ifndef TEMPLATE_H
+#define TEMPLATE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define TEMPLATE_VERSION 2
+
+/**
+ * Written in place of {{typedefs}}, before {{functions}} and {{unknown}}.
+ */
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+#define LIMIT 16
+
+#define ORIGIN (Point){ .x = 0, .y = 0 }
+
+struct Point point_scale(struct Point point, int32_t factor);
+
+#endif
+
+/* {{unterminated */
@@ -0,0 +1,12 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buffer Buffer;
+
+typedef struct Status {
+  int32_t code;
+} Status;
+
+bool buffer_save(struct Buffer *buffer, struct Status **status);
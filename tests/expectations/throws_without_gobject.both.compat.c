@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buffer Buffer;
+
+typedef struct Status {
+  int32_t code;
+} Status;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+bool buffer_save(struct Buffer *buffer, struct Status **status);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,12 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buffer Buffer;
+
+typedef struct {
+  int32_t code;
+} Status;
+
+bool buffer_save(Buffer *buffer, Status **status);
@@ -0,0 +1,17 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Buffer;
+
+struct Status {
+  int32_t code;
+};
+
+extern "C" {
+
+bool buffer_save(Buffer *buffer, Status **status);
+
+} // extern "C"
@@ -0,0 +1,12 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Buffer;
+
+struct Status {
+  int32_t code;
+};
+
+bool buffer_save(struct Buffer *buffer, struct Status **status);
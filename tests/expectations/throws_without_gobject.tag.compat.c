@@ -0,0 +1,20 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Buffer;
+
+struct Status {
+  int32_t code;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+bool buffer_save(struct Buffer *buffer, struct Status **status);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
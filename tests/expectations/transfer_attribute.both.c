@@ -0,0 +1,14 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buffer Buffer;
+
+struct Buffer *buffer_new(__attribute__((noescape)) const uint8_t *name);
+
+void buffer_free(struct Buffer *buffer);
+
+const uint8_t *buffer_data(const struct Buffer *buffer);
+
+uint8_t **buffer_into_parts(struct Buffer *buffer);
@@ -0,0 +1,22 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buffer Buffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+Buffer *buffer_new(__attribute__((noescape)) const uint8_t *name);
+
+void buffer_free(Buffer *buffer);
+
+const uint8_t *buffer_data(const Buffer *buffer);
+
+uint8_t **buffer_into_parts(Buffer *buffer);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,25 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+template<typename T = void>
+struct Box;
+
+struct Buffer;
+
+template<typename T = void>
+struct Option;
+
+extern "C" {
+
+Box<Buffer> buffer_new(__attribute__((noescape)) const uint8_t *name);
+
+void buffer_free(Option<Box<Buffer>> buffer);
+
+const uint8_t *buffer_data(const Buffer *buffer);
+
+uint8_t **buffer_into_parts(Buffer *buffer);
+
+} // extern "C"
@@ -0,0 +1,18 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Buffer:
+    pass
+
+  Buffer *buffer_new(const uint8_t *name);
+
+  void buffer_free(Buffer *buffer);
+
+  const uint8_t *buffer_data(const Buffer *buffer);
+
+  uint8_t **buffer_into_parts(Buffer *buffer);
@@ -0,0 +1,22 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Buffer Buffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/* transfer full */ Buffer *buffer_new(/* transfer none */ const uint8_t *name);
+
+void buffer_free(/* transfer full */ Buffer *buffer);
+
+/* transfer none */ const uint8_t *buffer_data(const Buffer *buffer);
+
+/* transfer container */ uint8_t **buffer_into_parts(/* transfer full */ Buffer *buffer);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
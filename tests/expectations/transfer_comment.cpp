@@ -0,0 +1,25 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+template<typename T = void>
+struct Box;
+
+struct Buffer;
+
+template<typename T = void>
+struct Option;
+
+extern "C" {
+
+/* transfer full */ Box<Buffer> buffer_new(/* transfer none */ const uint8_t *name);
+
+void buffer_free(/* transfer full */ Option<Box<Buffer>> buffer);
+
+/* transfer none */ const uint8_t *buffer_data(const Buffer *buffer);
+
+/* transfer container */ uint8_t **buffer_into_parts(/* transfer full */ Buffer *buffer);
+
+} // extern "C"
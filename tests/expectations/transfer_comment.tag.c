@@ -0,0 +1,14 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Buffer;
+
+/* transfer full */ struct Buffer *buffer_new(/* transfer none */ const uint8_t *name);
+
+void buffer_free(/* transfer full */ struct Buffer *buffer);
+
+/* transfer none */ const uint8_t *buffer_data(const struct Buffer *buffer);
+
+/* transfer container */ uint8_t **buffer_into_parts(/* transfer full */ struct Buffer *buffer);
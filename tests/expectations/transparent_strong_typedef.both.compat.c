@@ -0,0 +1,32 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A distance, in meters.
+ */
+typedef struct Meters {
+  double _0;
+} Meters;
+
+typedef struct Seconds {
+  double value;
+} Seconds;
+#define Seconds_MINUTE (Seconds){ .value = 60.0 }
+
+typedef uint32_t Count;
+
+typedef struct Id_Meters {
+  uint64_t id;
+} Id_Meters;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+double speed(struct Meters distance, struct Seconds time, Count count, struct Id_Meters id);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
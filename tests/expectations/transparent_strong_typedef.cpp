@@ -0,0 +1,28 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+/// A distance, in meters.
+struct Meters {
+  double _0;
+};
+
+struct Seconds {
+  double value;
+};
+static const Seconds Seconds_MINUTE = Seconds{ /* .value = */ 60.0 };
+
+using Count = uint32_t;
+
+template<typename T>
+struct Id {
+  uint64_t id;
+};
+
+extern "C" {
+
+double speed(Meters distance, Seconds time, Count count, Id<Meters> id);
+
+} // extern "C"
@@ -0,0 +1,22 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # A distance, in meters.
+  ctypedef struct Meters:
+    double _0;
+
+  ctypedef struct Seconds:
+    double value;
+  const Seconds Seconds_MINUTE # = <Seconds>{ 60.0 }
+
+  ctypedef uint32_t Count;
+
+  ctypedef struct Id_Meters:
+    uint64_t id;
+
+  double speed(Meters distance, Seconds time, Count count, Id_Meters id);
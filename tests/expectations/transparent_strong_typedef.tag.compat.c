@@ -0,0 +1,32 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A distance, in meters.
+ */
+struct Meters {
+  double _0;
+};
+
+struct Seconds {
+  double value;
+};
+#define Seconds_MINUTE (Seconds){ .value = 60.0 }
+
+typedef uint32_t Count;
+
+struct Id_Meters {
+  uint64_t id;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+double speed(struct Meters distance, struct Seconds time, Count count, struct Id_Meters id);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
@@ -0,0 +1,22 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Slot {
+  Point point;
+  Point owned;
+  uint8_t flags;
+};
+
+extern "C" {
+
+void root(const Slot *slot, Point *point);
+
+} // extern "C"
@@ -0,0 +1,17 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Slot {
+  struct Point point;
+  struct Point owned;
+  uint8_t flags;
+};
+
+void root(const struct Slot *slot, struct Point *point);
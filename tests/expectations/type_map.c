@@ -0,0 +1,15 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#define gchar char
+#define gsize size_t
+#define gint32 int32_t
+
+typedef struct {
+  gchar *data;
+  gsize len;
+  uint32_t flags;
+} Buffer;
+
+gsize buffer_fill(Buffer *buffer, gchar value, gint32 count);
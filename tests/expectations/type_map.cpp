@@ -0,0 +1,20 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#define gchar char
+#define gsize size_t
+#define gint32 int32_t
+
+struct Buffer {
+  gchar *data;
+  gsize len;
+  uint32_t flags;
+};
+
+extern "C" {
+
+gsize buffer_fill(Buffer *buffer, gchar value, gint32 count);
+
+} // extern "C"
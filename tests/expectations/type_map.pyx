@@ -0,0 +1,17 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+#define gchar char
+#define gsize size_t
+#define gint32 int32_t
+
+cdef extern from *:
+
+  ctypedef struct Buffer:
+    gchar *data;
+    gsize len;
+    uint32_t flags;
+
+  gsize buffer_fill(Buffer *buffer, gchar value, gint32 count);
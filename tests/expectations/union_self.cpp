@@ -4,6 +4,8 @@
 #include <ostream>
 #include <new>
 
+union Bar;
+
 template<typename T>
 struct Foo {
   const int32_t *something;
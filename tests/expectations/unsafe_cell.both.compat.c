@@ -0,0 +1,19 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Counter {
+  uint32_t value;
+  uint32_t limits[2];
+} Counter;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(const struct Counter *counter, const int32_t *value);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
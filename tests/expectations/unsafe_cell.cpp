@@ -0,0 +1,16 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Counter {
+  uint32_t value;
+  uint32_t limits[2];
+};
+
+extern "C" {
+
+void root(const Counter *counter, const int32_t *value);
+
+} // extern "C"
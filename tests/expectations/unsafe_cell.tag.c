@@ -0,0 +1,11 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Counter {
+  uint32_t value;
+  uint32_t limits[2];
+};
+
+void root(const struct Counter *counter, const int32_t *value);
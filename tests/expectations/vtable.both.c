@@ -0,0 +1,42 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Codec {
+  int32_t level;
+} Codec;
+
+/**
+ * The functions of the CodecOps vtable.
+ */
+typedef struct CodecOps {
+  struct Codec *(*codec_open)(int32_t level);
+  int32_t (*codec_encode)(struct Codec *codec, const uint8_t *data, uintptr_t len);
+#if defined(CODEC_FLUSH)
+  void (*codec_flush)(struct Codec *codec)
+#endif
+  ;
+  void (*codec_close)(struct Codec *codec);
+} CodecOps;
+
+struct Codec *codec_open(int32_t level);
+
+int32_t codec_encode(struct Codec *codec, const uint8_t *data, uintptr_t len);
+
+#if defined(CODEC_FLUSH)
+void codec_flush(struct Codec *codec);
+#endif
+
+void codec_close(struct Codec *codec);
+
+uint32_t codec_version(void);
+
+static const CodecOps codec_ops = {
+  codec_open,
+  codec_encode,
+#if defined(CODEC_FLUSH)
+  codec_flush,
+#endif
+  codec_close,
+};
@@ -0,0 +1,50 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t level;
+} Codec;
+
+/**
+ * The functions of the CodecOps vtable.
+ */
+typedef struct {
+  Codec *(*codec_open)(int32_t level);
+  int32_t (*codec_encode)(Codec *codec, const uint8_t *data, uintptr_t len);
+#if defined(CODEC_FLUSH)
+  void (*codec_flush)(Codec *codec)
+#endif
+  ;
+  void (*codec_close)(Codec *codec);
+} CodecOps;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+Codec *codec_open(int32_t level);
+
+int32_t codec_encode(Codec *codec, const uint8_t *data, uintptr_t len);
+
+#if defined(CODEC_FLUSH)
+void codec_flush(Codec *codec);
+#endif
+
+void codec_close(Codec *codec);
+
+uint32_t codec_version(void);
+
+static const CodecOps codec_ops = {
+  codec_open,
+  codec_encode,
+#if defined(CODEC_FLUSH)
+  codec_flush,
+#endif
+  codec_close,
+};
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
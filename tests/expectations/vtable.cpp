@@ -0,0 +1,45 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Codec {
+  int32_t level;
+};
+
+/// The functions of the CodecOps vtable.
+struct CodecOps {
+  Codec *(*codec_open)(int32_t level);
+  int32_t (*codec_encode)(Codec *codec, const uint8_t *data, uintptr_t len);
+#if defined(CODEC_FLUSH)
+  void (*codec_flush)(Codec *codec)
+#endif
+  ;
+  void (*codec_close)(Codec *codec);
+};
+
+extern "C" {
+
+Codec *codec_open(int32_t level);
+
+int32_t codec_encode(Codec *codec, const uint8_t *data, uintptr_t len);
+
+#if defined(CODEC_FLUSH)
+void codec_flush(Codec *codec);
+#endif
+
+void codec_close(Codec *codec);
+
+uint32_t codec_version();
+
+static const CodecOps codec_ops = {
+  codec_open,
+  codec_encode,
+#if defined(CODEC_FLUSH)
+  codec_flush,
+#endif
+  codec_close,
+};
+
+} // extern "C"
@@ -0,0 +1,28 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Codec:
+    int32_t level;
+
+  # The functions of the CodecOps vtable.
+  cdef struct CodecOps:
+    Codec *(*codec_open)(int32_t level);
+    int32_t (*codec_encode)(Codec *codec, const uint8_t *data, uintptr_t len);
+    void (*codec_flush)(Codec *codec);
+    void (*codec_close)(Codec *codec);
+
+  Codec *codec_open(int32_t level);
+
+  int32_t codec_encode(Codec *codec, const uint8_t *data, uintptr_t len);
+
+  IF CODEC_FLUSH:
+    void codec_flush(Codec *codec);
+
+  void codec_close(Codec *codec);
+
+  uint32_t codec_version();
@@ -0,0 +1,18 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __EMSCRIPTEN__
+#include <emscripten.h>
+#endif
+#ifndef EMSCRIPTEN_KEEPALIVE
+#define EMSCRIPTEN_KEEPALIVE __attribute__((used))
+#endif
+
+EMSCRIPTEN_KEEPALIVE int32_t buffer_len(uint32_t len);
+
+__attribute__((import_module("env"), import_name("log_message")))
+extern void log_message(uint32_t level);
+
+__attribute__((import_module("host"), import_name("now"))) extern double host_now(void);
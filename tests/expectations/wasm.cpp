@@ -0,0 +1,23 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+#ifdef __EMSCRIPTEN__
+#include <emscripten.h>
+#endif
+#ifndef EMSCRIPTEN_KEEPALIVE
+#define EMSCRIPTEN_KEEPALIVE __attribute__((used))
+#endif
+
+extern "C" {
+
+EMSCRIPTEN_KEEPALIVE int32_t buffer_len(uint32_t len);
+
+__attribute__((import_module("env"), import_name("log_message")))
+extern void log_message(uint32_t level);
+
+__attribute__((import_module("host"), import_name("now"))) extern double host_now();
+
+} // extern "C"
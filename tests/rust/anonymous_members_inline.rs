@@ -0,0 +1,19 @@
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Event {
+    pub kind: u32,
+    pub __bindgen_anon_1: Event__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union Event__bindgen_ty_1 {
+    pub key: u32,
+    pub __bindgen_anon_1: Event__bindgen_ty_1__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Event__bindgen_ty_1__bindgen_ty_1 {
+    pub x: i32,
+    pub y: i32,
+}
+#[no_mangle] pub extern "C" fn handle(e: *const Event) {}
@@ -0,0 +1,10 @@
+#[repr(C)]
+struct MyFoo {}
+
+impl MyFoo {
+    pub const GA: i32 = 10;
+    pub const MaxLen: u32 = 7;
+}
+
+#[no_mangle]
+pub extern "C" fn root(x: MyFoo) { }
@@ -0,0 +1,31 @@
+/// cbindgen:attributes=__attribute__((aligned(64)))
+#[repr(C)]
+pub struct CacheLine {
+    data: [u8; 64],
+}
+
+/// cbindgen:attributes=__attribute__((aligned(8)))
+#[repr(C)]
+pub union Value {
+    integer: i64,
+    real: f64,
+}
+
+/// cbindgen:attributes=__attribute__((unused))
+#[repr(C)]
+pub enum Kind {
+    A,
+    B,
+}
+
+/// cbindgen:attributes=__attribute__((aligned(16)))
+pub type Counter = u64;
+
+/// cbindgen:attributes=__attribute__((visibility("hidden")))
+#[no_mangle]
+pub static COUNTER: Counter = 0;
+
+/// cbindgen:attributes=__attribute__((hot))
+#[deprecated]
+#[no_mangle]
+pub extern "C" fn process(line: *mut CacheLine, value: Value, kind: Kind) {}
@@ -0,0 +1,35 @@
+/// A read-only mirror of a status register.
+#[repr(C)]
+pub struct Status {
+    /// cbindgen:bitfield=1
+    ready: u32,
+    /// cbindgen:bitfield=3
+    mode: u32,
+    /// cbindgen:bitfield=28
+    count: u32,
+    /// cbindgen:bitfield=1
+    enabled: bool,
+    /// cbindgen:bitfield=4
+    level: u8,
+    /// cbindgen:bitfield=4
+    channel: u8,
+}
+
+#[repr(C)]
+pub struct Invalid {
+    /// cbindgen:bitfield=3
+    ratio: f32,
+    /// cbindgen:bitfield=9
+    wide: u8,
+    /// cbindgen:bitfield=0
+    empty: u16,
+    /// cbindgen:bitfield=4
+    low: u8,
+    /// cbindgen:bitfield=6
+    straddling: u8,
+    /// cbindgen:bitfield=2
+    mixed: u16,
+}
+
+#[no_mangle]
+pub extern "C" fn root(status: Status, invalid: Invalid) {}
@@ -0,0 +1,22 @@
+bitflags! {
+    /// Constants shared by multiple CSS Box Alignment properties
+    #[repr(C)]
+    pub struct AlignFlags: u8 {
+        /// 'normal'
+        const NORMAL = 1;
+        /// 'start'
+        const START = 1 << 1;
+        /// 'end'
+        const END = 1 << 2;
+    }
+}
+
+/// cbindgen:bitflags
+#[repr(u32)]
+pub enum DebugFlags {
+    Verbose = 1,
+    Trace = 1 << 1,
+}
+
+#[no_mangle]
+pub extern "C" fn root(flags: AlignFlags, debug: DebugFlags) {}
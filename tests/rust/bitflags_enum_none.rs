@@ -0,0 +1,19 @@
+bitflags! {
+    #[repr(C)]
+    pub struct OpenFlags: u32 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+        const READ_WRITE = Self::READ.bits | Self::WRITE.bits;
+    }
+}
+
+bitflags! {
+    #[repr(C)]
+    pub struct WatchFlags: u8 {
+        const CREATE = 1;
+        const DELETE = 1 << 1;
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn watch(open: OpenFlags, watch: WatchFlags) {}
@@ -0,0 +1,28 @@
+bitflags! {
+    /// Constants shared by multiple CSS Box Alignment properties
+    #[repr(C)]
+    pub struct AlignFlags: u8 {
+        /// 'normal'
+        const NORMAL = 1;
+        /// 'start'
+        const START = 1 << 1;
+    }
+}
+
+/// cbindgen:bitflags
+#[repr(u32)]
+pub enum DebugFlags {
+    Verbose = 1,
+    Trace = 1 << 1,
+}
+
+/// cbindgen:bitflags
+/// cbindgen:flag-operators=false
+#[repr(u16)]
+pub enum LogFlags {
+    Stdout = 1,
+    Stderr = 1 << 1,
+}
+
+#[no_mangle]
+pub extern "C" fn root(flags: AlignFlags, debug: DebugFlags, log: LogFlags) {}
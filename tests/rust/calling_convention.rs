@@ -0,0 +1,34 @@
+pub type Callback = extern "stdcall" fn(i32) -> i32;
+
+#[repr(C)]
+pub struct Handlers {
+    on_event: Option<extern "fastcall" fn(u32)>,
+    on_error: extern "C" fn(i32),
+}
+
+#[no_mangle]
+pub extern "C" fn c_function(callback: Callback) {}
+
+#[no_mangle]
+pub extern "cdecl" fn cdecl_function(handlers: *const Handlers) {}
+
+#[no_mangle]
+pub extern "stdcall" fn stdcall_function(value: i32) -> *mut i32 {
+    std::ptr::null_mut()
+}
+
+#[no_mangle]
+pub extern "fastcall" fn fastcall_function(first: u32, second: u32) -> u32 {
+    first + second
+}
+
+#[no_mangle]
+pub extern "system" fn system_function() {}
+
+/// cbindgen:calling-convention=cdecl
+#[no_mangle]
+pub extern "stdcall" fn annotated_function() {}
+
+extern "stdcall" {
+    fn imported_function(value: i32);
+}
@@ -0,0 +1,23 @@
+pub const LEN: u32 = 4;
+pub const MASK: u32 = !LEN;
+pub const ENABLED: bool = true;
+pub const DISABLED: bool = !ENABLED;
+pub const MAX_LEN: u32 = u32::MAX;
+pub const MIN_OFFSET: i16 = i16::MIN + 1;
+pub const SIZE: u32 = Buffer::CAPACITY * 2 + 1;
+pub const WRAPPED: u8 = (Buffer::CAPACITY + 250) as u8;
+
+#[repr(C)]
+pub struct Buffer {
+    len: u32,
+}
+
+impl Buffer {
+    pub const CAPACITY: u32 = LEN * 4;
+    pub const DOUBLE_CAPACITY: u32 = Self::CAPACITY * 2;
+    pub const HALF_MAX: u64 = u64::MAX >> 1;
+    pub const FULL: u64 = u64::MAX;
+}
+
+#[no_mangle]
+pub extern "C" fn root(buffer: Buffer) {}
@@ -0,0 +1,14 @@
+#[repr(C)]
+pub struct Buf<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+#[repr(C)]
+pub struct Pair<T, const N: usize> {
+    items: [T; N],
+    buf: Buf<N>,
+}
+
+#[no_mangle]
+pub extern "C" fn root(small: Buf<4>, big: Buf<64>, pair: Pair<u32, 2>) {}
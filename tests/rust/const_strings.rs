@@ -0,0 +1,16 @@
+pub const GREETING: &str = "hello \"world\"\n\t\\ é??=";
+pub const EMPTY: &'static str = "";
+pub const MAGIC: &[u8] = b"\x7fELF\x00\xff";
+pub const TAG: &[u8; 4] = b"RIFF";
+
+#[repr(C)]
+pub struct Format {
+    version: u32,
+}
+
+impl Format {
+    pub const NAME: &'static str = "format";
+}
+
+#[no_mangle]
+pub extern "C" fn root(format: Format) {}
@@ -0,0 +1,48 @@
+/// A point on the screen.
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+    visible: bool,
+}
+
+pub struct Buffer;
+
+/// The mode of a buffer.
+#[repr(u8)]
+pub enum Mode {
+    Read,
+    Write = 4,
+    Append,
+}
+
+#[repr(C)]
+pub union Value {
+    integer: i64,
+    real: f64,
+}
+
+pub type Offset = u64;
+
+pub const MAX_NAME_LEN: u32 = 16;
+
+pub type Callback = extern "C" fn(buffer: *mut Buffer, data: *mut std::ffi::c_void);
+
+/// Creates a buffer named `name`.
+#[no_mangle]
+pub extern "C" fn buffer_new(name: *const std::os::raw::c_char, mode: Mode) -> *mut Buffer {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_seek(buffer: &mut Buffer, offset: Offset, origin: Point) -> bool {
+    false
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_get(buffer: &Buffer, event: u32, value: *mut Value) -> f64 {
+    0.0
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_watch(buffer: &Buffer, callback: Callback) {}
@@ -0,0 +1,5 @@
+/// Imported from the library named after the crate.
+#[no_mangle]
+pub extern "C" fn answer() -> u32 {
+    42
+}
@@ -0,0 +1,43 @@
+#[deprecated]
+#[repr(C)]
+pub struct DeprecatedStruct {
+    a: i32,
+}
+
+#[deprecated(note = "Use `Flags` instead")]
+#[repr(u8)]
+pub enum DeprecatedEnum {
+    A,
+    B,
+}
+
+#[deprecated = "Say \"hello\" instead"]
+#[repr(C)]
+pub enum DeprecatedEnumWithData {
+    Foo(i32),
+    Bar { x: u32 },
+}
+
+#[deprecated(since = "0.2.0")]
+pub type DeprecatedAlias = u32;
+
+#[deprecated(since = "0.2.0", note = "Use `new_function` instead")]
+#[no_mangle]
+pub extern "C" fn deprecated_function() {}
+
+#[deprecated]
+#[no_mangle]
+pub extern "C" fn deprecated_with_struct(
+    a: DeprecatedStruct,
+    b: DeprecatedEnum,
+    c: *const DeprecatedEnumWithData,
+    d: DeprecatedAlias,
+) {
+}
+
+#[deprecated(note = "Use `NEW_COUNTER` instead")]
+#[no_mangle]
+pub static mut DEPRECATED_COUNTER: u32 = 0;
+
+#[no_mangle]
+pub extern "C" fn new_function() {}
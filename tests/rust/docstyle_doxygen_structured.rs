@@ -0,0 +1,25 @@
+/// Copies `len` bytes from `src` to `dest`.
+///
+/// # Arguments
+///
+/// * `dest` - The buffer to copy to, which must hold `len` bytes.
+/// * `src` - The buffer to copy from.
+///   It may not overlap `dest`.
+/// * `len`: The number of bytes to copy.
+///
+/// # Returns
+///
+/// The number of bytes copied,
+/// which is always `len`.
+///
+/// # Safety
+///
+/// Both pointers must be valid.
+///
+/// # Examples
+///
+/// Some prose.
+#[no_mangle]
+pub unsafe extern "C" fn copy(dest: *mut u8, src: *const u8, len: usize) -> usize {
+    len
+}
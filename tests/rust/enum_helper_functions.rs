@@ -0,0 +1,24 @@
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[repr(C, u8)]
+pub enum Shape {
+    Circle { center: Point, radius: f32 },
+    Rect(Point, Point),
+    Label([u8; 4]),
+    Id(u32),
+    Empty,
+}
+
+#[repr(u8)]
+pub enum Event {
+    Key(u32),
+    Click { x: i32, y: i32 },
+    Quit,
+}
+
+#[no_mangle]
+pub extern "C" fn root(shape: Shape, event: Event) {}
@@ -0,0 +1,10 @@
+#[repr(C, u8)]
+pub enum Shape {
+    Circle { radius: f32 },
+    Rect(f32, f32),
+    Point(u32),
+    Empty,
+}
+
+#[no_mangle]
+pub extern "C" fn root(shape: Shape) {}
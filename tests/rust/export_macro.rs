@@ -0,0 +1,23 @@
+#[repr(C)]
+pub struct Counter {
+    value: u32,
+}
+
+#[no_mangle]
+pub static mut COUNTER: Counter = Counter { value: 0 };
+
+#[no_mangle]
+pub static VERSION: u32 = 1;
+
+#[no_mangle]
+pub extern "C" fn counter_increment(counter: *mut Counter) -> u32 {
+    unsafe {
+        (*counter).value += 1;
+        (*counter).value
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn counter_reset(counter: *mut Counter, first: u32, second: u32, third: u32, fourth: u32) {
+    unsafe { (*counter).value = first + second + third + fourth; }
+}
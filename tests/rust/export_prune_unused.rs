@@ -0,0 +1,42 @@
+#[repr(C)]
+pub struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color { r: 0, g: 0, b: 0 };
+}
+
+pub const WHITE: Color = Color { r: 255, g: 255, b: 255 };
+
+#[repr(C)]
+pub struct Rect {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+pub const EMPTY_RECT: Rect = Rect { x: 0, y: 0, w: 0, h: 0 };
+
+#[repr(C)]
+pub struct Theme {
+    accent: Color,
+}
+
+pub const DEFAULT_THEME: Theme = Theme { accent: Color { r: 0, g: 0, b: 255 } };
+
+#[repr(C)]
+pub struct Margins {
+    top: i32,
+    bottom: i32,
+}
+
+pub const NO_MARGINS: Margins = Margins { top: 0, bottom: 0 };
+
+pub const MAX_WIDTH: i32 = 1920;
+
+#[no_mangle]
+pub extern "C" fn fill(rect: Rect, color: Color) {}
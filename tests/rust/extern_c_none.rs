@@ -0,0 +1,23 @@
+#[repr(C)]
+pub struct Counter {
+    value: u64,
+}
+
+#[no_mangle]
+pub static mut COUNTER: Counter = Counter { value: 0 };
+
+#[no_mangle]
+pub static LIMIT: u64 = 100;
+
+#[no_mangle]
+pub extern "C" fn counter_increment(counter: *mut Counter) -> u64 {
+    unimplemented!()
+}
+
+#[deprecated(note = "use counter_increment")]
+#[no_mangle]
+pub extern "C" fn counter_bump(counter: *mut Counter) {}
+
+extern "C" {
+    fn counter_changed(counter: *const Counter);
+}
@@ -0,0 +1,9 @@
+#[no_mangle]
+pub extern "C" fn db_open(when: time_t) -> *mut sqlite3 {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn db_elapsed(db: *mut sqlite3) -> clock_t {
+    unimplemented!()
+}
@@ -0,0 +1,23 @@
+#[repr(C)]
+pub struct Packet {
+    len: u32,
+    /// cbindgen:flexible-array
+    data: [u8; 0],
+}
+
+#[repr(C)]
+pub struct Names {
+    count: usize,
+    /// cbindgen:flexible-array
+    names: [*const u8; 1],
+}
+
+#[repr(C)]
+pub struct NotTrailing {
+    /// cbindgen:flexible-array
+    data: [u8; 0],
+    len: u32,
+}
+
+#[no_mangle]
+pub extern "C" fn root(p: *const Packet, n: *mut Names, t: NotTrailing) {}
@@ -0,0 +1,19 @@
+#[no_mangle]
+#[cold]
+pub extern "C" fn report_error(code: i32) {}
+
+#[no_mangle]
+#[cold]
+pub extern "C" fn abort_with(code: i32) -> ! {
+    loop {}
+}
+
+#[no_mangle]
+pub extern "C" fn exit_with(code: i32) -> ! {
+    loop {}
+}
+
+#[no_mangle]
+pub extern "C" fn normal_return() -> u8 {
+    0
+}
@@ -0,0 +1,21 @@
+use std::os::raw::c_char;
+
+extern "C" {
+    fn log_message(level: i32, fmt: *const c_char, ...);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn print_message(fmt: *const c_char, args: ...) {}
+
+/// cbindgen:format-arg=2,3
+#[no_mangle]
+pub unsafe extern "C" fn print_to(stream: i32, format: *const c_char, args: ...) {}
+
+/// cbindgen:format-arg=1,0
+#[no_mangle]
+pub extern "C" fn print_list(format: *const c_char, list: *mut c_char) {}
+
+#[no_mangle]
+pub unsafe extern "C" fn sum(count: i32, args: ...) -> i32 {
+    0
+}
@@ -0,0 +1,15 @@
+use std::os::raw::c_void;
+
+/// Called with the user data given along with it.
+pub type Callback = extern "C" fn(user_data: *mut c_void, status: i32);
+
+/// cbindgen:arg-names=[user_data, status]
+pub type NamedCallback = Option<unsafe extern "C" fn(*mut c_void, i32) -> bool>;
+
+/// cbindgen:arg-names=[data, len]
+pub type RenamedCallback = fn(ptr: *const u8, n: usize);
+
+pub type KeywordCallback = fn(int: i32, float: f32);
+
+#[no_mangle]
+pub extern "C" fn root(a: Callback, b: NamedCallback, c: RenamedCallback, d: KeywordCallback) {}
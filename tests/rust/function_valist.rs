@@ -0,0 +1,29 @@
+use std::ffi::VaList;
+use std::os::raw::c_char;
+
+#[no_mangle]
+pub unsafe extern "C" fn print_message(level: i32, fmt: *const c_char, args: ...) {}
+
+/// cbindgen:format-arg=1,2
+#[no_mangle]
+pub unsafe extern "C" fn print_line(format: *const c_char, args: ...) {}
+
+/// cbindgen:variadic
+#[no_mangle]
+pub extern "C" fn push_values(count: i32) {}
+
+/// cbindgen:valist=false
+#[no_mangle]
+pub unsafe extern "C" fn sum(count: i32, args: ...) -> i32 {
+    0
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn concat(first: *const c_char, args: ...) -> *mut c_char {
+    concat_valist(first, args.as_va_list())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn concat_valist(first: *const c_char, args: VaList) -> *mut c_char {
+    std::ptr::null_mut()
+}
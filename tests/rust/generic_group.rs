@@ -0,0 +1,28 @@
+#[repr(C)]
+pub struct Value {
+    kind: u8,
+}
+
+/// cbindgen:generic-group=value_set
+#[no_mangle]
+pub extern "C" fn value_set_i32(value: *mut Value, v: i32) {}
+
+/// cbindgen:generic-group=value_set
+#[no_mangle]
+pub extern "C" fn value_set_f64(value: *mut Value, v: f64) {}
+
+/// cbindgen:generic-group=value_set
+#[no_mangle]
+pub extern "C" fn value_set_str(value: *mut Value, v: *const u8) {}
+
+/// cbindgen:generic-group=value_get
+#[no_mangle]
+pub extern "C" fn value_get_i32(value: *const Value) -> i32 {
+    0
+}
+
+/// cbindgen:generic-group=value_get
+#[no_mangle]
+pub extern "C" fn value_get_f64(value: *const Value, fallback: f64) -> f64 {
+    fallback
+}
@@ -0,0 +1,16 @@
+pub struct Buffer;
+
+/// Loads a buffer.
+#[no_mangle]
+pub extern "C" fn gbd_buffer_load(path: *const c_char, err: *mut *mut GError) -> *mut Buffer {
+    unimplemented!()
+}
+
+/// cbindgen:throws
+#[no_mangle]
+pub extern "C" fn gbd_buffer_save(buffer: *mut Buffer, error: *mut *mut c_void) -> bool {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_buffer_sync(buffer: *mut Buffer, error: *mut *mut GError) {}
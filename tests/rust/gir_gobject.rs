@@ -0,0 +1,22 @@
+/// cbindgen:signals=changed
+/// cbindgen:property=title:gchar*
+#[gobject(class)]
+pub struct Bar;
+
+#[gobject(class)]
+pub struct Label {
+    parent: GInitiallyUnowned,
+}
+
+/// cbindgen:ginterface
+pub trait Readable {
+    fn read(&mut self, buffer: *mut u8, len: usize) -> isize;
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_bar_new() -> *mut Bar {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_label_set_bar(label: &mut Label, bar: &Bar) {}
@@ -0,0 +1,55 @@
+/// A point on the screen.
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+    tag: [u8; 4],
+}
+
+/// cbindgen:gobject=boxed
+pub struct Buffer;
+
+pub struct Cursor;
+
+#[repr(C)]
+pub union Value {
+    integer: i64,
+    real: f64,
+}
+
+/// The mode of a buffer.
+#[repr(C)]
+pub enum Mode {
+    Read,
+    Write = 4,
+    Append,
+}
+
+pub type Offset = u64;
+
+/// The longest name of a buffer.
+pub const MAX_NAME_LEN: u32 = 16;
+
+pub const DEFAULT_NAME: &str = "buffer";
+
+/// Creates a buffer.
+///
+/// cbindgen:transfer=[[return;full]]
+#[no_mangle]
+pub extern "C" fn gbd_buffer_new(name: *const std::os::raw::c_char, mode: Mode) -> *mut Buffer {
+    unimplemented!()
+}
+
+/// cbindgen:transfer=[[buffer;full]]
+#[no_mangle]
+pub extern "C" fn gbd_buffer_free(buffer: *mut Buffer) {}
+
+#[no_mangle]
+pub extern "C" fn gbd_buffer_seek(buffer: &mut Buffer, offset: Offset, origin: Point) -> bool {
+    false
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_buffer_cursor(buffer: &Buffer, value: Value) -> *mut Cursor {
+    unimplemented!()
+}
@@ -0,0 +1,20 @@
+pub struct Buffer;
+
+pub struct Stream;
+
+/// cbindgen:gobject=boxed
+pub struct Range;
+
+#[no_mangle]
+pub extern "C" fn gbd_buffer_free(buffer: *mut Buffer) {}
+
+#[no_mangle]
+pub extern "C" fn gbd_stream_free(stream: *mut Stream) {}
+
+#[no_mangle]
+pub extern "C" fn gbd_stream_unref(stream: *mut Stream) {}
+
+#[no_mangle]
+pub extern "C" fn gbd_range_new() -> *mut Range {
+    unimplemented!()
+}
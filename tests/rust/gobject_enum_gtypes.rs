@@ -0,0 +1,8 @@
+#[repr(C)]
+pub enum BufferKind {
+    Plain,
+    LineBuffered,
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_buffer_kind(kind: BufferKind) {}
@@ -0,0 +1,20 @@
+/// A bar, whose parent is GObject.
+#[gobject(class)]
+pub struct Bar;
+
+/// A label, whose parent is the type of its first field.
+#[gobject(class)]
+pub struct Label {
+    parent: GInitiallyUnowned,
+    text: String,
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_bar_new() -> *mut Bar {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_label_new(bar: *mut Bar) -> *mut Label {
+    unimplemented!()
+}
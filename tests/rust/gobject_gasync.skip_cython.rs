@@ -0,0 +1,17 @@
+#[repr(C)]
+pub struct Buffer {
+    len: usize,
+}
+
+/// Loads the buffer.
+/// cbindgen:gasync=buffer_load
+pub async fn load(buffer: &mut Buffer, path: *const u8) -> Result<(), Error> {
+    unimplemented!()
+}
+
+/// cbindgen:gasync
+pub async fn fetch(url: *const u8) -> Result<Box<Buffer>, Error> {
+    unimplemented!()
+}
+
+pub async fn skipped() {}
@@ -0,0 +1,21 @@
+#[repr(C)]
+pub struct Chunk {
+    len: usize,
+}
+
+/// A source of chunks.
+/// cbindgen:ginterface
+pub trait ChunkSource {
+    /// Reads the next chunk.
+    fn read(&mut self, chunk: &mut Chunk) -> bool;
+    fn close(&self);
+    fn new() -> Self;
+}
+
+/// cbindgen:ginterface=GInitiallyUnowned
+pub trait Sink {}
+
+#[no_mangle]
+pub extern "C" fn gbd_chunk_source_next(source: &mut ChunkSource, chunk: &mut Chunk) -> bool {
+    unimplemented!()
+}
@@ -0,0 +1,4 @@
+#[no_mangle]
+pub extern "C" fn foo(a: i32, b: *const c_char, c: usize) -> bool {
+    unimplemented!()
+}
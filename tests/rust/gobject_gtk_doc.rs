@@ -0,0 +1,46 @@
+pub struct Buffer;
+
+/// Creates a buffer holding a copy of a key.
+///
+/// The bytes are copied.
+///
+/// # Arguments
+///
+/// * `key` - the bytes of the key
+/// * `flags` - unused
+///
+/// # Returns
+///
+/// a new buffer, to free with
+/// `buffer_free()`
+///
+/// cbindgen:ptrs-as-arrays=[[key;16]]
+/// cbindgen:transfer=[[return;full]]
+#[no_mangle]
+pub extern "C" fn buffer_new(key: *const u8, flags: u32) -> *mut Buffer {
+    unimplemented!()
+}
+
+/// Frees a buffer.
+///
+/// # Safety
+///
+/// The buffer must not be used afterwards.
+///
+/// cbindgen:transfer=[[buffer;full]]
+#[no_mangle]
+pub unsafe extern "C" fn buffer_free(buffer: *mut Buffer) {}
+
+/// Returns the length of a buffer, or 0 if there's none.
+#[no_mangle]
+pub extern "C" fn buffer_len(buffer: Option<&Buffer>) -> usize {
+    0
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_data(buffer: &Buffer, out: *mut [u8; 4]) -> *const u8 {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_clear(buffer: &mut Buffer) {}
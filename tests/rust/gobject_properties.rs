@@ -0,0 +1,18 @@
+/// cbindgen:property=[title:gchar*, item_count:guint, model:GObject*]
+#[gobject(class)]
+pub struct Bar;
+
+/// cbindgen:property=label:gchar*
+#[gobject(class)]
+pub struct Baz;
+
+#[no_mangle]
+pub extern "C" fn gbd_bar_new() -> *mut Bar {
+    unimplemented!()
+}
+
+/// The crate already exports this getter.
+#[no_mangle]
+pub extern "C" fn gbd_bar_get_item_count(bar: *mut Bar) -> u32 {
+    0
+}
@@ -0,0 +1,14 @@
+/// cbindgen:signals=[changed, item_added]
+#[gobject(class)]
+pub struct Bar;
+
+/// cbindgen:ginterface
+/// cbindgen:signals=closed
+pub trait Readable {
+    fn read(&mut self, buffer: *mut u8, len: usize) -> isize;
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_bar_new() -> *mut Bar {
+    unimplemented!()
+}
@@ -0,0 +1,11 @@
+#[repr(C)]
+pub struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_rgb_black() -> Color {
+    Color { r: 0, g: 0, b: 0 }
+}
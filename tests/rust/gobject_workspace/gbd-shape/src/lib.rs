@@ -0,0 +1,10 @@
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[no_mangle]
+pub extern "C" fn gbd_shape_origin() -> Point {
+    Point { x: 0, y: 0 }
+}
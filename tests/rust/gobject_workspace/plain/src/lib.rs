@@ -0,0 +1,7 @@
+#[repr(C)]
+pub struct Plain {
+    x: i32,
+}
+
+#[no_mangle]
+pub extern "C" fn plain(_plain: Plain) {}
@@ -0,0 +1,31 @@
+use std::os::raw::c_void;
+
+pub type ReadyCallback = extern "C" fn(result: i32, user_data: *mut c_void);
+pub type DestroyNotify = extern "C" fn(data: *mut c_void);
+
+/// Loads the file in the background.
+///
+/// # Arguments
+///
+/// * `callback` - called once the file is loaded
+/// * `user_data` - the data to pass to `callback`
+///
+/// cbindgen:callbacks=[[callback; user_data]]
+#[no_mangle]
+pub extern "C" fn load_async(
+    path: &u8,
+    callback: Option<extern "C" fn(result: i32, user_data: *mut c_void)>,
+    user_data: *mut c_void,
+) {
+}
+
+/// cbindgen:callbacks=[[func; data; destroy]]
+#[no_mangle]
+pub extern "C" fn watch(func: ReadyCallback, data: *mut c_void, destroy: DestroyNotify) -> u32 {
+    0
+}
+
+/// cbindgen:callbacks=[[func; data]]
+/// cbindgen:scope=[[func; call]]
+#[no_mangle]
+pub extern "C" fn foreach(func: ReadyCallback, data: *mut c_void) {}
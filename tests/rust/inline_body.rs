@@ -0,0 +1,21 @@
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+/// cbindgen:inline-body=return point->x;
+#[no_mangle]
+pub extern "C" fn point_x(point: *const Point) -> i32 {
+    unsafe { (*point).x }
+}
+
+#[no_mangle]
+pub extern "C" fn point_y(point: *const Point) -> i32 {
+    unsafe { (*point).y }
+}
+
+#[no_mangle]
+pub extern "C" fn point_length(point: *const Point) -> f64 {
+    0.0
+}
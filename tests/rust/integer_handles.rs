@@ -0,0 +1,27 @@
+/// A database connection.
+pub struct Connection {
+    url: String,
+}
+
+/// cbindgen:handle
+pub struct Statement;
+
+pub struct Row;
+
+#[no_mangle]
+pub extern "C" fn connection_open(url: *const u8) -> *mut Connection {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn connection_prepare(connection: &Connection, sql: *const u8) -> *mut Statement {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn statement_destroy(statement: *mut Statement) {}
+
+#[no_mangle]
+pub extern "C" fn statement_next(statement: &mut Statement) -> *const Row {
+    unimplemented!()
+}
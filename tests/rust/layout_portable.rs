@@ -0,0 +1,26 @@
+#[repr(align(8), C)]
+pub struct Align8Struct {
+    pub arg1: usize,
+    pub arg2: *mut u8,
+}
+
+#[repr(packed, C)]
+pub struct PackedStruct {
+    pub arg1: u8,
+    pub arg2: u32,
+}
+
+#[repr(align(16), C)]
+pub union Align16Union {
+    pub variant1: usize,
+    pub variant2: *mut u8,
+}
+
+#[repr(packed, C)]
+pub union PackedUnion {
+    pub variant1: u16,
+    pub variant2: u64,
+}
+
+#[no_mangle]
+pub extern "C" fn root(a: Align8Struct, b: PackedStruct, c: Align16Union, d: PackedUnion) {}
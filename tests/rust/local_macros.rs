@@ -0,0 +1,38 @@
+macro_rules! point {
+    ($name:ident, $ty:ty) => {
+        #[repr(C)]
+        pub struct $name {
+            x: $ty,
+            y: $ty,
+        }
+    };
+}
+
+macro_rules! getters {
+    ($($name:ident => $value:expr),* $(,)?) => {
+        $(
+            #[no_mangle]
+            pub extern "C" fn $name() -> i32 {
+                $value
+            }
+        )*
+    };
+}
+
+macro_rules! nested {
+    ($name:ident) => {
+        point!($name, f64);
+    };
+}
+
+point!(PointI, i32);
+point! { PointF, f32 }
+nested!(PointD);
+
+getters! {
+    get_one => 1,
+    get_two => 2,
+}
+
+#[no_mangle]
+pub extern "C" fn root(a: PointI, b: PointF, c: PointD) {}
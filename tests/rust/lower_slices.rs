@@ -0,0 +1,20 @@
+/// Writes the bytes to the stream.
+///
+/// # Arguments
+///
+/// * `data` - the bytes to write
+///
+/// cbindgen:lower-slices
+#[no_mangle]
+pub extern "C" fn stream_write(stream: *mut Stream, data: &[u8]) -> isize {
+    0
+}
+
+/// cbindgen:lower-slices
+#[no_mangle]
+pub extern "C" fn samples_scale(samples: &mut [f32], factor: f32, _: &[u8]) {}
+
+#[repr(C)]
+pub struct Stream {
+    fd: i32,
+}
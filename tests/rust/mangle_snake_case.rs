@@ -0,0 +1,17 @@
+#[repr(C)]
+pub struct Buffer<T> {
+    data: *mut T,
+    len: usize,
+}
+
+#[repr(C)]
+pub struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+pub type Bytes = Buffer<u8>;
+pub type Entry = Pair<Buffer<u8>, *const i32>;
+
+#[no_mangle]
+pub extern "C" fn root(bytes: Bytes, entry: Entry) {}
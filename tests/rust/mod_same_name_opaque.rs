@@ -0,0 +1,17 @@
+mod a {
+    pub struct Inner {
+        x: i32,
+    }
+}
+
+mod b {
+    pub struct Inner {
+        y: u8,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn a_inner(inner: *mut a::Inner) {}
+
+#[no_mangle]
+pub extern "C" fn b_inner(inner: *mut b::Inner) {}
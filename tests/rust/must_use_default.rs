@@ -0,0 +1,32 @@
+#[repr(C)]
+#[must_use]
+pub struct Status {
+    code: i32,
+}
+
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+pub type Outcome = Status;
+
+#[no_mangle]
+pub extern "C" fn try_open() -> Status {
+    Status { code: 0 }
+}
+
+#[no_mangle]
+pub extern "C" fn try_close() -> Outcome {
+    Status { code: 0 }
+}
+
+#[no_mangle]
+#[must_use]
+pub extern "C" fn origin() -> Point {
+    Point { x: 0, y: 0 }
+}
+
+#[no_mangle]
+pub extern "C" fn move_to(point: Point) {}
@@ -0,0 +1,33 @@
+pub const VERSION: u32 = 1;
+
+pub mod net {
+    pub const MAX_ADDRESS_LEN: usize = 16;
+
+    #[repr(C)]
+    pub struct Address {
+        bytes: [u8; MAX_ADDRESS_LEN],
+        port: u16,
+    }
+
+    pub mod tcp {
+        #[repr(C)]
+        pub struct Socket {
+            local: super::Address,
+            peer: *mut crate::Peer,
+        }
+
+        #[no_mangle]
+        pub extern "C" fn socket_open(address: *const super::Address) -> *mut Socket {
+            unimplemented!()
+        }
+    }
+}
+
+#[repr(C)]
+pub struct Peer {
+    socket: *mut net::tcp::Socket,
+    version: u32,
+}
+
+#[no_mangle]
+pub extern "C" fn peer_connect(peer: *mut Peer, address: net::Address) {}
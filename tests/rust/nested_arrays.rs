@@ -0,0 +1,21 @@
+pub const LEN: usize = 3;
+
+#[repr(C)]
+pub struct Grid<const N: usize> {
+    cells: [[u8; N]; N],
+    flat: [u8; N * N],
+}
+
+#[repr(C)]
+pub struct Matrix {
+    m: [[f32; 4]; 4],
+    cube: [[[i8; 2]; LEN]; 5],
+    rows: [*const u8; LEN],
+    padded: [u16; (LEN + 1) * 2],
+    block: [u32; { LEN }],
+    cast: [u32; LEN as usize - 1],
+    row: *const [i32; 4],
+}
+
+#[no_mangle]
+pub extern "C" fn root(m: Matrix, g: Grid<8>, out: *mut [[u8; 3]; 2]) {}
@@ -0,0 +1,23 @@
+use std::num::{NonZero, NonZeroU32, NonZeroUsize};
+
+pub type Handle = NonZeroU32;
+
+/// An entry of the table.
+#[repr(C)]
+pub struct Entry {
+    /// The number of references.
+    refs: NonZeroUsize,
+    id: NonZero<i16>,
+    next: Option<NonZeroU32>,
+}
+
+#[no_mangle]
+pub extern "C" fn lookup(handle: Handle, index: NonZero<u64>, entry: &Entry) -> Option<NonZeroU32> {
+    None
+}
+
+/// Creates a new entry.
+#[no_mangle]
+pub extern "C" fn create(refs: NonZeroUsize) -> NonZeroU32 {
+    unimplemented!()
+}
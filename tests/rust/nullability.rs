@@ -0,0 +1,20 @@
+use std::ptr::NonNull;
+
+struct Opaque;
+
+#[repr(C)]
+pub struct Callbacks {
+    on_event: extern "C" fn(event: i32),
+    on_error: Option<extern "C" fn(code: i32)>,
+}
+
+#[no_mangle]
+pub extern "C" fn opaque_get(opaque: &Opaque, out: &mut i32) {}
+
+#[no_mangle]
+pub extern "C" fn opaque_find(name: Option<&u8>, hint: Option<NonNull<Opaque>>) -> Option<NonNull<Opaque>> {
+    None
+}
+
+#[no_mangle]
+pub extern "C" fn callbacks_set(callbacks: *const Callbacks) {}
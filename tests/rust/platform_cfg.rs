@@ -0,0 +1,27 @@
+#[cfg(target_os = "windows")]
+pub type Handle = *mut u8;
+
+#[cfg(unix)]
+pub type Handle = i32;
+
+#[cfg(all(target_pointer_width = "64", target_env = "msvc"))]
+pub const WORD_SIZE: u32 = 8;
+
+#[cfg(not(target_arch = "x86_64"))]
+pub const WORD_SIZE: u32 = 4;
+
+#[cfg(feature = "tracing")]
+pub const TRACING: bool = true;
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+pub struct Epoll {
+    fd: i32,
+}
+
+#[no_mangle]
+pub extern "C" fn root(handle: Handle) {}
+
+#[cfg(target_os = "linux")]
+#[no_mangle]
+pub extern "C" fn epoll(epoll: Epoll) {}
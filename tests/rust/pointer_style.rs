@@ -0,0 +1,16 @@
+#[repr(C)]
+pub struct Node {
+    next: *mut Node,
+    name: *const u8,
+    children: *mut *mut Node,
+    names: *const *const u8,
+    visit: Option<extern "C" fn(node: *const Node) -> *mut Node>,
+}
+
+#[no_mangle]
+pub extern "C" fn node_find(root: *const Node, name: *const u8) -> *mut Node {
+    root as *mut Node
+}
+
+#[no_mangle]
+pub static mut LAST_NODE: *mut Node = 0 as *mut Node;
@@ -0,0 +1,29 @@
+#[repr(C)]
+pub struct r#Type {
+    r#type: i32,
+    r#match: u8,
+    größe: u32,
+}
+
+#[repr(u8)]
+pub enum Ordre {
+    Première,
+    r#Second,
+    Café,
+}
+
+pub type Δ = f32;
+
+#[export_name = "ordre_match"]
+pub extern "C" fn r#match(r#type: *const r#Type, π: Δ) -> Ordre {
+    Ordre::Première
+}
+
+#[export_name = "naive"]
+pub extern "C" fn naïve(r#ref: Ordre) {}
+
+#[no_mangle]
+pub static r#loop: Ordre = Ordre::Café;
+
+#[no_mangle]
+pub extern "C" fn r#type(r#in: i32) {}
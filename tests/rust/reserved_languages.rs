@@ -0,0 +1,19 @@
+#[repr(C)]
+pub struct Range {
+    from: u32,
+    to: u32,
+    restrict: bool,
+    and: u8,
+    lambda: [u8; 4],
+}
+
+#[repr(C)]
+pub enum Choice {
+    None,
+    Some,
+}
+
+pub type Callback = extern "C" fn(r#in: *const Range, pass: u32);
+
+#[no_mangle]
+pub extern "C" fn range_check(range: Range, choice: Choice, callback: Callback, r#is: bool, not: u32) {}
@@ -0,0 +1,20 @@
+#[repr(C)]
+pub enum Status {
+    Invalid,
+    NotFound,
+}
+
+#[repr(C)]
+pub struct Config {
+    verbose: bool,
+}
+
+#[no_mangle]
+pub extern "C" fn config_parse(text: *const u8, len: usize) -> Result<Config, Status> {
+    Err(Status::Invalid)
+}
+
+#[no_mangle]
+pub extern "C" fn config_check(config: *const Config) -> Result<(), Status> {
+    Ok(())
+}
@@ -0,0 +1,21 @@
+#[repr(C)]
+pub struct Internals {
+    value: u64,
+}
+
+#[repr(C)]
+pub struct Handle {
+    /// cbindgen:skip-field
+    /// cbindgen:pad=8
+    internals: *mut Internals,
+    /// cbindgen:skip-field
+    /// cbindgen:pad=16
+    cache: [u64; 2],
+    id: u32,
+    flags: u32,
+}
+
+#[no_mangle]
+pub extern "C" fn handle_flags(handle: *const Handle) -> u32 {
+    unsafe { (*handle).flags }
+}
@@ -0,0 +1,46 @@
+#[repr(C)]
+pub struct Zoo {
+    cage: Cage,
+    keeper: Keeper,
+}
+
+#[repr(C)]
+pub struct Keeper {
+    id: KeeperId,
+    cage: *mut Cage,
+}
+
+pub type KeeperId = u32;
+
+#[repr(C)]
+pub struct Cage {
+    size: Size,
+    animal: *mut Animal,
+}
+
+#[repr(C)]
+pub struct Animal {
+    size: Size,
+}
+
+#[repr(C)]
+pub struct Size {
+    width: u32,
+    height: u32,
+}
+
+pub const ZOO_SIZE: u32 = 10;
+pub const CAGE_SIZE: u32 = 2;
+
+#[no_mangle]
+pub extern "C" fn zoo_open(zoo: *mut Zoo) {}
+
+#[no_mangle]
+pub extern "C" fn cage_area(size: Size) -> u32 {
+    size.width * size.height
+}
+
+#[no_mangle]
+pub extern "C" fn animal_cage(animal: *const Animal) -> *mut Cage {
+    unimplemented!()
+}
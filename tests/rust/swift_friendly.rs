@@ -0,0 +1,26 @@
+use std::ptr::NonNull;
+
+struct Opaque;
+
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    #[no_mangle]
+    pub extern "C" fn Point_length(&self) -> f32 {
+        0.0
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn point_new(x: i32, y: i32) -> NonNull<Point> {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn opaque_find(name: *const u8, fallback: Option<NonNull<Opaque>>) -> Option<&'static Opaque> {
+    None
+}
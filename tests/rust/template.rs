@@ -0,0 +1,15 @@
+pub const LIMIT: u32 = 16;
+
+/// Written in place of {{typedefs}}, before {{functions}} and {{unknown}}.
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+#[no_mangle]
+pub extern "C" fn point_scale(point: Point, factor: i32) -> Point {
+    point
+}
@@ -0,0 +1,12 @@
+pub struct Buffer;
+
+#[repr(C)]
+pub struct Status {
+    code: i32,
+}
+
+/// cbindgen:throws
+#[no_mangle]
+pub extern "C" fn buffer_save(buffer: *mut Buffer, status: *mut *mut Status) -> bool {
+    true
+}
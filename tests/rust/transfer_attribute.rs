@@ -0,0 +1,21 @@
+pub struct Buffer;
+
+#[no_mangle]
+pub extern "C" fn buffer_new(name: &u8) -> Box<Buffer> {
+    unimplemented!()
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_free(buffer: Option<Box<Buffer>>) {}
+
+/// cbindgen:transfer=none
+#[no_mangle]
+pub extern "C" fn buffer_data(buffer: *const Buffer) -> *const u8 {
+    unimplemented!()
+}
+
+/// cbindgen:transfer=[[buffer;full], [return;container]]
+#[no_mangle]
+pub extern "C" fn buffer_into_parts(buffer: *mut Buffer) -> *mut *mut u8 {
+    unimplemented!()
+}
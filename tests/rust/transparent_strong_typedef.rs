@@ -0,0 +1,29 @@
+use std::marker::PhantomData;
+
+/// A distance, in meters.
+#[repr(transparent)]
+pub struct Meters(f64);
+
+#[repr(transparent)]
+pub struct Seconds {
+    value: f64,
+}
+
+/// cbindgen:transparent-typedef
+#[repr(transparent)]
+pub struct Count(u32);
+
+#[repr(transparent)]
+pub struct Id<T> {
+    id: u64,
+    marker: PhantomData<T>,
+}
+
+impl Seconds {
+    pub const MINUTE: Seconds = Seconds { value: 60.0 };
+}
+
+#[no_mangle]
+pub extern "C" fn speed(distance: Meters, time: Seconds, count: Count, id: Id<Meters>) -> f64 {
+    0.0
+}
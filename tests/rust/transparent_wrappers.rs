@@ -0,0 +1,18 @@
+use std::cell::UnsafeCell;
+use std::mem::{ManuallyDrop, MaybeUninit};
+
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[repr(C)]
+pub struct Slot {
+    point: MaybeUninit<Point>,
+    owned: ManuallyDrop<Point>,
+    flags: UnsafeCell<u8>,
+}
+
+#[no_mangle]
+pub extern "C" fn root(slot: &Slot, point: &mut MaybeUninit<Point>) {}
@@ -0,0 +1,13 @@
+use std::os::raw::c_char;
+
+#[repr(C)]
+pub struct Buffer {
+    data: *mut c_char,
+    len: usize,
+    flags: u32,
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_fill(buffer: *mut Buffer, value: c_char, count: i32) -> usize {
+    0
+}
@@ -0,0 +1,10 @@
+use std::cell::UnsafeCell;
+
+#[repr(C)]
+pub struct Counter {
+    value: UnsafeCell<u32>,
+    limits: std::cell::UnsafeCell<[u32; 2]>,
+}
+
+#[no_mangle]
+pub extern "C" fn root(counter: &Counter, value: &std::cell::UnsafeCell<i32>) {}
@@ -0,0 +1,30 @@
+#[repr(C)]
+pub struct Codec {
+    level: i32,
+}
+
+/// cbindgen:vtable=CodecOps
+#[no_mangle]
+pub extern "C" fn codec_open(level: i32) -> *mut Codec {
+    Box::into_raw(Box::new(Codec { level }))
+}
+
+/// cbindgen:vtable=CodecOps
+#[no_mangle]
+pub extern "C" fn codec_encode(codec: *mut Codec, data: *const u8, len: usize) -> i32 {
+    0
+}
+
+/// cbindgen:vtable=CodecOps
+#[cfg(feature = "flush")]
+#[no_mangle]
+pub extern "C" fn codec_flush(codec: *mut Codec) {}
+
+/// cbindgen:vtable=CodecOps
+#[no_mangle]
+pub extern "C" fn codec_close(codec: *mut Codec) {}
+
+#[no_mangle]
+pub extern "C" fn codec_version() -> u32 {
+    1
+}
@@ -0,0 +1,14 @@
+#[no_mangle]
+pub extern "C" fn buffer_len(len: usize) -> isize {
+    len as isize
+}
+
+extern "C" {
+    fn log_message(level: u32);
+}
+
+#[link(wasm_import_module = "host")]
+extern "C" {
+    #[link_name = "now"]
+    fn host_now() -> f64;
+}
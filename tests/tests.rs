@@ -36,6 +36,13 @@ fn run_cbindgen(
         Language::Cython => {
             command.arg("--lang").arg("cython");
         }
+        Language::Gir => {
+            command.arg("--lang").arg("gir");
+        }
+        Language::CSharp => {
+            command.arg("--lang").arg("csharp");
+        }
+        Language::Vapi | Language::Napi | Language::Ctypes => unreachable!(),
     }
 
     if let Some(style) = style {
@@ -60,6 +67,21 @@ fn run_cbindgen(
     cbindgen_output.stdout
 }
 
+/// The flags to compile against `package`, like GObject, or `None` when its
+/// headers aren't installed.
+fn pkg_config_cflags(package: &str) -> Option<Vec<String>> {
+    let out = Command::new("pkg-config")
+        .arg("--cflags")
+        .arg(package)
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let cflags = str::from_utf8(&out.stdout).ok()?;
+    Some(cflags.split_whitespace().map(String::from).collect())
+}
+
 fn compile(
     cbindgen_output: &Path,
     tests_path: &Path,
@@ -72,6 +94,9 @@ fn compile(
         Language::Cxx => env::var("CXX").unwrap_or_else(|_| "g++".to_owned()),
         Language::C => env::var("CC").unwrap_or_else(|_| "gcc".to_owned()),
         Language::Cython => env::var("CYTHON").unwrap_or_else(|_| "cython".to_owned()),
+        Language::Gir | Language::CSharp | Language::Vapi | Language::Napi | Language::Ctypes => {
+            unreachable!()
+        }
     };
 
     let file_name = cbindgen_output
@@ -111,6 +136,33 @@ fn compile(
                 }
             }
 
+            // The GObject bindings are only compiled where GObject, or GIO
+            // for the async functions and GLib for its types, is installed.
+            let header = fs::read_to_string(cbindgen_output).unwrap();
+            let package = if header.contains("#include <gio/gio.h>") {
+                Some("gio-2.0")
+            } else if header.contains("#include <glib-object.h>") {
+                Some("gobject-2.0")
+            } else if header.contains("#include <glib.h>") {
+                Some("glib-2.0")
+            } else {
+                None
+            };
+            if let Some(package) = package {
+                match pkg_config_cflags(package) {
+                    Some(cflags) => {
+                        command.args(cflags);
+                    }
+                    None => {
+                        println!(
+                            "Skipping {:?}: {} isn't installed",
+                            cbindgen_output, package
+                        );
+                        return;
+                    }
+                }
+            }
+
             if let Some(style) = style {
                 command.arg("-D");
                 command.arg(format!(
@@ -131,6 +183,9 @@ fn compile(
             command.arg("-o").arg(&object);
             command.arg(cbindgen_output);
         }
+        Language::Gir | Language::CSharp | Language::Vapi | Language::Napi | Language::Ctypes => {
+            unreachable!()
+        }
     }
 
     println!("Running: {:?}", command);
@@ -143,6 +198,9 @@ fn compile(
 }
 
 const SKIP_WARNING_AS_ERROR_SUFFIX: &'static str = ".skip_warning_as_error";
+// The Cython bindings of these tests use GLib types they don't declare, so
+// they're only compared with the expectations.
+const SKIP_CYTHON_SUFFIX: &str = ".skip_cython";
 
 fn run_compile_test(
     cbindgen_path: &'static str,
@@ -174,12 +232,17 @@ fn run_compile_test(
         // is extension-sensitive and won't work on them, so we use implementation files (`.pyx`)
         // in the test suite.
         Language::Cython => ".pyx",
+        Language::Gir => ".gir",
+        Language::CSharp => ".cs",
+        Language::Vapi | Language::Napi | Language::Ctypes => unreachable!(),
     };
 
     let skip_warning_as_error = name.rfind(SKIP_WARNING_AS_ERROR_SUFFIX).is_some();
+    let skip_cython = name.rfind(SKIP_CYTHON_SUFFIX).is_some();
 
-    let source_file =
-        format!("{}{}{}", name, style_ext, lang_ext).replace(SKIP_WARNING_AS_ERROR_SUFFIX, "");
+    let source_file = format!("{}{}{}", name, style_ext, lang_ext)
+        .replace(SKIP_WARNING_AS_ERROR_SUFFIX, "")
+        .replace(SKIP_CYTHON_SUFFIX, "");
 
     generated_file.push(source_file);
 
@@ -211,6 +274,15 @@ fn run_compile_test(
 
         cbindgen_outputs.insert(cbindgen_output);
 
+        // Introspection data and C# bindings are only compared with the
+        // expectations.
+        if language == Language::Gir || language == Language::CSharp {
+            return;
+        }
+        if language == Language::Cython && skip_cython {
+            return;
+        }
+
         compile(
             &generated_file,
             &tests_path,
@@ -283,6 +355,34 @@ fn test_file(cbindgen_path: &'static str, name: &'static str, filename: &'static
             &mut cbindgen_outputs,
         );
     }
+
+    // The `gir_*` tests also check the introspection data.
+    if name.starts_with("gir_") {
+        run_compile_test(
+            cbindgen_path,
+            name,
+            test,
+            tmp_dir,
+            Language::Gir,
+            /* cpp_compat = */ false,
+            None,
+            &mut HashSet::new(),
+        );
+    }
+
+    // The `csharp_*` tests also check the C# bindings.
+    if name.starts_with("csharp_") {
+        run_compile_test(
+            cbindgen_path,
+            name,
+            test,
+            tmp_dir,
+            Language::CSharp,
+            /* cpp_compat = */ false,
+            None,
+            &mut HashSet::new(),
+        );
+    }
 }
 
 macro_rules! test_file {